@@ -0,0 +1,253 @@
+//! Generates C code from a [`CFileBuilder`] that is serialized as JSON or RON, for front-ends that are not written in
+//! Rust.
+
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Read, Write},
+    path::{self, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{bail, Context};
+use c_codegen::{validate::Kind, CFileBuilder, Identifier, Standard};
+
+const USAGE: &str = "\
+Usage: c-codegen [OPTIONS] [INPUT]
+
+Generates C code from a CFileBuilder that is serialized as JSON or RON. Reads INPUT,
+or standard input if INPUT is missing or `-`.
+
+Options:
+  --format <FORMAT>       Format of the input, `json` or `ron`
+                          [default: the extension of INPUT, or `json`]
+  --standard <STANDARD>   Target standard, e.g. `c99` or `gnu11` [default: the standard of the input]
+  --line-width <COLUMNS>  Line width that the code is wrapped at [default: 80]
+  --header                Generate a header with the declarations of the file within an include guard
+  --validate              Check the file for semantic errors before generating code, including
+                          uses of undeclared names. Names from included headers count as undeclared
+  --output-dir <DIR>      Write NAME.c, or NAME.h for a header, into DIR instead of standard output
+  --name <NAME>           Name of the output file and the include guard
+                          [default: the file stem of INPUT, or `output`]
+  -h, --help              Print this help
+";
+
+const STANDARDS: [Standard; 10] = [
+    Standard::C89,
+    Standard::C99,
+    Standard::C11,
+    Standard::C17,
+    Standard::C23,
+    Standard::Gnu89,
+    Standard::Gnu99,
+    Standard::Gnu11,
+    Standard::Gnu17,
+    Standard::Gnu23,
+];
+
+#[derive(Clone, Copy)]
+enum Format {
+    Json,
+    Ron,
+}
+
+#[derive(Default)]
+struct Options {
+    input: Option<PathBuf>,
+    format: Option<Format>,
+    standard: Option<Standard>,
+    line_width: Option<usize>,
+    is_header: bool,
+    is_validated: bool,
+    output_dir: Option<PathBuf>,
+    name: Option<String>,
+}
+
+impl Options {
+    /// Parses the command-line arguments, or returns `None` if help is requested.
+    fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Option<Self>, String> {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--format" => {
+                    options.format = Some(match value(&mut args, &arg)?.as_str() {
+                        "json" => Format::Json,
+                        "ron" => Format::Ron,
+                        format => return Err(format!("unknown format `{format}`")),
+                    })
+                }
+                "--standard" => {
+                    let name = value(&mut args, &arg)?;
+                    let standard = STANDARDS
+                        .into_iter()
+                        .find(|standard| standard.to_string().eq_ignore_ascii_case(&name))
+                        .ok_or_else(|| format!("unknown standard `{name}`"))?;
+                    options.standard = Some(standard);
+                }
+                "--line-width" => {
+                    let columns = value(&mut args, &arg)?;
+                    let line_width = columns
+                        .parse()
+                        .map_err(|_| format!("invalid line width `{columns}`"))?;
+                    options.line_width = Some(line_width);
+                }
+                "--header" => options.is_header = true,
+                "--validate" => options.is_validated = true,
+                "--output-dir" => options.output_dir = Some(value(&mut args, &arg)?.into()),
+                "--name" => {
+                    let name = value(&mut args, &arg)?;
+                    if name.contains(path::is_separator) {
+                        return Err(format!(
+                            "the name `{name}` must not contain path separators"
+                        ));
+                    }
+                    options.name = Some(name);
+                }
+                "-" => options.input = None,
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+                _ if options.input.is_some() => return Err(format!("unexpected argument `{arg}`")),
+                _ => options.input = Some(arg.into()),
+            }
+        }
+
+        Ok(Some(options))
+    }
+
+    fn format(&self) -> Format {
+        self.format.unwrap_or_else(|| {
+            match self.input.as_ref().and_then(|input| input.extension()) {
+                Some(extension) if extension == "ron" => Format::Ron,
+                _ => Format::Json,
+            }
+        })
+    }
+
+    fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            self.input
+                .as_ref()
+                .and_then(|input| input.file_stem())
+                .map_or_else(
+                    || "output".to_string(),
+                    |stem| stem.to_string_lossy().into_owned(),
+                )
+        })
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprint!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(options: &Options) -> anyhow::Result<()> {
+    let mut file = read(options)?;
+    if let Some(standard) = options.standard {
+        file.set_standard(standard);
+    }
+    if let Some(line_width) = options.line_width {
+        file.set_line_width(line_width);
+    }
+
+    if options.is_validated {
+        // Shadowing declarations and reserved names are legal, so they don't fail the validation
+        let (warnings, errors): (Vec<_>, Vec<_>) = file
+            .symbols()
+            .diagnostics()
+            .iter()
+            .cloned()
+            .partition(|diagnostic| {
+                matches!(
+                    diagnostic.kind,
+                    Kind::Shadowing(_) | Kind::ReservedIdentifier(_)
+                )
+            });
+        for warning in &warnings {
+            eprint!("warning: {warning}");
+        }
+
+        let diagnostics = errors
+            .into_iter()
+            .chain(file.validate())
+            .collect::<Vec<_>>();
+        if !diagnostics.is_empty() {
+            for diagnostic in &diagnostics {
+                eprint!("error: {diagnostic}");
+            }
+            bail!("validation failed with {} error(s)", diagnostics.len());
+        }
+    }
+
+    let name = options.name();
+    let mut code = Vec::new();
+    if options.is_header {
+        file.generate_header(&include_guard(&name), &mut code)?;
+    } else {
+        file.generate(&mut code)?;
+    }
+
+    match &options.output_dir {
+        Some(output_dir) => {
+            let extension = if options.is_header { "h" } else { "c" };
+            let path = output_dir.join(format!("{name}.{extension}"));
+
+            fs::create_dir_all(output_dir)
+                .with_context(|| format!("failed to create `{}`", output_dir.display()))?;
+            fs::write(&path, code).with_context(|| format!("failed to write `{}`", path.display()))
+        }
+        None => io::stdout()
+            .write_all(&code)
+            .context("failed to write to standard output"),
+    }
+}
+
+fn read(options: &Options) -> anyhow::Result<CFileBuilder> {
+    let source = match &options.input {
+        Some(input) => fs::read_to_string(input)
+            .with_context(|| format!("failed to read `{}`", input.display()))?,
+        None => {
+            let mut source = String::new();
+            io::stdin()
+                .read_to_string(&mut source)
+                .context("failed to read standard input")?;
+            source
+        }
+    };
+
+    match options.format() {
+        Format::Json => serde_json::from_str(&source).context("invalid JSON input"),
+        Format::Ron => ron::from_str(&source).context("invalid RON input"),
+    }
+}
+
+/// Returns the include guard for the header of the given name, e.g. `POINT_H` for `point`.
+fn include_guard(name: &str) -> Identifier {
+    // Uppercase guards like `STRING_H` are not reserved at file scope like `string_h`, so they stay unprefixed
+    let guard = format!("{name}_h").to_ascii_uppercase();
+
+    Identifier::sanitize(&guard, &mut HashSet::new())
+}
+
+fn value<I: Iterator<Item = String>>(args: &mut I, option: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("`{option}` requires a value"))
+}
@@ -0,0 +1,140 @@
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use c_codegen::parse::parse_file;
+
+fn run(args: &[&str], stdin: &str) -> anyhow::Result<Output> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_c-codegen"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(stdin.as_bytes())?;
+
+    Ok(child.wait_with_output()?)
+}
+
+#[test]
+fn json() -> anyhow::Result<()> {
+    let file = parse_file(
+        "struct point { int x; int y; };
+        static int counter = 0;
+        int area(const struct point *point) { counter++; return point->x * point->y; }",
+    )?
+    .file;
+    let json = serde_json::to_string(&file)?;
+
+    let output = run(&[], &json)?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?, file.write_to_string()?);
+
+    // Leading underscores are stripped, since they would make the include guard reserved
+    let output = run(&["--header", "--name", "_point"], &json)?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "#ifndef POINT_H
+#define POINT_H
+
+struct point {
+  int x;
+  int y;
+};
+int area (const struct point *point);
+
+#endif /* POINT_H */
+"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ron_to_output_dir() -> anyhow::Result<()> {
+    let file = parse_file("enum color { RED, GREEN, BLUE };")?.file;
+
+    let dir = std::env::temp_dir().join(format!("c-codegen-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let input = dir.join("colors.ron");
+    fs::write(&input, ron::to_string(&file)?)?;
+    let output_dir = dir.join("out");
+
+    let output = run(
+        &[
+            input.to_str().expect("the path is UTF-8"),
+            "--line-width",
+            "10",
+            "--output-dir",
+            output_dir.to_str().expect("the path is UTF-8"),
+        ],
+        "",
+    )?;
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(output_dir.join("colors.c"))?,
+        "enum color {\n  RED,\n  GREEN,\n  BLUE\n};\n"
+    );
+
+    fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
+
+#[test]
+fn errors() -> anyhow::Result<()> {
+    let file = parse_file("void stop(void) { break; }")?.file;
+    let json = serde_json::to_string(&file)?;
+
+    let output = run(&["--validate"], &json)?;
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr)?,
+        "error: statements[0].body.statements[0]: `break` must be inside a loop or `switch`
+    break;
+error: validation failed with 1 error(s)
+"
+    );
+
+    // Undeclared names and conflicting declarations are errors, shadowing declarations only warnings
+    let file = parse_file("int x;\ndouble x;\nint f(int y) { { int y = 1; } return z; }")?.file;
+    let output = run(&["--validate"], &serde_json::to_string(&file)?)?;
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr)?,
+        "warning: statements[2].body.statements[0].statements[0]: `y` shadows a declaration of an enclosing scope
+    int y = 1;
+error: statements[1]: `x` is redeclared with an incompatible type
+    double x;
+error: statements[2].body.statements[1].expression: `z` is not declared
+    z
+error: validation failed with 2 error(s)
+"
+    );
+
+    let output = run(&[], "{\"statements\": 1}")?;
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)?.starts_with("error: invalid JSON input: "));
+
+    let output = run(&["--header", "--name", "../ord/hdr"], &json)?;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)?
+        .starts_with("error: the name `../ord/hdr` must not contain path separators\n\nUsage: "));
+
+    let output = run(&["--standard", "c42"], "")?;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8(output.stderr)?.starts_with("error: unknown standard `c42`\n\nUsage: ")
+    );
+
+    Ok(())
+}
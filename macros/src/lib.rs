@@ -0,0 +1,384 @@
+//! Quasi-quoting macros for C code templates.
+//!
+//! [`c_expr!`], [`c_stmt!`] and [`c_item!`] parse their C code at compile time, and expand to the Rust code that
+//! constructs an [`Expression`](c_codegen::Expression), [`Statement`](c_codegen::Statement) or
+//! [`FileLevelStatement`](c_codegen::FileLevelStatement) respectively.
+//!
+//! Rust values are spliced in with `#name`, and iterables with `#(...)*` or `#(...),*`, in the style of `quote!`. Each
+//! value is cloned and converted with [`Into`] into what its position requires: an expression wherever a primary
+//! expression can occur, a statement in place of a statement, or a file-level statement in place of a declaration.
+//! Iterables are consumed, and all iterables within a repetition are iterated in lockstep. Repetitions must expand to
+//! whole elements of a list, e.g. arguments, initializers or statements.
+//!
+//! ```
+//! use c_codegen::{Identifier, Value};
+//! use c_codegen_macros::c_expr;
+//!
+//! let function = Identifier::new("max").unwrap();
+//! let arguments = vec![Value::signed_integer(1), Value::signed_integer(2)];
+//! let call = c_expr!(#function(#(#arguments),*) + 1);
+//! assert_eq!(call.to_string(), "max(1, 2) + 1");
+//! ```
+//!
+//! Templates are tokenized as Rust, so they cannot contain comments or preprocessor directives, and invalid C
+//! fails to compile:
+//!
+//! ```compile_fail
+//! use c_codegen_macros::c_expr;
+//!
+//! let sum = c_expr!(1 +);
+//! ```
+//!
+//! So do values that do not convert into what their position requires, and repetitions within an expression:
+//!
+//! ```compile_fail
+//! use c_codegen::Statement;
+//! use c_codegen_macros::c_expr;
+//!
+//! let statement = Statement::Break;
+//! let sum = c_expr!(#statement + 1);
+//! ```
+//!
+//! ```compile_fail
+//! use c_codegen::Value;
+//! use c_codegen_macros::c_expr;
+//!
+//! let values = vec![Value::signed_integer(1), Value::signed_integer(2)];
+//! let sum = c_expr!(#(#values)+*);
+//! ```
+
+use std::collections::HashMap;
+
+use c_codegen::__private::{
+    self as template, Extent, Placeholder, Repetition, RepetitionError, Syntax,
+};
+use proc_macro::{Delimiter, Literal, Spacing, Span, TokenStream, TokenTree};
+
+/// Expands to an [`Expression`](c_codegen::Expression), e.g. `c_expr!(#x * 2)`.
+#[proc_macro]
+pub fn c_expr(input: TokenStream) -> TokenStream {
+    expand(input, Syntax::Expression)
+}
+
+/// Expands to a [`Statement`](c_codegen::Statement), e.g. `c_stmt!(while (#condition) { #(#body)* })`.
+#[proc_macro]
+pub fn c_stmt(input: TokenStream) -> TokenStream {
+    expand(input, Syntax::Statement)
+}
+
+/// Expands to a [`FileLevelStatement`](c_codegen::FileLevelStatement), e.g. `c_item!(int main(void) { #body })`.
+#[proc_macro]
+pub fn c_item(input: TokenStream) -> TokenStream {
+    expand(input, Syntax::FileLevelStatement)
+}
+
+enum Part {
+    Token {
+        text: String,
+        span: Span,
+        is_spaced: bool,
+    },
+    Interpolation {
+        id: usize,
+        variable: String,
+        span: Span,
+    },
+    Repetition {
+        index: usize,
+        parts: Vec<Part>,
+        separator: Option<char>,
+    },
+}
+
+struct Template {
+    parts: Vec<Part>,
+    /// The repetitions by index, with their spans.
+    repetitions: Vec<(Repetition, Span)>,
+}
+
+impl Template {
+    fn parse(input: TokenStream) -> Result<Self, (String, Span)> {
+        let mut next_id = 0;
+        let mut repetitions = Vec::new();
+        let parts = parts(input, false, &mut next_id, &mut repetitions)?;
+
+        Ok(Self { parts, repetitions })
+    }
+
+    /// Parses the C code, finds the list elements of each repetition, and writes the constructors of the parsed AST.
+    fn expansion(&self, syntax: Syntax) -> Result<TokenStream, (String, Span)> {
+        let source = Source::new(&self.parts, None);
+        let parsed = source.parse(syntax)?;
+
+        let mut repetitions: Vec<_> = self
+            .repetitions
+            .iter()
+            .map(|(repetition, _)| repetition.clone())
+            .collect();
+        for (index, repetition) in repetitions.iter_mut().enumerate() {
+            let repeated_source = Source::new(&self.parts, Some(index));
+            let repeated = repeated_source.parse(syntax)?;
+
+            repetition.extent = parsed
+                .extent(
+                    &source.placeholders,
+                    index,
+                    &repeated,
+                    &repeated_source.placeholders,
+                )
+                .map_err(|error| self.repetition_error(error))?;
+        }
+
+        let code = parsed
+            .expand(&source.placeholders, &repetitions)
+            .map_err(|error| self.repetition_error(error))?;
+
+        Ok(code.parse().expect("the expansion is valid Rust"))
+    }
+
+    fn repetition_error(&self, error: RepetitionError) -> (String, Span) {
+        (error.to_string(), self.repetitions[error.repetition].1)
+    }
+}
+
+/// The C source of a template, with each interpolation replaced by a placeholder.
+struct Source {
+    text: String,
+    /// The spans of the tokens, by the character offset where they start.
+    spans: Vec<(usize, Span)>,
+    /// The placeholders by name.
+    placeholders: HashMap<String, Placeholder>,
+}
+
+impl Source {
+    /// Writes the source, with each repetition occurring once, except for the doubled repetition that occurs twice.
+    fn new(parts: &[Part], doubled: Option<usize>) -> Self {
+        let mut source = Self {
+            text: String::new(),
+            spans: Vec::new(),
+            placeholders: HashMap::new(),
+        };
+        source.push(parts, None, doubled);
+
+        source
+    }
+
+    fn push(&mut self, parts: &[Part], repetition: Option<(usize, usize)>, doubled: Option<usize>) {
+        for part in parts {
+            match part {
+                Part::Token {
+                    text,
+                    span,
+                    is_spaced,
+                } => {
+                    self.spans.push((self.text.chars().count(), *span));
+                    self.text.push_str(text);
+                    if *is_spaced {
+                        self.text.push(' ');
+                    }
+                }
+                Part::Interpolation { id, variable, span } => {
+                    let name = match repetition {
+                        Some((_, copy)) if copy > 0 => format!("__c{id}_{copy}"),
+                        _ => format!("__c{id}"),
+                    };
+
+                    self.spans.push((self.text.chars().count(), *span));
+                    self.text.push_str(&format!("${name} "));
+                    self.placeholders.insert(
+                        name,
+                        Placeholder {
+                            id: *id,
+                            value: variable.clone(),
+                            repetition: repetition.map(|(index, _)| index),
+                            copy: repetition.map_or(0, |(_, copy)| copy),
+                        },
+                    );
+                }
+                Part::Repetition {
+                    index,
+                    parts,
+                    separator,
+                } => {
+                    let copies = if doubled == Some(*index) { 2 } else { 1 };
+
+                    for copy in 0..copies {
+                        if let (true, Some(separator)) = (copy > 0, separator) {
+                            self.text.push_str(&format!("{separator} "));
+                        }
+                        self.push(parts, Some((*index, copy)), doubled);
+                    }
+                }
+            }
+        }
+    }
+
+    fn parse(&self, syntax: Syntax) -> Result<template::Template, (String, Span)> {
+        template::parse(&self.text, syntax).map_err(|error| {
+            let offset = if error.position.line == 1 {
+                error.position.column.saturating_sub(1)
+            } else {
+                usize::MAX
+            };
+            let span = self
+                .spans
+                .iter()
+                .rev()
+                .find(|(start, _)| *start <= offset)
+                .map_or_else(Span::call_site, |(_, span)| *span);
+
+            (format!("invalid C: {}", error.kind), span)
+        })
+    }
+}
+
+fn expand(input: TokenStream, syntax: Syntax) -> TokenStream {
+    Template::parse(input)
+        .and_then(|template| template.expansion(syntax))
+        .unwrap_or_else(|(message, span)| compile_error(&message, span))
+}
+
+fn parts(
+    input: TokenStream,
+    is_repeated: bool,
+    next_id: &mut usize,
+    repetitions: &mut Vec<(Repetition, Span)>,
+) -> Result<Vec<Part>, (String, Span)> {
+    let mut parts = Vec::new();
+    let mut tokens = input.into_iter();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => match tokens.next() {
+                Some(TokenTree::Ident(variable)) => {
+                    parts.push(Part::Interpolation {
+                        id: *next_id,
+                        variable: variable.to_string(),
+                        span: variable.span(),
+                    });
+                    *next_id += 1;
+                }
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                    if is_repeated {
+                        return Err(("nested repetitions are not supported".to_string(), group.span()));
+                    }
+
+                    let repeated = self::parts(group.stream(), true, next_id, repetitions)?;
+                    if !repeated.iter().any(|part| matches!(part, Part::Interpolation { .. })) {
+                        return Err((
+                            "a repetition needs at least one `#name` to iterate over".to_string(),
+                            group.span(),
+                        ));
+                    }
+
+                    let separator = match tokens.next() {
+                        Some(TokenTree::Punct(punct)) if punct.as_char() == '*' => None,
+                        Some(TokenTree::Punct(separator)) => match tokens.next() {
+                            Some(TokenTree::Punct(punct)) if punct.as_char() == '*' => Some(separator.as_char()),
+                            _ => {
+                                return Err((
+                                    "expected `*` after the separator of a repetition".to_string(),
+                                    separator.span(),
+                                ))
+                            }
+                        },
+                        _ => {
+                            return Err((
+                                "expected `*` or a separator after a repetition".to_string(),
+                                group.span(),
+                            ))
+                        }
+                    };
+
+                    repetitions.push((
+                        Repetition {
+                            variables: repeated_variables(&repeated),
+                            extent: Extent::default(),
+                        },
+                        group.span(),
+                    ));
+                    parts.push(Part::Repetition {
+                        index: repetitions.len() - 1,
+                        parts: repeated,
+                        separator,
+                    });
+                }
+                _ => {
+                    return Err((
+                        "expected an identifier or `(` after `#`; preprocessor directives are not supported".to_string(),
+                        punct.span(),
+                    ))
+                }
+            },
+            TokenTree::Punct(punct) => parts.push(Part::Token {
+                text: punct.as_char().to_string(),
+                span: punct.span(),
+                is_spaced: punct.spacing() == Spacing::Alone,
+            }),
+            TokenTree::Ident(ident) => parts.push(Part::Token {
+                text: ident.to_string().trim_start_matches("r#").to_string(),
+                span: ident.span(),
+                is_spaced: true,
+            }),
+            TokenTree::Literal(literal) => parts.push(Part::Token {
+                text: literal.to_string(),
+                span: literal.span(),
+                is_spaced: true,
+            }),
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+
+                parts.push(Part::Token {
+                    text: open.to_string(),
+                    span: group.span_open(),
+                    is_spaced: false,
+                });
+                parts.extend(self::parts(
+                    group.stream(),
+                    is_repeated,
+                    next_id,
+                    repetitions,
+                )?);
+                parts.push(Part::Token {
+                    text: close.to_string(),
+                    span: group.span_close(),
+                    is_spaced: true,
+                });
+            }
+        }
+    }
+
+    Ok(parts)
+}
+
+/// The distinct variables interpolated within a repetition, in order of appearance.
+fn repeated_variables(parts: &[Part]) -> Vec<String> {
+    let mut variables = Vec::new();
+    for part in parts {
+        if let Part::Interpolation { variable, .. } = part {
+            if !variables.contains(variable) {
+                variables.push(variable.clone());
+            }
+        }
+    }
+    variables
+}
+
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    let tokens: TokenStream = format!("::core::compile_error!({})", Literal::string(message))
+        .parse()
+        .expect("the error is valid Rust");
+
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
@@ -0,0 +1,63 @@
+use c_codegen::{Identifier, Statement, Value};
+use c_codegen_macros::{c_expr, c_item, c_stmt};
+
+#[test]
+fn expressions() -> anyhow::Result<()> {
+    let x = Identifier::new("x")?;
+    let expression = c_expr!(#x * 2 + f(#x, sizeof(int)));
+    assert_eq!(expression.to_string(), "x * 2 + f(x, sizeof (int))");
+
+    let arguments = vec![Value::signed_integer(1), Value::signed_integer(2)];
+    let names = ["a", "b"]
+        .into_iter()
+        .map(Identifier::new)
+        .collect::<Result<Vec<_>, _>>()?;
+    let call = c_expr!(g(#(#arguments + #names),*));
+    assert_eq!(call.to_string(), "g(1 + a, 2 + b)");
+
+    let sum = c_expr!(#x + 1);
+    let product = c_expr!(#sum * 3);
+    assert_eq!(product.to_string(), "(x + 1) * 3");
+
+    // A comma operator is parenthesized as an argument, but not as a whole expression
+    let pair = c_expr!(#x, 1);
+    assert_eq!(c_expr!(f(#pair)).to_string(), "f((x, 1))");
+    assert_eq!(c_expr!(#pair).to_string(), "x, 1");
+
+    let empty: Vec<Value> = Vec::new();
+    assert_eq!(c_expr!(g(#(#empty),*)).to_string(), "g()");
+
+    Ok(())
+}
+
+#[test]
+fn statements() -> anyhow::Result<()> {
+    let condition = c_expr!(i < 10);
+    let body: Vec<Statement> = vec![c_stmt!(i++;), c_stmt!(continue;)];
+    let statement = c_stmt!(while (#condition) { #(#body)* });
+    assert_eq!(
+        statement.to_string(),
+        "while (i < 10)\n{\n  i++;\n  continue;\n}"
+    );
+
+    let values = vec![Value::signed_integer(1), Value::signed_integer(2)];
+    let statement = c_stmt!({ int x = 0; #(x += #values; f(x);)* return; });
+    assert_eq!(
+        statement.to_string(),
+        "{\n  int x = 0;\n  x += 1;\n  f(x);\n  x += 2;\n  f(x);\n  return;\n}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn items() -> anyhow::Result<()> {
+    let code = Value::signed_integer(0);
+    let main = c_item!(int main(void) { return #code; });
+    assert_eq!(main.to_string(), "int\nmain (void) {\n  return 0;\n}");
+
+    let item = c_item!(static const char *name = "c_codegen";);
+    assert_eq!(item.to_string(), "static const char *name = \"c_codegen\";");
+
+    Ok(())
+}
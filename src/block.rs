@@ -2,7 +2,8 @@ use pretty::Pretty;
 
 use crate::{pretty::impl_display_via_pretty, Statement};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Block {
     pub statements: Vec<Statement>,
 }
@@ -38,7 +39,11 @@ impl_display_via_pretty!(Block, 80);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Statement, Value};
+    use crate::{
+        r#type::{member::Member, Function, Struct},
+        statement::Include,
+        ConcreteType, FunctionDeclaration, Identifier, Statement, Value,
+    };
 
     #[test]
     fn single_line_block_indentation() -> anyhow::Result<()> {
@@ -94,6 +99,52 @@ mod tests {
     42;
   }
   2;
+}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn local_declarations() -> anyhow::Result<()> {
+        let block = Block {
+            statements: vec![
+                Include::with_angle_brackets("stdio.h").into(),
+                Struct::Definition {
+                    doc: None,
+                    attributes: Default::default(),
+                    name: Some(Identifier::new("point")?),
+                    members: vec![Member {
+                        doc: None,
+                        ty: ConcreteType::int(),
+                        name: Identifier::new("x")?,
+                        bit_field_size: None,
+                        alignment: None,
+                    }],
+                }
+                .into(),
+                FunctionDeclaration {
+                    doc: None,
+                    is_static: false,
+                    is_inline: false,
+                    name: Identifier::new("helper")?,
+                    ty: Function {
+                        parameters: Vec::new(),
+                        return_ty: ConcreteType::int(),
+                        is_variadic: false,
+                        has_unspecified_parameters: false,
+                    },
+                }
+                .into(),
+            ],
+        };
+        assert_eq!(
+            block.to_string(),
+            r#"{
+  #include <stdio.h>
+  struct point {
+    int x;
+  };
+  int helper (void);
 }"#
         );
         Ok(())
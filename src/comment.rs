@@ -0,0 +1,358 @@
+use pretty::Pretty;
+
+use crate::{pretty::impl_display_via_pretty, Identifier};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Style {
+    /// `/* ... */`
+    Block,
+    /// `// ...`
+    Line,
+}
+
+/// A free-standing comment.
+///
+/// The text is wrapped to the text width. Line breaks in the text start a new paragraph.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Comment {
+    pub text: String,
+    pub style: Style,
+}
+
+impl Comment {
+    /// Creates a new instance using a block comment.
+    pub fn block<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            style: Style::Block,
+        }
+    }
+
+    /// Creates a new instance using line comments.
+    pub fn line<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            style: Style::Line,
+        }
+    }
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Comment
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        // A line comment whose text contains a word that ends in a backslash is generated as a block comment instead,
+        // since the backslash at the end of a wrapped line would continue the comment on the next line
+        let is_spliced = self
+            .text
+            .split_whitespace()
+            .any(|word| word.ends_with('\\'));
+        let style = if is_spliced { Style::Block } else { self.style };
+
+        match style {
+            Style::Block => pretty_block(
+                allocator,
+                "/*",
+                paragraphs(&self.text).map(|text| (text, "")),
+            ),
+            Style::Line => {
+                let mut paragraphs = paragraphs(&self.text).peekable();
+                if paragraphs.peek().is_none() {
+                    return allocator.text("//");
+                }
+
+                allocator.intersperse(
+                    paragraphs.map(|text| {
+                        if text.is_empty() {
+                            allocator.text("//")
+                        } else {
+                            allocator
+                                .text("// ")
+                                .append(pretty_paragraph(allocator, text, "// "))
+                        }
+                    }),
+                    // Separate consecutive paragraphs by a blank comment line
+                    allocator
+                        .hardline()
+                        .append(allocator.text("//"))
+                        .append(allocator.hardline()),
+                )
+            }
+        }
+    }
+}
+
+impl_display_via_pretty!(Comment, 80);
+
+/// A Doxygen documentation comment.
+///
+/// # Source
+///
+/// https://www.doxygen.nl/manual/docblocks.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DocComment {
+    pub text: String,
+    /// Descriptions of function parameters, emitted as `@param` commands.
+    pub parameters: Vec<(Identifier, String)>,
+    /// Description of a function's return value, emitted as `@return` command.
+    pub returns: Option<String>,
+}
+
+impl DocComment {
+    /// Creates a new instance without parameter and return value descriptions.
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            parameters: Vec::new(),
+            returns: None,
+        }
+    }
+
+    /// Pretty prints the comment on the line(s) preceding a declaration.
+    pub(crate) fn pretty_preceding<'a, AllocatorT, AnnotationT>(
+        comment: Option<Self>,
+        allocator: &'a AllocatorT,
+    ) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT>
+    where
+        AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+        AllocatorT::Doc: Clone,
+        AnnotationT: Clone + 'a,
+    {
+        if let Some(comment) = comment {
+            comment.pretty(allocator).append(allocator.hardline())
+        } else {
+            allocator.nil()
+        }
+    }
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for DocComment
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|(name, text)| (format!("@param {name} {text}"), ""));
+
+        let returns = self
+            .returns
+            .iter()
+            .map(|text| (format!("@return {text}"), ""));
+
+        // Commands are separated from the description by a blank line
+        let commands = parameters
+            .chain(returns)
+            .enumerate()
+            .map(|(index, (text, _))| {
+                let separator = if index == 0 && !self.text.is_empty() {
+                    "\n"
+                } else {
+                    ""
+                };
+
+                (text, separator)
+            });
+
+        let paragraphs = paragraphs(&self.text)
+            .filter(|text| !text.is_empty())
+            .map(|text| (text.to_string(), ""))
+            .chain(commands)
+            .collect::<Vec<_>>();
+
+        pretty_block(
+            allocator,
+            "/**",
+            paragraphs
+                .iter()
+                .map(|(text, separator)| (text.as_str(), *separator)),
+        )
+    }
+}
+
+impl_display_via_pretty!(DocComment, 80);
+
+/// Splits the text into paragraphs at line breaks.
+fn paragraphs(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().map(str::trim)
+}
+
+/// Pretty prints a block comment that fits on a single line if possible, and otherwise starts each line with ` * `.
+///
+/// Each paragraph can be preceded by a separator, which is `"\n"` for a blank line.
+fn pretty_block<'a, 't, AllocatorT, AnnotationT, I>(
+    allocator: &'a AllocatorT,
+    opening: &'static str,
+    paragraphs: I,
+) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT>
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+    I: Iterator<Item = (&'t str, &'t str)>,
+{
+    // Empty paragraphs are a bare ` *`, without trailing whitespace
+    let line_start = |text: &str| {
+        if text.is_empty() {
+            allocator
+                .hardline()
+                .append(allocator.text(" *"))
+                .flat_alt(allocator.nil())
+        } else {
+            allocator
+                .hardline()
+                .append(allocator.text(" * "))
+                .flat_alt(allocator.space())
+        }
+    };
+
+    let body = allocator.concat(paragraphs.map(|(text, separator)| {
+        let separator = if separator.is_empty() {
+            allocator.nil()
+        } else {
+            allocator.hardline().append(allocator.text(" *"))
+        };
+
+        separator
+            .append(line_start(text))
+            .append(pretty_paragraph(allocator, text, " * "))
+    }));
+
+    allocator
+        .text(opening)
+        .append(body)
+        .append(
+            allocator
+                .hardline()
+                .append(allocator.text(" */"))
+                .flat_alt(allocator.text(" */")),
+        )
+        .group()
+}
+
+/// Pretty prints the words of a paragraph, wrapping them to the text width.
+///
+/// Wrapped lines are started with the given prefix.
+fn pretty_paragraph<'a, AllocatorT, AnnotationT>(
+    allocator: &'a AllocatorT,
+    text: &str,
+    prefix: &'static str,
+) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT>
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    // Comments are terminated by `*/`, so it cannot occur in the text
+    let text = text.replace("*/", "* /");
+
+    allocator.intersperse(
+        text.split_whitespace()
+            .map(|word| allocator.text(word.to_string()))
+            .collect::<Vec<_>>(),
+        allocator
+            .hardline()
+            .append(allocator.text(prefix))
+            .flat_alt(allocator.space())
+            .group(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line() {
+        let generated = Comment::line("Hello, world!").to_string();
+        assert_eq!(generated, "// Hello, world!");
+
+        let generated = Comment::line("First paragraph.\nSecond paragraph.").to_string();
+        assert_eq!(
+            generated,
+            r#"// First paragraph.
+//
+// Second paragraph."#
+        );
+    }
+
+    #[test]
+    fn line_edge_cases() {
+        assert_eq!(Comment::line("").to_string(), "//");
+        assert_eq!(
+            Comment::line("First.\n\nThird.").to_string(),
+            "// First.\n//\n//\n//\n// Third."
+        );
+
+        // A trailing backslash would splice the next line into the comment
+        assert_eq!(
+            Comment::line("Files are stored in C:\\data\\").to_string(),
+            "/* Files are stored in C:\\data\\ */"
+        );
+    }
+
+    #[test]
+    fn block() {
+        let generated = Comment::block("Hello, world!").to_string();
+        assert_eq!(generated, "/* Hello, world! */");
+
+        let generated = Comment::block("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.").to_string();
+        assert_eq!(
+            generated,
+            r#"/*
+ * Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod
+ * tempor incididunt ut labore et dolore magna aliqua.
+ */"#
+        );
+
+        // Empty paragraphs don't end in trailing whitespace
+        let generated = Comment::block("Lorem ipsum dolor sit amet, consectetur adipiscing elit.\n\nSed do eiusmod tempor incididunt ut labore et dolore magna aliqua.").to_string();
+        assert_eq!(
+            generated,
+            "/*\n * Lorem ipsum dolor sit amet, consectetur adipiscing elit.\n *\n * Sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.\n */"
+        );
+    }
+
+    #[test]
+    fn escaped_terminator() {
+        let generated = Comment::block("Matches */ and /* literally.").to_string();
+        assert_eq!(generated, "/* Matches * / and /* literally. */");
+    }
+
+    #[test]
+    fn doc_comment() -> anyhow::Result<()> {
+        let generated = DocComment::new("The x coordinate.").to_string();
+        assert_eq!(generated, "/** The x coordinate. */");
+
+        let generated = DocComment {
+            text: "Adds two values.".to_string(),
+            parameters: vec![
+                (Identifier::new("x")?, "The first value.".to_string()),
+                (Identifier::new("y")?, "The second value.".to_string()),
+            ],
+            returns: Some("The sum of both values.".to_string()),
+        }
+        .to_string();
+        assert_eq!(
+            generated,
+            r#"/**
+ * Adds two values.
+ *
+ * @param x The first value.
+ * @param y The second value.
+ * @return The sum of both values.
+ */"#
+        );
+
+        Ok(())
+    }
+}
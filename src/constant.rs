@@ -0,0 +1,698 @@
+//! Evaluation of constant expressions.
+//!
+//! Like [`inference`](crate::inference), evaluation follows the data model of the environment.
+
+use crate::{
+    inference::{self, Environment, IntegerRank, TypeError},
+    layout::{LayoutError, MemberLayout},
+    operator::{BinaryOperatorKind, PrefixOperatorKind, SizeOf},
+    r#type::Real,
+    ConcreteType, Expression, Value,
+};
+
+/// The maximum nesting of enumeration constants that refer to other enumeration constants.
+const MAX_DEPTH: usize = 256;
+
+/// The value of a constant expression.
+#[derive(Clone, Debug)]
+pub enum Constant {
+    Integer { value: i128, ty: ConcreteType },
+    Real { value: f64, ty: Real },
+}
+
+impl Constant {
+    pub fn ty(&self) -> ConcreteType {
+        match self {
+            Constant::Integer { ty, .. } => ty.clone(),
+            Constant::Real { ty, .. } => ConcreteType::Real(*ty),
+        }
+    }
+
+    /// Returns the value of an integer constant.
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Constant::Integer { value, .. } => Some(*value),
+            Constant::Real { .. } => None,
+        }
+    }
+
+    fn is_true(&self) -> bool {
+        match self {
+            Constant::Integer { value, .. } => *value != 0,
+            Constant::Real { value, .. } => *value != 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum EvaluationError {
+    #[error("`{0}` is not a constant expression")]
+    NotConstant(String),
+    #[error("`{0}` is out of range of its type")]
+    Overflow(String),
+    #[error("`{0}` divides by zero")]
+    DivisionByZero(String),
+    #[error("`{0}` shifts by a negative amount, or by at least the width of its type")]
+    ShiftOutOfRange(String),
+    #[error(transparent)]
+    Layout(#[from] LayoutError),
+    #[error(transparent)]
+    Type(#[from] TypeError),
+}
+
+impl Environment {
+    /// Evaluates an integer or floating constant expression.
+    ///
+    /// Unsigned arithmetic wraps around, whereas signed overflow, division by zero and out-of-range shifts are errors.
+    /// Conversions of out-of-range values to signed integer types wrap around, like GCC and Clang do.
+    pub fn evaluate(&self, expression: &Expression) -> Result<Constant, EvaluationError> {
+        self.evaluate_at_depth(expression, 0)
+    }
+
+    fn evaluate_at_depth(
+        &self,
+        expression: &Expression,
+        depth: usize,
+    ) -> Result<Constant, EvaluationError> {
+        let not_constant = || EvaluationError::NotConstant(expression.to_string());
+
+        match expression {
+            Expression::AlignOf(ty) => Ok(size(self.layout(ty)?.align)),
+            Expression::OffsetOf(offsetof) => {
+                self.type_of(expression)?;
+
+                // The offset of a bit-field cannot be taken
+                match self.record_layout(&offsetof.ty)?.member(&offsetof.member) {
+                    Some(MemberLayout {
+                        offset,
+                        bit_field: None,
+                        ..
+                    }) => Ok(size(*offset)),
+                    _ => Err(not_constant()),
+                }
+            }
+            Expression::BinaryOperator(operation) => {
+                let left = self.evaluate_at_depth(&operation.left, depth)?;
+
+                // The right operand of a logical operator is only evaluated if needed
+                let short_circuit = match operation.operator {
+                    BinaryOperatorKind::And if !left.is_true() => Some(false),
+                    BinaryOperatorKind::Or if left.is_true() => Some(true),
+                    BinaryOperatorKind::And | BinaryOperatorKind::Or => None,
+                    _ => {
+                        let right = self.evaluate_at_depth(&operation.right, depth)?;
+                        return self.binary_operator_constant(
+                            operation.operator,
+                            left,
+                            right,
+                            expression,
+                        );
+                    }
+                };
+
+                let value = match short_circuit {
+                    Some(value) => value,
+                    None => self.evaluate_at_depth(&operation.right, depth)?.is_true(),
+                };
+
+                Ok(int(value.into()))
+            }
+            Expression::Cast(cast) => {
+                let value = self.evaluate_at_depth(&cast.expression, depth)?;
+                self.convert(value, &inference::scalar_type(&cast.new_ty), expression)
+            }
+            Expression::Conditional(conditional) => {
+                let condition = self.evaluate_at_depth(&conditional.condition, depth)?;
+                let (chosen, other) = if condition.is_true() {
+                    (&conditional.then_branch, &conditional.else_branch)
+                } else {
+                    (&conditional.else_branch, &conditional.then_branch)
+                };
+
+                // The other branch is not evaluated, but contributes to the type of the result
+                let value = self.evaluate_at_depth(chosen, depth)?;
+                let other = self.type_of(other)?;
+                if !inference::is_arithmetic(&other) {
+                    return Err(not_constant());
+                }
+
+                let ty = self.usual_arithmetic_conversions(value.ty(), other);
+                self.convert(value, &ty, expression)
+            }
+            Expression::Parentheses(inner) => self.evaluate_at_depth(inner, depth),
+            Expression::PrefixOperator(operation) => {
+                let operand = self.evaluate_at_depth(&operation.operand, depth)?;
+
+                match (operation.operator, operand) {
+                    (PrefixOperatorKind::Positive, Constant::Real { value, ty }) => {
+                        Ok(Constant::Real { value, ty })
+                    }
+                    (PrefixOperatorKind::Negative, Constant::Real { value, ty }) => {
+                        Ok(Constant::Real { value: -value, ty })
+                    }
+                    (PrefixOperatorKind::Positive, operand) => {
+                        let ty = inference::promote(operand.ty());
+                        self.convert(operand, &ty, expression)
+                    }
+                    (PrefixOperatorKind::Negative, operand) => {
+                        let ty = inference::promote(operand.ty());
+                        let value = self
+                            .convert(operand, &ty, expression)?
+                            .as_integer()
+                            .ok_or_else(not_constant)?;
+
+                        self.integer(-value, ty, expression)
+                    }
+                    _ => Err(not_constant()),
+                }
+            }
+            Expression::SizeOf(sizeof) => {
+                let ty = match sizeof.as_ref() {
+                    SizeOf::Expression(inner) => self.type_of(inner)?,
+                    SizeOf::Type(ty) => ty.clone(),
+                };
+
+                Ok(size(self.layout(&ty)?.size))
+            }
+            Expression::Value(value) => {
+                let ty = inference::value_type(value, self.data_model())?;
+                match value {
+                    Value::Char { value } => Ok(int((*value as u32).into())),
+                    Value::Enum { value, .. } => Ok(int((*value).into())),
+                    Value::Real { value, kind } => Ok(real(*value, kind.unwrap_or(Real::Double))),
+                    Value::SignedInteger { value, .. } => {
+                        self.integer((*value).into(), ty, expression)
+                    }
+                    Value::Size { value } => self.integer(*value as i128, ty, expression),
+                    Value::UnsignedInteger { value, .. } => {
+                        self.integer((*value).into(), ty, expression)
+                    }
+                    Value::Array { .. }
+                    | Value::Pointer { .. }
+                    | Value::String(_)
+                    | Value::Struct { .. } => Err(not_constant()),
+                }
+            }
+            Expression::Variable(variable) => {
+                let value = self.constant(variable).ok_or_else(not_constant)?;
+                if depth >= MAX_DEPTH {
+                    return Err(not_constant());
+                }
+
+                let value = self.evaluate_at_depth(value, depth + 1)?;
+                let ty = self.type_of(expression)?;
+                self.convert(value, &ty, expression)
+            }
+            Expression::ArraySubscript(_)
+            | Expression::Assignment(_)
+            | Expression::CommaOperator(_)
+            | Expression::CompoundAssignment(_)
+            | Expression::FunctionCall(_)
+            | Expression::IndirectMemberAccess(_)
+            | Expression::InitializerList(_)
+            | Expression::MemberAccess(_)
+            | Expression::PostfixOperator(_) => Err(not_constant()),
+        }
+    }
+
+    fn binary_operator_constant(
+        &self,
+        operator: BinaryOperatorKind,
+        left: Constant,
+        right: Constant,
+        expression: &Expression,
+    ) -> Result<Constant, EvaluationError> {
+        let ty = self.binary_operator(operator, left.ty(), right.ty())?;
+
+        let is_comparison = matches!(
+            operator,
+            BinaryOperatorKind::Eq
+                | BinaryOperatorKind::Ne
+                | BinaryOperatorKind::Lt
+                | BinaryOperatorKind::Le
+                | BinaryOperatorKind::Gt
+                | BinaryOperatorKind::Ge
+        );
+        if is_comparison {
+            let common = self.usual_arithmetic_conversions(left.ty(), right.ty());
+            let left = self.convert(left, &common, expression)?;
+            let right = self.convert(right, &common, expression)?;
+
+            let ordering = match (left, right) {
+                (Constant::Integer { value: left, .. }, Constant::Integer { value: right, .. }) => {
+                    left.partial_cmp(&right)
+                }
+                (left, right) => as_f64(&left).partial_cmp(&as_f64(&right)),
+            };
+
+            let value = ordering.is_some_and(|ordering| match operator {
+                BinaryOperatorKind::Eq => ordering.is_eq(),
+                BinaryOperatorKind::Ne => ordering.is_ne(),
+                BinaryOperatorKind::Lt => ordering.is_lt(),
+                BinaryOperatorKind::Le => ordering.is_le(),
+                BinaryOperatorKind::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            });
+
+            // NaN compares unequal to everything
+            return Ok(int((value
+                || (ordering.is_none()
+                    && matches!(operator, BinaryOperatorKind::Ne)))
+            .into()));
+        }
+
+        if let ConcreteType::Real(kind) = ty {
+            let (left, right) = (as_f64(&left), as_f64(&right));
+            let value = match operator {
+                BinaryOperatorKind::Add => left + right,
+                BinaryOperatorKind::Sub => left - right,
+                BinaryOperatorKind::Mul => left * right,
+                _ => left / right,
+            };
+
+            return Ok(real(value, kind));
+        }
+
+        let rank = inference::integer_rank(&ty, self.data_model())
+            .ok_or_else(|| EvaluationError::NotConstant(expression.to_string()))?;
+
+        let is_shift = matches!(
+            operator,
+            BinaryOperatorKind::LShift | BinaryOperatorKind::RShift
+        );
+        let left = self
+            .convert(left, &ty, expression)?
+            .as_integer()
+            .unwrap_or_default();
+        let right = if is_shift {
+            right.as_integer().unwrap_or_default()
+        } else {
+            self.convert(right, &ty, expression)?
+                .as_integer()
+                .unwrap_or_default()
+        };
+
+        let value = match operator {
+            BinaryOperatorKind::Add => left + right,
+            BinaryOperatorKind::Sub => left - right,
+            BinaryOperatorKind::Mul => left.wrapping_mul(right),
+            BinaryOperatorKind::Div | BinaryOperatorKind::Mod if right == 0 => {
+                return Err(EvaluationError::DivisionByZero(expression.to_string()))
+            }
+            BinaryOperatorKind::Div => left / right,
+            BinaryOperatorKind::Mod => left % right,
+            BinaryOperatorKind::BitAnd => left & right,
+            BinaryOperatorKind::BitOr => left | right,
+            BinaryOperatorKind::BitXor => left ^ right,
+            BinaryOperatorKind::LShift | BinaryOperatorKind::RShift
+                if right < 0 || right >= rank.width.into() =>
+            {
+                return Err(EvaluationError::ShiftOutOfRange(expression.to_string()))
+            }
+            // Shifting a negative value to the left is undefined
+            BinaryOperatorKind::LShift if left < 0 => {
+                return Err(EvaluationError::Overflow(expression.to_string()))
+            }
+            BinaryOperatorKind::LShift => left << right,
+            _ => left >> right,
+        };
+
+        self.integer(value, ty, expression)
+    }
+
+    /// Converts the constant to the given arithmetic type.
+    fn convert(
+        &self,
+        constant: Constant,
+        ty: &ConcreteType,
+        expression: &Expression,
+    ) -> Result<Constant, EvaluationError> {
+        let ty = self.resolve(ty)?;
+
+        if let ConcreteType::Real(kind) = ty {
+            return Ok(real(as_f64(&constant), kind));
+        }
+
+        if matches!(ty, ConcreteType::Bool) {
+            return Ok(Constant::Integer {
+                value: constant.is_true().into(),
+                ty,
+            });
+        }
+
+        let Some(rank) = inference::integer_rank(&ty, self.data_model()) else {
+            return Err(EvaluationError::NotConstant(expression.to_string()));
+        };
+
+        match constant {
+            Constant::Integer { value, .. } => Ok(Constant::Integer {
+                value: wrap(value, rank),
+                ty,
+            }),
+            Constant::Real { value, .. } => {
+                // Converting a real that is out of range of the integer type is undefined
+                let truncated = value.trunc() as i128;
+                if !value.is_finite() || wrap(truncated, rank) != truncated {
+                    return Err(EvaluationError::Overflow(expression.to_string()));
+                }
+
+                Ok(Constant::Integer {
+                    value: truncated,
+                    ty,
+                })
+            }
+        }
+    }
+
+    /// Creates an integer constant, which wraps around for unsigned types and must be in range for signed types.
+    fn integer(
+        &self,
+        value: i128,
+        ty: ConcreteType,
+        expression: &Expression,
+    ) -> Result<Constant, EvaluationError> {
+        let Some(rank) = inference::integer_rank(&ty, self.data_model()) else {
+            return Err(EvaluationError::NotConstant(expression.to_string()));
+        };
+
+        if rank.is_signed && wrap(value, rank) != value {
+            return Err(EvaluationError::Overflow(expression.to_string()));
+        }
+
+        Ok(Constant::Integer {
+            value: wrap(value, rank),
+            ty,
+        })
+    }
+}
+
+fn int(value: i128) -> Constant {
+    Constant::Integer {
+        value,
+        ty: ConcreteType::int(),
+    }
+}
+
+fn size(value: u64) -> Constant {
+    Constant::Integer {
+        value: value.into(),
+        ty: ConcreteType::Size,
+    }
+}
+
+/// Creates a real constant, rounded to the precision of its type.
+fn real(value: f64, ty: Real) -> Constant {
+    let value = match ty {
+        Real::Float => f64::from(value as f32),
+        Real::Double | Real::LongDouble => value,
+    };
+
+    Constant::Real { value, ty }
+}
+
+/// Reduces the value modulo 2^width into the range of the integer type.
+fn wrap(value: i128, rank: IntegerRank) -> i128 {
+    let modulus = 1i128 << rank.width;
+    let value = value.rem_euclid(modulus);
+
+    if rank.is_signed && value >= modulus / 2 {
+        value - modulus
+    } else {
+        value
+    }
+}
+
+fn as_f64(constant: &Constant) -> f64 {
+    match constant {
+        Constant::Integer { value, .. } => *value as f64,
+        Constant::Real { value, .. } => *value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operator::{BinaryOperator, Cast, PrefixOperator},
+        r#type::{member::Member, Array, Enum, Enumerator, Integer, IntegerKind, Scalar, Struct},
+        validate::Segment,
+        CFileBuilder, Identifier, VariableDeclaration,
+    };
+
+    fn binary<L, R>(left: L, operator: BinaryOperatorKind, right: R) -> Expression
+    where
+        L: Into<Expression>,
+        R: Into<Expression>,
+    {
+        BinaryOperator {
+            left: left.into(),
+            operator,
+            right: right.into(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn arithmetic() -> anyhow::Result<()> {
+        let environment = Environment::default();
+
+        let cases = [
+            (
+                binary(
+                    Value::unsigned_integer(1),
+                    BinaryOperatorKind::Sub,
+                    Value::unsigned_integer(2),
+                ),
+                4294967295,
+                "unsigned int",
+            ),
+            (
+                binary(
+                    PrefixOperator {
+                        operand: Value::signed_integer(1).into(),
+                        operator: PrefixOperatorKind::Negative,
+                    },
+                    BinaryOperatorKind::Lt,
+                    Value::unsigned_integer(0),
+                ),
+                0,
+                "int",
+            ),
+            (
+                binary(
+                    Value::signed_integer(-7),
+                    BinaryOperatorKind::Mod,
+                    Value::signed_integer(2),
+                ),
+                -1,
+                "int",
+            ),
+            (
+                binary(
+                    Value::signed_integer(1),
+                    BinaryOperatorKind::LShift,
+                    Value::signed_integer(30),
+                ),
+                1 << 30,
+                "int",
+            ),
+            (
+                Cast {
+                    new_ty: Scalar::Integer(Integer {
+                        kind: IntegerKind::Char,
+                        is_signed: false,
+                    }),
+                    expression: Value::signed_integer(300).into(),
+                }
+                .into(),
+                44,
+                "unsigned char",
+            ),
+            (
+                binary(
+                    Value::signed_integer(0),
+                    BinaryOperatorKind::And,
+                    binary(
+                        Value::signed_integer(1),
+                        BinaryOperatorKind::Div,
+                        Value::signed_integer(0),
+                    ),
+                ),
+                0,
+                "int",
+            ),
+        ];
+
+        for (expression, expected, ty) in cases {
+            let constant = environment.evaluate(&expression)?;
+            assert_eq!(constant.as_integer(), Some(expected));
+            assert_eq!(constant.ty().to_string(), ty);
+        }
+
+        let constant = environment.evaluate(&binary(
+            Value::float(1.1),
+            BinaryOperatorKind::Mul,
+            Value::signed_integer(2),
+        ))?;
+        assert!(matches!(
+            constant,
+            Constant::Real { value, ty: Real::Float } if value == f64::from(1.1f32 * 2.0)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> anyhow::Result<()> {
+        let environment = Environment::default();
+
+        let overflow = binary(
+            Value::signed_integer(2147483647),
+            BinaryOperatorKind::Add,
+            Value::signed_integer(1),
+        );
+        assert_eq!(
+            environment.evaluate(&overflow).unwrap_err().to_string(),
+            "`2147483647 + 1` is out of range of its type"
+        );
+
+        let shift = binary(
+            Value::signed_integer(1),
+            BinaryOperatorKind::LShift,
+            Value::signed_integer(31),
+        );
+        assert!(matches!(
+            environment.evaluate(&shift),
+            Err(EvaluationError::Overflow(_))
+        ));
+
+        let division = binary(
+            Value::signed_integer(1),
+            BinaryOperatorKind::Div,
+            Value::signed_integer(0),
+        );
+        assert!(matches!(
+            environment.evaluate(&division),
+            Err(EvaluationError::DivisionByZero(_))
+        ));
+
+        let shift = binary(
+            Value::unsigned_integer(1),
+            BinaryOperatorKind::LShift,
+            Value::signed_integer(32),
+        );
+        assert!(matches!(
+            environment.evaluate(&shift),
+            Err(EvaluationError::ShiftOutOfRange(_))
+        ));
+
+        let variable = Expression::from(Identifier::new("x")?);
+        assert!(matches!(
+            environment.evaluate(&variable),
+            Err(EvaluationError::NotConstant(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sizeof_and_enumerators() -> anyhow::Result<()> {
+        let mut environment = Environment::default();
+        environment.define_tag(Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
+            name: Some(Identifier::new("pair")?),
+            members: vec![
+                Member {
+                    doc: None,
+                    ty: ConcreteType::Char,
+                    name: Identifier::new("tag")?,
+                    bit_field_size: None,
+                    alignment: None,
+                },
+                Member {
+                    doc: None,
+                    ty: ConcreteType::double(),
+                    name: Identifier::new("value")?,
+                    bit_field_size: None,
+                    alignment: None,
+                },
+            ],
+        });
+
+        let pair = ConcreteType::Struct(Struct::Tag {
+            name: Identifier::new("pair")?,
+        });
+        let sizeof = |ty| SizeOf::Type(ty).into();
+        assert_eq!(
+            environment.evaluate(&sizeof(pair.clone()))?.as_integer(),
+            Some(16)
+        );
+        assert_eq!(
+            environment
+                .evaluate(&Expression::AlignOf(pair))?
+                .as_integer(),
+            Some(8)
+        );
+        assert_eq!(
+            environment
+                .evaluate(&sizeof(ConcreteType::Array(Array {
+                    element_type: Box::new(ConcreteType::int()),
+                    size: 4.into(),
+                })))?
+                .as_integer(),
+            Some(16)
+        );
+        assert!(matches!(
+            environment.evaluate(&sizeof(ConcreteType::Struct(Struct::Tag {
+                name: Identifier::new("missing")?,
+            }))),
+            Err(EvaluationError::Layout(LayoutError::UnknownLayout(_)))
+        ));
+
+        // Escape sequences of string literals count as a single byte
+        let string = Expression::from(Value::String("\\n".to_string()));
+        assert_eq!(
+            environment
+                .evaluate(&SizeOf::Expression(string).into())?
+                .as_integer(),
+            Some(2)
+        );
+
+        // Enumeration constants from a file
+        let mut file = CFileBuilder::default();
+        file.add_statement(Enum::Definition {
+            doc: None,
+            name: Some(Identifier::new("color")?),
+            underlying_ty: None,
+            values: vec![
+                Enumerator::new(Identifier::new("RED")?),
+                Enumerator::with_value(Identifier::new("GREEN")?, Value::signed_integer(4)),
+                Enumerator::new(Identifier::new("BLUE")?),
+            ]
+            .try_into()?,
+            trailing_comma: false,
+        })
+        .add_statement(VariableDeclaration {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: ConcreteType::int(),
+            identifier: Identifier::new("x")?,
+            initializer: Some(Identifier::new("BLUE")?.into()),
+        });
+
+        let environment = file
+            .symbols()
+            .environment(&vec![Segment::Field("statements"), Segment::Index(1)].into())
+            .expect("declaration is visited");
+        assert_eq!(
+            environment
+                .evaluate(&Identifier::new("BLUE")?.into())?
+                .as_integer(),
+            Some(5)
+        );
+
+        Ok(())
+    }
+}
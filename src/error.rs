@@ -1,11 +1,28 @@
-use crate::{identifier, non_empty_vec};
+use crate::{
+    function, identifier, non_empty_vec, order, parse, r#type::enumeration, standard, value,
+    variable,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error(transparent)]
+    Enumeration(#[from] enumeration::Error),
+    #[error(transparent)]
+    Function(#[from] function::Error),
+    #[error(transparent)]
     Identifier(#[from] identifier::Error),
     #[error(transparent)]
     NonEmptyVec(#[from] non_empty_vec::Error),
+    #[error(transparent)]
+    Order(#[from] order::Error),
+    #[error(transparent)]
+    Parse(#[from] parse::Error),
+    #[error(transparent)]
+    Standard(#[from] standard::Error),
+    #[error(transparent)]
+    Value(#[from] value::Error),
+    #[error(transparent)]
+    Variable(#[from] variable::Error),
 }
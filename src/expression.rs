@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use pretty::Pretty;
 
 use crate::{
@@ -5,17 +7,19 @@ use crate::{
     macros::impl_froms,
     operator::{
         ArraySubscript, Assignment, BinaryOperator, Cast, CommaOperator, CompoundAssignment,
-        Conditional, PostfixOperator, PrefixOperator, SizeOf,
+        Conditional, OffsetOf, PostfixOperator, PrefixOperator, SizeOf,
     },
+    parse,
     pretty::impl_display_via_pretty,
     r#type::{
         member::{IndirectMemberAccess, MemberAccess},
         InitializerList,
     },
-    ConcreteType, Value, Variable,
+    ConcreteType, Standard, Value, Variable,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Expression {
     AlignOf(ConcreteType),
     ArraySubscript(Box<ArraySubscript>),
@@ -29,6 +33,7 @@ pub enum Expression {
     IndirectMemberAccess(Box<IndirectMemberAccess>),
     InitializerList(InitializerList),
     MemberAccess(Box<MemberAccess>),
+    OffsetOf(Box<OffsetOf>),
     Parentheses(Box<Expression>),
     PostfixOperator(Box<PostfixOperator>),
     PrefixOperator(Box<PrefixOperator>),
@@ -49,6 +54,7 @@ impl_froms!(Expression:
     box IndirectMemberAccess,
     InitializerList,
     box MemberAccess,
+    box OffsetOf,
     box PostfixOperator,
     box PrefixOperator,
     box SizeOf,
@@ -56,6 +62,28 @@ impl_froms!(Expression:
     Variable,
 );
 
+impl Expression {
+    /// Parses an expression, e.g. `a[i] * 2 + f(b)`.
+    ///
+    /// Typedef names are not known, so only those of standard headers, e.g. `size_t`, are recognized as types.
+    pub fn parse(source: &str) -> Result<Self, parse::Error> {
+        parse::parse_expression(source, HashMap::new())
+    }
+
+    /// Parses an expression, replacing each placeholder, e.g. `$value`, with the fragment of the same name.
+    ///
+    /// A placeholder can occur wherever a primary expression can, and more than once. Expressions other than primary
+    /// expressions are parenthesized, so they are not split up by the operators around the placeholder.
+    pub fn parse_with<I, S, F>(source: &str, placeholders: I) -> Result<Self, parse::Error>
+    where
+        I: IntoIterator<Item = (S, F)>,
+        S: Into<String>,
+        F: Into<parse::Fragment>,
+    {
+        parse::parse_expression(source, parse::placeholders(placeholders))
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Expression
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
@@ -65,7 +93,7 @@ where
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
         match self {
             Expression::AlignOf(ty) => allocator
-                .text("_Alignof")
+                .text(Standard::current().alignof_keyword())
                 .append(allocator.text(ty.to_string()).parens()),
             Expression::ArraySubscript(array_subscript) => array_subscript.pretty(allocator),
             Expression::Assignment(assignment) => assignment.pretty(allocator),
@@ -78,6 +106,7 @@ where
             Expression::IndirectMemberAccess(member_access) => member_access.pretty(allocator),
             Expression::InitializerList(initializer_list) => initializer_list.pretty(allocator),
             Expression::MemberAccess(member_access) => member_access.pretty(allocator),
+            Expression::OffsetOf(offsetof) => offsetof.pretty(allocator),
             Expression::Parentheses(expr) => expr.pretty(allocator).parens(),
             Expression::PrefixOperator(operation) => operation.pretty(allocator),
             Expression::PostfixOperator(operation) => operation.pretty(allocator),
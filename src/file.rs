@@ -1,39 +1,133 @@
 use core::fmt;
-use std::io;
+use std::{collections::HashMap, io};
 
 use crate::{
+    headers,
     macros::impl_froms,
+    order, parse, pretty,
     r#type::{Definition as TypeDefinition, Enum, Struct, Union},
-    statement::Include,
-    FunctionDeclaration, FunctionDefinition, VariableDeclaration,
+    scope::{self, SymbolTable},
+    standard,
+    statement::{Define, Include, Pragma, StaticAssert, Typedef},
+    validate::{self, Diagnostic},
+    Comment, FunctionDeclaration, FunctionDefinition, Identifier, Standard, StorageClass,
+    VariableDeclaration, VariableDeclarationList,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum FileLevelStatement {
+    Comment(Comment),
+    Define(Define),
     FunctionDeclaration(FunctionDeclaration),
     FunctionDefinition(FunctionDefinition),
     Include(Include),
+    Pragma(Pragma),
+    StaticAssert(StaticAssert),
     TypeDefinition(TypeDefinition),
+    Typedef(Typedef),
     VariableDeclaration(VariableDeclaration),
+    VariableDeclarationList(VariableDeclarationList),
 }
 
-impl_froms!(FileLevelStatement: FunctionDeclaration, FunctionDefinition, Include, TypeDefinition(Enum, Struct, Union), VariableDeclaration);
+impl_froms!(FileLevelStatement: Comment, Define, FunctionDeclaration, FunctionDefinition, Include, Pragma, StaticAssert, TypeDefinition(Enum, Struct, Union), Typedef, VariableDeclaration, VariableDeclarationList);
+
+impl FileLevelStatement {
+    /// Parses a file-level statement, e.g. a function definition, including comments and directives.
+    pub fn parse(source: &str) -> Result<Self, parse::Error> {
+        parse::parse_file_level_statement(source, HashMap::new())
+    }
+
+    /// Parses a file-level statement, replacing each placeholder, e.g. `$value`, with the fragment of the same name.
+    ///
+    /// A placeholder can replace the whole statement, or an expression or statement within it.
+    pub fn parse_with<I, S, F>(source: &str, placeholders: I) -> Result<Self, parse::Error>
+    where
+        I: IntoIterator<Item = (S, F)>,
+        S: Into<String>,
+        F: Into<parse::Fragment>,
+    {
+        parse::parse_file_level_statement(source, parse::placeholders(placeholders))
+    }
+
+    /// Converts the statement into its declaration in a header, or `None` if it is not visible to other translation
+    /// units.
+    fn into_header_statement(self) -> Option<Self> {
+        match self {
+            FileLevelStatement::FunctionDefinition(definition) if definition.is_static => None,
+            FileLevelStatement::FunctionDefinition(definition) => Some(
+                FunctionDeclaration {
+                    doc: definition.doc,
+                    is_static: false,
+                    is_inline: false,
+                    name: definition.name,
+                    ty: definition.ty,
+                }
+                .into(),
+            ),
+            FileLevelStatement::FunctionDeclaration(declaration) if declaration.is_static => None,
+            FileLevelStatement::VariableDeclaration(declaration)
+                if declaration.storage_class == Some(StorageClass::Static) =>
+            {
+                None
+            }
+            FileLevelStatement::VariableDeclaration(declaration) => Some(
+                VariableDeclaration {
+                    storage_class: Some(StorageClass::Extern),
+                    initializer: None,
+                    ..declaration
+                }
+                .into(),
+            ),
+            FileLevelStatement::VariableDeclarationList(declaration)
+                if declaration.storage_class == Some(StorageClass::Static) =>
+            {
+                None
+            }
+            FileLevelStatement::VariableDeclarationList(mut declaration) => {
+                declaration.storage_class = Some(StorageClass::Extern);
+                for declarator in declaration.declarators.iter_mut() {
+                    declarator.initializer = None;
+                }
+
+                Some(declaration.into())
+            }
+            statement => Some(statement),
+        }
+    }
+}
 
 impl fmt::Display for FileLevelStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            FileLevelStatement::Comment(comment) => write!(f, "{comment}"),
+            FileLevelStatement::Define(define) => write!(f, "{define}"),
             FileLevelStatement::FunctionDeclaration(declaration) => write!(f, "{declaration}"),
             FileLevelStatement::FunctionDefinition(definition) => write!(f, "{definition}"),
             FileLevelStatement::Include(include) => write!(f, "{include}"),
+            FileLevelStatement::Pragma(pragma) => write!(f, "{pragma}"),
+            FileLevelStatement::StaticAssert(assertion) => write!(f, "{assertion}"),
             FileLevelStatement::TypeDefinition(definition) => write!(f, "{definition}"),
-            FileLevelStatement::VariableDeclaration(declaration) => write!(f, "{declaration}"),
+            FileLevelStatement::Typedef(typedef) => write!(f, "{typedef}"),
+            FileLevelStatement::VariableDeclaration(declaration) => write!(f, "{declaration};"),
+            FileLevelStatement::VariableDeclarationList(declaration) => {
+                write!(f, "{declaration};")
+            }
         }
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default)
+)]
 pub struct CFileBuilder {
     statements: Vec<FileLevelStatement>,
+    standard: Standard,
+    line_width: Option<usize>,
+    infers_includes: bool,
 }
 
 impl CFileBuilder {
@@ -44,22 +138,146 @@ impl CFileBuilder {
         self
     }
 
+    /// Returns the C standard that code is generated for.
+    pub fn standard(&self) -> Standard {
+        self.standard
+    }
+
+    /// Sets the C standard that code is generated for.
+    ///
+    /// This selects the spelling of keywords that differ between standards (e.g. `_Bool` vs `bool`). Generation fails
+    /// if the file uses a language feature that the standard lacks, or an identifier that is one of its keywords.
+    pub fn set_standard(&mut self, standard: Standard) -> &mut Self {
+        self.standard = standard;
+
+        self
+    }
+
+    /// Returns the line width that code is wrapped at, if it differs from the default of 80 columns.
+    pub fn line_width(&self) -> Option<usize> {
+        self.line_width
+    }
+
+    /// Sets the line width that code is wrapped at.
+    pub fn set_line_width(&mut self, line_width: usize) -> &mut Self {
+        self.line_width = Some(line_width);
+
+        self
+    }
+
+    /// Returns whether the standard headers that the file needs are included automatically.
+    pub fn infers_includes(&self) -> bool {
+        self.infers_includes
+    }
+
+    /// Sets whether the standard headers that the file needs are included automatically.
+    ///
+    /// When enabled, the file is scanned for types, macros and functions of the standard library, e.g. `uint32_t`,
+    /// `size_t`, `bool`, `NULL` or `printf`. Each header that they need and that the file does not include yet is
+    /// included at the top of the file, in alphabetical order.
+    pub fn set_infers_includes(&mut self, infers_includes: bool) -> &mut Self {
+        self.infers_includes = infers_includes;
+
+        self
+    }
+
+    /// Orders the statements of the file such that each one comes after the definitions and declarations it depends
+    /// on, e.g. a structure after the structures that it contains by value.
+    ///
+    /// Structures and unions that refer to each other through pointers are declared in advance, e.g. `struct node;`.
+    /// The file is left unchanged if definitions contain each other by value, which C cannot express.
+    pub fn order_by_dependencies(&mut self) -> Result<&mut Self, order::Error> {
+        self.statements = order::order(&self.statements)?;
+
+        Ok(self)
+    }
+
+    /// Checks the file for semantic errors that would make the generated code fail to compile, e.g. assigning to an
+    /// rvalue or a `const` object, `break` outside of a loop, or `goto` to a label that does not exist.
+    ///
+    /// Unlike [`generate`](Self::generate), this does not stop at the first error.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        self.standard.scope(|| validate::validate(&self.statements))
+    }
+
+    /// Resolves the identifiers in the file to their declarations.
+    ///
+    /// The returned table reports undeclared identifiers, redeclarations, shadowing and reserved names, and can be
+    /// queried for the declaration that a name refers to at a given [`Path`](crate::validate::Path).
+    pub fn symbols(&self) -> SymbolTable {
+        self.standard.scope(|| scope::resolve(&self.statements))
+    }
+
     /// Generates the C code and writes it to the given [`Write`] instance using UTF-8 encoding.
-    pub fn generate<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
-        for declaration in &self.statements {
-            writeln!(writer, "{declaration}")?;
-        }
+    pub fn generate<W: io::Write>(&self, writer: &mut W) -> crate::Result<()> {
+        standard::check(&self.statements, self.standard)?;
+        validate::keywords(&self.statements, self.standard)?;
+        validate::declaration_lists(&self.statements)?;
+        validate::values(&self.statements)?;
+        validate::underlying_types(&self.statements)?;
+        validate::return_types(&self.statements)?;
 
-        Ok(())
+        self.scope(|| {
+            for include in self.inferred_includes(&self.statements) {
+                writeln!(writer, "{include}")?;
+            }
+            for declaration in &self.statements {
+                writeln!(writer, "{declaration}")?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Generates a header for the file, and writes it to the given [`Write`] instance using UTF-8 encoding.
+    ///
+    /// The header contains the declarations of the file that are visible to other translation units, within an
+    /// include guard: function definitions become prototypes, and variables become `extern` declarations without
+    /// initializer. Static functions and variables are left out.
+    pub fn generate_header<W: io::Write>(
+        &self,
+        guard: &Identifier,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        let statements: Vec<_> = self
+            .statements
+            .iter()
+            .cloned()
+            .filter_map(FileLevelStatement::into_header_statement)
+            .collect();
+        standard::check(&statements, self.standard)?;
+        validate::keywords(&statements, self.standard)?;
+        validate::declaration_lists(&statements)?;
+        validate::values(&statements)?;
+        validate::underlying_types(&statements)?;
+        validate::return_types(&statements)?;
+
+        self.scope(|| {
+            writeln!(writer, "#ifndef {guard}")?;
+            writeln!(writer, "#define {guard}")?;
+            writeln!(writer)?;
+            for include in self.inferred_includes(&statements) {
+                writeln!(writer, "{include}")?;
+            }
+            for declaration in &statements {
+                writeln!(writer, "{declaration}")?;
+            }
+            writeln!(writer)?;
+            writeln!(writer, "#endif /* {guard} */")?;
+
+            Ok(())
+        })
     }
 
-    /// Generates the C code and writes it to the given file path.
-    pub fn write_to_file(&self, file_path: &str) -> io::Result<()> {
-        let mut file = std::fs::File::create(file_path)?;
-        self.generate(&mut file)
+    /// Generates the C code and writes it to the given file path. The file is left untouched if generation fails.
+    pub fn write_to_file(&self, file_path: &str) -> crate::Result<()> {
+        let code = self.write_to_string()?;
+        std::fs::write(file_path, code)?;
+
+        Ok(())
     }
 
-    pub fn write_to_string(&self) -> io::Result<String> {
+    pub fn write_to_string(&self) -> crate::Result<String> {
         let mut buffer = Vec::new();
         self.generate(&mut buffer)?;
 
@@ -68,4 +286,247 @@ impl CFileBuilder {
 
         Ok(string)
     }
+
+    /// Returns the standard headers that the statements need but do not include, if includes are inferred.
+    fn inferred_includes(&self, statements: &[FileLevelStatement]) -> Vec<Include> {
+        if self.infers_includes {
+            headers::missing(statements, self.standard)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Renders code for the standard and with the line width of the file within the given closure.
+    fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.standard.scope(|| match self.line_width {
+            Some(line_width) => pretty::with_line_width(line_width, f),
+            None => f(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_file;
+
+    #[test]
+    fn line_width() -> anyhow::Result<()> {
+        let mut file = parse_file("enum color { RED, GREEN, BLUE };")?.file;
+        assert_eq!(file.write_to_string()?, "enum color {RED, GREEN, BLUE};\n");
+
+        file.set_line_width(10);
+        assert_eq!(
+            file.write_to_string()?,
+            "enum color {\n  RED,\n  GREEN,\n  BLUE\n};\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn header() -> anyhow::Result<()> {
+        let file = parse_file(
+            "#include <stddef.h>
+            struct point { int x; int y; };
+            static int counter = 0;
+            int origin_x = 0, origin_y = 0;
+            const char *name = \"point\";
+            static int helper(void) { return 1; }
+            int area(const struct point *point) { return point->x * point->y; }",
+        )?
+        .file;
+
+        let mut header = Vec::new();
+        file.generate_header(&Identifier::new("POINT_H")?, &mut header)?;
+        assert_eq!(
+            String::from_utf8(header)?,
+            r#"#ifndef POINT_H
+#define POINT_H
+
+#include <stddef.h>
+struct point {
+  int x;
+  int y;
+};
+extern int origin_x, origin_y;
+extern const char *name;
+int area (const struct point *point);
+
+#endif /* POINT_H */
+"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn infers_includes() -> anyhow::Result<()> {
+        let mut file = parse_file(
+            "#include <stdio.h>
+            struct buffer { uint32_t length; size_t capacity; char *data; };
+            static void free(struct buffer *buffer) { buffer->data = NULL; }
+            bool is_empty(const struct buffer *buffer) {
+              printf(\"%u\", buffer->length);
+              return buffer->length == 0 ? true : false;
+            }",
+        )?
+        .file;
+        file.set_standard(Standard::C99);
+        assert!(file
+            .write_to_string()?
+            .starts_with("#include <stdio.h>\nstruct buffer {"));
+
+        file.set_infers_includes(true);
+        assert!(file.write_to_string()?.starts_with(
+            "#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+struct buffer {"
+        ));
+
+        let mut header = Vec::new();
+        file.generate_header(&Identifier::new("BUFFER_H")?, &mut header)?;
+        assert!(String::from_utf8(header)?.starts_with(
+            "#ifndef BUFFER_H
+#define BUFFER_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+struct buffer {"
+        ));
+
+        file.set_standard(Standard::C23);
+        assert!(file
+            .write_to_string()?
+            .starts_with("#include <stddef.h>\n#include <stdint.h>\n#include <stdio.h>\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn infers_includes_for_shadowed_names() -> anyhow::Result<()> {
+        let mut file = parse_file(
+            "int clamp(int exit) { int abs = exit < 0 ? -exit : exit; return abs; }
+            void quit(void) { exit(1); }
+            void release(char *free);",
+        )?
+        .file;
+        file.set_infers_includes(true);
+        assert!(file
+            .write_to_string()?
+            .starts_with("#include <stdlib.h>\nint\nclamp"));
+
+        let mut file =
+            parse_file("int clamp(int abs) { for (int exit = 0; exit;) {} return abs; }")?.file;
+        file.set_infers_includes(true);
+        assert!(file.write_to_string()?.starts_with("int\nclamp"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn infers_includes_for_declared_types() -> anyhow::Result<()> {
+        let mut file = parse_file(
+            "typedef int size_t;
+            size_t count(void) { return errno ? 0 : 1; }",
+        )?
+        .file;
+        file.set_infers_includes(true);
+        assert!(file
+            .write_to_string()?
+            .starts_with("#include <errno.h>\ntypedef int size_t;"));
+
+        // `<stdbool.h>` and `<stdint.h>` do not exist before C99
+        let mut file = parse_file("long limit = UINT32_MAX;")?.file;
+        file.set_infers_includes(true);
+        assert!(file.write_to_string()?.starts_with("#include <stdint.h>\n"));
+        file.set_standard(Standard::C89);
+        assert_eq!(file.write_to_string()?, "long limit = UINT32_MAX;\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_finite_reals() -> anyhow::Result<()> {
+        use crate::{value, ConcreteType, Value};
+
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let mut file = CFileBuilder::default();
+            file.add_statement(VariableDeclaration {
+                doc: None,
+                storage_class: None,
+                alignment: None,
+                ty: ConcreteType::double(),
+                identifier: Identifier::new("x")?,
+                initializer: Some(Value::double(value).into()),
+            });
+
+            assert!(matches!(
+                file.write_to_string(),
+                Err(crate::Error::Value(value::Error::NonFiniteReal(_)))
+            ));
+        }
+
+        // Constants beyond the range of `double` are rejected by the parser
+        assert!(parse_file("double x = 1e999;").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_to_file() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!("c-codegen-{}.c", std::process::id()));
+        let path = path.to_str().expect("is valid UTF-8");
+
+        let file = parse_file("int x;")?.file;
+        file.write_to_file(path)?;
+        assert_eq!(std::fs::read_to_string(path)?, "int x;\n");
+
+        // A rejected file does not replace the previous output
+        let mut file = parse_file("void f(...);")?.file;
+        file.set_standard(Standard::C17);
+        assert!(file.write_to_file(path).is_err());
+        assert_eq!(std::fs::read_to_string(path)?, "int x;\n");
+
+        std::fs::remove_file(path)?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() -> anyhow::Result<()> {
+        use crate::r#type::Union;
+
+        let file = parse_file(
+            "#include <stddef.h>
+            struct point { int x; int y : 4; };
+            union value { long integer; double real; };
+            enum color { RED, GREEN = 2, };
+            static const double scale = 1.5;
+            int area(const struct point *point) {
+              for (size_t i = 0; i < 2; i++) { if (point->x > 0) continue; }
+              return point->x * (*point).y;
+            }",
+        )?
+        .file;
+
+        let json = serde_json::to_string(&file)?;
+        let deserialized: CFileBuilder = serde_json::from_str(&json)?;
+        assert_eq!(deserialized, file);
+        assert_eq!(deserialized.write_to_string()?, file.write_to_string()?);
+
+        let empty_union = serde_json::from_str::<Union>(
+            r#"{"Definition":{"doc":null,"name":null,"members":[]}}"#,
+        );
+        assert_eq!(
+            empty_union.unwrap_err().to_string(),
+            "Cannot be empty at line 1 column 51"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,1032 @@
+//! Rewriting of the syntax tree by value.
+//!
+//! Each `fold_*` method of [`Fold`] defaults to the `walk_*` function of the same node, which folds the children of the
+//! node in source order and rebuilds it. An implementation overrides the methods of the nodes it rewrites, and calls
+//! the `walk_*` function from the override to continue into the children.
+
+use crate::{
+    function::{FunctionCall, FunctionParameter},
+    operator::{
+        ArraySubscript, Assignment, BinaryOperator, Cast, CommaOperator, CompoundAssignment,
+        Conditional, OffsetOf, PostfixOperator, PrefixOperator, SizeOf,
+    },
+    r#type::{
+        member::{IndirectMemberAccess, Member, MemberAccess},
+        Array, ArraySize, Definition as TypeDefinition, Enum, Enumerator, Function,
+        InitializerList, OpaqueType, Pointer, Scalar, Struct, StructAttributes, Union,
+    },
+    statement::{
+        Define, Do, For, ForDeclaration, Goto, If, Include, Label, Pragma, Return, StaticAssert,
+        Switch, Typedef, While,
+    },
+    variable::Declarator,
+    Block, Comment, ConcreteType, DocComment, Expression, FileLevelStatement, FunctionDeclaration,
+    FunctionDefinition, Identifier, Statement, Value, Variable, VariableDeclaration,
+    VariableDeclarationList,
+};
+
+/// A rewrite of the syntax tree by value.
+pub trait Fold {
+    fn fold_file_level_statement(&mut self, statement: FileLevelStatement) -> FileLevelStatement {
+        walk_file_level_statement(self, statement)
+    }
+
+    fn fold_statement(&mut self, statement: Statement) -> Statement {
+        walk_statement(self, statement)
+    }
+
+    fn fold_block(&mut self, block: Block) -> Block {
+        walk_block(self, block)
+    }
+
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        walk_expression(self, expression)
+    }
+
+    fn fold_value(&mut self, value: Value) -> Value {
+        walk_value(self, value)
+    }
+
+    /// Folds a variable that is used in an expression.
+    fn fold_variable(&mut self, variable: Variable) -> Variable {
+        walk_variable(self, variable)
+    }
+
+    fn fold_identifier(&mut self, identifier: Identifier) -> Identifier {
+        identifier
+    }
+
+    fn fold_comment(&mut self, comment: Comment) -> Comment {
+        comment
+    }
+
+    fn fold_doc_comment(&mut self, doc: DocComment) -> DocComment {
+        doc
+    }
+
+    fn fold_array_subscript(&mut self, subscript: ArraySubscript) -> ArraySubscript {
+        walk_array_subscript(self, subscript)
+    }
+
+    fn fold_assignment(&mut self, assignment: Assignment) -> Assignment {
+        walk_assignment(self, assignment)
+    }
+
+    fn fold_binary_operator(&mut self, operator: BinaryOperator) -> BinaryOperator {
+        walk_binary_operator(self, operator)
+    }
+
+    fn fold_cast(&mut self, cast: Cast) -> Cast {
+        walk_cast(self, cast)
+    }
+
+    fn fold_comma_operator(&mut self, operator: CommaOperator) -> CommaOperator {
+        walk_comma_operator(self, operator)
+    }
+
+    fn fold_compound_assignment(&mut self, assignment: CompoundAssignment) -> CompoundAssignment {
+        walk_compound_assignment(self, assignment)
+    }
+
+    fn fold_conditional(&mut self, conditional: Conditional) -> Conditional {
+        walk_conditional(self, conditional)
+    }
+
+    fn fold_function_call(&mut self, call: FunctionCall) -> FunctionCall {
+        walk_function_call(self, call)
+    }
+
+    fn fold_indirect_member_access(
+        &mut self,
+        access: IndirectMemberAccess,
+    ) -> IndirectMemberAccess {
+        walk_indirect_member_access(self, access)
+    }
+
+    fn fold_initializer_list(&mut self, list: InitializerList) -> InitializerList {
+        walk_initializer_list(self, list)
+    }
+
+    fn fold_member_access(&mut self, access: MemberAccess) -> MemberAccess {
+        walk_member_access(self, access)
+    }
+
+    fn fold_offset_of(&mut self, offset_of: OffsetOf) -> OffsetOf {
+        walk_offset_of(self, offset_of)
+    }
+
+    fn fold_postfix_operator(&mut self, operator: PostfixOperator) -> PostfixOperator {
+        walk_postfix_operator(self, operator)
+    }
+
+    fn fold_prefix_operator(&mut self, operator: PrefixOperator) -> PrefixOperator {
+        walk_prefix_operator(self, operator)
+    }
+
+    fn fold_size_of(&mut self, size_of: SizeOf) -> SizeOf {
+        walk_size_of(self, size_of)
+    }
+
+    fn fold_define(&mut self, define: Define) -> Define {
+        walk_define(self, define)
+    }
+
+    fn fold_do(&mut self, do_statement: Do) -> Do {
+        walk_do(self, do_statement)
+    }
+
+    fn fold_for(&mut self, for_statement: For) -> For {
+        walk_for(self, for_statement)
+    }
+
+    fn fold_for_declaration(&mut self, declaration: ForDeclaration) -> ForDeclaration {
+        walk_for_declaration(self, declaration)
+    }
+
+    fn fold_goto(&mut self, goto: Goto) -> Goto {
+        walk_goto(self, goto)
+    }
+
+    fn fold_if(&mut self, if_statement: If) -> If {
+        walk_if(self, if_statement)
+    }
+
+    fn fold_include(&mut self, include: Include) -> Include {
+        include
+    }
+
+    fn fold_label(&mut self, label: Label) -> Label {
+        walk_label(self, label)
+    }
+
+    fn fold_pragma(&mut self, pragma: Pragma) -> Pragma {
+        pragma
+    }
+
+    fn fold_return(&mut self, return_statement: Return) -> Return {
+        walk_return(self, return_statement)
+    }
+
+    fn fold_static_assert(&mut self, assertion: StaticAssert) -> StaticAssert {
+        walk_static_assert(self, assertion)
+    }
+
+    fn fold_switch(&mut self, switch: Switch) -> Switch {
+        walk_switch(self, switch)
+    }
+
+    fn fold_typedef(&mut self, typedef: Typedef) -> Typedef {
+        walk_typedef(self, typedef)
+    }
+
+    fn fold_while(&mut self, while_statement: While) -> While {
+        walk_while(self, while_statement)
+    }
+
+    fn fold_function_declaration(
+        &mut self,
+        declaration: FunctionDeclaration,
+    ) -> FunctionDeclaration {
+        walk_function_declaration(self, declaration)
+    }
+
+    fn fold_function_definition(&mut self, definition: FunctionDefinition) -> FunctionDefinition {
+        walk_function_definition(self, definition)
+    }
+
+    fn fold_function_parameter(&mut self, parameter: FunctionParameter) -> FunctionParameter {
+        walk_function_parameter(self, parameter)
+    }
+
+    fn fold_variable_declaration(
+        &mut self,
+        declaration: VariableDeclaration,
+    ) -> VariableDeclaration {
+        walk_variable_declaration(self, declaration)
+    }
+
+    fn fold_variable_declaration_list(
+        &mut self,
+        declaration: VariableDeclarationList,
+    ) -> VariableDeclarationList {
+        walk_variable_declaration_list(self, declaration)
+    }
+
+    fn fold_declarator(&mut self, declarator: Declarator) -> Declarator {
+        walk_declarator(self, declarator)
+    }
+
+    fn fold_concrete_type(&mut self, ty: ConcreteType) -> ConcreteType {
+        walk_concrete_type(self, ty)
+    }
+
+    fn fold_opaque_type(&mut self, ty: OpaqueType) -> OpaqueType {
+        walk_opaque_type(self, ty)
+    }
+
+    fn fold_function_type(&mut self, function: Function) -> Function {
+        walk_function_type(self, function)
+    }
+
+    fn fold_scalar(&mut self, scalar: Scalar) -> Scalar {
+        walk_scalar(self, scalar)
+    }
+
+    fn fold_pointer(&mut self, pointer: Pointer) -> Pointer {
+        walk_pointer(self, pointer)
+    }
+
+    fn fold_array(&mut self, array: Array) -> Array {
+        walk_array(self, array)
+    }
+
+    fn fold_array_size(&mut self, size: ArraySize) -> ArraySize {
+        walk_array_size(self, size)
+    }
+
+    fn fold_type_definition(&mut self, definition: TypeDefinition) -> TypeDefinition {
+        walk_type_definition(self, definition)
+    }
+
+    fn fold_enum(&mut self, enumeration: Enum) -> Enum {
+        walk_enum(self, enumeration)
+    }
+
+    fn fold_enumerator(&mut self, enumerator: Enumerator) -> Enumerator {
+        walk_enumerator(self, enumerator)
+    }
+
+    fn fold_struct(&mut self, structure: Struct) -> Struct {
+        walk_struct(self, structure)
+    }
+
+    fn fold_union(&mut self, union: Union) -> Union {
+        walk_union(self, union)
+    }
+
+    fn fold_member(&mut self, member: Member) -> Member {
+        walk_member(self, member)
+    }
+}
+
+pub fn walk_file_level_statement<F: Fold + ?Sized>(
+    folder: &mut F,
+    statement: FileLevelStatement,
+) -> FileLevelStatement {
+    match statement {
+        FileLevelStatement::Comment(comment) => folder.fold_comment(comment).into(),
+        FileLevelStatement::Define(define) => folder.fold_define(define).into(),
+        FileLevelStatement::FunctionDeclaration(declaration) => {
+            folder.fold_function_declaration(declaration).into()
+        }
+        FileLevelStatement::FunctionDefinition(definition) => {
+            folder.fold_function_definition(definition).into()
+        }
+        FileLevelStatement::Include(include) => folder.fold_include(include).into(),
+        FileLevelStatement::Pragma(pragma) => folder.fold_pragma(pragma).into(),
+        FileLevelStatement::StaticAssert(assertion) => folder.fold_static_assert(assertion).into(),
+        FileLevelStatement::TypeDefinition(definition) => {
+            folder.fold_type_definition(definition).into()
+        }
+        FileLevelStatement::Typedef(typedef) => folder.fold_typedef(typedef).into(),
+        FileLevelStatement::VariableDeclaration(declaration) => {
+            folder.fold_variable_declaration(declaration).into()
+        }
+        FileLevelStatement::VariableDeclarationList(declaration) => {
+            folder.fold_variable_declaration_list(declaration).into()
+        }
+    }
+}
+
+pub fn walk_statement<F: Fold + ?Sized>(folder: &mut F, statement: Statement) -> Statement {
+    match statement {
+        Statement::Expression(expression) => {
+            Statement::Expression(folder.fold_expression(expression))
+        }
+        Statement::Label(label) => folder.fold_label(*label).into(),
+        Statement::If(if_statement) => folder.fold_if(*if_statement).into(),
+        Statement::Switch(switch) => Statement::Switch(Box::new(folder.fold_switch(*switch))),
+        Statement::While(while_statement) => {
+            Statement::While(Box::new(folder.fold_while(*while_statement)))
+        }
+        Statement::Do(do_statement) => folder.fold_do(*do_statement).into(),
+        Statement::For(for_statement) => folder.fold_for(*for_statement).into(),
+        Statement::Block(block) => folder.fold_block(block).into(),
+        Statement::Comment(comment) => folder.fold_comment(comment).into(),
+        Statement::Null => Statement::Null,
+        Statement::Goto(goto) => folder.fold_goto(goto).into(),
+        Statement::Break => Statement::Break,
+        Statement::Continue => Statement::Continue,
+        Statement::Return(return_statement) => folder.fold_return(return_statement).into(),
+        Statement::StaticAssert(assertion) => folder.fold_static_assert(assertion).into(),
+        Statement::Typedef(typedef) => folder.fold_typedef(typedef).into(),
+        Statement::VariableDeclaration(declaration) => {
+            folder.fold_variable_declaration(declaration).into()
+        }
+        Statement::VariableDeclarationList(declaration) => {
+            folder.fold_variable_declaration_list(declaration).into()
+        }
+        Statement::FunctionDeclaration(declaration) => {
+            folder.fold_function_declaration(declaration).into()
+        }
+        Statement::TypeDefinition(definition) => {
+            Statement::TypeDefinition(folder.fold_type_definition(definition))
+        }
+        Statement::Include(include) => folder.fold_include(include).into(),
+        Statement::Define(define) => folder.fold_define(define).into(),
+        Statement::Pragma(pragma) => folder.fold_pragma(pragma).into(),
+    }
+}
+
+pub fn walk_block<F: Fold + ?Sized>(folder: &mut F, block: Block) -> Block {
+    Block {
+        statements: block
+            .statements
+            .into_iter()
+            .map(|statement| folder.fold_statement(statement))
+            .collect(),
+    }
+}
+
+pub fn walk_expression<F: Fold + ?Sized>(folder: &mut F, expression: Expression) -> Expression {
+    match expression {
+        Expression::AlignOf(ty) => Expression::AlignOf(folder.fold_concrete_type(ty)),
+        Expression::ArraySubscript(subscript) => folder.fold_array_subscript(*subscript).into(),
+        Expression::Assignment(assignment) => folder.fold_assignment(*assignment).into(),
+        Expression::BinaryOperator(operator) => folder.fold_binary_operator(*operator).into(),
+        Expression::Cast(cast) => folder.fold_cast(*cast).into(),
+        Expression::CommaOperator(operator) => folder.fold_comma_operator(*operator).into(),
+        Expression::CompoundAssignment(assignment) => {
+            folder.fold_compound_assignment(*assignment).into()
+        }
+        Expression::Conditional(conditional) => folder.fold_conditional(*conditional).into(),
+        Expression::FunctionCall(call) => folder.fold_function_call(*call).into(),
+        Expression::IndirectMemberAccess(access) => {
+            folder.fold_indirect_member_access(*access).into()
+        }
+        Expression::InitializerList(list) => folder.fold_initializer_list(list).into(),
+        Expression::MemberAccess(access) => folder.fold_member_access(*access).into(),
+        Expression::OffsetOf(offset_of) => folder.fold_offset_of(*offset_of).into(),
+        Expression::Parentheses(expression) => {
+            Expression::Parentheses(Box::new(folder.fold_expression(*expression)))
+        }
+        Expression::PostfixOperator(operator) => folder.fold_postfix_operator(*operator).into(),
+        Expression::PrefixOperator(operator) => folder.fold_prefix_operator(*operator).into(),
+        Expression::SizeOf(size_of) => folder.fold_size_of(*size_of).into(),
+        Expression::Value(value) => Expression::Value(folder.fold_value(value)),
+        Expression::Variable(variable) => Expression::Variable(folder.fold_variable(variable)),
+    }
+}
+
+pub fn walk_value<F: Fold + ?Sized>(folder: &mut F, value: Value) -> Value {
+    match value {
+        Value::Array { values, base_type } => Value::Array {
+            base_type: folder.fold_concrete_type(base_type),
+            values: values
+                .into_iter()
+                .map(|value| folder.fold_value(value))
+                .collect(),
+        },
+        Value::Struct { fields } => Value::Struct {
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (name, folder.fold_value(value)))
+                .collect(),
+        },
+        value @ (Value::Char { .. }
+        | Value::Enum { .. }
+        | Value::Pointer { .. }
+        | Value::Real { .. }
+        | Value::SignedInteger { .. }
+        | Value::Size { .. }
+        | Value::String(_)
+        | Value::UnsignedInteger { .. }) => value,
+    }
+}
+
+pub fn walk_variable<F: Fold + ?Sized>(folder: &mut F, variable: Variable) -> Variable {
+    folder.fold_identifier(variable)
+}
+
+pub fn walk_array_subscript<F: Fold + ?Sized>(
+    folder: &mut F,
+    subscript: ArraySubscript,
+) -> ArraySubscript {
+    ArraySubscript {
+        array: folder.fold_expression(subscript.array),
+        index: folder.fold_expression(subscript.index),
+    }
+}
+
+pub fn walk_assignment<F: Fold + ?Sized>(folder: &mut F, assignment: Assignment) -> Assignment {
+    Assignment {
+        left: folder.fold_expression(assignment.left),
+        right: folder.fold_expression(assignment.right),
+    }
+}
+
+pub fn walk_binary_operator<F: Fold + ?Sized>(
+    folder: &mut F,
+    operator: BinaryOperator,
+) -> BinaryOperator {
+    BinaryOperator {
+        left: folder.fold_expression(operator.left),
+        operator: operator.operator,
+        right: folder.fold_expression(operator.right),
+    }
+}
+
+pub fn walk_cast<F: Fold + ?Sized>(folder: &mut F, cast: Cast) -> Cast {
+    Cast {
+        new_ty: folder.fold_scalar(cast.new_ty),
+        expression: folder.fold_expression(cast.expression),
+    }
+}
+
+pub fn walk_comma_operator<F: Fold + ?Sized>(
+    folder: &mut F,
+    operator: CommaOperator,
+) -> CommaOperator {
+    CommaOperator {
+        left: folder.fold_expression(operator.left),
+        right: folder.fold_expression(operator.right),
+    }
+}
+
+pub fn walk_compound_assignment<F: Fold + ?Sized>(
+    folder: &mut F,
+    assignment: CompoundAssignment,
+) -> CompoundAssignment {
+    CompoundAssignment {
+        left: folder.fold_expression(assignment.left),
+        operator: assignment.operator,
+        right: folder.fold_expression(assignment.right),
+    }
+}
+
+pub fn walk_conditional<F: Fold + ?Sized>(folder: &mut F, conditional: Conditional) -> Conditional {
+    Conditional {
+        condition: folder.fold_expression(conditional.condition),
+        then_branch: folder.fold_expression(conditional.then_branch),
+        else_branch: folder.fold_expression(conditional.else_branch),
+    }
+}
+
+pub fn walk_function_call<F: Fold + ?Sized>(folder: &mut F, call: FunctionCall) -> FunctionCall {
+    FunctionCall {
+        callee: folder.fold_expression(call.callee),
+        arguments: call
+            .arguments
+            .into_iter()
+            .map(|argument| folder.fold_expression(argument))
+            .collect(),
+    }
+}
+
+pub fn walk_indirect_member_access<F: Fold + ?Sized>(
+    folder: &mut F,
+    access: IndirectMemberAccess,
+) -> IndirectMemberAccess {
+    IndirectMemberAccess {
+        left: folder.fold_expression(access.left),
+        member: folder.fold_identifier(access.member),
+    }
+}
+
+pub fn walk_initializer_list<F: Fold + ?Sized>(
+    folder: &mut F,
+    list: InitializerList,
+) -> InitializerList {
+    match list {
+        InitializerList::Named(fields) => InitializerList::Named(
+            fields
+                .into_iter()
+                .map(|(name, value)| (folder.fold_identifier(name), folder.fold_expression(value)))
+                .collect(),
+        ),
+        InitializerList::Ordered(values) => InitializerList::Ordered(
+            values
+                .into_iter()
+                .map(|value| folder.fold_expression(value))
+                .collect(),
+        ),
+    }
+}
+
+pub fn walk_member_access<F: Fold + ?Sized>(folder: &mut F, access: MemberAccess) -> MemberAccess {
+    MemberAccess {
+        left: folder.fold_expression(access.left),
+        member: folder.fold_identifier(access.member),
+    }
+}
+
+pub fn walk_offset_of<F: Fold + ?Sized>(folder: &mut F, offset_of: OffsetOf) -> OffsetOf {
+    OffsetOf {
+        ty: folder.fold_concrete_type(offset_of.ty),
+        member: folder.fold_identifier(offset_of.member),
+    }
+}
+
+pub fn walk_postfix_operator<F: Fold + ?Sized>(
+    folder: &mut F,
+    operator: PostfixOperator,
+) -> PostfixOperator {
+    PostfixOperator {
+        operand: folder.fold_expression(operator.operand),
+        operator: operator.operator,
+    }
+}
+
+pub fn walk_prefix_operator<F: Fold + ?Sized>(
+    folder: &mut F,
+    operator: PrefixOperator,
+) -> PrefixOperator {
+    PrefixOperator {
+        operand: folder.fold_expression(operator.operand),
+        operator: operator.operator,
+    }
+}
+
+pub fn walk_size_of<F: Fold + ?Sized>(folder: &mut F, size_of: SizeOf) -> SizeOf {
+    match size_of {
+        SizeOf::Expression(expression) => SizeOf::Expression(folder.fold_expression(expression)),
+        SizeOf::Type(ty) => SizeOf::Type(folder.fold_concrete_type(ty)),
+    }
+}
+
+pub fn walk_define<F: Fold + ?Sized>(folder: &mut F, define: Define) -> Define {
+    Define {
+        name: folder.fold_identifier(define.name),
+        parameters: define.parameters.map(|parameters| {
+            parameters
+                .into_iter()
+                .map(|parameter| folder.fold_identifier(parameter))
+                .collect()
+        }),
+        body: define.body,
+    }
+}
+
+pub fn walk_do<F: Fold + ?Sized>(folder: &mut F, do_statement: Do) -> Do {
+    Do {
+        body: folder.fold_statement(do_statement.body),
+        condition: folder.fold_expression(do_statement.condition),
+    }
+}
+
+pub fn walk_for<F: Fold + ?Sized>(folder: &mut F, for_statement: For) -> For {
+    For {
+        init: for_statement
+            .init
+            .map(|init| folder.fold_for_declaration(init)),
+        condition: folder.fold_expression(for_statement.condition),
+        step: for_statement.step.map(|step| folder.fold_expression(step)),
+        body: folder.fold_statement(for_statement.body),
+    }
+}
+
+pub fn walk_for_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    declaration: ForDeclaration,
+) -> ForDeclaration {
+    match declaration {
+        ForDeclaration::Expression(expression) => {
+            ForDeclaration::Expression(folder.fold_expression(expression))
+        }
+        ForDeclaration::VariableDeclaration(declaration) => {
+            folder.fold_variable_declaration(*declaration).into()
+        }
+        ForDeclaration::VariableDeclarationList(declaration) => {
+            folder.fold_variable_declaration_list(*declaration).into()
+        }
+    }
+}
+
+pub fn walk_goto<F: Fold + ?Sized>(folder: &mut F, goto: Goto) -> Goto {
+    Goto {
+        label: folder.fold_identifier(goto.label),
+    }
+}
+
+pub fn walk_if<F: Fold + ?Sized>(folder: &mut F, if_statement: If) -> If {
+    If {
+        condition: folder.fold_expression(if_statement.condition),
+        then_statement: folder.fold_statement(if_statement.then_statement),
+        else_statement: if_statement
+            .else_statement
+            .map(|statement| folder.fold_statement(statement)),
+    }
+}
+
+pub fn walk_label<F: Fold + ?Sized>(folder: &mut F, label: Label) -> Label {
+    Label {
+        identifier: folder.fold_identifier(label.identifier),
+        statement: label
+            .statement
+            .map(|statement| folder.fold_statement(statement)),
+    }
+}
+
+pub fn walk_return<F: Fold + ?Sized>(folder: &mut F, return_statement: Return) -> Return {
+    Return {
+        expression: return_statement
+            .expression
+            .map(|expression| folder.fold_expression(expression)),
+    }
+}
+
+pub fn walk_static_assert<F: Fold + ?Sized>(
+    folder: &mut F,
+    assertion: StaticAssert,
+) -> StaticAssert {
+    StaticAssert {
+        condition: folder.fold_expression(assertion.condition),
+        message: assertion.message,
+    }
+}
+
+pub fn walk_switch<F: Fold + ?Sized>(folder: &mut F, switch: Switch) -> Switch {
+    let condition = folder.fold_expression(switch.condition);
+    let cases = switch
+        .cases
+        .into_iter()
+        .map(|(value, statements)| {
+            (
+                folder.fold_expression(value),
+                statements
+                    .into_iter()
+                    .map(|statement| folder.fold_statement(statement))
+                    .collect(),
+            )
+        })
+        .collect();
+    let default = switch.default.map(|statements| {
+        statements
+            .into_iter()
+            .map(|statement| folder.fold_statement(statement))
+            .collect()
+    });
+
+    Switch {
+        condition,
+        cases,
+        default,
+    }
+}
+
+pub fn walk_typedef<F: Fold + ?Sized>(folder: &mut F, typedef: Typedef) -> Typedef {
+    Typedef {
+        ty: folder.fold_opaque_type(typedef.ty),
+        alias: folder.fold_identifier(typedef.alias),
+    }
+}
+
+pub fn walk_while<F: Fold + ?Sized>(folder: &mut F, while_statement: While) -> While {
+    While {
+        condition: folder.fold_expression(while_statement.condition),
+        body: folder.fold_statement(while_statement.body),
+    }
+}
+
+pub fn walk_function_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    declaration: FunctionDeclaration,
+) -> FunctionDeclaration {
+    FunctionDeclaration {
+        doc: declaration.doc.map(|doc| folder.fold_doc_comment(doc)),
+        is_static: declaration.is_static,
+        is_inline: declaration.is_inline,
+        name: folder.fold_identifier(declaration.name),
+        ty: folder.fold_function_type(declaration.ty),
+    }
+}
+
+pub fn walk_function_definition<F: Fold + ?Sized>(
+    folder: &mut F,
+    definition: FunctionDefinition,
+) -> FunctionDefinition {
+    FunctionDefinition {
+        doc: definition.doc.map(|doc| folder.fold_doc_comment(doc)),
+        is_static: definition.is_static,
+        is_inline: definition.is_inline,
+        name: folder.fold_identifier(definition.name),
+        ty: folder.fold_function_type(definition.ty),
+        body: folder.fold_block(definition.body),
+    }
+}
+
+pub fn walk_function_parameter<F: Fold + ?Sized>(
+    folder: &mut F,
+    parameter: FunctionParameter,
+) -> FunctionParameter {
+    FunctionParameter {
+        ty: folder.fold_concrete_type(parameter.ty),
+        name: parameter.name.map(|name| folder.fold_identifier(name)),
+    }
+}
+
+pub fn walk_variable_declaration<F: Fold + ?Sized>(
+    folder: &mut F,
+    declaration: VariableDeclaration,
+) -> VariableDeclaration {
+    VariableDeclaration {
+        doc: declaration.doc.map(|doc| folder.fold_doc_comment(doc)),
+        storage_class: declaration.storage_class,
+        alignment: declaration
+            .alignment
+            .map(|alignment| folder.fold_expression(alignment)),
+        ty: folder.fold_concrete_type(declaration.ty),
+        identifier: folder.fold_identifier(declaration.identifier),
+        initializer: declaration
+            .initializer
+            .map(|initializer| folder.fold_expression(initializer)),
+    }
+}
+
+pub fn walk_variable_declaration_list<F: Fold + ?Sized>(
+    folder: &mut F,
+    declaration: VariableDeclarationList,
+) -> VariableDeclarationList {
+    VariableDeclarationList {
+        doc: declaration.doc.map(|doc| folder.fold_doc_comment(doc)),
+        storage_class: declaration.storage_class,
+        alignment: declaration
+            .alignment
+            .map(|alignment| folder.fold_expression(alignment)),
+        ty: folder.fold_concrete_type(declaration.ty),
+        declarators: declaration
+            .declarators
+            .map(|declarator| folder.fold_declarator(declarator)),
+    }
+}
+
+pub fn walk_declarator<F: Fold + ?Sized>(folder: &mut F, declarator: Declarator) -> Declarator {
+    Declarator {
+        pointers: declarator.pointers,
+        identifier: folder.fold_identifier(declarator.identifier),
+        dimensions: declarator
+            .dimensions
+            .into_iter()
+            .map(|size| folder.fold_array_size(size))
+            .collect(),
+        initializer: declarator
+            .initializer
+            .map(|initializer| folder.fold_expression(initializer)),
+    }
+}
+
+pub fn walk_concrete_type<F: Fold + ?Sized>(folder: &mut F, ty: ConcreteType) -> ConcreteType {
+    match ty {
+        ConcreteType::Array(array) => folder.fold_array(array).into(),
+        ConcreteType::Const(ty) => ConcreteType::Const(Box::new(folder.fold_concrete_type(*ty))),
+        ConcreteType::Enum(enumeration) => folder.fold_enum(enumeration).into(),
+        ConcreteType::Pointer(pointer) => folder.fold_pointer(*pointer).into(),
+        ConcreteType::Struct(structure) => folder.fold_struct(structure).into(),
+        ConcreteType::Typedef(typedef) => folder.fold_typedef(*typedef).into(),
+        ConcreteType::TypedefName(name) => ConcreteType::TypedefName(folder.fold_identifier(name)),
+        ConcreteType::Union(union) => folder.fold_union(union).into(),
+        ty @ (ConcreteType::Bool
+        | ConcreteType::Char
+        | ConcreteType::Integer(_)
+        | ConcreteType::Real(_)
+        | ConcreteType::Size
+        | ConcreteType::StrongInt(_)
+        | ConcreteType::Void) => ty,
+    }
+}
+
+pub fn walk_opaque_type<F: Fold + ?Sized>(folder: &mut F, ty: OpaqueType) -> OpaqueType {
+    match ty {
+        OpaqueType::ConcreteType(ty) => OpaqueType::ConcreteType(folder.fold_concrete_type(ty)),
+        OpaqueType::Function(function) => OpaqueType::Function(folder.fold_function_type(function)),
+    }
+}
+
+pub fn walk_function_type<F: Fold + ?Sized>(folder: &mut F, function: Function) -> Function {
+    let return_ty = folder.fold_concrete_type(function.return_ty);
+    let parameters = function
+        .parameters
+        .into_iter()
+        .map(|parameter| folder.fold_function_parameter(parameter))
+        .collect();
+
+    Function {
+        parameters,
+        return_ty,
+        ..function
+    }
+}
+
+pub fn walk_scalar<F: Fold + ?Sized>(folder: &mut F, scalar: Scalar) -> Scalar {
+    match scalar {
+        Scalar::Pointer(pointer) => Scalar::Pointer(folder.fold_pointer(pointer)),
+        scalar @ (Scalar::Bool
+        | Scalar::Char
+        | Scalar::Integer(_)
+        | Scalar::StrongInt(_)
+        | Scalar::Real(_)
+        | Scalar::Void) => scalar,
+    }
+}
+
+pub fn walk_pointer<F: Fold + ?Sized>(folder: &mut F, pointer: Pointer) -> Pointer {
+    Pointer {
+        pointer_ty: folder.fold_opaque_type(pointer.pointer_ty),
+        is_const: pointer.is_const,
+    }
+}
+
+pub fn walk_array<F: Fold + ?Sized>(folder: &mut F, array: Array) -> Array {
+    Array {
+        element_type: Box::new(folder.fold_concrete_type(*array.element_type)),
+        size: folder.fold_array_size(array.size),
+    }
+}
+
+pub fn walk_array_size<F: Fold + ?Sized>(folder: &mut F, size: ArraySize) -> ArraySize {
+    match size {
+        ArraySize::Length(length) => ArraySize::Length(Box::new(folder.fold_expression(*length))),
+        ArraySize::Static(length) => ArraySize::Static(Box::new(folder.fold_expression(*length))),
+        size @ (ArraySize::Unspecified | ArraySize::Star) => size,
+    }
+}
+
+pub fn walk_type_definition<F: Fold + ?Sized>(
+    folder: &mut F,
+    definition: TypeDefinition,
+) -> TypeDefinition {
+    match definition {
+        TypeDefinition::Enum(enumeration) => TypeDefinition::Enum(folder.fold_enum(enumeration)),
+        TypeDefinition::Struct(structure) => TypeDefinition::Struct(folder.fold_struct(structure)),
+        TypeDefinition::Union(union) => TypeDefinition::Union(folder.fold_union(union)),
+    }
+}
+
+pub fn walk_enum<F: Fold + ?Sized>(folder: &mut F, enumeration: Enum) -> Enum {
+    match enumeration {
+        Enum::Definition {
+            doc,
+            name,
+            underlying_ty,
+            values,
+            trailing_comma,
+        } => Enum::Definition {
+            doc: doc.map(|doc| folder.fold_doc_comment(doc)),
+            name: name.map(|name| folder.fold_identifier(name)),
+            underlying_ty: underlying_ty.map(|ty| Box::new(folder.fold_concrete_type(*ty))),
+            values: values.map(|enumerator| folder.fold_enumerator(enumerator)),
+            trailing_comma,
+        },
+        Enum::Tag { name } => Enum::Tag {
+            name: folder.fold_identifier(name),
+        },
+    }
+}
+
+pub fn walk_enumerator<F: Fold + ?Sized>(folder: &mut F, enumerator: Enumerator) -> Enumerator {
+    Enumerator {
+        doc: enumerator.doc.map(|doc| folder.fold_doc_comment(doc)),
+        name: folder.fold_identifier(enumerator.name),
+        value: enumerator.value.map(|value| folder.fold_expression(value)),
+    }
+}
+
+pub fn walk_struct<F: Fold + ?Sized>(folder: &mut F, structure: Struct) -> Struct {
+    match structure {
+        Struct::Definition {
+            doc,
+            attributes,
+            name,
+            members,
+        } => Struct::Definition {
+            doc: doc.map(|doc| folder.fold_doc_comment(doc)),
+            attributes: StructAttributes {
+                packed: attributes.packed,
+                aligned: attributes
+                    .aligned
+                    .map(|aligned| Box::new(folder.fold_expression(*aligned))),
+            },
+            name: name.map(|name| folder.fold_identifier(name)),
+            members: members
+                .into_iter()
+                .map(|member| folder.fold_member(member))
+                .collect(),
+        },
+        Struct::Tag { name } => Struct::Tag {
+            name: folder.fold_identifier(name),
+        },
+    }
+}
+
+pub fn walk_union<F: Fold + ?Sized>(folder: &mut F, union: Union) -> Union {
+    match union {
+        Union::Definition {
+            doc,
+            attributes,
+            name,
+            members,
+        } => Union::Definition {
+            doc: doc.map(|doc| folder.fold_doc_comment(doc)),
+            attributes: StructAttributes {
+                packed: attributes.packed,
+                aligned: attributes
+                    .aligned
+                    .map(|aligned| Box::new(folder.fold_expression(*aligned))),
+            },
+            name: name.map(|name| folder.fold_identifier(name)),
+            members: members.map(|member| folder.fold_member(member)),
+        },
+        Union::Tag { name } => Union::Tag {
+            name: folder.fold_identifier(name),
+        },
+    }
+}
+
+pub fn walk_member<F: Fold + ?Sized>(folder: &mut F, member: Member) -> Member {
+    Member {
+        doc: member.doc.map(|doc| folder.fold_doc_comment(doc)),
+        alignment: member
+            .alignment
+            .map(|alignment| folder.fold_expression(alignment)),
+        ty: folder.fold_concrete_type(member.ty),
+        name: folder.fold_identifier(member.name),
+        bit_field_size: member.bit_field_size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operator::BinaryOperatorKind,
+        visit::tests::{every_variant_counts, Counts, EVERY_VARIANT},
+    };
+
+    /// Folds additions of integer constants.
+    struct ConstantFolding;
+
+    impl Fold for ConstantFolding {
+        fn fold_expression(&mut self, expression: Expression) -> Expression {
+            match walk_expression(self, expression) {
+                Expression::BinaryOperator(operator) => match *operator {
+                    BinaryOperator {
+                        left: Expression::Value(Value::SignedInteger { value: left, .. }),
+                        operator: BinaryOperatorKind::Add,
+                        right: Expression::Value(Value::SignedInteger { value: right, .. }),
+                    } => Value::signed_integer(left + right).into(),
+                    operator => operator.into(),
+                },
+                expression => expression,
+            }
+        }
+    }
+
+    #[test]
+    fn constant_folding() -> anyhow::Result<()> {
+        let statement = Statement::parse("return 1 + 2 + 3 > x + 4;")?;
+        assert_eq!(
+            ConstantFolding.fold_statement(statement).to_string(),
+            "return 6 > x + 4;"
+        );
+
+        Ok(())
+    }
+
+    impl Fold for Counts {
+        fn fold_expression(&mut self, expression: Expression) -> Expression {
+            self.expression(&expression);
+            walk_expression(self, expression)
+        }
+
+        fn fold_statement(&mut self, statement: Statement) -> Statement {
+            self.statement(&statement);
+            walk_statement(self, statement)
+        }
+
+        fn fold_identifier(&mut self, identifier: Identifier) -> Identifier {
+            self.identifiers.push(identifier.to_string());
+            identifier
+        }
+    }
+
+    #[test]
+    fn every_variant() -> anyhow::Result<()> {
+        let function = FileLevelStatement::parse(EVERY_VARIANT)?;
+
+        // Folding visits the same nodes as visiting
+        let mut counts = Counts::default();
+        let folded = counts.fold_file_level_statement(function.clone());
+        assert_eq!(counts, every_variant_counts()?);
+        assert_eq!(folded, function);
+
+        // Without overrides, folding returns the same tree
+        struct Identity;
+        impl Fold for Identity {}
+        assert_eq!(
+            Identity.fold_file_level_statement(function.clone()),
+            function
+        );
+
+        Ok(())
+    }
+}
@@ -1,10 +1,23 @@
 use pretty::Pretty;
 
 use crate::{
-    pretty::impl_display_via_pretty, r#type::Function, Block, ConcreteType, Expression, Identifier,
+    pretty::impl_display_via_pretty, r#type::Function, Block, ConcreteType, DocComment, Expression,
+    Identifier,
 };
 
-#[derive(Clone, Debug)]
+/// Errors that can occur when generating a function or function type
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The return type is printed before the name, which cannot express a nested declarator like
+    /// `int (*f(void))(int)`. Such types can be returned through a type alias instead.
+    #[error(
+        "functions returning `{0}` are not supported, use a type alias for the return type instead"
+    )]
+    ReturnType(Box<ConcreteType>),
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FunctionCall {
     pub callee: Expression,
     pub arguments: Vec<Expression>,
@@ -28,7 +41,8 @@ where
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FunctionParameter {
     pub ty: ConcreteType,
     pub name: Option<Identifier>,
@@ -41,27 +55,45 @@ where
     AnnotationT: Clone + 'a,
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
-        let builder = allocator.text(self.ty.to_string());
         if let Some(name) = self.name {
-            builder
-                .append(allocator.space())
-                .append(allocator.text(name))
+            self.ty.pretty_definition(name, allocator)
         } else {
-            builder
+            self.ty.pretty(allocator)
         }
     }
 }
 
+impl_display_via_pretty!(FunctionParameter, 80);
+
 /// # Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Function-Declarations
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Declaration {
+    pub doc: Option<DocComment>,
     pub is_static: bool,
+    /// Whether the function is declared `inline`, which requires C99.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_inline: bool,
     pub name: Identifier,
     pub ty: Function,
 }
 
+impl Declaration {
+    /// Turns the declaration into a definition with the given body.
+    pub fn define(self, body: Block) -> Definition {
+        Definition {
+            doc: self.doc,
+            is_static: self.is_static,
+            is_inline: self.is_inline,
+            name: self.name,
+            ty: self.ty,
+            body,
+        }
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Declaration
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
@@ -69,10 +101,21 @@ where
     AnnotationT: Clone + 'a,
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let builder = DocComment::pretty_preceding(self.doc, allocator);
+
         let builder = if self.is_static {
-            allocator.text("static").append(allocator.space())
+            builder
+                .append(allocator.text("static"))
+                .append(allocator.space())
+        } else {
+            builder
+        };
+        let builder = if self.is_inline {
+            builder
+                .append(allocator.text("inline"))
+                .append(allocator.space())
         } else {
-            allocator.nil()
+            builder
         };
 
         let return_type = self.ty.pretty_return_type(allocator);
@@ -92,15 +135,33 @@ impl_display_via_pretty!(Declaration, 80);
 /// # Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Function-Definitions
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Definition {
+    pub doc: Option<DocComment>,
     pub is_static: bool,
+    /// Whether the function is defined `inline`, which requires C99.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_inline: bool,
     pub name: Identifier,
-    pub parameters: Vec<(ConcreteType, Identifier)>,
-    pub return_ty: ConcreteType,
+    /// The signature of the function. Parameters can only be unnamed since C23.
+    pub ty: Function,
     pub body: Block,
 }
 
+impl Definition {
+    /// Returns the declaration of the function, e.g. for use in a header file.
+    pub fn declaration(&self) -> Declaration {
+        Declaration {
+            doc: self.doc.clone(),
+            is_static: self.is_static,
+            is_inline: self.is_inline,
+            name: self.name.clone(),
+            ty: self.ty.clone(),
+        }
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Definition
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
@@ -108,28 +169,31 @@ where
     AnnotationT: Clone + 'a,
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let builder = DocComment::pretty_preceding(self.doc, allocator);
+
         let builder = if self.is_static {
-            allocator.text("static").append(allocator.space())
+            builder
+                .append(allocator.text("static"))
+                .append(allocator.space())
         } else {
-            allocator.nil()
+            builder
         };
+        let builder = if self.is_inline {
+            builder
+                .append(allocator.text("inline"))
+                .append(allocator.space())
+        } else {
+            builder
+        };
+
+        let parameters = self.ty.pretty_parameters(allocator);
 
         builder
-            .append(allocator.text(self.return_ty.to_string()))
+            .append(allocator.text(self.ty.return_ty.to_string()))
             .append(allocator.hardline())
             .append(allocator.text(self.name))
             .append(allocator.space())
-            .append(allocator.text("("))
-            .append(allocator.intersperse(
-                self.parameters.into_iter().map(|(ty, name)| {
-                    allocator
-                        .text(ty.to_string())
-                        .append(allocator.space())
-                        .append(allocator.text(name))
-                }),
-                allocator.text(",").append(allocator.space()),
-            ))
-            .append(allocator.text(")"))
+            .append(parameters)
             .append(allocator.space())
             .append(self.body.pretty(allocator))
     }
@@ -141,6 +205,7 @@ impl_display_via_pretty!(Definition, 80);
 mod tests {
     use crate::{
         operator::{ArraySubscript, BinaryOperator, BinaryOperatorKind},
+        r#type::{Array, ArraySize, Pointer},
         statement::Return,
         Value, Variable,
     };
@@ -151,7 +216,9 @@ mod tests {
     #[test]
     fn declaration_with_two_parameters() -> anyhow::Result<()> {
         let generated = Declaration {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("foo")?,
             ty: Function {
                 return_ty: ConcreteType::int(),
@@ -165,6 +232,8 @@ mod tests {
                         name: None,
                     },
                 ],
+                is_variadic: false,
+                has_unspecified_parameters: false,
             },
         }
         .to_string();
@@ -178,7 +247,9 @@ mod tests {
     #[test]
     fn declaration_with_parameter_name() -> anyhow::Result<()> {
         let generated = Declaration {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("foo")?,
             ty: Function {
                 return_ty: ConcreteType::int(),
@@ -192,6 +263,8 @@ mod tests {
                         name: Some(Identifier::new("y")?),
                     },
                 ],
+                is_variadic: false,
+                has_unspecified_parameters: false,
             },
         }
         .to_string();
@@ -205,13 +278,25 @@ mod tests {
     #[test]
     fn definition_with_integer_sum() -> anyhow::Result<()> {
         let generated = Definition {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("add_values")?,
-            return_ty: ConcreteType::int(),
-            parameters: vec![
-                (ConcreteType::int(), Identifier::new("x")?),
-                (ConcreteType::int(), Identifier::new("y")?),
-            ],
+            ty: Function {
+                parameters: vec![
+                    FunctionParameter {
+                        ty: ConcreteType::int(),
+                        name: Some(Identifier::new("x")?),
+                    },
+                    FunctionParameter {
+                        ty: ConcreteType::int(),
+                        name: Some(Identifier::new("y")?),
+                    },
+                ],
+                return_ty: ConcreteType::int(),
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Return {
                     expression: Some(
@@ -243,10 +328,19 @@ add_values (int x, int y) {
     #[test]
     fn static_function() -> anyhow::Result<()> {
         let generated = Definition {
+            doc: None,
             is_static: true,
+            is_inline: false,
             name: Identifier::new("foo")?,
-            return_ty: ConcreteType::int(),
-            parameters: vec![(ConcreteType::int(), Identifier::new("x")?)],
+            ty: Function {
+                parameters: vec![FunctionParameter {
+                    ty: ConcreteType::int(),
+                    name: Some(Identifier::new("x")?),
+                }],
+                return_ty: ConcreteType::int(),
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Return {
                     expression: Some(
@@ -304,4 +398,219 @@ foo (int x) {
 
         Ok(())
     }
+
+    #[test]
+    fn documented_definition() -> anyhow::Result<()> {
+        let generated = Definition {
+            doc: Some(DocComment {
+                text: "Adds two values.".to_string(),
+                parameters: vec![
+                    (Identifier::new("x")?, "The first value.".to_string()),
+                    (Identifier::new("y")?, "The second value.".to_string()),
+                ],
+                returns: Some("The sum of both values.".to_string()),
+            }),
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new("add_values")?,
+            ty: Function {
+                parameters: vec![
+                    FunctionParameter {
+                        ty: ConcreteType::int(),
+                        name: Some(Identifier::new("x")?),
+                    },
+                    FunctionParameter {
+                        ty: ConcreteType::int(),
+                        name: Some(Identifier::new("y")?),
+                    },
+                ],
+                return_ty: ConcreteType::int(),
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+            body: Block {
+                statements: vec![Return {
+                    expression: Some(
+                        BinaryOperator {
+                            left: Variable::new("x")?.into(),
+                            operator: BinaryOperatorKind::Add,
+                            right: Variable::new("y")?.into(),
+                        }
+                        .into(),
+                    ),
+                }
+                .into()],
+            },
+        }
+        .to_string();
+
+        assert_eq!(
+            generated,
+            r#"/**
+ * Adds two values.
+ *
+ * @param x The first value.
+ * @param y The second value.
+ * @return The sum of both values.
+ */
+int
+add_values (int x, int y) {
+  return x + y;
+}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parameters() -> anyhow::Result<()> {
+        let generated = Declaration {
+            doc: None,
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new("fill")?,
+            ty: Function {
+                parameters: vec![
+                    FunctionParameter {
+                        ty: ConcreteType::int().into_const(),
+                        name: Some(Identifier::new("n")?),
+                    },
+                    FunctionParameter {
+                        ty: Array {
+                            element_type: Box::new(ConcreteType::int()),
+                            size: ArraySize::Static(Box::new(Value::size(10).into())),
+                        }
+                        .into(),
+                        name: Some(Identifier::new("a")?),
+                    },
+                    FunctionParameter {
+                        ty: Pointer {
+                            pointer_ty: Function {
+                                parameters: vec![FunctionParameter {
+                                    ty: ConcreteType::int(),
+                                    name: None,
+                                }],
+                                return_ty: ConcreteType::Void,
+                                is_variadic: false,
+                                has_unspecified_parameters: false,
+                            }
+                            .into(),
+                            is_const: false,
+                        }
+                        .into(),
+                        name: Some(Identifier::new("callback")?),
+                    },
+                ],
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+        }
+        .to_string();
+
+        assert_eq!(
+            generated,
+            "void fill (const int n, int a[static 10], void (*callback)(int));"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn declaration_from_definition() -> anyhow::Result<()> {
+        let declaration = Declaration {
+            doc: None,
+            is_static: true,
+            is_inline: false,
+            name: Identifier::new("answer")?,
+            ty: Function {
+                parameters: Vec::new(),
+                return_ty: ConcreteType::int(),
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+        };
+        assert_eq!(declaration.to_string(), "static int answer (void);");
+
+        let definition = declaration.define(Block {
+            statements: vec![Return {
+                expression: Some(Value::signed_integer(42).into()),
+            }
+            .into()],
+        });
+        assert_eq!(
+            definition.to_string(),
+            r#"static int
+answer (void) {
+  return 42;
+}"#
+        );
+
+        assert_eq!(
+            definition.declaration().to_string(),
+            "static int answer (void);"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_return_types() -> anyhow::Result<()> {
+        let callback = Function {
+            parameters: vec![FunctionParameter {
+                ty: ConcreteType::int(),
+                name: None,
+            }],
+            return_ty: ConcreteType::int(),
+            is_variadic: false,
+            has_unspecified_parameters: false,
+        };
+        let row = Array {
+            element_type: Box::new(ConcreteType::int()),
+            size: ArraySize::Length(Box::new(Value::signed_integer(3).into())),
+        };
+
+        // Return types are printed before the name, which cannot express `int (*f(void))(int)`
+        for return_ty in [
+            Pointer {
+                pointer_ty: callback.into(),
+                is_const: false,
+            }
+            .into(),
+            Pointer {
+                pointer_ty: ConcreteType::Array(row.clone()).into(),
+                is_const: false,
+            }
+            .into(),
+            ConcreteType::Array(row),
+        ] {
+            let mut file = crate::CFileBuilder::default();
+            file.add_statement(Definition {
+                doc: None,
+                is_static: false,
+                is_inline: false,
+                name: Identifier::new("f")?,
+                ty: Function {
+                    parameters: Vec::new(),
+                    return_ty: return_ty.clone(),
+                    is_variadic: false,
+                    has_unspecified_parameters: false,
+                },
+                body: Block {
+                    statements: Vec::new(),
+                },
+            });
+
+            let message = format!(
+                "functions returning `{return_ty}` are not supported, use a type alias for the return type instead"
+            );
+            assert_eq!(file.write_to_string().unwrap_err().to_string(), message);
+            assert!(file
+                .validate()
+                .iter()
+                .any(|diagnostic| diagnostic.kind.to_string() == message));
+        }
+
+        Ok(())
+    }
 }
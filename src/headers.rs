@@ -0,0 +1,221 @@
+//! Inference of the standard headers that a file needs.
+//!
+//! A header is needed for each type, macro or function of the standard library that the file uses, e.g. `uint32_t`
+//! needs `<stdint.h>` and `printf` needs `<stdio.h>`. Names that the file declares itself are not attributed to a
+//! header, unless the declaration is local and the use lies outside of its scope. Built-in types like `size_t` are
+//! treated like the names they are spelled with.
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::{
+    function::FunctionParameter,
+    operator::OffsetOf,
+    r#type::{Enumerator, Function, Scalar},
+    statement::{Define, For, Include, Typedef},
+    variable::Declarator,
+    visit::{self, Visitor},
+    Block, ConcreteType, FileLevelStatement, FunctionDeclaration, FunctionDefinition, Identifier,
+    Standard, Variable, VariableDeclaration,
+};
+
+/// Returns the standard headers that the statements need but do not include, in alphabetical order.
+pub(crate) fn missing(statements: &[FileLevelStatement], standard: Standard) -> Vec<Include> {
+    let mut collector = Collector::default();
+    for statement in statements {
+        collector.visit_file_level_statement(statement);
+    }
+
+    collector
+        .names
+        .iter()
+        .filter(|name| !collector.declared.contains(*name))
+        .filter_map(|name| header(name.as_ref(), standard))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|header| !collector.included.contains(*header))
+        .map(Include::with_angle_brackets)
+        .collect()
+}
+
+/// Returns the standard header that declares the given name, if any.
+///
+/// `<stdbool.h>` and `<stdint.h>` were introduced in C99, so their names are not attributed to a header before.
+fn header(name: &str, standard: Standard) -> Option<&'static str> {
+    let header = match name {
+        "assert" => "assert.h",
+        "errno" | "EDOM" | "EILSEQ" | "ERANGE" => "errno.h",
+        "bool" | "true" | "false" if !standard.is_at_least(Standard::C23) => "stdbool.h",
+        "CHAR_BIT" | "CHAR_MAX" | "CHAR_MIN" | "INT_MAX" | "INT_MIN" | "LLONG_MAX"
+        | "LLONG_MIN" | "LONG_MAX" | "LONG_MIN" | "SCHAR_MAX" | "SCHAR_MIN" | "SHRT_MAX"
+        | "SHRT_MIN" | "UCHAR_MAX" | "UINT_MAX" | "ULLONG_MAX" | "ULONG_MAX" | "USHRT_MAX" => {
+            "limits.h"
+        }
+        "NULL" | "offsetof" | "ptrdiff_t" | "size_t" => "stddef.h",
+        "int8_t" | "int16_t" | "int32_t" | "int64_t" | "int_fast8_t" | "int_fast16_t"
+        | "int_fast32_t" | "int_fast64_t" | "int_least8_t" | "int_least16_t" | "int_least32_t"
+        | "int_least64_t" | "intmax_t" | "intptr_t" | "uint8_t" | "uint16_t" | "uint32_t"
+        | "uint64_t" | "uint_fast8_t" | "uint_fast16_t" | "uint_fast32_t" | "uint_fast64_t"
+        | "uint_least8_t" | "uint_least16_t" | "uint_least32_t" | "uint_least64_t"
+        | "uintmax_t" | "uintptr_t" | "INT8_MAX" | "INT8_MIN" | "INT16_MAX" | "INT16_MIN"
+        | "INT32_MAX" | "INT32_MIN" | "INT64_MAX" | "INT64_MIN" | "SIZE_MAX" | "UINT8_MAX"
+        | "UINT16_MAX" | "UINT32_MAX" | "UINT64_MAX" => "stdint.h",
+        "EOF" | "FILE" | "fclose" | "fflush" | "fgets" | "fopen" | "fprintf" | "fputc"
+        | "fputs" | "fread" | "fscanf" | "fwrite" | "getchar" | "perror" | "printf" | "putchar"
+        | "puts" | "scanf" | "snprintf" | "sprintf" | "sscanf" | "stderr" | "stdin" | "stdout" => {
+            "stdio.h"
+        }
+        "EXIT_FAILURE" | "EXIT_SUCCESS" | "abort" | "abs" | "atexit" | "atoi" | "atol"
+        | "bsearch" | "calloc" | "exit" | "free" | "malloc" | "qsort" | "realloc" | "strtod"
+        | "strtol" | "strtoul" => "stdlib.h",
+        "memchr" | "memcmp" | "memcpy" | "memmove" | "memset" | "strcat" | "strchr" | "strcmp"
+        | "strcpy" | "strlen" | "strncat" | "strncmp" | "strncpy" | "strrchr" | "strstr" => {
+            "string.h"
+        }
+        _ => return None,
+    };
+
+    if matches!(header, "stdbool.h" | "stdint.h") && !standard.is_at_least(Standard::C99) {
+        return None;
+    }
+
+    Some(header)
+}
+
+#[derive(Default)]
+struct Collector {
+    /// Names that the statements refer to outside of the scopes of local declarations of the same name.
+    names: HashSet<Identifier>,
+    /// Names that the statements declare at file scope, which includes macros.
+    declared: HashSet<Identifier>,
+    /// Names that the enclosing functions, blocks and prototypes declare, innermost last.
+    scopes: Vec<HashSet<Identifier>>,
+    /// Paths that the statements include.
+    included: HashSet<String>,
+}
+
+impl Collector {
+    fn declare(&mut self, name: &Identifier) {
+        self.scopes
+            .last_mut()
+            .unwrap_or(&mut self.declared)
+            .insert(name.clone());
+    }
+
+    fn refer(&mut self, name: &Identifier) {
+        if !self.scopes.iter().any(|scope| scope.contains(name)) {
+            self.names.insert(name.clone());
+        }
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashSet::new());
+        f(self);
+        self.scopes.pop();
+    }
+}
+
+impl Visitor for Collector {
+    fn visit_variable(&mut self, variable: &Variable) {
+        self.refer(variable);
+    }
+
+    fn visit_concrete_type(&mut self, ty: &ConcreteType) {
+        match ty {
+            ConcreteType::Size => self.refer(&Identifier::new_unchecked("size_t")),
+            ConcreteType::StrongInt(integer) => {
+                self.refer(&Identifier::new_unchecked(&integer.to_string()))
+            }
+            ConcreteType::TypedefName(name) => self.refer(name),
+            _ => {}
+        }
+
+        visit::walk_concrete_type(self, ty);
+    }
+
+    fn visit_scalar(&mut self, scalar: &Scalar) {
+        if let Scalar::StrongInt(integer) = scalar {
+            self.refer(&Identifier::new_unchecked(&integer.to_string()));
+        }
+
+        visit::walk_scalar(self, scalar);
+    }
+
+    fn visit_offset_of(&mut self, offset_of: &OffsetOf) {
+        self.refer(&Identifier::new_unchecked("offsetof"));
+
+        visit::walk_offset_of(self, offset_of);
+    }
+
+    fn visit_include(&mut self, include: &Include) {
+        self.included.insert(include.path.clone());
+    }
+
+    fn visit_define(&mut self, define: &Define) {
+        self.declared.insert(define.name.clone());
+
+        visit::walk_define(self, define);
+    }
+
+    fn visit_typedef(&mut self, typedef: &Typedef) {
+        self.declare(&typedef.alias);
+
+        visit::walk_typedef(self, typedef);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &Enumerator) {
+        self.declare(&enumerator.name);
+
+        visit::walk_enumerator(self, enumerator);
+    }
+
+    fn visit_function_declaration(&mut self, declaration: &FunctionDeclaration) {
+        self.declare(&declaration.name);
+
+        visit::walk_function_declaration(self, declaration);
+    }
+
+    fn visit_function_definition(&mut self, definition: &FunctionDefinition) {
+        self.declare(&definition.name);
+
+        // The parameters are declared in the scope of the body, unlike those of prototypes
+        self.scoped(|collector| {
+            collector.visit_concrete_type(&definition.ty.return_ty);
+            for parameter in &definition.ty.parameters {
+                collector.visit_function_parameter(parameter);
+            }
+            collector.visit_block(&definition.body);
+        });
+    }
+
+    fn visit_function_type(&mut self, function: &Function) {
+        self.scoped(|collector| visit::walk_function_type(collector, function));
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.scoped(|collector| visit::walk_block(collector, block));
+    }
+
+    fn visit_for(&mut self, for_statement: &For) {
+        self.scoped(|collector| visit::walk_for(collector, for_statement));
+    }
+
+    fn visit_function_parameter(&mut self, parameter: &FunctionParameter) {
+        if let Some(name) = &parameter.name {
+            self.declare(name);
+        }
+
+        visit::walk_function_parameter(self, parameter);
+    }
+
+    fn visit_variable_declaration(&mut self, declaration: &VariableDeclaration) {
+        self.declare(&declaration.identifier);
+
+        visit::walk_variable_declaration(self, declaration);
+    }
+
+    fn visit_declarator(&mut self, declarator: &Declarator) {
+        self.declare(&declarator.identifier);
+
+        visit::walk_declarator(self, declarator);
+    }
+}
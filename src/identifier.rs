@@ -1,5 +1,7 @@
 use core::fmt;
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet};
+
+use crate::Standard;
 
 /// Errors that can occur when creating an identifier
 #[derive(Debug, thiserror::Error)]
@@ -10,13 +12,24 @@ pub enum Error {
     StartsWithDigit,
     #[error("identifier can only contain letters, digits, and underscore")]
     InvalidCharacters,
+    #[error("`{0}` is a keyword")]
+    Keyword(String),
 }
 
 #[repr(transparent)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(try_from = "String", into = "String")
+)]
 pub struct Identifier(String);
 
 impl Identifier {
+    /// Creates an identifier, which must not be a keyword of every standard, e.g. `while`.
+    ///
+    /// Names that only some standards reserve, e.g. `bool` in C23 or `typeof` in the GNU dialects, are accepted here
+    /// and rejected when generating code for those standards.
     pub fn new<S: Into<String>>(value: S) -> Result<Self, Error> {
         let string = value.into();
 
@@ -36,8 +49,78 @@ impl Identifier {
             return Err(Error::InvalidCharacters);
         }
 
+        if Standard::C89.is_keyword(&string) {
+            return Err(Error::Keyword(string));
+        }
+
         Ok(Self(string))
     }
+
+    /// Creates an identifier without validating it, for names that are known to be valid or that are never printed.
+    pub(crate) fn new_unchecked(value: &str) -> Self {
+        Self(value.to_string())
+    }
+
+    /// Creates a legal identifier from an arbitrary string, e.g. a name in another language, that differs from the
+    /// taken identifiers, and adds it to them.
+    ///
+    /// Invalid characters are replaced by underscores and leading underscores are removed. A name that starts with a
+    /// digit, or that is [reserved at file scope](Self::is_reserved_at_file_scope) like `string`, is prefixed with
+    /// `x`, a keyword of any standard is suffixed with an underscore, and a taken name is suffixed with a number, e.g.
+    /// `2d-point` becomes `x2d_point` and `int` becomes `int_`. The identifier is therefore not reserved in any
+    /// scope.
+    pub fn sanitize(value: &str, taken: &mut HashSet<Identifier>) -> Self {
+        let mut name: String = value
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+            .trim_start_matches('_')
+            .to_string();
+
+        if name.is_empty() {
+            name.push_str("id");
+        } else if name.starts_with(|c: char| c.is_ascii_digit())
+            || Self(name.clone()).is_reserved_at_file_scope()
+        {
+            name.insert(0, 'x');
+        }
+        if Standard::Gnu23.is_keyword(&name) {
+            name.push('_');
+        }
+
+        let mut identifier = Self(name.clone());
+        for suffix in 2.. {
+            if !taken.contains(&identifier) {
+                break;
+            }
+            identifier = Self(format!("{name}_{suffix}"));
+        }
+
+        taken.insert(identifier.clone());
+        identifier
+    }
+
+    /// Whether the identifier is reserved in every scope, because it starts with two underscores, or an underscore
+    /// and an uppercase letter, e.g. `__x` or `_Upper`.
+    pub fn is_reserved(&self) -> bool {
+        let mut chars = self.0.chars();
+        chars.next() == Some('_')
+            && chars
+                .next()
+                .is_some_and(|c| c == '_' || c.is_ascii_uppercase())
+    }
+
+    /// Whether the identifier is reserved at file scope, because it is [reserved](Self::is_reserved) in every scope,
+    /// starts with an underscore, or starts with `is`, `mem` or `str` and a lowercase letter, which are reserved for
+    /// future library functions.
+    pub fn is_reserved_at_file_scope(&self) -> bool {
+        self.0.starts_with('_')
+            || ["is", "mem", "str"].iter().any(|prefix| {
+                self.0
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_lowercase()))
+            })
+    }
 }
 
 impl AsRef<str> for Identifier {
@@ -64,6 +147,14 @@ impl<'a> From<&'a Identifier> for Cow<'a, str> {
     }
 }
 
+impl TryFrom<String> for Identifier {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
 impl From<Identifier> for String {
     fn from(identifier: Identifier) -> Self {
         identifier.0
@@ -110,4 +201,67 @@ mod tests {
         let id = Identifier::new("_valid_identifier").unwrap();
         assert_eq!(id.to_string(), "_valid_identifier");
     }
+
+    #[test]
+    fn test_keyword() {
+        let result = Identifier::new("while");
+        assert!(matches!(result, Err(Error::Keyword(keyword)) if keyword == "while"));
+
+        // Keywords of later standards are only rejected by the generation of a file in that standard
+        assert!(Identifier::new("bool").is_ok());
+    }
+
+    #[test]
+    fn test_reserved() {
+        let reserved = |name: &str| {
+            let id = Identifier::new(name).unwrap();
+            (id.is_reserved(), id.is_reserved_at_file_scope())
+        };
+
+        assert_eq!(reserved("__x"), (true, true));
+        assert_eq!(reserved("_Upper"), (true, true));
+        assert_eq!(reserved("_lower"), (false, true));
+        assert_eq!(reserved("strdup"), (false, true));
+        assert_eq!(reserved("memory"), (false, true));
+        assert_eq!(reserved("isvalid"), (false, true));
+        assert_eq!(reserved("is_valid"), (false, false));
+        assert_eq!(reserved("string_"), (false, true));
+        assert_eq!(reserved("stride"), (false, true));
+        assert_eq!(reserved("st"), (false, false));
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let mut taken = HashSet::from([Identifier::new("point").unwrap()]);
+        let mut sanitize = |value: &str| Identifier::sanitize(value, &mut taken).to_string();
+
+        assert_eq!(sanitize("2d-point"), "x2d_point");
+        assert_eq!(sanitize("int"), "int_");
+        assert_eq!(sanitize("__init__"), "init__");
+        assert_eq!(sanitize("point"), "point_2");
+        assert_eq!(sanitize("point"), "point_3");
+        assert_eq!(sanitize("bool"), "bool_");
+        assert_eq!(sanitize("größe"), "gr__e");
+        assert_eq!(sanitize("___"), "id");
+        assert_eq!(sanitize(""), "id_2");
+        assert_eq!(sanitize("string"), "xstring");
+        assert_eq!(sanitize("_memory"), "xmemory");
+        assert_eq!(sanitize("isolate"), "xisolate");
+        assert_eq!(sanitize("island"), "xisland");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let id = Identifier::new("valid_identifier").unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "\"valid_identifier\"");
+        assert_eq!(serde_json::from_str::<Identifier>(&json).unwrap(), id);
+
+        let result = serde_json::from_str::<Identifier>("\"123invalid\"");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "identifier cannot start with a digit"
+        );
+    }
 }
@@ -0,0 +1,1276 @@
+//! Computation of the C type of expressions.
+//!
+//! Integer sizes follow the [`DataModel`] of the environment, which defaults to LP64, i.e. `int` has 32 bits and `long`,
+//! `size_t` and pointers have 64 bits. Type aliases like `int64_t` and `size_t` rank like the standard integer type of
+//! the lowest rank with the same size, e.g. `long long` for `int64_t` on LLP64. Plain `char` is assumed to be signed.
+
+use std::collections::HashMap;
+
+use crate::{
+    layout::{DataModel, Layout},
+    operator::{BinaryOperatorKind, PrefixOperatorKind, SizeOf},
+    r#type::{
+        Array, Definition as TypeDefinition, Enum, Integer, IntegerKind, OpaqueType, Pointer, Real,
+        Scalar, StrongInt, Struct, Union,
+    },
+    value, ConcreteType, Expression, Identifier, Value,
+};
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum TypeError {
+    #[error("`{0}` is not declared")]
+    UndeclaredIdentifier(Identifier),
+    #[error("`{0}` is an incomplete type")]
+    IncompleteType(Box<ConcreteType>),
+    #[error("invalid operands to `{operator}`: `{left}` and `{right}`")]
+    InvalidOperands {
+        operator: String,
+        left: Box<ConcreteType>,
+        right: Box<ConcreteType>,
+    },
+    #[error("invalid operand to `{operator}`: `{operand}`")]
+    InvalidOperand {
+        operator: String,
+        operand: Box<ConcreteType>,
+    },
+    #[error("`{ty}` has no member named `{member}`")]
+    NoSuchMember {
+        ty: Box<ConcreteType>,
+        member: Identifier,
+    },
+    #[error("`{0}` is not a function")]
+    NotCallable(Box<ConcreteType>),
+    #[error("the function expects {expected} arguments, but {found} were passed")]
+    ArgumentCount { expected: usize, found: usize },
+    #[error("the type of `{0}` depends on the context")]
+    Untyped(String),
+}
+
+/// The declarations that are visible to an expression.
+#[derive(Clone, Debug, Default)]
+pub struct Environment {
+    /// Variables, parameters, functions and enumeration constants.
+    ordinary: HashMap<Identifier, OpaqueType>,
+    typedefs: HashMap<Identifier, OpaqueType>,
+    /// Complete structure and union definitions, by tag.
+    tags: HashMap<Identifier, TypeDefinition>,
+    /// The values of enumeration constants.
+    constants: HashMap<Identifier, Expression>,
+    /// The maximum member alignment of structures that are defined in `#pragma pack` regions, by tag or type alias.
+    packs: HashMap<Identifier, u64>,
+    data_model: DataModel,
+}
+
+impl Environment {
+    /// Declares a variable, parameter, function or enumeration constant.
+    pub fn declare<T: Into<OpaqueType>>(&mut self, name: Identifier, ty: T) -> &mut Self {
+        self.ordinary.entry(name).or_insert(ty.into());
+
+        self
+    }
+
+    /// Declares an enumeration constant, which has type `int`, or the fixed underlying type of its enumeration.
+    pub fn declare_constant<E: Into<Expression>>(
+        &mut self,
+        name: Identifier,
+        ty: ConcreteType,
+        value: E,
+    ) -> &mut Self {
+        if !self.ordinary.contains_key(&name) {
+            self.ordinary.insert(name.clone(), ty.into());
+            self.constants.insert(name, value.into());
+        }
+
+        self
+    }
+
+    /// Declares a type alias.
+    pub fn declare_typedef<T: Into<OpaqueType>>(&mut self, alias: Identifier, ty: T) -> &mut Self {
+        self.typedefs.entry(alias).or_insert(ty.into());
+
+        self
+    }
+
+    /// Defines the structure, union or enumeration with the tag of the definition. Anonymous definitions are ignored.
+    pub fn define_tag<T: Into<TypeDefinition>>(&mut self, definition: T) -> &mut Self {
+        let definition = definition.into();
+
+        let name = match &definition {
+            TypeDefinition::Enum(Enum::Definition { name, .. })
+            | TypeDefinition::Struct(Struct::Definition { name, .. })
+            | TypeDefinition::Union(Union::Definition { name, .. }) => name.clone(),
+            _ => None,
+        };
+
+        if let Some(name) = name {
+            self.tags.entry(name).or_insert(definition);
+        }
+
+        self
+    }
+
+    /// Sets the sizes and alignments of scalar types, which defaults to [`DataModel::LP64`].
+    pub fn set_data_model(&mut self, data_model: DataModel) -> &mut Self {
+        self.data_model = data_model;
+
+        self
+    }
+
+    pub fn data_model(&self) -> &DataModel {
+        &self.data_model
+    }
+
+    /// Lays out a structure or union, by tag or type alias, as if defined within `#pragma pack(push, N)`.
+    pub fn set_pack(&mut self, name: Identifier, alignment: u64) -> &mut Self {
+        self.packs.entry(name).or_insert(alignment);
+
+        self
+    }
+
+    pub(crate) fn pack(&self, name: &Identifier) -> Option<u64> {
+        self.packs.get(name).copied()
+    }
+
+    /// Returns the value of an enumeration constant.
+    pub(crate) fn constant(&self, name: &Identifier) -> Option<&Expression> {
+        self.constants.get(name)
+    }
+
+    /// Returns the complete definition of a structure, union or enumeration.
+    pub(crate) fn tag(&self, name: &Identifier) -> Option<&TypeDefinition> {
+        self.tags.get(name)
+    }
+
+    /// Returns the type of the expression.
+    ///
+    /// Arrays keep their type, e.g. for `sizeof`, but function designators decay to function pointers. Type qualifiers
+    /// of the result are dropped.
+    pub fn type_of(&self, expression: &Expression) -> Result<ConcreteType, TypeError> {
+        match self.designator(expression)? {
+            OpaqueType::ConcreteType(ty) => self.resolve(&ty),
+            OpaqueType::Function(function) => Ok(pointer_to(function)),
+        }
+    }
+
+    /// Returns the type of the value of the expression, after array-to-pointer and function-to-pointer conversions.
+    fn value_of(&self, expression: &Expression) -> Result<ConcreteType, TypeError> {
+        let ty = self.type_of(expression)?;
+
+        Ok(match ty {
+            ConcreteType::Array(array) => pointer_to(*array.element_type),
+            ty => ty,
+        })
+    }
+
+    /// Returns the type of the expression, which can be a function type.
+    fn designator(&self, expression: &Expression) -> Result<OpaqueType, TypeError> {
+        let ty = match expression {
+            Expression::AlignOf(_) => ConcreteType::Size,
+            Expression::OffsetOf(offsetof) => {
+                self.member(&self.resolve(&offsetof.ty)?, &offsetof.member)?;
+                ConcreteType::Size
+            }
+            Expression::ArraySubscript(subscript) => {
+                let array = self.value_of(&subscript.array)?;
+                let index = self.value_of(&subscript.index)?;
+
+                match (&array, &index) {
+                    (ConcreteType::Pointer(pointer), index)
+                    | (index, ConcreteType::Pointer(pointer))
+                        if is_integer(index) =>
+                    {
+                        self.pointee(pointer, "[]")?
+                    }
+                    _ => {
+                        return Err(TypeError::InvalidOperands {
+                            operator: "[]".to_string(),
+                            left: Box::new(array),
+                            right: Box::new(index),
+                        })
+                    }
+                }
+            }
+            Expression::Assignment(assignment) => {
+                self.type_of(&assignment.right)?;
+                self.value_of(&assignment.left)?
+            }
+            Expression::BinaryOperator(operation) => self.binary_operator(
+                operation.operator,
+                self.value_of(&operation.left)?,
+                self.value_of(&operation.right)?,
+            )?,
+            Expression::Cast(cast) => {
+                self.type_of(&cast.expression)?;
+                scalar_type(&cast.new_ty)
+            }
+            Expression::CommaOperator(comma) => {
+                self.type_of(&comma.left)?;
+                self.value_of(&comma.right)?
+            }
+            Expression::CompoundAssignment(assignment) => {
+                self.type_of(&assignment.right)?;
+                self.value_of(&assignment.left)?
+            }
+            Expression::Conditional(conditional) => {
+                self.value_of(&conditional.condition)?;
+
+                let then_ty = self.value_of(&conditional.then_branch)?;
+                let else_ty = self.value_of(&conditional.else_branch)?;
+                if is_arithmetic(&then_ty) && is_arithmetic(&else_ty) {
+                    self.usual_arithmetic_conversions(then_ty, else_ty)
+                } else if matches!(else_ty, ConcreteType::Pointer(_))
+                    && !matches!(then_ty, ConcreteType::Pointer(_))
+                {
+                    // A null pointer constant converts to the type of the other pointer
+                    else_ty
+                } else {
+                    then_ty
+                }
+            }
+            Expression::FunctionCall(call) => {
+                let callee = self.value_of(&call.callee)?;
+                let function = match &callee {
+                    ConcreteType::Pointer(pointer) => match &pointer.pointer_ty {
+                        OpaqueType::Function(function) => function,
+                        _ => return Err(TypeError::NotCallable(Box::new(callee))),
+                    },
+                    _ => return Err(TypeError::NotCallable(Box::new(callee))),
+                };
+
+                if function.parameters.len() != call.arguments.len() {
+                    return Err(TypeError::ArgumentCount {
+                        expected: function.parameters.len(),
+                        found: call.arguments.len(),
+                    });
+                }
+
+                for argument in &call.arguments {
+                    self.type_of(argument)?;
+                }
+
+                function.return_ty.clone()
+            }
+            Expression::IndirectMemberAccess(access) => match self.value_of(&access.left)? {
+                ConcreteType::Pointer(pointer) => {
+                    let ty = self.pointee(&pointer, "->")?;
+                    self.member(&ty, &access.member)?
+                }
+                operand => {
+                    return Err(TypeError::InvalidOperand {
+                        operator: "->".to_string(),
+                        operand: Box::new(operand),
+                    })
+                }
+            },
+            Expression::InitializerList(_) => {
+                return Err(TypeError::Untyped(expression.to_string()));
+            }
+            Expression::MemberAccess(access) => {
+                let ty = self.type_of(&access.left)?;
+                self.member(&ty, &access.member)?
+            }
+            Expression::Parentheses(inner) => return self.designator(inner),
+            Expression::PostfixOperator(operation) => {
+                let operand = self.value_of(&operation.operand)?;
+                if !is_scalar(&operand) {
+                    return Err(TypeError::InvalidOperand {
+                        operator: operation.operator.to_string(),
+                        operand: Box::new(operand),
+                    });
+                }
+
+                operand
+            }
+            Expression::PrefixOperator(operation) => {
+                return self.prefix_operator(operation.operator, &operation.operand)
+            }
+            Expression::SizeOf(sizeof) => {
+                if let SizeOf::Expression(inner) = sizeof.as_ref() {
+                    self.type_of(inner)?;
+                }
+
+                ConcreteType::Size
+            }
+            Expression::Value(value) => value_type(value, &self.data_model)?,
+            Expression::Variable(variable) => {
+                return self
+                    .ordinary
+                    .get(variable)
+                    .cloned()
+                    .ok_or_else(|| TypeError::UndeclaredIdentifier(variable.clone()))
+            }
+        };
+
+        Ok(OpaqueType::ConcreteType(ty))
+    }
+
+    fn prefix_operator(
+        &self,
+        operator: PrefixOperatorKind,
+        operand: &Expression,
+    ) -> Result<OpaqueType, TypeError> {
+        let invalid = |operand| TypeError::InvalidOperand {
+            operator: operator.to_string(),
+            operand: Box::new(operand),
+        };
+
+        let ty = match operator {
+            PrefixOperatorKind::Increment | PrefixOperatorKind::Decrement => {
+                let operand = self.value_of(operand)?;
+                if !is_scalar(&operand) {
+                    return Err(invalid(operand));
+                }
+
+                operand
+            }
+            PrefixOperatorKind::Positive | PrefixOperatorKind::Negative => {
+                let operand = self.value_of(operand)?;
+                if !is_arithmetic(&operand) {
+                    return Err(invalid(operand));
+                }
+
+                promote(operand)
+            }
+            // `&` does not apply array-to-pointer or function-to-pointer conversions
+            PrefixOperatorKind::Address => match self.designator(operand)? {
+                OpaqueType::ConcreteType(ty) => pointer_to(ty),
+                OpaqueType::Function(function) => pointer_to(function),
+            },
+            PrefixOperatorKind::Indirection => match self.value_of(operand)? {
+                ConcreteType::Pointer(pointer) => return Ok(pointer.pointer_ty.clone()),
+                operand => return Err(invalid(operand)),
+            },
+        };
+
+        Ok(OpaqueType::ConcreteType(ty))
+    }
+
+    pub(crate) fn binary_operator(
+        &self,
+        operator: BinaryOperatorKind,
+        left: ConcreteType,
+        right: ConcreteType,
+    ) -> Result<ConcreteType, TypeError> {
+        let ty = match operator {
+            // Only pointers to objects support arithmetic, not function pointers
+            BinaryOperatorKind::Add => match (&left, &right) {
+                (pointer, integer) | (integer, pointer)
+                    if is_object_pointer(pointer) && is_integer(integer) =>
+                {
+                    Some(pointer.clone())
+                }
+                _ => None,
+            },
+            BinaryOperatorKind::Sub => match (&left, &right) {
+                (left, right) if is_object_pointer(left) && is_object_pointer(right) => {
+                    Some(ConcreteType::TypedefName(
+                        Identifier::new("ptrdiff_t").expect("is a valid identifier"),
+                    ))
+                }
+                (left, right) if is_object_pointer(left) && is_integer(right) => Some(left.clone()),
+                _ => None,
+            },
+            // Pointers are only ordered relative to other pointers
+            BinaryOperatorKind::Lt
+            | BinaryOperatorKind::Le
+            | BinaryOperatorKind::Gt
+            | BinaryOperatorKind::Ge => match (&left, &right) {
+                (ConcreteType::Pointer(_), ConcreteType::Pointer(_)) => Some(ConcreteType::int()),
+                (left, right) if is_arithmetic(left) && is_arithmetic(right) => {
+                    Some(ConcreteType::int())
+                }
+                _ => {
+                    return Err(TypeError::InvalidOperands {
+                        operator: operator.to_string(),
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    })
+                }
+            },
+            BinaryOperatorKind::Eq
+            | BinaryOperatorKind::Ne
+            | BinaryOperatorKind::And
+            | BinaryOperatorKind::Or => {
+                (is_scalar(&left) && is_scalar(&right)).then(ConcreteType::int)
+            }
+            _ => None,
+        };
+
+        if let Some(ty) = ty {
+            return Ok(ty);
+        }
+
+        let is_valid = match operator {
+            BinaryOperatorKind::Add
+            | BinaryOperatorKind::Sub
+            | BinaryOperatorKind::Mul
+            | BinaryOperatorKind::Div => is_arithmetic(&left) && is_arithmetic(&right),
+            BinaryOperatorKind::Mod
+            | BinaryOperatorKind::BitAnd
+            | BinaryOperatorKind::BitOr
+            | BinaryOperatorKind::BitXor
+            | BinaryOperatorKind::LShift
+            | BinaryOperatorKind::RShift => is_integer(&left) && is_integer(&right),
+            _ => false,
+        };
+
+        if !is_valid {
+            return Err(TypeError::InvalidOperands {
+                operator: operator.to_string(),
+                left: Box::new(left),
+                right: Box::new(right),
+            });
+        }
+
+        Ok(match operator {
+            // The type of a shift is the promoted type of the left operand
+            BinaryOperatorKind::LShift | BinaryOperatorKind::RShift => promote(left),
+            _ => self.usual_arithmetic_conversions(left, right),
+        })
+    }
+
+    /// Returns the common type of two arithmetic operands.
+    ///
+    /// # Source
+    ///
+    /// https://en.cppreference.com/w/c/language/conversion#Usual_arithmetic_conversions
+    pub(crate) fn usual_arithmetic_conversions(
+        &self,
+        left: ConcreteType,
+        right: ConcreteType,
+    ) -> ConcreteType {
+        match (&left, &right) {
+            (ConcreteType::Real(left_real), ConcreteType::Real(right_real)) => {
+                return if real_rank(*right_real) > real_rank(*left_real) {
+                    right
+                } else {
+                    left
+                };
+            }
+            (ConcreteType::Real(_), _) => return left,
+            (_, ConcreteType::Real(_)) => return right,
+            _ => {}
+        }
+
+        let left = promote(left);
+        let right = promote(right);
+
+        let (Some(left_rank), Some(right_rank)) = (
+            integer_rank(&left, &self.data_model),
+            integer_rank(&right, &self.data_model),
+        ) else {
+            return left;
+        };
+
+        if left_rank.is_signed == right_rank.is_signed {
+            return if right_rank.rank > left_rank.rank {
+                right
+            } else {
+                left
+            };
+        }
+
+        let (signed, signed_rank, unsigned, unsigned_rank) = if left_rank.is_signed {
+            (left, left_rank, right, right_rank)
+        } else {
+            (right, right_rank, left, left_rank)
+        };
+
+        if unsigned_rank.rank >= signed_rank.rank {
+            unsigned
+        } else if signed_rank.width > unsigned_rank.width {
+            signed
+        } else {
+            to_unsigned(signed)
+        }
+    }
+
+    /// Resolves type aliases and strips type qualifiers. Unknown aliases are kept.
+    pub(crate) fn resolve(&self, ty: &ConcreteType) -> Result<ConcreteType, TypeError> {
+        match ty {
+            ConcreteType::Const(ty) => self.resolve(ty),
+            ConcreteType::Typedef(typedef) => match &typedef.ty {
+                OpaqueType::ConcreteType(ty) => self.resolve(ty),
+                OpaqueType::Function(function) => Ok(pointer_to(function.clone())),
+            },
+            ConcreteType::TypedefName(alias) => match self.typedefs.get(alias) {
+                Some(OpaqueType::ConcreteType(ty)) => self.resolve(ty),
+                Some(OpaqueType::Function(function)) => Ok(pointer_to(function.clone())),
+                // Aliases from headers, e.g. `ptrdiff_t`
+                None => Ok(ty.clone()),
+            },
+            ty => Ok(ty.clone()),
+        }
+    }
+
+    /// Returns the type that the pointer points to, which cannot be a function.
+    fn pointee(&self, pointer: &Pointer, operator: &str) -> Result<ConcreteType, TypeError> {
+        match &pointer.pointer_ty {
+            OpaqueType::ConcreteType(ty) => self.resolve(ty),
+            OpaqueType::Function(_) => Err(TypeError::InvalidOperand {
+                operator: operator.to_string(),
+                operand: Box::new(ConcreteType::Pointer(Box::new(pointer.clone()))),
+            }),
+        }
+    }
+
+    /// Returns the type of a member of a structure or union.
+    fn member(&self, ty: &ConcreteType, member: &Identifier) -> Result<ConcreteType, TypeError> {
+        self.resolve(&self.member_declaration(ty, member)?)
+    }
+
+    /// Returns the declared type of a member of a structure or union, including its qualifiers.
+    fn member_declaration(
+        &self,
+        ty: &ConcreteType,
+        member: &Identifier,
+    ) -> Result<ConcreteType, TypeError> {
+        let definition = match ty {
+            ConcreteType::TypedefName(_) => {
+                return Err(TypeError::IncompleteType(Box::new(ty.clone())))
+            }
+            ConcreteType::Struct(Struct::Tag { name })
+            | ConcreteType::Union(Union::Tag { name }) => self
+                .tags
+                .get(name)
+                .ok_or_else(|| TypeError::IncompleteType(Box::new(ty.clone())))?,
+            _ => &match ty {
+                ConcreteType::Struct(structure) => TypeDefinition::Struct(structure.clone()),
+                ConcreteType::Union(union) => TypeDefinition::Union(union.clone()),
+                _ => {
+                    return Err(TypeError::InvalidOperand {
+                        operator: ".".to_string(),
+                        operand: Box::new(ty.clone()),
+                    })
+                }
+            },
+        };
+
+        let found = match definition {
+            TypeDefinition::Struct(Struct::Definition { members, .. }) => members
+                .iter()
+                .find(|candidate| candidate.name == *member)
+                .cloned(),
+            TypeDefinition::Union(Union::Definition { members, .. }) => members
+                .iter()
+                .find(|candidate| candidate.name == *member)
+                .cloned(),
+            _ => None,
+        };
+
+        let found = found.ok_or_else(|| TypeError::NoSuchMember {
+            ty: Box::new(tag(ty)),
+            member: member.clone(),
+        })?;
+
+        Ok(found.ty)
+    }
+
+    /// Returns whether an lvalue can be assigned to, which excludes arrays, functions and `const` objects.
+    pub(crate) fn is_modifiable(&self, lvalue: &Expression) -> Result<bool, TypeError> {
+        let ty = match self.designator(lvalue)? {
+            OpaqueType::ConcreteType(ty) => self.resolve(&ty)?,
+            OpaqueType::Function(_) => return Ok(false),
+        };
+
+        Ok(!matches!(ty, ConcreteType::Array(_)) && !self.is_const(lvalue)?)
+    }
+
+    /// Returns whether the object that an lvalue designates is `const`-qualified.
+    fn is_const(&self, lvalue: &Expression) -> Result<bool, TypeError> {
+        let pointee_is_const = |pointer: ConcreteType| match pointer {
+            ConcreteType::Pointer(pointer) => match &pointer.pointer_ty {
+                OpaqueType::ConcreteType(ty) => self.is_const_type(ty),
+                OpaqueType::Function(_) => false,
+            },
+            _ => false,
+        };
+
+        Ok(match lvalue {
+            Expression::Variable(variable) => match self.ordinary.get(variable) {
+                Some(OpaqueType::ConcreteType(ty)) => self.is_const_type(ty),
+                _ => false,
+            },
+            Expression::Parentheses(inner) => self.is_const(inner)?,
+            Expression::MemberAccess(access) => {
+                let ty = self.type_of(&access.left)?;
+                self.is_const(&access.left)?
+                    || self.is_const_type(&self.member_declaration(&ty, &access.member)?)
+            }
+            Expression::IndirectMemberAccess(access) => {
+                let pointer = self.value_of(&access.left)?;
+                let ty = match &pointer {
+                    ConcreteType::Pointer(pointer) => self.pointee(pointer, "->")?,
+                    _ => return Ok(false),
+                };
+                pointee_is_const(pointer)
+                    || self.is_const_type(&self.member_declaration(&ty, &access.member)?)
+            }
+            Expression::ArraySubscript(subscript) => {
+                let array = self.value_of(&subscript.array)?;
+                let pointer = if matches!(array, ConcreteType::Pointer(_)) {
+                    array
+                } else {
+                    self.value_of(&subscript.index)?
+                };
+                pointee_is_const(pointer)
+            }
+            Expression::PrefixOperator(operation) => {
+                operation.operator == PrefixOperatorKind::Indirection
+                    && pointee_is_const(self.value_of(&operation.operand)?)
+            }
+            _ => false,
+        })
+    }
+
+    /// Returns whether the type is `const`-qualified, directly or through a type alias.
+    fn is_const_type(&self, ty: &ConcreteType) -> bool {
+        match ty {
+            ConcreteType::Const(_) => true,
+            // The elements of an array are qualified rather than the array
+            ConcreteType::Array(array) => self.is_const_type(&array.element_type),
+            ConcreteType::Typedef(typedef) => match &typedef.ty {
+                OpaqueType::ConcreteType(ty) => self.is_const_type(ty),
+                OpaqueType::Function(_) => false,
+            },
+            ConcreteType::TypedefName(alias) => match self.typedefs.get(alias) {
+                Some(OpaqueType::ConcreteType(ty)) => self.is_const_type(ty),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Returns the tag of a named structure or union definition, e.g. `struct s`, to refer to it in errors.
+fn tag(ty: &ConcreteType) -> ConcreteType {
+    match ty {
+        ConcreteType::Struct(Struct::Definition {
+            name: Some(name), ..
+        }) => ConcreteType::Struct(Struct::Tag { name: name.clone() }),
+        ConcreteType::Union(Union::Definition {
+            name: Some(name), ..
+        }) => ConcreteType::Union(Union::Tag { name: name.clone() }),
+        ty => ty.clone(),
+    }
+}
+
+pub(crate) fn pointer_to<T: Into<OpaqueType>>(ty: T) -> ConcreteType {
+    ConcreteType::Pointer(Box::new(Pointer {
+        pointer_ty: ty.into(),
+        is_const: false,
+    }))
+}
+
+pub(crate) fn scalar_type(scalar: &Scalar) -> ConcreteType {
+    match scalar {
+        Scalar::Bool => ConcreteType::Bool,
+        Scalar::Char => ConcreteType::Char,
+        Scalar::Integer(integer) => ConcreteType::Integer(integer.clone()),
+        Scalar::Pointer(pointer) => ConcreteType::Pointer(Box::new(pointer.clone())),
+        Scalar::Real(real) => ConcreteType::Real(*real),
+        Scalar::StrongInt(integer) => ConcreteType::StrongInt(integer.clone()),
+        Scalar::Void => ConcreteType::Void,
+    }
+}
+
+/// Returns the type of a literal.
+///
+/// Integer constants without a suffix have the first of `int`, `long` and `long long` that can represent their value.
+pub(crate) fn value_type(value: &Value, data_model: &DataModel) -> Result<ConcreteType, TypeError> {
+    // The first integer type of at least `int` whose width fits the value
+    let literal_kind = |bits: u64| {
+        [IntegerKind::Int, IntegerKind::Long]
+            .into_iter()
+            .find(|kind| bits <= data_model.integer(*kind).size * 8)
+            .unwrap_or(IntegerKind::LongLong)
+    };
+
+    let ty = match value {
+        Value::Array { values, base_type } => ConcreteType::Array(Array {
+            element_type: Box::new(base_type.clone()),
+            size: values.len().into(),
+        }),
+        // Character constants have type `int`
+        Value::Char { .. } | Value::Enum { .. } => ConcreteType::int(),
+        Value::Pointer { .. } => pointer_to(ConcreteType::Void),
+        Value::Real { kind, .. } => ConcreteType::Real(kind.unwrap_or(Real::Double)),
+        Value::SignedInteger { value, kind } => {
+            // The number of bits including the sign bit
+            let bits = u64::from(i64::BITS - value.unsigned_abs().leading_zeros()) + 1;
+
+            ConcreteType::Integer(Integer {
+                kind: kind.unwrap_or_else(|| literal_kind(bits)),
+                is_signed: true,
+            })
+        }
+        Value::Size { .. } => ConcreteType::Size,
+        Value::String(string) => ConcreteType::Array(Array {
+            element_type: Box::new(ConcreteType::Char),
+            size: (value::string_length(string) + 1).into(),
+        }),
+        Value::Struct { .. } => return Err(TypeError::Untyped(value.to_string())),
+        Value::UnsignedInteger { value, kind } => {
+            let bits = u64::from(u64::BITS - value.leading_zeros());
+
+            ConcreteType::Integer(Integer {
+                kind: kind.unwrap_or_else(|| literal_kind(bits)),
+                is_signed: false,
+            })
+        }
+    };
+
+    Ok(ty)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct IntegerRank {
+    /// The integer conversion rank, where `int` has rank 3.
+    pub(crate) rank: u8,
+    pub(crate) width: u8,
+    pub(crate) is_signed: bool,
+}
+
+const INT_RANK: u8 = 3;
+
+pub(crate) fn integer_rank(ty: &ConcreteType, data_model: &DataModel) -> Option<IntegerRank> {
+    let width = |layout: Layout| (layout.size * 8) as u8;
+
+    let (rank, width, is_signed) = match ty {
+        ConcreteType::Bool => (0, 1, false),
+        ConcreteType::Char => (1, 8, true),
+        // Enumerations without a fixed underlying type are compatible with `int`
+        ConcreteType::Enum(Enum::Definition {
+            underlying_ty: Some(underlying_ty),
+            ..
+        }) => return integer_rank(underlying_ty, data_model),
+        ConcreteType::Enum(_) => (INT_RANK, width(data_model.int), true),
+        ConcreteType::Integer(integer) => (
+            kind_rank(integer.kind),
+            width(data_model.integer(integer.kind)),
+            integer.is_signed,
+        ),
+        ConcreteType::Size => (
+            kind_rank(data_model.size_kind()),
+            width(data_model.pointer),
+            false,
+        ),
+        ConcreteType::StrongInt(integer) => (
+            kind_rank(data_model.strong_int_kind(integer)),
+            width(data_model.strong_int(integer)),
+            is_signed_strong_int(integer),
+        ),
+        _ => return None,
+    };
+
+    Some(IntegerRank {
+        rank,
+        width,
+        is_signed,
+    })
+}
+
+/// Returns the rank of a standard integer type. Type aliases, e.g. `size_t`, have the rank of the type they alias.
+fn kind_rank(kind: IntegerKind) -> u8 {
+    match kind {
+        IntegerKind::Char => 1,
+        IntegerKind::Short => 2,
+        IntegerKind::Int => INT_RANK,
+        IntegerKind::Long => 4,
+        IntegerKind::LongLong => 5,
+    }
+}
+
+fn is_signed_strong_int(integer: &StrongInt) -> bool {
+    matches!(
+        integer,
+        StrongInt::Int8
+            | StrongInt::Int16
+            | StrongInt::Int32
+            | StrongInt::Int64
+            | StrongInt::IntFast8
+            | StrongInt::IntFast16
+            | StrongInt::IntFast32
+            | StrongInt::IntFast64
+            | StrongInt::IntLeast8
+            | StrongInt::IntLeast16
+            | StrongInt::IntLeast32
+            | StrongInt::IntLeast64
+            | StrongInt::IntMax
+            | StrongInt::IntPtr
+    )
+}
+
+/// Converts a signed integer type to the corresponding unsigned type.
+fn to_unsigned(ty: ConcreteType) -> ConcreteType {
+    match ty {
+        ConcreteType::Integer(Integer { kind, .. }) => ConcreteType::Integer(Integer {
+            kind,
+            is_signed: false,
+        }),
+        ConcreteType::StrongInt(integer) => ConcreteType::StrongInt(match integer {
+            StrongInt::Int8 => StrongInt::Uint8,
+            StrongInt::Int16 => StrongInt::Uint16,
+            StrongInt::Int32 => StrongInt::Uint32,
+            StrongInt::Int64 => StrongInt::Uint64,
+            StrongInt::IntFast8 => StrongInt::UintFast8,
+            StrongInt::IntFast16 => StrongInt::UintFast16,
+            StrongInt::IntFast32 => StrongInt::UintFast32,
+            StrongInt::IntFast64 => StrongInt::UintFast64,
+            StrongInt::IntLeast8 => StrongInt::UintLeast8,
+            StrongInt::IntLeast16 => StrongInt::UintLeast16,
+            StrongInt::IntLeast32 => StrongInt::UintLeast32,
+            StrongInt::IntLeast64 => StrongInt::UintLeast64,
+            StrongInt::IntMax => StrongInt::UintMax,
+            StrongInt::IntPtr => StrongInt::UintPtr,
+            integer => integer,
+        }),
+        ty => ty,
+    }
+}
+
+/// Applies the integer promotions, which convert integers with a rank below `int` to `int`.
+pub(crate) fn promote(ty: ConcreteType) -> ConcreteType {
+    // Whether a rank is below that of `int` does not depend on the data model
+    match integer_rank(&ty, &DataModel::LP64) {
+        Some(rank) if rank.rank < INT_RANK => ConcreteType::int(),
+        _ => match ty {
+            ConcreteType::Enum(Enum::Definition {
+                underlying_ty: Some(underlying_ty),
+                ..
+            }) => promote(*underlying_ty),
+            // Enumerations without a fixed underlying type are compatible with `int`
+            ConcreteType::Enum(_) => ConcreteType::int(),
+            ty => ty,
+        },
+    }
+}
+
+fn real_rank(real: Real) -> u8 {
+    match real {
+        Real::Float => 0,
+        Real::Double => 1,
+        Real::LongDouble => 2,
+    }
+}
+
+fn is_integer(ty: &ConcreteType) -> bool {
+    integer_rank(ty, &DataModel::LP64).is_some()
+}
+
+pub(crate) fn is_arithmetic(ty: &ConcreteType) -> bool {
+    is_integer(ty) || matches!(ty, ConcreteType::Real(_))
+}
+
+/// Whether the type is a pointer to an object, rather than to a function.
+fn is_object_pointer(ty: &ConcreteType) -> bool {
+    matches!(ty, ConcreteType::Pointer(pointer) if matches!(pointer.pointer_ty, OpaqueType::ConcreteType(_)))
+}
+
+fn is_scalar(ty: &ConcreteType) -> bool {
+    is_arithmetic(ty) || matches!(ty, ConcreteType::Pointer(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        function::{FunctionCall, FunctionParameter},
+        operator::{ArraySubscript, BinaryOperator, PrefixOperator},
+        r#type::{
+            member::{IndirectMemberAccess, Member, MemberAccess},
+            Function,
+        },
+        validate::Segment,
+    };
+
+    fn binary(left: &str, operator: BinaryOperatorKind, right: &str) -> anyhow::Result<Expression> {
+        Ok(BinaryOperator {
+            left: Identifier::new(left)?.into(),
+            operator,
+            right: Identifier::new(right)?.into(),
+        }
+        .into())
+    }
+
+    #[test]
+    fn arithmetic_conversions() -> anyhow::Result<()> {
+        let mut environment = Environment::default();
+        environment
+            .declare(Identifier::new("c")?, ConcreteType::Char)
+            .declare(Identifier::new("i")?, ConcreteType::int())
+            .declare(Identifier::new("u")?, ConcreteType::unsigned_int())
+            .declare(
+                Identifier::new("l")?,
+                ConcreteType::Integer(Integer {
+                    kind: IntegerKind::Long,
+                    is_signed: true,
+                }),
+            )
+            .declare(Identifier::new("f")?, ConcreteType::float())
+            .declare(Identifier::new("d")?, ConcreteType::double());
+
+        let cases = [
+            (binary("c", BinaryOperatorKind::Add, "c")?, "int"),
+            (binary("i", BinaryOperatorKind::Add, "u")?, "unsigned int"),
+            (binary("u", BinaryOperatorKind::Mul, "l")?, "long"),
+            (binary("l", BinaryOperatorKind::Div, "f")?, "float"),
+            (binary("f", BinaryOperatorKind::Sub, "d")?, "double"),
+            (binary("c", BinaryOperatorKind::LShift, "l")?, "int"),
+            (binary("d", BinaryOperatorKind::Lt, "u")?, "int"),
+        ];
+        for (expression, expected) in cases {
+            assert_eq!(environment.type_of(&expression)?.to_string(), expected);
+        }
+
+        assert!(matches!(
+            environment.type_of(&binary("d", BinaryOperatorKind::Mod, "i")?),
+            Err(TypeError::InvalidOperands { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn enumerations() -> anyhow::Result<()> {
+        let file = crate::parse::parse_file(
+            "enum small { SMALL };
+            enum big : unsigned long long { BIG = 1ull << 40, BIGGER };
+            enum tiny : unsigned char { TINY };
+            enum big b;
+            int x = SMALL + BIGGER + TINY;",
+        )?
+        .file;
+        let symbols = file.symbols();
+        let environment = symbols
+            .environment(&vec![Segment::Field("statements"), Segment::Index(4)].into())
+            .unwrap_or_default();
+
+        // Enumeration constants have the fixed underlying type of their enumeration, if any
+        let cases = [
+            ("SMALL", "int"),
+            ("BIG", "unsigned long long"),
+            ("BIGGER", "unsigned long long"),
+            ("TINY", "unsigned char"),
+        ];
+        for (name, expected) in cases {
+            let constant = Expression::from(Identifier::new(name)?);
+            assert_eq!(environment.type_of(&constant)?.to_string(), expected);
+        }
+        assert_eq!(
+            environment
+                .evaluate(&Identifier::new("BIGGER")?.into())?
+                .as_integer(),
+            Some((1 << 40) + 1)
+        );
+        assert_eq!(
+            environment
+                .type_of(&binary("TINY", BinaryOperatorKind::Add, "TINY")?)?
+                .to_string(),
+            "int"
+        );
+        assert_eq!(
+            environment
+                .type_of(&binary("SMALL", BinaryOperatorKind::Add, "BIG")?)?
+                .to_string(),
+            "unsigned long long"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn data_models() -> anyhow::Result<()> {
+        let long = |is_signed| {
+            ConcreteType::Integer(Integer {
+                kind: IntegerKind::Long,
+                is_signed,
+            })
+        };
+        let literal = || Expression::from(Value::signed_integer(4000000000));
+
+        let mut environment = Environment::default();
+        environment
+            .declare(Identifier::new("l")?, long(true))
+            .declare(Identifier::new("ul")?, long(false))
+            .declare(
+                Identifier::new("i64")?,
+                ConcreteType::StrongInt(StrongInt::Int64),
+            )
+            .declare(
+                Identifier::new("u32")?,
+                ConcreteType::StrongInt(StrongInt::Uint32),
+            )
+            .declare(Identifier::new("size")?, ConcreteType::Size);
+        let long_plus_literal = BinaryOperator {
+            left: Identifier::new("l")?.into(),
+            operator: BinaryOperatorKind::Add,
+            right: literal(),
+        }
+        .into();
+
+        // `int64_t` and `size_t` are aliases of `long` and `unsigned long`
+        let cases = [
+            (
+                binary("i64", BinaryOperatorKind::Add, "ul")?,
+                "unsigned long",
+            ),
+            (binary("size", BinaryOperatorKind::Add, "l")?, "size_t"),
+            (binary("u32", BinaryOperatorKind::Add, "l")?, "long"),
+        ];
+        for (expression, expected) in cases {
+            assert_eq!(environment.type_of(&expression)?.to_string(), expected);
+        }
+        assert_eq!(environment.type_of(&long_plus_literal)?.to_string(), "long");
+
+        // `int64_t` is an alias of `long long` where `long` has 32 bits
+        environment.set_data_model(DataModel::LLP64);
+        let cases = [
+            (binary("i64", BinaryOperatorKind::Add, "ul")?, "int64_t"),
+            (binary("size", BinaryOperatorKind::Add, "i64")?, "size_t"),
+            (binary("size", BinaryOperatorKind::Add, "l")?, "size_t"),
+        ];
+        for (expression, expected) in cases {
+            assert_eq!(environment.type_of(&expression)?.to_string(), expected);
+        }
+        assert_eq!(
+            environment.type_of(&long_plus_literal)?.to_string(),
+            "long long"
+        );
+
+        // `size_t` is an alias of `unsigned int` where pointers have 32 bits
+        environment.set_data_model(DataModel::ILP32);
+        let cases = [
+            (binary("i64", BinaryOperatorKind::Add, "ul")?, "int64_t"),
+            (
+                binary("size", BinaryOperatorKind::Add, "l")?,
+                "unsigned long",
+            ),
+            (binary("size", BinaryOperatorKind::Add, "u32")?, "size_t"),
+        ];
+        for (expression, expected) in cases {
+            assert_eq!(environment.type_of(&expression)?.to_string(), expected);
+        }
+        assert_eq!(
+            environment.type_of(&long_plus_literal)?.to_string(),
+            "long long"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn pointers() -> anyhow::Result<()> {
+        let mut environment = Environment::default();
+        environment
+            .declare(
+                Identifier::new("array")?,
+                Array {
+                    element_type: Box::new(ConcreteType::int()),
+                    size: 4.into(),
+                },
+            )
+            .declare(Identifier::new("p")?, pointer_to(ConcreteType::int()))
+            .declare(Identifier::new("i")?, ConcreteType::int());
+
+        let array = Expression::from(Identifier::new("array")?);
+        assert_eq!(environment.type_of(&array)?.to_string(), "int[4]");
+        assert_eq!(
+            environment
+                .type_of(&binary("array", BinaryOperatorKind::Add, "i")?)?
+                .to_string(),
+            "int *"
+        );
+        assert_eq!(
+            environment
+                .type_of(&binary("p", BinaryOperatorKind::Sub, "array")?)?
+                .to_string(),
+            "ptrdiff_t"
+        );
+        assert_eq!(
+            environment
+                .type_of(
+                    &ArraySubscript {
+                        array: Value::signed_integer(2).into(),
+                        index: Identifier::new("p")?.into(),
+                    }
+                    .into()
+                )?
+                .to_string(),
+            "int"
+        );
+        assert!(matches!(
+            environment
+                .type_of(
+                    &PrefixOperator {
+                        operand: array,
+                        operator: PrefixOperatorKind::Address,
+                    }
+                    .into()
+                )?,
+            ConcreteType::Pointer(pointer) if matches!(
+                pointer.pointer_ty,
+                OpaqueType::ConcreteType(ConcreteType::Array(_))
+            )
+        ));
+
+        // Escape sequences of string literals count as a single byte, universal character names as their encoding
+        let strings = [
+            ("\\n\\x41", "char[3]"),
+            ("\\0\\012", "char[3]"),
+            ("caf\\u00e9", "char[6]"),
+        ];
+        for (string, expected) in strings {
+            let string = Expression::from(Value::String(string.to_string()));
+            assert_eq!(environment.type_of(&string)?.to_string(), expected);
+        }
+
+        let error = environment
+            .type_of(&binary("p", BinaryOperatorKind::Add, "p")?)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid operands to `+`: `int *` and `int *`"
+        );
+
+        let error = environment
+            .type_of(&binary("p", BinaryOperatorKind::Lt, "i")?)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid operands to `<`: `int *` and `int`"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn function_designators() -> anyhow::Result<()> {
+        let function = Function {
+            parameters: vec![FunctionParameter {
+                ty: ConcreteType::int(),
+                name: None,
+            }],
+            return_ty: ConcreteType::int(),
+            is_variadic: false,
+            has_unspecified_parameters: false,
+        };
+
+        let mut environment = Environment::default();
+        environment
+            .declare(Identifier::new("g")?, function.clone())
+            .declare(Identifier::new("h")?, pointer_to(function))
+            .declare(Identifier::new("i")?, ConcreteType::int());
+
+        let g = Expression::from(Identifier::new("g")?);
+        assert_eq!(environment.value_of(&g)?.to_string(), "int (*)(int)");
+
+        // Function pointers don't support arithmetic
+        for (left, operator, right) in [
+            ("g", BinaryOperatorKind::Add, "i"),
+            ("i", BinaryOperatorKind::Add, "h"),
+            ("h", BinaryOperatorKind::Sub, "i"),
+            ("g", BinaryOperatorKind::Sub, "h"),
+        ] {
+            assert!(matches!(
+                environment.type_of(&binary(left, operator, right)?),
+                Err(TypeError::InvalidOperands { .. })
+            ));
+        }
+        assert_eq!(
+            environment
+                .type_of(&binary("g", BinaryOperatorKind::Eq, "h")?)?
+                .to_string(),
+            "int"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn members_and_calls() -> anyhow::Result<()> {
+        let point = Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
+            name: Some(Identifier::new("point")?),
+            members: vec![
+                Member {
+                    doc: None,
+                    ty: ConcreteType::int(),
+                    name: Identifier::new("x")?,
+                    bit_field_size: None,
+                    alignment: None,
+                },
+                Member {
+                    doc: None,
+                    ty: ConcreteType::double(),
+                    name: Identifier::new("y")?,
+                    bit_field_size: None,
+                    alignment: None,
+                },
+            ],
+        };
+        let tag = Struct::Tag {
+            name: Identifier::new("point")?,
+        };
+
+        let mut environment = Environment::default();
+        environment
+            .define_tag(point)
+            .declare(Identifier::new("origin")?, tag.clone())
+            .declare(Identifier::new("cursor")?, pointer_to(tag))
+            .declare(
+                Identifier::new("distance")?,
+                Function {
+                    parameters: vec![FunctionParameter {
+                        ty: pointer_to(ConcreteType::Struct(Struct::Tag {
+                            name: Identifier::new("point")?,
+                        })),
+                        name: None,
+                    }],
+                    return_ty: ConcreteType::double(),
+                    is_variadic: false,
+                    has_unspecified_parameters: false,
+                },
+            );
+
+        let y = MemberAccess {
+            left: Identifier::new("origin")?.into(),
+            member: Identifier::new("y")?,
+        };
+        assert_eq!(environment.type_of(&y.into())?.to_string(), "double");
+
+        let x = IndirectMemberAccess {
+            left: Identifier::new("cursor")?.into(),
+            member: Identifier::new("x")?,
+        };
+        assert_eq!(environment.type_of(&x.into())?.to_string(), "int");
+
+        let z = MemberAccess {
+            left: Identifier::new("origin")?.into(),
+            member: Identifier::new("z")?,
+        };
+        let error = environment.type_of(&z.into()).unwrap_err();
+        assert_eq!(error.to_string(), "`struct point` has no member named `z`");
+
+        let call = FunctionCall {
+            callee: Identifier::new("distance")?.into(),
+            arguments: vec![Identifier::new("cursor")?.into()],
+        };
+        assert_eq!(environment.type_of(&call.into())?.to_string(), "double");
+
+        let call = FunctionCall {
+            callee: Identifier::new("distance")?.into(),
+            arguments: Vec::new(),
+        };
+        assert!(matches!(
+            environment.type_of(&call.into()),
+            Err(TypeError::ArgumentCount {
+                expected: 1,
+                found: 0
+            })
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1313 @@
+//! Memory layout of types for a target data model.
+//!
+//! Structures and unions are laid out the way GCC does for the System V ABI: members are placed in order at the next
+//! offset that satisfies their alignment, and a bit-field is placed at the next free bit, unless it would straddle a
+//! boundary of the storage unit of its declared type. Unnamed bit-fields cannot be represented by [`Member`], so
+//! padding and zero-width bit-fields, which move the next bit-field to the next storage unit, are not supported.
+//!
+//! Packed structures, alignment specifiers and `#pragma pack` regions are honored like GCC does: packing lowers the
+//! alignment of members to one unless they have an explicit alignment, whereas a pragma caps every alignment.
+//!
+//! To lock the ABI of a structure, the padding can be made explicit with [`Environment::insert_padding`] and checked
+//! at compile time with [`Environment::layout_assertions`].
+
+use std::{cmp::Reverse, collections::HashSet};
+
+use crate::{
+    inference::{self, Environment},
+    operator::{BinaryOperator, BinaryOperatorKind, OffsetOf, SizeOf},
+    r#type::{
+        member::Member, Array, ArraySize, Definition as TypeDefinition, Enum, IntegerKind,
+        OpaqueType, Real, StrongInt, Struct, StructAttributes, Union,
+    },
+    statement::StaticAssert,
+    ConcreteType, DocComment, Expression, Identifier, Value,
+};
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum LayoutError {
+    #[error("the layout of `{0}` is unknown")]
+    UnknownLayout(Box<ConcreteType>),
+    #[error("the bit-field `{member}` is wider than its type")]
+    BitFieldWidth { member: Identifier },
+    #[error("the bit-field `{member}` has a width of zero, which is only allowed for unnamed bit-fields")]
+    ZeroWidthBitField { member: Identifier },
+    #[error("the bit-field `{member}` does not have an integer type")]
+    BitFieldType { member: Identifier },
+    #[error(
+        "only the last member of a structure can be a flexible array member, but `{member}` is not"
+    )]
+    FlexibleArrayMember { member: Identifier },
+    #[error("`{0}` is not a structure or union")]
+    NotARecord(Box<ConcreteType>),
+    #[error("an anonymous structure cannot be referred to in assertions")]
+    Anonymous,
+    #[error("`{0}` is not a valid alignment")]
+    InvalidAlignment(String),
+    #[error("the size of `{0}` exceeds the maximum object size")]
+    TooLarge(Box<ConcreteType>),
+}
+
+/// The size and alignment of a type, in bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Layout {
+    pub size: u64,
+    pub align: u64,
+}
+
+impl Layout {
+    pub const fn new(size: u64, align: u64) -> Self {
+        Self { size, align }
+    }
+
+    /// Creates the layout of a scalar that is aligned to its size.
+    pub const fn scalar(size: u64) -> Self {
+        Self::new(size, size)
+    }
+}
+
+/// The sizes and alignments of the scalar types of a target.
+///
+/// `char` and `_Bool` always occupy a single byte. `size_t` and `intptr_t` have the layout of a pointer, `intmax_t`
+/// has the layout of `long long`, and `int_fast16_t` and `int_fast32_t` have the layout of `long`.
+///
+/// A custom data model can be created by specifying all fields, e.g. starting from one of the predefined models.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DataModel {
+    pub short: Layout,
+    pub int: Layout,
+    pub long: Layout,
+    pub long_long: Layout,
+    pub pointer: Layout,
+    pub float: Layout,
+    pub double: Layout,
+    pub long_double: Layout,
+}
+
+impl DataModel {
+    /// 64-bit Unix-like targets, e.g. x86-64 Linux and macOS.
+    pub const LP64: Self = Self {
+        short: Layout::scalar(2),
+        int: Layout::scalar(4),
+        long: Layout::scalar(8),
+        long_long: Layout::scalar(8),
+        pointer: Layout::scalar(8),
+        float: Layout::scalar(4),
+        double: Layout::scalar(8),
+        long_double: Layout::scalar(16),
+    };
+
+    /// 32-bit targets, e.g. i386 Linux, where 8-byte scalars are only aligned to 4 bytes in structures.
+    pub const ILP32: Self = Self {
+        short: Layout::scalar(2),
+        int: Layout::scalar(4),
+        long: Layout::scalar(4),
+        long_long: Layout::new(8, 4),
+        pointer: Layout::scalar(4),
+        float: Layout::scalar(4),
+        double: Layout::new(8, 4),
+        long_double: Layout::new(12, 4),
+    };
+
+    /// 64-bit Windows.
+    pub const LLP64: Self = Self {
+        short: Layout::scalar(2),
+        int: Layout::scalar(4),
+        long: Layout::scalar(4),
+        long_long: Layout::scalar(8),
+        pointer: Layout::scalar(8),
+        float: Layout::scalar(4),
+        double: Layout::scalar(8),
+        long_double: Layout::scalar(8),
+    };
+
+    /// Returns the maximum size of an object, which is the maximum value of `ptrdiff_t` like in GCC.
+    pub fn max_object_size(&self) -> u64 {
+        let bits = (self.pointer.size * 8).clamp(1, 64);
+        u64::MAX >> (64 - bits) >> 1
+    }
+
+    pub(crate) fn integer(&self, kind: IntegerKind) -> Layout {
+        match kind {
+            IntegerKind::Char => Layout::scalar(1),
+            IntegerKind::Short => self.short,
+            IntegerKind::Int => self.int,
+            IntegerKind::Long => self.long,
+            IntegerKind::LongLong => self.long_long,
+        }
+    }
+
+    pub(crate) fn real(&self, real: Real) -> Layout {
+        match real {
+            Real::Float => self.float,
+            Real::Double => self.double,
+            Real::LongDouble => self.long_double,
+        }
+    }
+
+    pub(crate) fn strong_int(&self, integer: &StrongInt) -> Layout {
+        match integer {
+            StrongInt::Int8
+            | StrongInt::IntFast8
+            | StrongInt::IntLeast8
+            | StrongInt::Uint8
+            | StrongInt::UintFast8
+            | StrongInt::UintLeast8 => Layout::scalar(1),
+            StrongInt::Int16
+            | StrongInt::IntLeast16
+            | StrongInt::Uint16
+            | StrongInt::UintLeast16 => self.short,
+            StrongInt::Int32
+            | StrongInt::IntLeast32
+            | StrongInt::Uint32
+            | StrongInt::UintLeast32 => self.int,
+            StrongInt::IntFast16
+            | StrongInt::IntFast32
+            | StrongInt::UintFast16
+            | StrongInt::UintFast32 => self.long,
+            StrongInt::IntPtr | StrongInt::UintPtr => self.pointer,
+            _ => self.long_long,
+        }
+    }
+
+    /// Returns the standard integer type that a fixed-width integer type is an alias of, e.g. `long long` for
+    /// `int64_t` if `long` has 32 bits.
+    pub(crate) fn strong_int_kind(&self, integer: &StrongInt) -> IntegerKind {
+        self.alias_kind(self.strong_int(integer))
+    }
+
+    /// Returns the standard integer type that `size_t` is an alias of.
+    pub(crate) fn size_kind(&self) -> IntegerKind {
+        self.alias_kind(self.pointer)
+    }
+
+    /// Returns the integer type of the lowest rank with the size of the layout, like the definitions of type aliases
+    /// in common C libraries.
+    fn alias_kind(&self, layout: Layout) -> IntegerKind {
+        [
+            IntegerKind::Char,
+            IntegerKind::Short,
+            IntegerKind::Int,
+            IntegerKind::Long,
+            IntegerKind::LongLong,
+        ]
+        .into_iter()
+        .find(|kind| self.integer(*kind).size == layout.size)
+        .unwrap_or(IntegerKind::LongLong)
+    }
+}
+
+impl Default for DataModel {
+    fn default() -> Self {
+        Self::LP64
+    }
+}
+
+/// The layout of a structure or union, including the placement of its members.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordLayout {
+    pub size: u64,
+    pub align: u64,
+    pub members: Vec<MemberLayout>,
+    /// The unused bytes between members and at the end of the record, in order.
+    ///
+    /// Unused bits in a byte that is partially occupied by a bit-field are not reported.
+    pub padding: Vec<Padding>,
+}
+
+impl RecordLayout {
+    pub fn layout(&self) -> Layout {
+        Layout::new(self.size, self.align)
+    }
+
+    /// Returns the layout of the member with the given name.
+    pub fn member(&self, name: &Identifier) -> Option<&MemberLayout> {
+        self.members.iter().find(|member| member.name == *name)
+    }
+}
+
+/// The placement of a member in a structure or union.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberLayout {
+    pub name: Identifier,
+    /// The offset of the member, in bytes. For bit-fields, this is the offset of the byte that contains the first bit.
+    pub offset: u64,
+    /// The layout of the declared type of the member.
+    pub layout: Layout,
+    pub bit_field: Option<BitField>,
+}
+
+/// The placement of a bit-field, in bits from the start of the record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BitField {
+    pub offset: u64,
+    pub width: u64,
+}
+
+/// A range of unused bytes in a record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Padding {
+    pub offset: u64,
+    pub size: u64,
+}
+
+impl Environment {
+    /// Returns the size and alignment of a complete object type.
+    pub fn layout(&self, ty: &ConcreteType) -> Result<Layout, LayoutError> {
+        let unknown = || LayoutError::UnknownLayout(Box::new(ty.clone()));
+        let model = self.data_model();
+
+        match ty {
+            ConcreteType::Array(array) => match &array.size {
+                // Variable-length arrays have no constant size
+                ArraySize::Length(length) => {
+                    let length = self
+                        .evaluate(length)
+                        .ok()
+                        .and_then(|length| u64::try_from(length.as_integer()?).ok())
+                        .ok_or_else(unknown)?;
+                    let element = self.layout(&array.element_type)?;
+
+                    let size = element
+                        .size
+                        .checked_mul(length)
+                        .filter(|size| *size <= model.max_object_size())
+                        .ok_or_else(|| LayoutError::TooLarge(Box::new(ty.clone())))?;
+
+                    Ok(Layout::new(size, element.align))
+                }
+                ArraySize::Star | ArraySize::Static(_) | ArraySize::Unspecified => Err(unknown()),
+            },
+            ConcreteType::Bool | ConcreteType::Char => Ok(Layout::scalar(1)),
+            ConcreteType::Const(ty) => self.layout(ty),
+            ConcreteType::Enum(Enum::Definition {
+                underlying_ty: Some(ty),
+                ..
+            }) => self.layout(ty),
+            ConcreteType::Enum(Enum::Tag { name }) => match self.tag(name) {
+                Some(TypeDefinition::Enum(enumeration)) => {
+                    self.layout(&ConcreteType::Enum(enumeration.clone()))
+                }
+                _ => Err(unknown()),
+            },
+            ConcreteType::Enum(_) => Ok(model.int),
+            ConcreteType::Integer(integer) => Ok(model.integer(integer.kind)),
+            ConcreteType::Pointer(_) | ConcreteType::Size => Ok(model.pointer),
+            ConcreteType::Real(real) => Ok(model.real(*real)),
+            ConcreteType::StrongInt(integer) => Ok(model.strong_int(integer)),
+            ConcreteType::Struct(Struct::Definition { .. })
+            | ConcreteType::Union(Union::Definition { .. }) => {
+                self.record_layout(ty).map(|record| record.layout())
+            }
+            ConcreteType::Struct(Struct::Tag { name })
+            | ConcreteType::Union(Union::Tag { name }) => match self.tag(name) {
+                Some(TypeDefinition::Struct(structure)) => {
+                    self.layout(&ConcreteType::Struct(structure.clone()))
+                }
+                Some(TypeDefinition::Union(union)) => {
+                    self.layout(&ConcreteType::Union(union.clone()))
+                }
+                _ => Err(unknown()),
+            },
+            ConcreteType::Typedef(typedef) => match &typedef.ty {
+                OpaqueType::ConcreteType(ty) => self.layout(ty),
+                OpaqueType::Function(_) => Err(unknown()),
+            },
+            ConcreteType::TypedefName(_) => match self.resolve(ty) {
+                Ok(ConcreteType::TypedefName(_)) | Err(_) => Err(unknown()),
+                // The alias can refer to a structure that is packed by a pragma
+                Ok(ConcreteType::Struct(_) | ConcreteType::Union(_)) => {
+                    self.record_layout(ty).map(|record| record.layout())
+                }
+                Ok(ty) => self.layout(&ty),
+            },
+            ConcreteType::Void => Err(unknown()),
+        }
+    }
+
+    /// Returns the placement of the members of a structure or union.
+    ///
+    /// Packing and alignment attributes are honored, as well as `#pragma pack` regions that are registered with
+    /// [`Environment::set_pack`] for the tag or type alias.
+    pub fn record_layout(&self, ty: &ConcreteType) -> Result<RecordLayout, LayoutError> {
+        let pack = self.pack_of(ty);
+        let too_large = || LayoutError::TooLarge(Box::new(ty.clone()));
+
+        match self.resolve(ty) {
+            Ok(ConcreteType::Struct(Struct::Definition {
+                attributes,
+                members,
+                ..
+            })) => {
+                let mut builder = RecordBuilder::default();
+                for (index, member) in members.iter().enumerate() {
+                    let explicit = member
+                        .alignment
+                        .as_ref()
+                        .map(|alignment| self.alignment(alignment))
+                        .transpose()?;
+                    let align = |natural: u64| {
+                        let align = if attributes.packed { 1 } else { natural };
+                        let align = explicit.map_or(align, |explicit| align.max(explicit));
+                        pack.map_or(align, |pack| align.min(pack))
+                    };
+
+                    match member.bit_field_size {
+                        Some(width) => {
+                            let layout = self.bit_field_layout(&member.ty, &member.name, width)?;
+                            builder
+                                .bit_field(
+                                    &member.name,
+                                    layout,
+                                    align(layout.align),
+                                    width as u64,
+                                    attributes.packed,
+                                )
+                                .ok_or_else(too_large)?;
+                        }
+                        None => {
+                            let is_last = index + 1 == members.len();
+                            let layout = self.member_layout(&member.ty, &member.name, is_last)?;
+                            builder
+                                .member(&member.name, layout, align(layout.align))
+                                .ok_or_else(too_large)?;
+                        }
+                    }
+                }
+
+                let aligned = attributes
+                    .aligned
+                    .as_ref()
+                    .map(|alignment| self.alignment(alignment))
+                    .transpose()?;
+                builder
+                    .finish(aligned, self.data_model().max_object_size())
+                    .ok_or_else(too_large)
+            }
+            Ok(ConcreteType::Union(Union::Definition {
+                attributes,
+                members,
+                ..
+            })) => {
+                let mut builder = RecordBuilder::default();
+                for member in members.iter() {
+                    let (layout, bit_field) = match member.bit_field_size {
+                        Some(width) => (
+                            self.bit_field_layout(&member.ty, &member.name, width)?,
+                            Some(BitField {
+                                offset: 0,
+                                width: width as u64,
+                            }),
+                        ),
+                        None => (self.layout(&member.ty)?, None),
+                    };
+
+                    let explicit = member
+                        .alignment
+                        .as_ref()
+                        .map(|alignment| self.alignment(alignment))
+                        .transpose()?;
+                    let align = if attributes.packed { 1 } else { layout.align };
+                    let align = explicit.map_or(align, |explicit| align.max(explicit));
+                    let align = pack.map_or(align, |pack| align.min(pack));
+
+                    builder
+                        .overlapping(&member.name, layout, align, bit_field)
+                        .ok_or_else(too_large)?;
+                }
+
+                let aligned = attributes
+                    .aligned
+                    .as_ref()
+                    .map(|alignment| self.alignment(alignment))
+                    .transpose()?;
+                builder
+                    .finish(aligned, self.data_model().max_object_size())
+                    .ok_or_else(too_large)
+            }
+            Ok(
+                ConcreteType::Struct(Struct::Tag { name })
+                | ConcreteType::Union(Union::Tag { name }),
+            ) => match self.tag(&name) {
+                Some(TypeDefinition::Struct(structure)) => {
+                    self.record_layout(&ConcreteType::Struct(structure.clone()))
+                }
+                Some(TypeDefinition::Union(union)) => {
+                    self.record_layout(&ConcreteType::Union(union.clone()))
+                }
+                _ => Err(LayoutError::UnknownLayout(Box::new(ty.clone()))),
+            },
+            _ => Err(LayoutError::NotARecord(Box::new(ty.clone()))),
+        }
+    }
+
+    /// Returns the maximum alignment of the members of a structure or union that is defined in a `#pragma pack`
+    /// region.
+    fn pack_of(&self, ty: &ConcreteType) -> Option<u64> {
+        match ty {
+            ConcreteType::Const(ty) => self.pack_of(ty),
+            ConcreteType::Struct(Struct::Definition {
+                name: Some(name), ..
+            })
+            | ConcreteType::Struct(Struct::Tag { name })
+            | ConcreteType::TypedefName(name)
+            | ConcreteType::Union(Union::Definition {
+                name: Some(name), ..
+            })
+            | ConcreteType::Union(Union::Tag { name }) => self.pack(name),
+            _ => None,
+        }
+    }
+
+    /// Evaluates an alignment specifier, which has to be a power of two.
+    fn alignment(&self, alignment: &Expression) -> Result<u64, LayoutError> {
+        self.evaluate(alignment)
+            .ok()
+            .and_then(|constant| u64::try_from(constant.as_integer()?).ok())
+            .filter(|alignment| alignment.is_power_of_two())
+            .ok_or_else(|| LayoutError::InvalidAlignment(alignment.to_string()))
+    }
+
+    /// Reorders the members of a structure by decreasing alignment, which minimizes the padding between them.
+    ///
+    /// Members with the same alignment keep their relative order and a flexible array member stays last. Bit-fields
+    /// are moved according to the alignment of their declared type.
+    pub fn reorder_members(&self, structure: &Struct) -> Result<Struct, LayoutError> {
+        let StructDefinition {
+            doc,
+            attributes,
+            name,
+            members,
+        } = self.struct_definition(structure)?;
+        let record = self.record_layout(&ConcreteType::Struct(structure.clone()))?;
+
+        let mut reordered = members.iter().zip(&record.members).collect::<Vec<_>>();
+        reordered.sort_by_key(|(member, layout)| {
+            (
+                self.is_flexible_array(&member.ty),
+                Reverse(layout.layout.align),
+            )
+        });
+
+        Ok(Struct::Definition {
+            doc: doc.clone(),
+            attributes: attributes.clone(),
+            name: name.clone(),
+            members: reordered
+                .into_iter()
+                .map(|(member, _)| member.clone())
+                .collect(),
+        })
+    }
+
+    /// Inserts `uint8_t _padN[size]` members wherever the compiler would insert padding, without changing the layout.
+    ///
+    /// Names that are already used by members are skipped. Unused bits in a byte that is partially occupied by a
+    /// bit-field, and trailing padding after a flexible array member, are left implicit.
+    pub fn insert_padding(&self, structure: &Struct) -> Result<Struct, LayoutError> {
+        let StructDefinition {
+            doc,
+            attributes,
+            name,
+            members,
+        } = self.struct_definition(structure)?;
+        let record = self.record_layout(&ConcreteType::Struct(structure.clone()))?;
+
+        let mut names = members
+            .iter()
+            .map(|member| member.name.clone())
+            .collect::<HashSet<_>>();
+        let mut index = 0;
+        let mut padding_member = |padding: &Padding| {
+            let name = loop {
+                let name = Identifier::new(format!("_pad{index}"))
+                    .expect("padding names are valid identifiers");
+                index += 1;
+
+                if names.insert(name.clone()) {
+                    break name;
+                }
+            };
+
+            Member {
+                doc: None,
+                ty: Array {
+                    element_type: Box::new(StrongInt::Uint8.into()),
+                    size: (padding.size as usize).into(),
+                }
+                .into(),
+                name,
+                bit_field_size: None,
+                alignment: None,
+            }
+        };
+
+        let mut padding = record.padding.iter().peekable();
+        let mut padded = Vec::new();
+        for (member, layout) in members.iter().zip(&record.members) {
+            while let Some(hole) = padding.next_if(|hole| hole.offset < layout.offset) {
+                padded.push(padding_member(hole));
+            }
+
+            padded.push(member.clone());
+        }
+
+        // The trailing padding of a structure with a flexible array member overlaps the array
+        if !members
+            .last()
+            .is_some_and(|member| self.is_flexible_array(&member.ty))
+        {
+            padded.extend(padding.map(&mut padding_member));
+        }
+
+        Ok(Struct::Definition {
+            doc: doc.clone(),
+            attributes: attributes.clone(),
+            name: name.clone(),
+            members: padded,
+        })
+    }
+
+    /// Returns `_Static_assert`s of the size of a named structure and the offsets of its members, which are meant to
+    /// be emitted right after its definition. Bit-fields are skipped, as their offset cannot be taken.
+    ///
+    /// The offset assertions use `offsetof`, which requires `<stddef.h>`.
+    pub fn layout_assertions(&self, structure: &Struct) -> Result<Vec<StaticAssert>, LayoutError> {
+        let StructDefinition { name, members, .. } = self.struct_definition(structure)?;
+        let name = name.as_ref().ok_or(LayoutError::Anonymous)?;
+        let record = self.record_layout(&ConcreteType::Struct(structure.clone()))?;
+
+        let tag = ConcreteType::Struct(Struct::Tag { name: name.clone() });
+        let equals = |left: Expression, value: u64| -> Expression {
+            BinaryOperator {
+                left,
+                operator: BinaryOperatorKind::Eq,
+                right: Value::size(value as usize).into(),
+            }
+            .into()
+        };
+
+        let mut assertions = vec![StaticAssert {
+            condition: equals(SizeOf::Type(tag.clone()).into(), record.size),
+            message: Some(format!("{tag} must be {} bytes", record.size)),
+        }];
+        assertions.extend(
+            members
+                .iter()
+                .zip(&record.members)
+                .filter(|(member, _)| member.bit_field_size.is_none())
+                .map(|(member, layout)| StaticAssert {
+                    condition: equals(
+                        OffsetOf {
+                            ty: tag.clone(),
+                            member: member.name.clone(),
+                        }
+                        .into(),
+                        layout.offset,
+                    ),
+                    message: Some(format!(
+                        "{} must be at offset {} of {tag}",
+                        member.name, layout.offset
+                    )),
+                }),
+        );
+
+        Ok(assertions)
+    }
+
+    /// Returns the documentation, tag and members of a structure, looking up its definition if needed.
+    fn struct_definition<'s>(
+        &'s self,
+        structure: &'s Struct,
+    ) -> Result<StructDefinition<'s>, LayoutError> {
+        let definition = match structure {
+            Struct::Tag { name } => match self.tag(name) {
+                Some(TypeDefinition::Struct(definition)) => definition,
+                _ => structure,
+            },
+            Struct::Definition { .. } => structure,
+        };
+
+        match definition {
+            Struct::Definition {
+                doc,
+                attributes,
+                name,
+                members,
+            } => Ok(StructDefinition {
+                doc,
+                attributes,
+                name,
+                members,
+            }),
+            Struct::Tag { .. } => Err(LayoutError::UnknownLayout(Box::new(ConcreteType::Struct(
+                structure.clone(),
+            )))),
+        }
+    }
+
+    fn is_flexible_array(&self, ty: &ConcreteType) -> bool {
+        matches!(
+            self.resolve(ty),
+            Ok(ConcreteType::Array(Array {
+                size: ArraySize::Unspecified,
+                ..
+            }))
+        )
+    }
+
+    /// Returns the layout of a structure member, which can be a flexible array member if it is the last one.
+    fn member_layout(
+        &self,
+        ty: &ConcreteType,
+        name: &Identifier,
+        is_last: bool,
+    ) -> Result<Layout, LayoutError> {
+        match self.resolve(ty) {
+            Ok(ConcreteType::Array(array)) if self.is_flexible_array(ty) => {
+                if !is_last {
+                    return Err(LayoutError::FlexibleArrayMember {
+                        member: name.clone(),
+                    });
+                }
+
+                let element = self.layout(&array.element_type)?;
+                Ok(Layout::new(0, element.align))
+            }
+            _ => self.layout(ty),
+        }
+    }
+
+    /// Returns the layout of the declared type of a bit-field.
+    fn bit_field_layout(
+        &self,
+        ty: &ConcreteType,
+        name: &Identifier,
+        width: usize,
+    ) -> Result<Layout, LayoutError> {
+        let resolved = self
+            .resolve(ty)
+            .map_err(|_| LayoutError::UnknownLayout(Box::new(ty.clone())))?;
+        let Some(rank) = inference::integer_rank(&resolved, self.data_model()) else {
+            return Err(LayoutError::BitFieldType {
+                member: name.clone(),
+            });
+        };
+
+        if width == 0 {
+            return Err(LayoutError::ZeroWidthBitField {
+                member: name.clone(),
+            });
+        }
+
+        if width > usize::from(rank.width) {
+            return Err(LayoutError::BitFieldWidth {
+                member: name.clone(),
+            });
+        }
+
+        self.layout(&resolved)
+    }
+}
+
+/// The fields of [`Struct::Definition`].
+struct StructDefinition<'s> {
+    doc: &'s Option<DocComment>,
+    attributes: &'s StructAttributes,
+    name: &'s Option<Identifier>,
+    members: &'s Vec<Member>,
+}
+
+/// Places the members of a record in order.
+#[derive(Default)]
+struct RecordBuilder {
+    /// The next free bit.
+    offset: u64,
+    /// The end of the last member, in bytes.
+    end: u64,
+    align: u64,
+    members: Vec<MemberLayout>,
+    padding: Vec<Padding>,
+}
+
+impl RecordBuilder {
+    /// Places a member with the given alignment, which can differ from the alignment of its type.
+    ///
+    /// Returns `None` if the record becomes too large to address its bits.
+    fn member(&mut self, name: &Identifier, layout: Layout, align: u64) -> Option<()> {
+        let offset = self.offset.div_ceil(8).checked_next_multiple_of(align)?;
+        let end = offset.checked_add(layout.size)?;
+        let bit_offset = end.checked_mul(8)?;
+        self.pad(offset);
+
+        self.members.push(MemberLayout {
+            name: name.clone(),
+            offset,
+            layout,
+            bit_field: None,
+        });
+        self.offset = bit_offset;
+        self.end = end;
+        self.align = self.align.max(align);
+        Some(())
+    }
+
+    /// Places a bit-field, which is placed at the next free bit in packed structures.
+    fn bit_field(
+        &mut self,
+        name: &Identifier,
+        layout: Layout,
+        align: u64,
+        width: u64,
+        is_packed: bool,
+    ) -> Option<()> {
+        let unit = align * 8;
+
+        // A bit-field cannot straddle the boundary of an aligned storage unit of its type
+        let unit_start = self.offset / unit * unit;
+        if !is_packed && self.offset.checked_add(width)? > unit_start + layout.size * 8 {
+            self.offset = self.offset.checked_next_multiple_of(unit)?;
+        }
+
+        let offset = self.offset;
+        self.pad(offset / 8);
+
+        self.members.push(MemberLayout {
+            name: name.clone(),
+            offset: offset / 8,
+            layout,
+            bit_field: Some(BitField { offset, width }),
+        });
+        self.offset = offset.checked_add(width)?;
+        self.end = self.offset.div_ceil(8);
+        self.align = self.align.max(align);
+        Some(())
+    }
+
+    /// Places a union member at the start of the record.
+    fn overlapping(
+        &mut self,
+        name: &Identifier,
+        layout: Layout,
+        align: u64,
+        bit_field: Option<BitField>,
+    ) -> Option<()> {
+        let size = match bit_field {
+            Some(bit_field) => bit_field.width.div_ceil(8),
+            None => layout.size,
+        };
+
+        self.members.push(MemberLayout {
+            name: name.clone(),
+            offset: 0,
+            layout,
+            bit_field,
+        });
+        self.end = self.end.max(size);
+        self.offset = self.end.checked_mul(8)?;
+        self.align = self.align.max(align);
+        Some(())
+    }
+
+    /// Records the unused bytes before the given offset.
+    fn pad(&mut self, offset: u64) {
+        if offset > self.end {
+            self.padding.push(Padding {
+                offset: self.end,
+                size: offset - self.end,
+            });
+        }
+    }
+
+    /// Completes the layout, with an optional minimum alignment of the record, unless it exceeds the maximum size.
+    fn finish(mut self, aligned: Option<u64>, max_size: u64) -> Option<RecordLayout> {
+        let align = self.align.max(aligned.unwrap_or(1));
+        let size = self
+            .end
+            .checked_next_multiple_of(align)
+            .filter(|size| *size <= max_size)?;
+        self.pad(size);
+
+        Some(RecordLayout {
+            size,
+            align,
+            members: self.members,
+            padding: self.padding,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operator::{BinaryOperator, BinaryOperatorKind, SizeOf},
+        r#type::{member::Member, Array, Integer},
+        statement::{Pragma, Typedef},
+        validate::Segment,
+        CFileBuilder, Expression, Value,
+    };
+
+    fn member(
+        ty: ConcreteType,
+        name: &str,
+        bit_field_size: Option<usize>,
+    ) -> anyhow::Result<Member> {
+        Ok(Member {
+            doc: None,
+            ty,
+            name: Identifier::new(name)?,
+            bit_field_size,
+            alignment: None,
+        })
+    }
+
+    fn structure(members: Vec<Member>) -> ConcreteType {
+        ConcreteType::Struct(Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
+            name: None,
+            members,
+        })
+    }
+
+    fn offsets(layout: &RecordLayout) -> Vec<u64> {
+        layout.members.iter().map(|member| member.offset).collect()
+    }
+
+    #[test]
+    fn data_models() -> anyhow::Result<()> {
+        let long = ConcreteType::Integer(Integer {
+            kind: IntegerKind::Long,
+            is_signed: true,
+        });
+        let sizeof_long = Expression::from(SizeOf::Type(long.clone()));
+
+        let mut environment = Environment::default();
+        assert_eq!(environment.layout(&long)?, Layout::scalar(8));
+        assert_eq!(environment.evaluate(&sizeof_long)?.as_integer(), Some(8));
+
+        environment.set_data_model(DataModel::LLP64);
+        assert_eq!(environment.layout(&long)?, Layout::scalar(4));
+        assert_eq!(
+            environment.layout(&StrongInt::UintPtr.into())?,
+            Layout::scalar(8)
+        );
+
+        environment.set_data_model(DataModel::ILP32);
+        assert_eq!(environment.evaluate(&sizeof_long)?.as_integer(), Some(4));
+        assert_eq!(
+            environment.layout(&StrongInt::Int64.into())?,
+            Layout::new(8, 4)
+        );
+
+        // `LONG_MAX + 1` overflows with 32-bit longs
+        let overflow = Expression::from(BinaryOperator {
+            left: Value::SignedInteger {
+                value: 2147483647,
+                kind: Some(IntegerKind::Long),
+            }
+            .into(),
+            operator: BinaryOperatorKind::Add,
+            right: Value::signed_integer(1).into(),
+        });
+        assert!(environment.evaluate(&overflow).is_err());
+
+        environment.set_data_model(DataModel {
+            long_double: Layout::scalar(8),
+            ..DataModel::LP64
+        });
+        assert_eq!(
+            environment.layout(&Real::LongDouble.into())?,
+            Layout::scalar(8)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn structures() -> anyhow::Result<()> {
+        let mixed = structure(vec![
+            member(ConcreteType::Char, "a", None)?,
+            member(ConcreteType::int(), "b", None)?,
+            member(ConcreteType::Char, "c", None)?,
+            member(ConcreteType::double(), "d", None)?,
+        ]);
+
+        let mut environment = Environment::default();
+        let layout = environment.record_layout(&mixed)?;
+        assert_eq!(layout.layout(), Layout::new(24, 8));
+        assert_eq!(offsets(&layout), [0, 4, 8, 16]);
+        assert_eq!(
+            layout.padding,
+            [
+                Padding { offset: 1, size: 3 },
+                Padding { offset: 9, size: 7 }
+            ]
+        );
+
+        environment.set_data_model(DataModel::ILP32);
+        let layout = environment.record_layout(&mixed)?;
+        assert_eq!(layout.layout(), Layout::new(20, 4));
+        assert_eq!(offsets(&layout), [0, 4, 8, 12]);
+
+        let flexible = structure(vec![
+            member(
+                ConcreteType::Integer(Integer {
+                    kind: IntegerKind::Short,
+                    is_signed: true,
+                }),
+                "length",
+                None,
+            )?,
+            member(
+                Array {
+                    element_type: Box::new(ConcreteType::int()),
+                    size: ArraySize::Unspecified,
+                }
+                .into(),
+                "data",
+                None,
+            )?,
+        ]);
+        let layout = environment.record_layout(&flexible)?;
+        assert_eq!(layout.layout(), Layout::new(4, 4));
+        assert_eq!(offsets(&layout), [0, 4]);
+        assert_eq!(layout.padding, [Padding { offset: 2, size: 2 }]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bit_fields() -> anyhow::Result<()> {
+        let environment = Environment::default();
+
+        let flags = structure(vec![
+            member(ConcreteType::unsigned_int(), "a", Some(3))?,
+            member(ConcreteType::unsigned_int(), "b", Some(6))?,
+            member(ConcreteType::unsigned_int(), "c", Some(25))?,
+            member(ConcreteType::unsigned_char(), "d", None)?,
+        ]);
+        let layout = environment.record_layout(&flags)?;
+        assert_eq!(layout.layout(), Layout::new(12, 4));
+        assert_eq!(
+            layout
+                .members
+                .iter()
+                .map(|member| member.bit_field.map(|bit_field| bit_field.offset))
+                .collect::<Vec<_>>(),
+            [Some(0), Some(3), Some(32), None]
+        );
+        assert_eq!(
+            layout.member(&Identifier::new("d")?).map(|d| d.offset),
+            Some(8)
+        );
+        assert_eq!(
+            layout.padding,
+            [
+                Padding { offset: 2, size: 2 },
+                Padding { offset: 9, size: 3 }
+            ]
+        );
+
+        let unsigned_short = ConcreteType::Integer(Integer {
+            kind: IntegerKind::Short,
+            is_signed: false,
+        });
+        let packed = structure(vec![
+            member(ConcreteType::Char, "a", None)?,
+            member(unsigned_short.clone(), "b", Some(7))?,
+            member(unsigned_short, "c", Some(10))?,
+        ]);
+        let layout = environment.record_layout(&packed)?;
+        assert_eq!(layout.layout(), Layout::new(4, 2));
+        assert_eq!(offsets(&layout), [0, 1, 2]);
+
+        let union = ConcreteType::Union(Union::Definition {
+            doc: None,
+            attributes: Default::default(),
+            name: None,
+            members: vec![
+                member(ConcreteType::Char, "a", None)?,
+                member(ConcreteType::int(), "b", Some(3))?,
+                member(
+                    Array {
+                        element_type: Box::new(ConcreteType::Integer(Integer {
+                            kind: IntegerKind::Short,
+                            is_signed: true,
+                        })),
+                        size: 3.into(),
+                    }
+                    .into(),
+                    "c",
+                    None,
+                )?,
+            ]
+            .try_into()?,
+        });
+        let layout = environment.record_layout(&union)?;
+        assert_eq!(layout.layout(), Layout::new(8, 4));
+        assert_eq!(layout.padding, [Padding { offset: 6, size: 2 }]);
+
+        let too_wide = structure(vec![member(ConcreteType::Char, "a", Some(9))?]);
+        assert_eq!(
+            environment
+                .record_layout(&too_wide)
+                .unwrap_err()
+                .to_string(),
+            "the bit-field `a` is wider than its type"
+        );
+
+        let zero_width = structure(vec![member(ConcreteType::int(), "a", Some(0))?]);
+        assert!(matches!(
+            environment.record_layout(&zero_width),
+            Err(LayoutError::ZeroWidthBitField { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn too_large() -> anyhow::Result<()> {
+        let array = |element_type: ConcreteType, length: usize| {
+            ConcreteType::from(Array {
+                element_type: Box::new(element_type),
+                size: length.into(),
+            })
+        };
+
+        let mut environment = Environment::default();
+        let overflowing = array(ConcreteType::int(), 1 << 62);
+        assert!(matches!(
+            environment.layout(&overflowing),
+            Err(LayoutError::TooLarge(_))
+        ));
+
+        let halves = structure(vec![
+            member(array(ConcreteType::Char, 1 << 61), "a", None)?,
+            member(array(ConcreteType::Char, 1 << 61), "b", None)?,
+        ]);
+        assert!(matches!(
+            environment.record_layout(&halves),
+            Err(LayoutError::TooLarge(_))
+        ));
+
+        // Objects cannot be larger than `PTRDIFF_MAX`
+        let gigabytes = array(ConcreteType::Char, 1 << 31);
+        assert_eq!(environment.layout(&gigabytes)?, Layout::new(1 << 31, 1));
+        environment.set_data_model(DataModel::ILP32);
+        assert!(matches!(
+            environment.layout(&gigabytes),
+            Err(LayoutError::TooLarge(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn packing() -> anyhow::Result<()> {
+        let packed = |members| {
+            ConcreteType::Struct(Struct::Definition {
+                doc: None,
+                attributes: StructAttributes {
+                    packed: true,
+                    aligned: None,
+                },
+                name: None,
+                members,
+            })
+        };
+        let short = ConcreteType::Integer(Integer {
+            kind: IntegerKind::Short,
+            is_signed: true,
+        });
+        let aligned = |alignment| Some(Expression::from(Value::size(alignment)));
+
+        let environment = Environment::default();
+        let layout = environment.record_layout(&packed(vec![
+            member(ConcreteType::Char, "a", None)?,
+            member(ConcreteType::int(), "b", None)?,
+            member(short.clone(), "c", None)?,
+        ]))?;
+        assert_eq!(layout.layout(), Layout::new(7, 1));
+        assert_eq!(offsets(&layout), [0, 1, 5]);
+
+        // An explicit alignment is not affected by packing
+        let layout = environment.record_layout(&packed(vec![
+            member(ConcreteType::Char, "a", None)?,
+            Member {
+                alignment: aligned(4),
+                ..member(ConcreteType::int(), "b", None)?
+            },
+            member(short.clone(), "c", None)?,
+        ]))?;
+        assert_eq!(layout.layout(), Layout::new(12, 4));
+        assert_eq!(offsets(&layout), [0, 4, 8]);
+
+        // Bit-fields of packed structures can straddle storage units
+        let layout = environment.record_layout(&packed(vec![
+            member(ConcreteType::unsigned_int(), "a", Some(3))?,
+            member(ConcreteType::unsigned_int(), "b", Some(30))?,
+        ]))?;
+        assert_eq!(layout.layout(), Layout::new(5, 1));
+
+        let over_aligned = ConcreteType::Struct(Struct::Definition {
+            doc: None,
+            attributes: StructAttributes {
+                packed: false,
+                aligned: aligned(16).map(Box::new),
+            },
+            name: None,
+            members: vec![member(ConcreteType::int(), "a", None)?],
+        });
+        assert_eq!(environment.layout(&over_aligned)?, Layout::new(16, 16));
+
+        let union = |attributes| {
+            Ok::<_, anyhow::Error>(ConcreteType::Union(Union::Definition {
+                doc: None,
+                attributes,
+                name: None,
+                members: vec![
+                    member(ConcreteType::Char, "a", None)?,
+                    member(ConcreteType::int(), "b", None)?,
+                ]
+                .try_into()?,
+            }))
+        };
+        let packed_union = union(StructAttributes {
+            packed: true,
+            aligned: None,
+        })?;
+        assert_eq!(environment.layout(&packed_union)?, Layout::new(4, 1));
+        let over_aligned_union = union(StructAttributes {
+            packed: false,
+            aligned: aligned(8).map(Box::new),
+        })?;
+        assert_eq!(environment.layout(&over_aligned_union)?, Layout::new(8, 8));
+
+        // A pack region caps all alignments, including explicit ones
+        let mut file = CFileBuilder::default();
+        file.add_statement(Pragma::PushPack(2))
+            .add_statement(Struct::Definition {
+                doc: None,
+                attributes: Default::default(),
+                name: Some(Identifier::new("record")?),
+                members: vec![
+                    member(ConcreteType::Char, "a", None)?,
+                    member(ConcreteType::int(), "b", None)?,
+                    member(ConcreteType::double(), "d", None)?,
+                ],
+            })
+            .add_statement(Typedef {
+                ty: ConcreteType::Struct(Struct::Definition {
+                    doc: None,
+                    attributes: Default::default(),
+                    name: None,
+                    members: vec![
+                        member(ConcreteType::Char, "a", None)?,
+                        Member {
+                            alignment: aligned(8),
+                            ..member(
+                                ConcreteType::Integer(Integer {
+                                    kind: IntegerKind::Long,
+                                    is_signed: true,
+                                }),
+                                "b",
+                                None,
+                            )?
+                        },
+                    ],
+                })
+                .into(),
+                alias: Identifier::new("entry")?,
+            })
+            .add_statement(Pragma::PopPack);
+
+        let environment = file
+            .symbols()
+            .environment(&vec![Segment::Field("statements"), Segment::Index(3)].into())
+            .expect("pragma is visited");
+        let layout = environment.record_layout(&ConcreteType::Struct(Struct::Tag {
+            name: Identifier::new("record")?,
+        }))?;
+        assert_eq!(layout.layout(), Layout::new(14, 2));
+        assert_eq!(offsets(&layout), [0, 2, 6]);
+
+        let layout =
+            environment.record_layout(&ConcreteType::TypedefName(Identifier::new("entry")?))?;
+        assert_eq!(layout.layout(), Layout::new(10, 2));
+        assert_eq!(offsets(&layout), [0, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn padding_and_assertions() -> anyhow::Result<()> {
+        let packet = Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
+            name: Some(Identifier::new("packet")?),
+            members: vec![
+                member(ConcreteType::Char, "kind", None)?,
+                member(ConcreteType::int(), "length", None)?,
+                member(ConcreteType::Char, "_pad0", None)?,
+                member(ConcreteType::double(), "timestamp", None)?,
+            ],
+        };
+
+        let mut environment = Environment::default();
+        let padded = environment.insert_padding(&packet)?;
+        let layout = environment.record_layout(&padded.clone().into())?;
+        assert_eq!(layout.layout(), Layout::new(24, 8));
+        assert!(layout.padding.is_empty());
+
+        let reordered = environment.reorder_members(&packet)?;
+        let layout = environment.record_layout(&reordered.clone().into())?;
+        assert_eq!(layout.layout(), Layout::new(16, 8));
+        assert_eq!(offsets(&layout), [0, 8, 12, 13]);
+
+        let mut file = CFileBuilder::default();
+        file.add_statement(TypeDefinition::from(padded.clone()));
+        for assertion in environment.layout_assertions(&padded)? {
+            file.add_statement(assertion);
+        }
+        assert_eq!(
+            file.write_to_string()?,
+            r#"struct packet {
+  char kind;
+  uint8_t _pad1[3];
+  int length;
+  char _pad0;
+  uint8_t _pad2[7];
+  double timestamp;
+};
+_Static_assert(sizeof (struct packet) == 24, "struct packet must be 24 bytes");
+_Static_assert(offsetof(struct packet, kind) == 0, "kind must be at offset 0 of struct packet");
+_Static_assert(offsetof(struct packet, _pad1) == 1, "_pad1 must be at offset 1 of struct packet");
+_Static_assert(offsetof(struct packet, length) == 4, "length must be at offset 4 of struct packet");
+_Static_assert(offsetof(struct packet, _pad0) == 8, "_pad0 must be at offset 8 of struct packet");
+_Static_assert(offsetof(struct packet, _pad2) == 9, "_pad2 must be at offset 9 of struct packet");
+_Static_assert(offsetof(struct packet, timestamp) == 16, "timestamp must be at offset 16 of struct packet");
+"#
+        );
+
+        // The assertions hold for the data model they were generated for
+        environment.define_tag(padded.clone());
+        for assertion in environment.layout_assertions(&padded)? {
+            assert_eq!(
+                environment.evaluate(&assertion.condition)?.as_integer(),
+                Some(1)
+            );
+        }
+
+        // Without explicit padding, the layout differs between data models
+        environment.set_data_model(DataModel::ILP32);
+        let assertions = environment.layout_assertions(&packet)?;
+        assert_eq!(
+            assertions[0].to_string(),
+            r#"_Static_assert(sizeof (struct packet) == 20, "struct packet must be 20 bytes");"#
+        );
+
+        Ok(())
+    }
+}
@@ -1,25 +1,59 @@
 mod block;
+pub mod comment;
+pub mod constant;
 mod error;
 mod expression;
 mod file;
+pub mod fold;
 pub mod function;
+mod headers;
 pub mod identifier;
+pub mod inference;
+pub mod layout;
 mod macros;
 pub mod non_empty_vec;
 pub mod operator;
+pub mod order;
+pub mod parse;
 pub mod pretty;
+pub mod scope;
+pub mod semantic;
+pub mod standard;
 pub mod statement;
 mod storage_class;
 pub mod r#type;
+pub mod validate;
 pub mod value;
 pub mod variable;
+pub mod visit;
+pub mod visit_mut;
 
 pub use self::{
-    block::Block, error::Error, expression::Expression, file::CFileBuilder,
-    function::Declaration as FunctionDeclaration, function::Definition as FunctionDefinition,
-    identifier::Identifier, r#type::ConcreteType, statement::Statement,
-    storage_class::StorageClass, value::Value, variable::Declaration as VariableDeclaration,
+    block::Block,
+    comment::{Comment, DocComment},
+    error::Error,
+    expression::Expression,
+    file::{CFileBuilder, FileLevelStatement},
+    function::Declaration as FunctionDeclaration,
+    function::Definition as FunctionDefinition,
+    identifier::Identifier,
+    r#type::ConcreteType,
+    standard::Standard,
+    statement::Statement,
+    storage_class::StorageClass,
+    value::Value,
+    variable::Declaration as VariableDeclaration,
+    variable::DeclarationList as VariableDeclarationList,
     variable::Variable,
 };
 
+/// Not public API, only for `c_codegen_macros` and the code that its macros expand to.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::parse::template::{
+        assignment_expression, operand, parse, Extent, Placeholder, Repetition, RepetitionError,
+        Syntax, Template,
+    };
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
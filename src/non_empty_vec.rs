@@ -9,6 +9,33 @@ pub enum Error {
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct NonEmptyVec<T>(Vec<T>);
 
+impl<T> NonEmptyVec<T> {
+    /// Creates a vector with a single element.
+    pub fn new(first: T) -> Self {
+        Self(vec![first])
+    }
+
+    /// Appends an element.
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Returns a mutable iterator over the elements.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.0.iter_mut()
+    }
+
+    /// Maps each element, which keeps the vector non-empty.
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> NonEmptyVec<U> {
+        NonEmptyVec(self.0.into_iter().map(f).collect())
+    }
+}
+
 impl<T> TryFrom<Vec<T>> for NonEmptyVec<T> {
     type Error = Error;
 
@@ -21,6 +48,23 @@ impl<T> TryFrom<Vec<T>> for NonEmptyVec<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for NonEmptyVec<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Deserializes a sequence, which must not be empty.
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for NonEmptyVec<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer)?
+            .try_into()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl<T> IntoIterator for NonEmptyVec<T> {
     type Item = T;
     type IntoIter = std::vec::IntoIter<T>;
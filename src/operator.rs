@@ -5,6 +5,7 @@ mod cast;
 mod comma;
 mod compound_assignment;
 mod conditional;
+mod offsetof;
 mod postfix;
 mod prefix;
 mod sizeof;
@@ -17,6 +18,7 @@ pub use self::{
     comma::CommaOperator,
     compound_assignment::{CompoundAssignment, CompoundAssignmentOperator},
     conditional::Conditional,
+    offsetof::OffsetOf,
     postfix::{PostfixOperator, PostfixOperatorKind},
     prefix::{PrefixOperator, PrefixOperatorKind},
     sizeof::SizeOf,
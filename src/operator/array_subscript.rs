@@ -2,7 +2,8 @@ use pretty::Pretty;
 
 use crate::Expression;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ArraySubscript {
     pub array: Expression,
     pub index: Expression,
@@ -4,7 +4,8 @@ use pretty::Pretty;
 /// # Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Assignment-Operators
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Assignment {
     pub left: Expression, // This should be an l-value
     pub right: Expression,
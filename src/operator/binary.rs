@@ -3,7 +3,8 @@ use pretty::Pretty;
 
 use crate::{pretty::impl_display_via_pretty, Expression};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct BinaryOperator {
     pub left: Expression,
     pub operator: BinaryOperatorKind,
@@ -28,7 +29,8 @@ where
 
 impl_display_via_pretty!(BinaryOperator, 80);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum BinaryOperatorKind {
     Add,    // +
     Sub,    // -
@@ -50,6 +52,27 @@ pub enum BinaryOperatorKind {
     BitXor, // ^
 }
 
+impl BinaryOperatorKind {
+    /// Returns the precedence of the operator, where higher binds more tightly.
+    pub(crate) fn precedence(self) -> u8 {
+        match self {
+            BinaryOperatorKind::Or => 1,
+            BinaryOperatorKind::And => 2,
+            BinaryOperatorKind::BitOr => 3,
+            BinaryOperatorKind::BitXor => 4,
+            BinaryOperatorKind::BitAnd => 5,
+            BinaryOperatorKind::Eq | BinaryOperatorKind::Ne => 6,
+            BinaryOperatorKind::Lt
+            | BinaryOperatorKind::Le
+            | BinaryOperatorKind::Gt
+            | BinaryOperatorKind::Ge => 7,
+            BinaryOperatorKind::LShift | BinaryOperatorKind::RShift => 8,
+            BinaryOperatorKind::Add | BinaryOperatorKind::Sub => 9,
+            BinaryOperatorKind::Mul | BinaryOperatorKind::Div | BinaryOperatorKind::Mod => 10,
+        }
+    }
+}
+
 impl fmt::Display for BinaryOperatorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(match self {
@@ -2,7 +2,8 @@ use pretty::Pretty;
 
 use crate::{r#type::Scalar, Expression};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Cast {
     pub new_ty: Scalar,
     pub expression: Expression,
@@ -1,7 +1,8 @@
 use crate::Expression;
 use pretty::Pretty;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct CommaOperator {
     pub left: Expression,
     pub right: Expression,
@@ -5,7 +5,8 @@ use std::fmt;
 /// # Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Assignment-Operators
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct CompoundAssignment {
     pub left: Expression,
     pub operator: CompoundAssignmentOperator,
@@ -30,7 +31,8 @@ where
 
 impl_display_via_pretty!(CompoundAssignment, 80);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum CompoundAssignmentOperator {
     Add, // +=
     Sub, // -=
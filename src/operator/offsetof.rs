@@ -0,0 +1,55 @@
+use pretty::Pretty;
+
+use crate::{pretty::impl_display_via_pretty, ConcreteType, Identifier};
+
+/// The `offsetof` macro from `<stddef.h>`, which returns the offset of a member of a structure or union in bytes.
+///
+/// # Source
+///
+/// https://en.cppreference.com/w/c/types/offsetof
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct OffsetOf {
+    pub ty: ConcreteType,
+    pub member: Identifier,
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for OffsetOf
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        allocator.text("offsetof").append(
+            allocator
+                .text(self.ty.to_string())
+                .append(allocator.text(","))
+                .append(allocator.space())
+                .append(allocator.text(self.member))
+                .parens(),
+        )
+    }
+}
+
+impl_display_via_pretty!(OffsetOf, 80);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r#type::Struct;
+
+    #[test]
+    fn offsetof() -> anyhow::Result<()> {
+        let generated = OffsetOf {
+            ty: ConcreteType::Struct(Struct::Tag {
+                name: Identifier::new("point")?,
+            }),
+            member: Identifier::new("y")?,
+        }
+        .to_string();
+        assert_eq!(generated, "offsetof(struct point, y)");
+
+        Ok(())
+    }
+}
@@ -3,7 +3,8 @@ use pretty::Pretty;
 use std::fmt;
 
 /// Represents postfix increment (x++) and decrement (x--) operations
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PostfixOperator {
     pub operand: Expression,
     pub operator: PostfixOperatorKind,
@@ -24,7 +25,8 @@ where
 
 impl_display_via_pretty!(PostfixOperator, 80);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum PostfixOperatorKind {
     Increment, // x++
     Decrement, // x--
@@ -1,4 +1,4 @@
-use crate::{pretty::impl_display_via_pretty, Expression};
+use crate::{pretty::impl_display_via_pretty, Expression, Value};
 use pretty::Pretty;
 use std::fmt;
 
@@ -9,7 +9,8 @@ use std::fmt;
 /// - negative (-x)
 /// - address-of (&x)
 /// - indirection (*x)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct PrefixOperator {
     pub operand: Expression,
     pub operator: PrefixOperatorKind,
@@ -22,15 +23,34 @@ where
     AllocatorT::Doc: Clone,
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let operator = self.operator.to_string();
+
+        // `- -x` and `- --x` would otherwise be read as a decrement, and `& &x` as a label address
+        let operand_start = match &self.operand {
+            Expression::PrefixOperator(operand) => operand.operator.to_string().chars().next(),
+            Expression::Value(Value::SignedInteger { value, .. }) if *value < 0 => Some('-'),
+            Expression::Value(Value::Real { value, .. }) if value.is_sign_negative() => Some('-'),
+            _ => None,
+        };
+        let separator = if operand_start
+            .is_some_and(|start| matches!(start, '+' | '-' | '&') && operator.ends_with(start))
+        {
+            allocator.space()
+        } else {
+            allocator.nil()
+        };
+
         allocator
-            .text(self.operator.to_string())
+            .text(operator)
+            .append(separator)
             .append(self.operand.pretty(allocator))
     }
 }
 
 impl_display_via_pretty!(PrefixOperator, 80);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum PrefixOperatorKind {
     Increment,   // ++x
     Decrement,   // --x
@@ -113,6 +133,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn nested() -> anyhow::Result<()> {
+        let negation = |operand: Expression| -> Expression {
+            PrefixOperator {
+                operand,
+                operator: PrefixOperatorKind::Negative,
+            }
+            .into()
+        };
+
+        let double_negation = negation(negation(Variable::new("x")?.into()));
+        assert_eq!(Statement::Expression(double_negation).to_string(), "- -x;");
+
+        let negative_constant = negation(Value::signed_integer(-1).into());
+        assert_eq!(
+            Statement::Expression(negative_constant).to_string(),
+            "- -1;"
+        );
+
+        let indirection = PrefixOperator {
+            operand: PrefixOperator {
+                operand: Variable::new("p")?.into(),
+                operator: PrefixOperatorKind::Indirection,
+            }
+            .into(),
+            operator: PrefixOperatorKind::Indirection,
+        };
+        assert_eq!(
+            Statement::Expression(indirection.into()).to_string(),
+            "**p;"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn pointer() -> anyhow::Result<()> {
         let address = Statement::Expression(
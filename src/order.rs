@@ -0,0 +1,501 @@
+//! Ordering of file-level statements by their dependencies.
+//!
+//! A statement depends on the definitions of the structures, unions and enumerations that it uses by value, and on
+//! the declarations of the typedef names, variables and enumeration constants that it refers to. A structure or union
+//! that is only used through a pointer needs to be declared, but not defined, so a cycle through pointers is broken by
+//! a forward declaration, e.g. `struct node;`.
+//!
+//! Statements keep their relative order unless a dependency requires otherwise. Comments move along with the statement
+//! that follows them, and no statement moves in front of a preprocessor directive that precedes it. Calls between
+//! functions are not considered, as they only need a prototype.
+
+use core::fmt;
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    r#type::{
+        Definition as TypeDefinition, Enum, Enumerator, Function, OpaqueType, Pointer, Struct,
+        Union,
+    },
+    statement::Typedef,
+    variable::Declarator,
+    visit::{self, Visitor},
+    Block, ConcreteType, FileLevelStatement, FunctionDefinition, Identifier, Variable,
+    VariableDeclaration,
+};
+
+/// Errors that can occur when ordering statements by their dependencies
+#[derive(Debug, thiserror::Error)]
+#[error("definitions contain each other by value: {}", cycle.join(" -> "))]
+pub struct Error {
+    /// The definitions that form the cycle, starting and ending with the same definition.
+    pub cycle: Vec<String>,
+}
+
+/// Orders the statements such that each one comes after its dependencies.
+pub(crate) fn order(statements: &[FileLevelStatement]) -> Result<Vec<FileLevelStatement>, Error> {
+    let units = units(statements);
+    let collectors: Vec<_> = units
+        .iter()
+        .map(|unit| {
+            let mut collector = Collector::default();
+            for statement in unit {
+                collector.visit_file_level_statement(statement);
+            }
+            collector
+        })
+        .collect();
+
+    let mut tags = HashMap::new();
+    let mut names = HashMap::new();
+    let mut aliases = HashMap::new();
+    for (index, collector) in collectors.iter().enumerate() {
+        for tag in &collector.defined_tags {
+            tags.entry(tag.name.clone()).or_insert(index);
+        }
+        for name in &collector.declared_names {
+            names.entry(name.clone()).or_insert(index);
+        }
+        for (alias, references) in &collector.aliases {
+            aliases.entry(alias.clone()).or_insert(references);
+        }
+    }
+
+    let mut nodes: Vec<Node> = Vec::with_capacity(units.len());
+    let mut directives = Vec::new();
+    for (index, collector) in collectors.iter().enumerate() {
+        let mut node = Node::default();
+        for &directive in &directives {
+            node.dependencies.push((directive, String::new()));
+        }
+        if units[index].iter().any(|statement| is_directive(statement)) {
+            directives.push(index);
+        }
+
+        let mut references: Vec<_> = collector.references.iter().collect();
+        let mut expanded = HashSet::new();
+        while let Some(reference) = references.pop() {
+            match &reference.name {
+                Name::Tag(tag) => match tags.get(&tag.name) {
+                    Some(&definition) if reference.is_by_value || tag.keyword == "enum" => {
+                        node.dependencies.push((definition, tag.to_string()));
+                    }
+                    Some(&definition) if definition != index => {
+                        node.declarations.push((definition, tag.clone()));
+                    }
+                    _ => {}
+                },
+                Name::Ordinary(name) => {
+                    if let Some(&declaration) = names.get(name) {
+                        if declaration != index {
+                            node.dependencies.push((declaration, name.to_string()));
+                        }
+                    }
+
+                    // Using an alias by value requires the aliased type to be complete.
+                    if reference.is_by_value && expanded.insert(name) {
+                        if let Some(aliased) = aliases.get(name) {
+                            references.extend(aliased.iter());
+                        }
+                    }
+                }
+            }
+        }
+
+        nodes.push(node);
+    }
+
+    let mut units: Vec<_> = units.into_iter().map(Some).collect();
+    let mut declared = HashSet::new();
+    let mut ordered = Vec::new();
+    while units.iter().any(Option::is_some) {
+        let is_emitted = |index: usize| units[index].is_none();
+        let is_ready = |index: &usize| {
+            !is_emitted(*index)
+                && nodes[*index]
+                    .dependencies
+                    .iter()
+                    .all(|(dependency, _)| is_emitted(*dependency))
+        };
+
+        let next = (0..units.len()).filter(is_ready).find(|index| {
+            nodes[*index]
+                .declarations
+                .iter()
+                .all(|(definition, tag)| is_emitted(*definition) || declared.contains(&tag.name))
+        });
+
+        let index = match next {
+            Some(index) => index,
+            None => match (0..units.len()).find(is_ready) {
+                Some(index) => {
+                    for (definition, tag) in &nodes[index].declarations {
+                        if !is_emitted(*definition) && declared.insert(tag.name.clone()) {
+                            ordered.push(tag.forward_declaration());
+                        }
+                    }
+                    index
+                }
+                None => return Err(cycle(&nodes, &units)),
+            },
+        };
+
+        let collector = &collectors[index];
+        declared.extend(collector.defined_tags.iter().map(|tag| tag.name.clone()));
+        declared.extend(collector.declared_tags.iter().cloned());
+        let unit = units[index].take().expect("the unit is not emitted yet");
+        ordered.extend(unit.into_iter().cloned());
+    }
+
+    Ok(ordered)
+}
+
+/// Groups each statement with the comments in front of it.
+fn units(statements: &[FileLevelStatement]) -> Vec<Vec<&FileLevelStatement>> {
+    let mut units = Vec::new();
+    let mut unit = Vec::new();
+    for statement in statements {
+        unit.push(statement);
+        if !matches!(statement, FileLevelStatement::Comment(_)) {
+            units.push(std::mem::take(&mut unit));
+        }
+    }
+    if !unit.is_empty() {
+        units.push(unit);
+    }
+
+    units
+}
+
+fn is_directive(statement: &FileLevelStatement) -> bool {
+    matches!(
+        statement,
+        FileLevelStatement::Define(_)
+            | FileLevelStatement::Include(_)
+            | FileLevelStatement::Pragma(_)
+    )
+}
+
+/// Finds a cycle of dependencies among the statements that are not emitted yet, which each wait for another one.
+fn cycle(nodes: &[Node], units: &[Option<Vec<&FileLevelStatement>>]) -> Error {
+    let mut path: Vec<(usize, &str)> = Vec::new();
+    let mut index = units
+        .iter()
+        .position(Option::is_some)
+        .expect("a statement is not emitted yet");
+
+    loop {
+        if let Some(start) = path.iter().position(|(visited, _)| *visited == index) {
+            let mut cycle: Vec<_> = path[start..]
+                .iter()
+                .map(|(_, label)| label.to_string())
+                .collect();
+            cycle.rotate_right(1);
+            cycle.push(cycle[0].clone());
+
+            return Error { cycle };
+        }
+
+        let (dependency, label) = nodes[index]
+            .dependencies
+            .iter()
+            .find(|(dependency, _)| units[*dependency].is_some())
+            .expect("a statement that is not ready waits for another one");
+        path.push((index, label));
+        index = *dependency;
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    /// The statements that must come first, with the name of what they declare.
+    dependencies: Vec<(usize, String)>,
+    /// The definitions of structures and unions that are used through a pointer, which may be replaced by a forward
+    /// declaration.
+    declarations: Vec<(usize, Tag)>,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Tag {
+    keyword: &'static str,
+    name: Identifier,
+}
+
+impl Tag {
+    fn forward_declaration(&self) -> FileLevelStatement {
+        let name = self.name.clone();
+        match self.keyword {
+            "union" => Union::Tag { name }.into(),
+            _ => Struct::Tag { name }.into(),
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.keyword, self.name)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Name {
+    Tag(Tag),
+    Ordinary(Identifier),
+}
+
+#[derive(Clone, Debug)]
+struct Reference {
+    name: Name,
+    /// Whether the reference requires a complete type, i.e. it is not behind a pointer.
+    is_by_value: bool,
+}
+
+#[derive(Default)]
+struct Collector {
+    /// The number of pointers and function types around the current type.
+    indirection: usize,
+    /// The number of blocks around the current statement.
+    depth: usize,
+    defined_tags: Vec<Tag>,
+    declared_tags: Vec<Identifier>,
+    declared_names: Vec<Identifier>,
+    /// The references of the aliased type of each typedef, as if it were used by value.
+    aliases: Vec<(Identifier, Vec<Reference>)>,
+    references: Vec<Reference>,
+}
+
+impl Collector {
+    fn reference(&mut self, name: Name) {
+        self.references.push(Reference {
+            name,
+            is_by_value: self.indirection == 0,
+        });
+    }
+
+    fn tag(&mut self, keyword: &'static str, name: &Identifier, is_definition: bool) {
+        let tag = Tag {
+            keyword,
+            name: name.clone(),
+        };
+
+        if !is_definition {
+            self.reference(Name::Tag(tag));
+        } else if self.depth == 0 {
+            self.defined_tags.push(tag);
+        }
+    }
+
+    fn declare(&mut self, name: &Identifier) {
+        if self.depth == 0 {
+            self.declared_names.push(name.clone());
+        }
+    }
+
+    fn indirect(&mut self, f: impl FnOnce(&mut Self)) {
+        self.indirection += 1;
+        f(self);
+        self.indirection -= 1;
+    }
+}
+
+impl Visitor for Collector {
+    fn visit_block(&mut self, block: &Block) {
+        self.depth += 1;
+        visit::walk_block(self, block);
+        self.depth -= 1;
+    }
+
+    fn visit_variable(&mut self, variable: &Variable) {
+        self.reference(Name::Ordinary(variable.clone()));
+    }
+
+    fn visit_concrete_type(&mut self, ty: &ConcreteType) {
+        if let ConcreteType::TypedefName(name) = ty {
+            self.reference(Name::Ordinary(name.clone()));
+        }
+
+        visit::walk_concrete_type(self, ty);
+    }
+
+    fn visit_pointer(&mut self, pointer: &Pointer) {
+        self.indirect(|collector| visit::walk_pointer(collector, pointer));
+    }
+
+    fn visit_function_type(&mut self, function: &Function) {
+        // Parameters and return types of prototypes may be incomplete.
+        self.indirect(|collector| visit::walk_function_type(collector, function));
+    }
+
+    fn visit_function_definition(&mut self, definition: &FunctionDefinition) {
+        visit::walk_function_type(self, &definition.ty);
+        self.visit_block(&definition.body);
+    }
+
+    fn visit_type_definition(&mut self, definition: &TypeDefinition) {
+        match definition {
+            TypeDefinition::Struct(Struct::Tag { name })
+            | TypeDefinition::Union(Union::Tag { name })
+                if self.depth == 0 =>
+            {
+                self.declared_tags.push(name.clone());
+            }
+            definition => visit::walk_type_definition(self, definition),
+        }
+    }
+
+    fn visit_typedef(&mut self, typedef: &Typedef) {
+        if self.depth == 0 {
+            let mut aliased = Collector::default();
+            aliased.visit_opaque_type(&typedef.ty);
+            self.aliases
+                .push((typedef.alias.clone(), aliased.references));
+        }
+        self.declare(&typedef.alias);
+
+        // An alias of a structure or union does not need its definition.
+        match &typedef.ty {
+            OpaqueType::ConcreteType(
+                ty @ (ConcreteType::Struct(Struct::Tag { .. })
+                | ConcreteType::Union(Union::Tag { .. })),
+            ) => self.indirect(|collector| collector.visit_concrete_type(ty)),
+            ty => self.visit_opaque_type(ty),
+        }
+    }
+
+    fn visit_enum(&mut self, enumeration: &Enum) {
+        match enumeration {
+            Enum::Definition {
+                name: Some(name), ..
+            } => self.tag("enum", name, true),
+            Enum::Definition { name: None, .. } => {}
+            Enum::Tag { name } => self.tag("enum", name, false),
+        }
+
+        visit::walk_enum(self, enumeration);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &Enumerator) {
+        self.declare(&enumerator.name);
+
+        visit::walk_enumerator(self, enumerator);
+    }
+
+    fn visit_struct(&mut self, structure: &Struct) {
+        match structure {
+            Struct::Definition {
+                name: Some(name), ..
+            } => self.tag("struct", name, true),
+            Struct::Definition { name: None, .. } => {}
+            Struct::Tag { name } => self.tag("struct", name, false),
+        }
+
+        visit::walk_struct(self, structure);
+    }
+
+    fn visit_union(&mut self, union: &Union) {
+        match union {
+            Union::Definition {
+                name: Some(name), ..
+            } => self.tag("union", name, true),
+            Union::Definition { name: None, .. } => {}
+            Union::Tag { name } => self.tag("union", name, false),
+        }
+
+        visit::walk_union(self, union);
+    }
+
+    fn visit_variable_declaration(&mut self, declaration: &VariableDeclaration) {
+        self.declare(&declaration.identifier);
+
+        visit::walk_variable_declaration(self, declaration);
+    }
+
+    fn visit_declarator(&mut self, declarator: &Declarator) {
+        self.declare(&declarator.identifier);
+
+        visit::walk_declarator(self, declarator);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::parse_file;
+
+    #[test]
+    fn by_value() -> anyhow::Result<()> {
+        let mut file = parse_file(
+            "#include <stddef.h>
+            typedef struct point point_t;
+            // A line between two points.
+            struct line { point_t start; point_t end; };
+            static struct line lines[COUNT];
+            struct point { int x; int y; };
+            enum limits { COUNT = 3 };",
+        )?
+        .file;
+
+        file.order_by_dependencies()?;
+        assert_eq!(
+            file.write_to_string()?,
+            "#include <stddef.h>
+struct point {
+  int x;
+  int y;
+};
+typedef struct point point_t;
+// A line between two points.
+struct line {
+  point_t start;
+  point_t end;
+};
+enum limits {COUNT = 3};
+static struct line lines[COUNT];
+"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn forward_declarations() -> anyhow::Result<()> {
+        let mut file = parse_file(
+            "struct list { struct entry first; };
+            struct entry { struct list *owner; struct entry *next; };",
+        )?
+        .file;
+
+        file.order_by_dependencies()?;
+        assert_eq!(
+            file.write_to_string()?,
+            "struct list;
+struct entry {
+  struct list *owner;
+  struct entry *next;
+};
+struct list {
+  struct entry first;
+};
+"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn cycle() -> anyhow::Result<()> {
+        let mut file = parse_file(
+            "struct a { struct b b; };
+            struct b { union c c; };
+            union c { struct a a; int x; };",
+        )?
+        .file;
+
+        let error = file.order_by_dependencies().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "definitions contain each other by value: struct a -> struct b -> union c -> struct a"
+        );
+
+        Ok(())
+    }
+}
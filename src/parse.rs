@@ -0,0 +1,822 @@
+//! Parsing of preprocessed C source into the AST of this crate.
+//!
+//! The parser accepts the subset of C that the AST can represent, including the comments, `#include`, `#define` and
+//! `#pragma pack` directives that the generator emits, so parsing generated code gives back an equivalent file.
+//! Constructs without a representation, e.g. `volatile` or `!x`, are rejected with [`ErrorKind::Unsupported`].
+//!
+//! Typedef names are tracked per scope, to tell declarations like `T *x;` apart from multiplications.
+//!
+//! Single expressions, statements and file-level statements can also be parsed with [`Expression::parse`],
+//! [`Statement::parse`] and [`FileLevelStatement::parse`], which replace placeholders like `$value` with given
+//! [`Fragment`]s.
+
+mod declaration;
+mod expression;
+mod lexer;
+mod statement;
+pub(crate) mod template;
+
+use core::fmt;
+use std::collections::HashMap;
+
+use crate::{
+    macros::impl_froms,
+    validate::{Path, Segment},
+    Block, CFileBuilder, Expression, FileLevelStatement, Identifier, Statement, Value, Variable,
+};
+
+use self::{
+    lexer::{RawComment, Token, TokenKind},
+    template::Conversion,
+};
+
+/// A position in the source, starting at line 1 and column 1.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("{position}: {kind}")]
+pub struct Error {
+    pub position: Position,
+    pub kind: ErrorKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ErrorKind {
+    #[error("unexpected character `{0}`")]
+    UnexpectedCharacter(char),
+    #[error("unterminated {0}")]
+    Unterminated(&'static str),
+    #[error("expected {expected}, found {found}")]
+    Expected { expected: String, found: String },
+    #[error("invalid constant `{0}`")]
+    InvalidConstant(String),
+    #[error("unknown placeholder `${0}`")]
+    UnknownPlaceholder(String),
+    /// A placeholder whose fragment cannot occur at its position, e.g. a statement in an expression.
+    #[error("placeholder `${0}` cannot be used here")]
+    MisplacedPlaceholder(String),
+    /// Valid C that cannot be represented by the AST.
+    #[error("{0} are not supported")]
+    Unsupported(String),
+}
+
+/// The positions of the statements of a parsed file, by their [`Path`].
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    positions: HashMap<Path, Position>,
+}
+
+impl SourceMap {
+    /// Returns the position of the node at the path, or otherwise of the innermost statement that contains it.
+    ///
+    /// This locates the [`Diagnostic`](crate::validate::Diagnostic)s of a parsed file in its source.
+    pub fn position(&self, path: &Path) -> Option<Position> {
+        (0..=path.0.len())
+            .rev()
+            .find_map(|length| self.positions.get(&Path(path.0[..length].to_vec())))
+            .copied()
+    }
+}
+
+/// A parsed translation unit.
+#[derive(Clone, Debug)]
+pub struct ParsedFile {
+    pub file: CFileBuilder,
+    pub source_map: SourceMap,
+}
+
+/// Parses a preprocessed C file.
+pub fn parse_file(source: &str) -> Result<ParsedFile, Error> {
+    let mut parser = Parser::new(source)?;
+    let statements = parser.translation_unit()?;
+
+    let mut file = CFileBuilder::default();
+    for statement in statements {
+        file.add_statement(statement);
+    }
+
+    Ok(ParsedFile {
+        file,
+        source_map: parser.source_map,
+    })
+}
+
+/// A value that replaces a placeholder, e.g. `$value`.
+///
+/// Expressions can replace placeholders wherever a primary expression or a statement can occur, in which case they
+/// become an expression statement. Statements can replace placeholders in place of statements in blocks, and
+/// file-level statements in place of declarations at file level.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Fragment {
+    Expression(Expression),
+    FileLevelStatement(FileLevelStatement),
+    Statement(Statement),
+}
+
+impl_froms!(Fragment: Expression(Value, Variable), FileLevelStatement, Statement(Block));
+
+/// Collects the placeholders of the public parsing functions.
+pub(crate) fn placeholders<I, S, F>(placeholders: I) -> HashMap<String, Fragment>
+where
+    I: IntoIterator<Item = (S, F)>,
+    S: Into<String>,
+    F: Into<Fragment>,
+{
+    placeholders
+        .into_iter()
+        .map(|(name, fragment)| (name.into(), fragment.into()))
+        .collect()
+}
+
+/// Parses a single expression, replacing placeholders with the given fragments.
+pub(crate) fn parse_expression(
+    source: &str,
+    placeholders: HashMap<String, Fragment>,
+) -> Result<Expression, Error> {
+    let mut parser = Parser::new(source)?;
+    parser.placeholders = placeholders;
+
+    let expression = parser.expression()?;
+    parser.expect_end()?;
+
+    Ok(expression)
+}
+
+/// Parses a single statement, replacing placeholders with the given fragments.
+pub(crate) fn parse_statement(
+    source: &str,
+    placeholders: HashMap<String, Fragment>,
+) -> Result<Statement, Error> {
+    let mut parser = Parser::new(source)?;
+    parser.placeholders = placeholders;
+
+    let statement = parser.single_statement()?;
+    parser.expect_end()?;
+
+    Ok(statement)
+}
+
+/// Parses a single file-level statement, replacing placeholders with the given fragments.
+pub(crate) fn parse_file_level_statement(
+    source: &str,
+    placeholders: HashMap<String, Fragment>,
+) -> Result<FileLevelStatement, Error> {
+    let mut parser = Parser::new(source)?;
+    parser.placeholders = placeholders;
+
+    let statement = parser.single_file_level_statement()?;
+    parser.expect_end()?;
+
+    Ok(statement)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    /// The index of the next token, which can be a comment.
+    index: usize,
+    /// The ordinary identifiers that are declared in each scope, mapped to whether they are typedef names.
+    scopes: Vec<HashMap<String, bool>>,
+    /// The path of the statement that is being parsed.
+    path: Vec<Segment>,
+    source_map: SourceMap,
+    /// The fragments that replace placeholders, by name.
+    placeholders: HashMap<String, Fragment>,
+    /// Whether unknown placeholders are accepted as whatever fragment their position requires.
+    is_template: bool,
+    /// The conversions of the unknown placeholders of a template, by name.
+    conversions: HashMap<String, Conversion>,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self, Error> {
+        Ok(Self {
+            tokens: lexer::tokenize(source)?,
+            index: 0,
+            scopes: vec![HashMap::new()],
+            path: Vec::new(),
+            source_map: SourceMap::default(),
+            placeholders: HashMap::new(),
+            is_template: false,
+            conversions: HashMap::new(),
+        })
+    }
+
+    /// Fails unless all input has been consumed.
+    fn expect_end(&self) -> Result<(), Error> {
+        if self.peek().kind == TokenKind::Eof {
+            Ok(())
+        } else {
+            Err(self.expected("end of input"))
+        }
+    }
+
+    /// Returns the index of the `n`th token after the current one, skipping comments.
+    fn lookahead(&self, n: usize) -> usize {
+        let mut index = self.index;
+        let mut remaining = n;
+        loop {
+            match self.tokens[index].kind {
+                TokenKind::Eof => return index,
+                TokenKind::Comment(_) => {}
+                _ if remaining == 0 => return index,
+                _ => remaining -= 1,
+            }
+
+            index += 1;
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.lookahead(0)]
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        &self.tokens[self.lookahead(n)]
+    }
+
+    /// Consumes the next token, skipping comments. The end of input is never consumed.
+    fn next(&mut self) -> Token {
+        let index = self.lookahead(0);
+        let token = self.tokens[index].clone();
+        if token.kind != TokenKind::Eof {
+            self.index = index + 1;
+        }
+
+        token
+    }
+
+    /// Consumes the comment that directly follows, if any.
+    fn comment(&mut self) -> Option<(RawComment, Position)> {
+        let token = &self.tokens[self.index];
+        if let TokenKind::Comment(comment) = &token.kind {
+            let comment = (comment.clone(), token.position);
+            self.index += 1;
+
+            Some(comment)
+        } else {
+            None
+        }
+    }
+
+    fn is_punctuator(&self, punctuator: &str) -> bool {
+        self.is_punctuator_at(0, punctuator)
+    }
+
+    fn is_punctuator_at(&self, n: usize, punctuator: &str) -> bool {
+        matches!(self.peek_nth(n).kind, TokenKind::Punctuator(p) if p == punctuator)
+    }
+
+    fn eat(&mut self, punctuator: &str) -> bool {
+        let is_punctuator = self.is_punctuator(punctuator);
+        if is_punctuator {
+            self.next();
+        }
+
+        is_punctuator
+    }
+
+    fn expect(&mut self, punctuator: &'static str) -> Result<Position, Error> {
+        if self.is_punctuator(punctuator) {
+            Ok(self.next().position)
+        } else {
+            Err(self.expected(format!("`{punctuator}`")))
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.is_keyword_at(0, keyword)
+    }
+
+    fn is_keyword_at(&self, n: usize, keyword: &str) -> bool {
+        matches!(&self.peek_nth(n).kind, TokenKind::Identifier(name) if name == keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let is_keyword = self.is_keyword(keyword);
+        if is_keyword {
+            self.next();
+        }
+
+        is_keyword
+    }
+
+    /// Returns the name of the next token if it is an identifier, but not a keyword.
+    fn peek_identifier(&self, n: usize) -> Option<&str> {
+        match &self.peek_nth(n).kind {
+            TokenKind::Identifier(name) if !is_keyword(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn identifier(&mut self) -> Result<(Identifier, Position), Error> {
+        if self.peek_identifier(0).is_none() {
+            return Err(self.expected("identifier"));
+        }
+
+        let token = self.next();
+        let TokenKind::Identifier(name) = token.kind else {
+            unreachable!("the token is an identifier");
+        };
+        let identifier = Identifier::new(name).map_err(|error| Error {
+            position: token.position,
+            kind: ErrorKind::InvalidConstant(error.to_string()),
+        })?;
+
+        Ok((identifier, token.position))
+    }
+
+    fn expected<S: Into<String>>(&self, expected: S) -> Error {
+        let token = self.peek();
+
+        Error {
+            position: token.position,
+            kind: ErrorKind::Expected {
+                expected: expected.into(),
+                found: token.describe(),
+            },
+        }
+    }
+
+    fn unsupported<S: Into<String>>(&self, position: Position, construct: S) -> Error {
+        Error {
+            position,
+            kind: ErrorKind::Unsupported(construct.into()),
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Declares an ordinary identifier in the current scope, which hides typedef names of enclosing scopes.
+    fn declare(&mut self, name: &Identifier, is_typedef: bool) {
+        self.scopes
+            .last_mut()
+            .expect("the file scope is never popped")
+            .insert(name.to_string(), is_typedef);
+    }
+
+    fn is_typedef_name(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+            // Names from standard headers that are not declared in the file
+            .unwrap_or_else(|| declaration::builtin_type(name).is_some())
+    }
+
+    /// Parses a node at a field of the current path.
+    fn at<T>(&mut self, field: &'static str, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.path.push(Segment::Field(field));
+        let result = f(self);
+        self.path.pop();
+
+        result
+    }
+
+    /// Parses a node at an index of the current path.
+    fn at_index<T>(&mut self, index: usize, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.path.push(Segment::Index(index));
+        let result = f(self);
+        self.path.pop();
+
+        result
+    }
+
+    /// Records the position of the node at the current path.
+    fn record(&mut self, position: Position) {
+        self.source_map
+            .positions
+            .insert(Path(self.path.clone()), position);
+    }
+}
+
+/// Returns whether the name is reserved, so it cannot be used as identifier.
+fn is_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "_Alignas"
+            | "_Alignof"
+            | "_Atomic"
+            | "_Bool"
+            | "_Complex"
+            | "_Generic"
+            | "_Imaginary"
+            | "_Noreturn"
+            | "_Static_assert"
+            | "_Thread_local"
+            | "__alignof__"
+            | "__attribute__"
+            | "__builtin_offsetof"
+            | "alignas"
+            | "alignof"
+            | "auto"
+            | "bool"
+            | "break"
+            | "case"
+            | "char"
+            | "const"
+            | "constexpr"
+            | "continue"
+            | "default"
+            | "do"
+            | "double"
+            | "else"
+            | "enum"
+            | "extern"
+            | "float"
+            | "for"
+            | "goto"
+            | "if"
+            | "inline"
+            | "int"
+            | "long"
+            | "offsetof"
+            | "register"
+            | "restrict"
+            | "return"
+            | "short"
+            | "signed"
+            | "sizeof"
+            | "static"
+            | "static_assert"
+            | "struct"
+            | "switch"
+            | "thread_local"
+            | "typedef"
+            | "typeof"
+            | "union"
+            | "unsigned"
+            | "void"
+            | "volatile"
+            | "while"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Standard;
+
+    /// Parses the source and generates it again for the standard, checking that the generated code parses into the
+    /// same tree.
+    fn round_trip(source: &str, standard: Standard) -> anyhow::Result<String> {
+        let mut file = parse_file(source)?.file;
+        file.set_standard(standard);
+        let generated = file.write_to_string()?;
+
+        let mut reparsed = parse_file(&generated)?.file;
+        reparsed.set_standard(standard);
+        assert_eq!(reparsed, file, "generated code:\n{generated}");
+
+        Ok(generated)
+    }
+
+    #[test]
+    fn round_trip_declarations() -> anyhow::Result<()> {
+        let source = r#"#include <stdint.h>
+#include "config.h"
+#define MAX(a, b) ((a) > (b) \
+  ? (a) \
+  : (b))
+#pragma pack(push, 1)
+/** A point on the screen. */
+struct point {
+  /** The horizontal position. */
+  int32_t x;
+  int32_t y : 4;
+};
+#pragma pack(pop)
+enum color : uint8_t {RED, GREEN = 2, BLUE};
+typedef struct point point_t;
+typedef void (*callback_t)(point_t *point, size_t count);
+static const char *const names[3] = { "a", "b", "c" };
+unsigned long long mask = 255u;
+int a, *b, c[2];
+static_assert(sizeof (point_t) == 8, "unexpected size");
+// Line comment
+//
+// Second paragraph
+/* Block comment */
+"#;
+        assert_eq!(round_trip(source, Standard::C23)?, source);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_functions() -> anyhow::Result<()> {
+        let source = r#"/**
+ * Sums the values.
+ *
+ * @param values The values to sum.
+ * @param count The number of values.
+ * @return The sum.
+ */
+int
+sum (const int *values, size_t count)
+{
+  int total = 0;
+  for (size_t i = 0; i < count; ++i)
+  {
+    total += values[i];
+  }
+  return total;
+}
+static void
+run (struct point *p)
+{
+  p->x = (int) 1.5;
+  if (p->x > 0 && p->y <= -1)
+    p->y--;
+  else
+  {
+    goto done;
+  }
+  switch (p->x)
+  {
+    case 1:
+      break;
+    default:
+      p->x = p->x ? sum(&p->y, sizeof p->y) : 0;
+  }
+  do
+  {
+    continue;
+  }
+  while (0);
+done:
+  return;
+}
+"#;
+        // The generator places braces differently, so compare the output of the generated code instead
+        let generated = round_trip(source, Standard::C17)?;
+        assert_eq!(round_trip(&generated, Standard::C17)?, generated);
+        assert!(generated
+            .contains("      default:\n        p->x = p->x ? sum(&p->y, sizeof p->y) : 0;\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trip_constructs() -> anyhow::Result<()> {
+        let source = r#"char newline = '\n', quote = '\'', backslash = '\\', nul = '\0';
+double one = 1.0, big = 1e300, small = 1.5e-7, hex = 0x1.8p3, hex_float = 0x1p-2f;
+int negation = -(-1), nested = - -1, decrement = - --negation;
+int (*row)[3];
+int (*rows[2])[3];
+const int (*const readonly)[4];
+void unspecified();
+void none(void);
+int printf(const char *format, ...);
+static inline int twice(int x) { return 2 * x; }
+inline void reset(void);
+struct packed { char c; int i; } __attribute__((packed));
+struct aligned { char c; } __attribute__((aligned(8)));
+"#;
+        let generated = round_trip(source, Standard::Gnu17)?;
+        assert!(generated
+            .contains("char newline = '\\n', quote = '\\'', backslash = '\\\\', nul = '\\0';\n"));
+        assert!(generated.contains(
+            "double one = 1.0, big = 1e300, small = 1.5e-7, hex = 12.0, hex_float = 0.25f;\n"
+        ));
+        assert!(
+            generated.contains("int negation = -(-1), nested = - -1, decrement = - --negation;\n")
+        );
+        assert!(generated
+            .contains("int (*row)[3];\nint (*rows[2])[3];\nconst int (*const readonly)[4];\n"));
+        assert!(generated.contains("void unspecified ();\nvoid none (void);\n"));
+        assert!(generated.contains("int printf (const char *format, ...);\n"));
+        assert!(generated.contains("static inline int\ntwice (int x)"));
+
+        // `(...)` and labels at the end of blocks require C23
+        round_trip("void f(...);\nvoid g(void) { done: }\n", Standard::C23)?;
+        let mut file = parse_file("void f(...);\n")?.file;
+        file.set_standard(Standard::C17);
+        assert!(file.write_to_string().is_err());
+        let mut file = parse_file("void g(void) { done: }\n")?.file;
+        file.set_standard(Standard::C17);
+        assert!(file.write_to_string().is_err());
+
+        // Function pointers are returned through typedef names
+        round_trip(
+            "typedef int (*callback_t)(int);\ncallback_t fc (void);\n",
+            Standard::C17,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn constants() -> anyhow::Result<()> {
+        let generated = round_trip(
+            r#"unsigned long size = sizeof (0xFFFFFFFF);
+int mode = 0777;
+char delete = '\x7f';
+const char *hex = "\x41" "BC", *octal = "\1" "2", *complete = "\101" "2";
+"#,
+            Standard::C17,
+        )?;
+        assert_eq!(
+            generated,
+            r#"unsigned long size = sizeof (4294967295u);
+int mode = 511;
+char delete = '\177';
+const char *hex = "\101BC", *octal = "\0012", *complete = "\1012";
+"#
+        );
+
+        // Characters above `0x7F` are written as escapes rather than as multi-byte UTF-8 sequences
+        let generated = round_trip("char f = '\\377', g = '\\x80';\n", Standard::C17)?;
+        assert_eq!(generated, "char f = '\\377', g = '\\200';\n");
+
+        // Escapes must fit in a single byte
+        assert!(parse_file("char c = '\\400';").is_err());
+        assert!(parse_file("char c = '\\x100';").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn typedef_names() -> anyhow::Result<()> {
+        let source = r#"typedef int T;
+void
+f (int a, int b)
+{
+  T * x = 0;
+  a * b = 0;
+  {
+    int T;
+    T * b = 0;
+  }
+}
+"#;
+        let generated = round_trip(source, Standard::C17)?;
+
+        // Declarations are printed without a space after the `*`, multiplications with one
+        assert!(generated.contains("  T *x = 0;\n  a * b = 0;\n"));
+        assert!(generated.contains("    int T;\n    T * b = 0;\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source| parse_file(source).unwrap_err().to_string();
+
+        assert_eq!(error("int x = !y;"), "1:9: `!` operators are not supported");
+        assert_eq!(
+            error("int\nf (void)\n{\n  return 1\n}"),
+            "5:1: expected `;`, found `}`"
+        );
+        assert_eq!(
+            error("volatile int x;"),
+            "1:1: `volatile` specifiers are not supported"
+        );
+        assert_eq!(error("x = 1;"), "1:1: expected declaration, found `x`");
+
+        // Return types are printed before the name, so function and array pointers have to be returned via typedefs
+        assert_eq!(
+            error("int (*fc(void))(int);"),
+            "1:1: functions returning function pointers are not supported"
+        );
+        assert_eq!(
+            error("int (*rows(void))[3];"),
+            "1:1: functions returning array pointers are not supported"
+        );
+    }
+
+    #[test]
+    fn expressions() -> anyhow::Result<()> {
+        let expression = Expression::parse("a[i] * 2 + f(b)")?;
+        assert!(matches!(expression, Expression::BinaryOperator(_)));
+        assert_eq!(expression.to_string(), "a[i] * 2 + f(b)");
+
+        let value = Expression::parse("(int) x")?;
+        let expression = Expression::parse_with("$value * $value - 1", [("value", value)])?;
+        assert_eq!(expression.to_string(), "((int) x) * ((int) x) - 1");
+
+        assert_eq!(
+            Expression::parse("$missing + 1").unwrap_err().to_string(),
+            "1:1: unknown placeholder `$missing`"
+        );
+        assert_eq!(
+            Expression::parse("x + 1 )").unwrap_err().to_string(),
+            "1:7: expected end of input, found `)`"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn statements() -> anyhow::Result<()> {
+        let condition = Expression::parse("count > 0")?;
+        let statement = Statement::parse_with(
+            "if ($condition) { int *p = &x; return *p; }",
+            [("condition", condition)],
+        )?;
+        assert_eq!(
+            statement.to_string(),
+            r#"if (count > 0)
+{
+  int *p = &x;
+  return *p;
+}
+"#
+        );
+
+        assert_eq!(
+            Statement::parse("}").unwrap_err().to_string(),
+            "1:1: expected statement, found `}`"
+        );
+
+        // Statements replace placeholders in place of statements, and expressions become expression statements
+        let statement = Statement::parse_with(
+            "while (1) { $body $step; }",
+            [
+                ("body", Fragment::from(Statement::Break)),
+                ("step", Expression::parse("i += 1")?.into()),
+            ],
+        )?;
+        assert_eq!(
+            statement.to_string(),
+            r#"while (1)
+{
+  break;
+  i += 1;
+}"#
+        );
+
+        assert_eq!(
+            Expression::parse_with("$body + 1", [("body", Statement::Break)])
+                .unwrap_err()
+                .to_string(),
+            "1:1: placeholder `$body` cannot be used here"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn file_level_statements() -> anyhow::Result<()> {
+        let body = Statement::parse("return 0;")?;
+        let statement =
+            FileLevelStatement::parse_with("int\nmain (void)\n{\n  $body\n}", [("body", body)])?;
+        assert!(matches!(
+            statement,
+            FileLevelStatement::FunctionDefinition(_)
+        ));
+        assert_eq!(statement.to_string(), "int\nmain (void) {\n  return 0;\n}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn source_map() -> anyhow::Result<()> {
+        let source = r#"int x;
+
+void
+f (void)
+{
+  x = 1;
+  break;
+}
+"#;
+        let parsed = parse_file(source)?;
+
+        let diagnostics = parsed.file.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            parsed.source_map.position(&diagnostics[0].path),
+            Some(Position { line: 7, column: 3 })
+        );
+
+        let path = Path(vec![
+            Segment::Field("statements"),
+            Segment::Index(1),
+            Segment::Field("body"),
+            Segment::Field("statements"),
+            Segment::Index(0),
+        ]);
+        assert_eq!(
+            parsed.source_map.position(&path),
+            Some(Position { line: 6, column: 3 })
+        );
+
+        Ok(())
+    }
+}
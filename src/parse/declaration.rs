@@ -0,0 +1,1177 @@
+use crate::{
+    function::FunctionParameter,
+    non_empty_vec::NonEmptyVec,
+    r#type::{
+        member::Member, Array, ArraySize, Definition as TypeDefinition, Enum, Enumerator, Function,
+        Integer, IntegerKind, OpaqueType, Pointer, Real, StrongInt, Struct, StructAttributes,
+        Union,
+    },
+    statement::{StaticAssert, Typedef},
+    variable::Declarator as VariableDeclarator,
+    Block, ConcreteType, DocComment, Expression, FunctionDeclaration, FunctionDefinition,
+    Identifier, StorageClass, Value, VariableDeclaration, VariableDeclarationList,
+};
+
+use super::{lexer::TokenKind, Error, ErrorKind, Parser, Position};
+
+/// A declaration, which can occur at file scope and in blocks.
+pub(super) enum Declaration {
+    FunctionDeclaration(FunctionDeclaration),
+    FunctionDefinition(FunctionDefinition),
+    StaticAssert(StaticAssert),
+    TypeDefinition(TypeDefinition),
+    Typedef(Typedef),
+    VariableDeclaration(VariableDeclaration),
+    VariableDeclarationList(VariableDeclarationList),
+}
+
+/// The specifiers that precede the declarators of a declaration.
+struct Specifiers {
+    position: Position,
+    storage: Option<Storage>,
+    alignment: Option<Expression>,
+    is_inline: bool,
+    ty: ConcreteType,
+}
+
+#[derive(Clone)]
+enum Storage {
+    Typedef,
+    Class(StorageClass),
+}
+
+/// A declarator, which derives the type of a declared name from the type of the specifiers.
+#[derive(Clone)]
+struct Declarator {
+    name: Option<(Identifier, Position)>,
+    /// The constness of each pointer level, starting with the one closest to the specifiers.
+    pointers: Vec<bool>,
+    /// Array dimensions and parameter lists, from left to right.
+    suffixes: Vec<Suffix>,
+    /// A parenthesized declarator, e.g. `(*callback)`, which applies to the type derived by the outer declarator.
+    inner: Option<Box<Declarator>>,
+}
+
+#[derive(Clone)]
+enum Suffix {
+    Array(ArraySize),
+    Function(Parameters),
+}
+
+/// A parameter list, e.g. `(int a, ...)`.
+#[derive(Clone)]
+struct Parameters {
+    parameters: Vec<FunctionParameter>,
+    is_variadic: bool,
+    /// Whether the list is `()`, rather than `(void)`.
+    is_unspecified: bool,
+}
+
+/// Whether a declarator has to declare a name.
+#[derive(Clone, Copy, PartialEq)]
+enum DeclaratorKind {
+    Named,
+    /// The declarator of a type name, e.g. in a cast.
+    Abstract,
+    /// The declarator of a parameter, which can be named or not.
+    Either,
+}
+
+impl Declarator {
+    /// Returns the declared name and its type.
+    fn apply(
+        self,
+        ty: OpaqueType,
+        position: Position,
+    ) -> Result<(Option<(Identifier, Position)>, OpaqueType), Error> {
+        let unsupported = |construct: &str| Error {
+            position,
+            kind: ErrorKind::Unsupported(construct.to_string()),
+        };
+
+        let mut ty = ty;
+        for is_const in self.pointers {
+            ty = ConcreteType::from(Pointer {
+                pointer_ty: ty,
+                is_const,
+            })
+            .into();
+        }
+
+        // The suffix closest to the name applies last
+        for suffix in self.suffixes.into_iter().rev() {
+            ty = match (suffix, ty) {
+                (Suffix::Array(size), OpaqueType::ConcreteType(element_type)) => {
+                    ConcreteType::from(Array {
+                        element_type: Box::new(element_type),
+                        size,
+                    })
+                    .into()
+                }
+                (Suffix::Array(_), OpaqueType::Function(_)) => {
+                    return Err(unsupported("arrays of functions"))
+                }
+                (Suffix::Function(parameters), OpaqueType::ConcreteType(return_ty)) => {
+                    // The generator prints return types before the name, which cannot express a nested declarator
+                    // like `int (*f(void))(int)`
+                    let pointer = match &return_ty {
+                        ConcreteType::Const(ty) => ty.as_ref(),
+                        ty => ty,
+                    };
+                    if let ConcreteType::Pointer(pointer) = pointer {
+                        match pointer.base_type() {
+                            OpaqueType::Function(_) => {
+                                return Err(unsupported("functions returning function pointers"))
+                            }
+                            OpaqueType::ConcreteType(ConcreteType::Array(_)) => {
+                                return Err(unsupported("functions returning array pointers"))
+                            }
+                            OpaqueType::ConcreteType(_) => {}
+                        }
+                    }
+
+                    OpaqueType::Function(Function {
+                        parameters: parameters.parameters,
+                        return_ty,
+                        is_variadic: parameters.is_variadic,
+                        has_unspecified_parameters: parameters.is_unspecified,
+                    })
+                }
+                (Suffix::Function(_), OpaqueType::Function(_)) => {
+                    return Err(unsupported("functions returning functions"))
+                }
+            };
+        }
+
+        match self.inner {
+            Some(inner) => inner.apply(ty, position),
+            None => Ok((self.name, ty)),
+        }
+    }
+
+    /// Converts the declarator to a declarator of a [`VariableDeclarationList`], if it only consists of pointers and
+    /// array dimensions.
+    fn into_variable_declarator(
+        self,
+        initializer: Option<Expression>,
+    ) -> Option<VariableDeclarator> {
+        if self.inner.is_some() {
+            return None;
+        }
+
+        let dimensions = self
+            .suffixes
+            .into_iter()
+            .map(|suffix| match suffix {
+                Suffix::Array(size) => Some(size),
+                Suffix::Function(_) => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(VariableDeclarator {
+            pointers: self.pointers,
+            identifier: self.name?.0,
+            dimensions,
+            initializer,
+        })
+    }
+}
+
+impl Parser {
+    /// Returns whether the `n`th next token starts a declaration.
+    pub(super) fn is_declaration_start(&self, n: usize) -> bool {
+        match &self.peek_nth(n).kind {
+            TokenKind::Identifier(name) => {
+                is_specifier_keyword(name)
+                    || matches!(name.as_str(), "_Static_assert" | "static_assert")
+                    || (!super::is_keyword(name) && self.is_typedef_name(name))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the `n`th next token starts a type name, e.g. in a cast.
+    pub(super) fn is_type_name_start(&self, n: usize) -> bool {
+        match &self.peek_nth(n).kind {
+            TokenKind::Identifier(name) => {
+                is_type_keyword(name)
+                    || matches!(name.as_str(), "const" | "volatile" | "restrict" | "_Atomic")
+                    || (!super::is_keyword(name) && self.is_typedef_name(name))
+            }
+            _ => false,
+        }
+    }
+
+    /// Parses a declaration, including its terminating semicolon or function body.
+    pub(super) fn declaration(
+        &mut self,
+        doc: Option<DocComment>,
+        is_file_scope: bool,
+    ) -> Result<Declaration, Error> {
+        if self.is_keyword("_Static_assert") || self.is_keyword("static_assert") {
+            return self.static_assert().map(Declaration::StaticAssert);
+        }
+
+        let specifiers = self.specifiers()?;
+
+        if self.eat(";") {
+            return self.type_definition(specifiers, doc);
+        }
+
+        let declarator = self.declarator(DeclaratorKind::Named)?;
+        let position = declarator
+            .name
+            .as_ref()
+            .map_or(specifiers.position, |(_, position)| *position);
+
+        // A list of variables keeps the declarators, rather than their derived types
+        let (name, ty) = declarator
+            .clone()
+            .apply(specifiers.ty.clone().into(), position)?;
+        let (name, name_position) = name.expect("named declarators have a name");
+
+        if matches!(specifiers.storage, Some(Storage::Typedef))
+            || !matches!(ty, OpaqueType::Function(_))
+        {
+            self.reject_inline(&specifiers)?;
+        }
+
+        match (specifiers.storage, ty) {
+            (Some(Storage::Typedef), ty) => {
+                if self.is_punctuator(",") {
+                    return Err(self.unsupported(position, "typedefs with multiple declarators"));
+                }
+                self.expect(";")?;
+
+                let ty = match ty {
+                    OpaqueType::ConcreteType(mut ty) => {
+                        set_doc(&mut ty, doc);
+                        OpaqueType::ConcreteType(ty)
+                    }
+                    ty => ty,
+                };
+
+                self.declare(&name, true);
+                Ok(Declaration::Typedef(Typedef { ty, alias: name }))
+            }
+            (storage, OpaqueType::Function(function)) => {
+                let is_static = match storage {
+                    Some(Storage::Class(StorageClass::Static)) => true,
+                    None | Some(Storage::Class(StorageClass::Extern)) => false,
+                    Some(_) => {
+                        return Err(self.unsupported(
+                            specifiers.position,
+                            "functions with storage classes other than `static` and `extern`",
+                        ))
+                    }
+                };
+                if specifiers.alignment.is_some() {
+                    return Err(self.unsupported(specifiers.position, "aligned functions"));
+                }
+                let is_inline = specifiers.is_inline;
+
+                self.declare(&name, false);
+
+                if self.is_punctuator("{") {
+                    if !is_file_scope {
+                        return Err(self.unsupported(name_position, "nested functions"));
+                    }
+
+                    let body = self.at("body", |parser| parser.function_body(&function))?;
+
+                    Ok(Declaration::FunctionDefinition(FunctionDefinition {
+                        doc,
+                        is_static,
+                        is_inline,
+                        name,
+                        ty: function,
+                        body,
+                    }))
+                } else {
+                    if self.is_punctuator(",") {
+                        return Err(self.unsupported(
+                            position,
+                            "function declarations with multiple declarators",
+                        ));
+                    }
+                    self.expect(";")?;
+
+                    Ok(Declaration::FunctionDeclaration(FunctionDeclaration {
+                        doc,
+                        is_static,
+                        is_inline,
+                        name,
+                        ty: function,
+                    }))
+                }
+            }
+            (storage, OpaqueType::ConcreteType(ty)) => {
+                let storage_class = storage.map(|storage| match storage {
+                    Storage::Class(storage_class) => storage_class,
+                    Storage::Typedef => unreachable!("typedefs are handled above"),
+                });
+
+                let initializer = if self.eat("=") {
+                    Some(self.initializer()?)
+                } else {
+                    None
+                };
+                self.declare(&name, false);
+
+                if !self.is_punctuator(",") {
+                    self.expect(";")?;
+
+                    return Ok(Declaration::VariableDeclaration(VariableDeclaration {
+                        doc,
+                        storage_class,
+                        alignment: specifiers.alignment,
+                        ty,
+                        identifier: name,
+                        initializer,
+                    }));
+                }
+
+                // Further declarators share the specifiers, but not the pointers and dimensions
+                let mut declarators = vec![declarator
+                    .into_variable_declarator(initializer)
+                    .ok_or_else(|| {
+                        self.unsupported(position, "function pointers in declaration lists")
+                    })?];
+                while self.eat(",") {
+                    let declarator = self.declarator(DeclaratorKind::Named)?;
+                    let (name, position) = declarator
+                        .name
+                        .clone()
+                        .expect("named declarators have a name");
+
+                    let initializer = if self.eat("=") {
+                        Some(self.initializer()?)
+                    } else {
+                        None
+                    };
+                    self.declare(&name, false);
+
+                    declarators.push(
+                        declarator
+                            .into_variable_declarator(initializer)
+                            .ok_or_else(|| {
+                                self.unsupported(position, "function pointers in declaration lists")
+                            })?,
+                    );
+                }
+                self.expect(";")?;
+
+                Ok(Declaration::VariableDeclarationList(
+                    VariableDeclarationList {
+                        doc,
+                        storage_class,
+                        alignment: specifiers.alignment,
+                        ty: specifiers.ty,
+                        declarators: NonEmptyVec::try_from(declarators)
+                            .expect("the list has at least one declarator"),
+                    },
+                ))
+            }
+        }
+    }
+
+    /// Parses a declaration without declarators, e.g. `struct point { int x; int y; };`.
+    fn type_definition(
+        &mut self,
+        specifiers: Specifiers,
+        doc: Option<DocComment>,
+    ) -> Result<Declaration, Error> {
+        self.reject_inline(&specifiers)?;
+        if specifiers.storage.is_some() || specifiers.alignment.is_some() {
+            return Err(self.unsupported(
+                specifiers.position,
+                "storage classes and alignment specifiers without declarators",
+            ));
+        }
+
+        let mut ty = specifiers.ty;
+        set_doc(&mut ty, doc);
+
+        let definition = match ty {
+            ConcreteType::Enum(enumeration) => TypeDefinition::Enum(enumeration),
+            ConcreteType::Struct(structure) => TypeDefinition::Struct(structure),
+            ConcreteType::Union(union) => TypeDefinition::Union(union),
+            _ => {
+                return Err(Error {
+                    position: specifiers.position,
+                    kind: ErrorKind::Expected {
+                        expected: "declarator".to_string(),
+                        found: "`;`".to_string(),
+                    },
+                })
+            }
+        };
+
+        Ok(Declaration::TypeDefinition(definition))
+    }
+
+    fn function_body(&mut self, function: &Function) -> Result<Block, Error> {
+        self.push_scope();
+        for parameter in &function.parameters {
+            if let Some(name) = &parameter.name {
+                self.declare(name, false);
+            }
+        }
+
+        let body = self.block();
+        self.pop_scope();
+
+        body
+    }
+
+    pub(super) fn static_assert(&mut self) -> Result<StaticAssert, Error> {
+        self.next();
+        self.expect("(")?;
+
+        let condition = self.conditional_expression()?;
+        let message = if self.eat(",") {
+            let token = self.next();
+            let TokenKind::String(mut message) = token.kind else {
+                return Err(Error {
+                    position: token.position,
+                    kind: ErrorKind::Expected {
+                        expected: "string literal".to_string(),
+                        found: token.describe(),
+                    },
+                });
+            };
+
+            // Adjacent string literals are concatenated
+            while let TokenKind::String(text) = &self.peek().kind {
+                message.push_str(text);
+                self.next();
+            }
+
+            Some(message)
+        } else {
+            None
+        };
+
+        self.expect(")")?;
+        self.expect(";")?;
+
+        Ok(StaticAssert { condition, message })
+    }
+
+    /// Parses a type name, e.g. `const char *` in a cast.
+    pub(super) fn type_name(&mut self) -> Result<ConcreteType, Error> {
+        let specifiers = self.specifiers()?;
+        self.reject_inline(&specifiers)?;
+        if specifiers.storage.is_some() || specifiers.alignment.is_some() {
+            return Err(self.unsupported(
+                specifiers.position,
+                "storage classes and alignment specifiers in type names",
+            ));
+        }
+
+        let declarator = self.declarator(DeclaratorKind::Abstract)?;
+        match declarator.apply(specifiers.ty.into(), specifiers.position)? {
+            (_, OpaqueType::ConcreteType(ty)) => Ok(ty),
+            (_, OpaqueType::Function(_)) => {
+                Err(self.unsupported(specifiers.position, "function type names"))
+            }
+        }
+    }
+
+    fn specifiers(&mut self) -> Result<Specifiers, Error> {
+        let position = self.peek().position;
+
+        let mut storage = None;
+        let mut alignment = None;
+        let mut is_inline = false;
+        let mut is_const = false;
+        let mut keywords = Vec::new();
+        let mut ty = None;
+
+        loop {
+            let token = self.peek().clone();
+            let TokenKind::Identifier(name) = &token.kind else {
+                break;
+            };
+
+            match name.as_str() {
+                "typedef" | "extern" | "static" | "auto" | "register" => {
+                    if storage.is_some() {
+                        return Err(self.unsupported(token.position, "multiple storage classes"));
+                    }
+
+                    storage = Some(match name.as_str() {
+                        "typedef" => Storage::Typedef,
+                        "extern" => Storage::Class(StorageClass::Extern),
+                        "static" => Storage::Class(StorageClass::Static),
+                        "auto" => Storage::Class(StorageClass::Auto),
+                        _ => Storage::Class(StorageClass::Register),
+                    });
+                    self.next();
+                }
+                "const" => {
+                    is_const = true;
+                    self.next();
+                }
+                "inline" => {
+                    is_inline = true;
+                    self.next();
+                }
+                "_Alignas" | "alignas" => {
+                    self.next();
+                    self.expect("(")?;
+                    if self.is_type_name_start(0) {
+                        return Err(
+                            self.unsupported(token.position, "alignment specifiers of types")
+                        );
+                    }
+                    alignment = Some(self.conditional_expression()?);
+                    self.expect(")")?;
+                }
+                "__attribute__" => {
+                    for (name, argument, position) in self.gnu_attributes()? {
+                        match (name.as_str(), argument) {
+                            ("aligned" | "__aligned__", Some(argument)) => {
+                                alignment = Some(argument);
+                            }
+                            _ => {
+                                return Err(
+                                    self.unsupported(position, format!("`{name}` attributes"))
+                                )
+                            }
+                        }
+                    }
+                }
+                "volatile" | "restrict" | "_Atomic" | "_Noreturn" | "_Thread_local"
+                | "thread_local" | "constexpr" | "_Complex" | "_Imaginary" | "typeof" => {
+                    return Err(self.unsupported(token.position, format!("`{name}` specifiers")));
+                }
+                "struct" | "union" | "enum" if ty.is_none() && keywords.is_empty() => {
+                    ty = Some(match name.as_str() {
+                        "struct" => self.structure()?,
+                        "union" => self.union()?,
+                        _ => self.enumeration()?,
+                    });
+                }
+                name if is_type_keyword(name) && ty.is_none() => {
+                    keywords.push(name.to_string());
+                    self.next();
+                }
+                name if ty.is_none()
+                    && keywords.is_empty()
+                    && !super::is_keyword(name)
+                    && self.is_typedef_name(name) =>
+                {
+                    ty = Some(builtin_type(name).unwrap_or_else(|| {
+                        ConcreteType::TypedefName(
+                            Identifier::new(name).expect("tokens are valid identifiers"),
+                        )
+                    }));
+                    self.next();
+                }
+                _ => break,
+            }
+        }
+
+        let ty = match ty {
+            Some(ty) => ty,
+            None if keywords.is_empty() => return Err(self.expected("type specifier")),
+            None => arithmetic_type(&keywords).ok_or_else(|| Error {
+                position,
+                kind: ErrorKind::Expected {
+                    expected: "valid combination of type specifiers".to_string(),
+                    found: format!("`{}`", keywords.join(" ")),
+                },
+            })?,
+        };
+
+        Ok(Specifiers {
+            position,
+            storage,
+            alignment,
+            is_inline,
+            ty: if is_const { ty.into_const() } else { ty },
+        })
+    }
+
+    /// Fails if the specifiers of something other than a function contain `inline`.
+    fn reject_inline(&self, specifiers: &Specifiers) -> Result<(), Error> {
+        if specifiers.is_inline {
+            Err(self.unsupported(
+                specifiers.position,
+                "`inline` specifiers outside of function declarations",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Parses `__attribute__((name, name(argument)))`.
+    fn gnu_attributes(&mut self) -> Result<Vec<(String, Option<Expression>, Position)>, Error> {
+        self.next();
+        self.expect("(")?;
+        self.expect("(")?;
+
+        let mut attributes = Vec::new();
+        loop {
+            attributes.push(self.attribute()?);
+
+            if !self.eat(",") {
+                break;
+            }
+        }
+
+        self.expect(")")?;
+        self.expect(")")?;
+
+        Ok(attributes)
+    }
+
+    /// Parses `[[gnu::name, gnu::name(argument)]]`.
+    fn standard_attributes(
+        &mut self,
+    ) -> Result<Vec<(String, Option<Expression>, Position)>, Error> {
+        self.expect("[")?;
+        self.expect("[")?;
+
+        let mut attributes = Vec::new();
+        loop {
+            let position = self.peek().position;
+            if !self.eat_keyword("gnu") || !self.eat("::") {
+                return Err(self.unsupported(position, "attributes outside of the `gnu` namespace"));
+            }
+            attributes.push(self.attribute()?);
+
+            if !self.eat(",") {
+                break;
+            }
+        }
+
+        self.expect("]")?;
+        self.expect("]")?;
+
+        Ok(attributes)
+    }
+
+    fn attribute(&mut self) -> Result<(String, Option<Expression>, Position), Error> {
+        let token = self.next();
+        let TokenKind::Identifier(name) = token.kind else {
+            return Err(Error {
+                position: token.position,
+                kind: ErrorKind::Expected {
+                    expected: "attribute".to_string(),
+                    found: token.describe(),
+                },
+            });
+        };
+
+        let argument = if self.eat("(") {
+            let argument = self.assignment_expression()?;
+            self.expect(")")?;
+
+            Some(argument)
+        } else {
+            None
+        };
+
+        Ok((name, argument, token.position))
+    }
+
+    fn struct_attributes(&mut self) -> Result<StructAttributes, Error> {
+        let mut attributes = StructAttributes::default();
+
+        loop {
+            let list = if self.is_keyword("__attribute__") {
+                self.gnu_attributes()?
+            } else if self.is_punctuator("[") && self.is_punctuator_at(1, "[") {
+                self.standard_attributes()?
+            } else {
+                return Ok(attributes);
+            };
+
+            for (name, argument, position) in list {
+                match (name.as_str(), argument) {
+                    ("packed" | "__packed__", None) => attributes.packed = true,
+                    ("aligned" | "__aligned__", Some(argument)) => {
+                        attributes.aligned = Some(Box::new(argument));
+                    }
+                    _ => return Err(self.unsupported(position, format!("`{name}` attributes"))),
+                }
+            }
+        }
+    }
+
+    /// Adds the attributes that follow the closing brace of a definition, e.g. `struct s { ... } __attribute__((packed));`
+    fn trailing_attributes(&mut self, attributes: &mut StructAttributes) -> Result<(), Error> {
+        let trailing = self.struct_attributes()?;
+        attributes.packed |= trailing.packed;
+        if trailing.aligned.is_some() {
+            attributes.aligned = trailing.aligned;
+        }
+
+        Ok(())
+    }
+
+    fn structure(&mut self) -> Result<ConcreteType, Error> {
+        let position = self.next().position;
+        let mut attributes = self.struct_attributes()?;
+        let name = self.optional_identifier()?;
+
+        if self.eat("{") {
+            let members = self.members()?;
+            self.trailing_attributes(&mut attributes)?;
+
+            Ok(Struct::Definition {
+                doc: None,
+                attributes,
+                name,
+                members,
+            }
+            .into())
+        } else {
+            let name = name.ok_or_else(|| self.expected("identifier or `{`"))?;
+            if !attributes.is_empty() {
+                return Err(self.unsupported(position, "attributes of incomplete structures"));
+            }
+
+            Ok(Struct::Tag { name }.into())
+        }
+    }
+
+    fn union(&mut self) -> Result<ConcreteType, Error> {
+        let position = self.next().position;
+        let mut attributes = self.struct_attributes()?;
+        let name = self.optional_identifier()?;
+
+        if self.eat("{") {
+            let members = self.members()?;
+            let members = NonEmptyVec::try_from(members)
+                .map_err(|_| self.unsupported(position, "unions without members"))?;
+            self.trailing_attributes(&mut attributes)?;
+
+            Ok(Union::Definition {
+                doc: None,
+                attributes,
+                name,
+                members,
+            }
+            .into())
+        } else {
+            let name = name.ok_or_else(|| self.expected("identifier or `{`"))?;
+            if !attributes.is_empty() {
+                return Err(self.unsupported(position, "attributes of incomplete unions"));
+            }
+
+            Ok(Union::Tag { name }.into())
+        }
+    }
+
+    fn optional_identifier(&mut self) -> Result<Option<Identifier>, Error> {
+        if self.peek_identifier(0).is_some() {
+            Ok(Some(self.identifier()?.0))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses the members of a structure or union, up to and including the closing brace.
+    fn members(&mut self) -> Result<Vec<Member>, Error> {
+        let mut members = Vec::new();
+
+        loop {
+            let doc = self.leading_doc();
+            if self.eat("}") {
+                return Ok(members);
+            }
+
+            let specifiers = self.specifiers()?;
+            self.reject_inline(&specifiers)?;
+            if specifiers.storage.is_some() {
+                return Err(self.unsupported(specifiers.position, "storage classes of members"));
+            }
+            if self.is_punctuator(";") {
+                return Err(self.unsupported(specifiers.position, "anonymous members"));
+            }
+
+            let mut doc = doc;
+            loop {
+                let declarator = self.declarator(DeclaratorKind::Named)?;
+                let (name, ty) =
+                    declarator.apply(specifiers.ty.clone().into(), specifiers.position)?;
+                let (name, position) = name.expect("named declarators have a name");
+                let OpaqueType::ConcreteType(ty) = ty else {
+                    return Err(self.unsupported(position, "function members"));
+                };
+
+                let bit_field_size = if self.eat(":") {
+                    Some(self.bit_field_size()?)
+                } else {
+                    None
+                };
+
+                members.push(Member {
+                    doc: doc.take(),
+                    ty,
+                    name,
+                    bit_field_size,
+                    alignment: specifiers.alignment.clone(),
+                });
+
+                if !self.eat(",") {
+                    break;
+                }
+            }
+
+            self.expect(";")?;
+        }
+    }
+
+    fn bit_field_size(&mut self) -> Result<usize, Error> {
+        let position = self.peek().position;
+
+        match self.conditional_expression()? {
+            Expression::Value(Value::SignedInteger { value, kind: None }) => usize::try_from(value)
+                .map_err(|_| Error {
+                    position,
+                    kind: ErrorKind::InvalidConstant(value.to_string()),
+                }),
+            _ => Err(self.unsupported(position, "bit-field widths other than integer constants")),
+        }
+    }
+
+    fn enumeration(&mut self) -> Result<ConcreteType, Error> {
+        self.next();
+        let name = self.optional_identifier()?;
+
+        let underlying_ty = if self.is_punctuator(":") && self.is_type_name_start(1) {
+            self.next();
+            Some(Box::new(self.type_name()?))
+        } else {
+            None
+        };
+
+        if !self.eat("{") {
+            let name = name.ok_or_else(|| self.expected("identifier or `{`"))?;
+
+            return Ok(Enum::Tag { name }.into());
+        }
+
+        let mut values = Vec::new();
+        let mut trailing_comma = false;
+        loop {
+            let doc = self.leading_doc();
+            if self.eat("}") {
+                break;
+            }
+
+            let (name, _) = self.identifier()?;
+            let value = if self.eat("=") {
+                Some(self.conditional_expression()?)
+            } else {
+                None
+            };
+            self.declare(&name, false);
+            values.push(Enumerator { doc, name, value });
+
+            trailing_comma = self.eat(",");
+            if !trailing_comma {
+                self.expect("}")?;
+                break;
+            }
+        }
+
+        let values = NonEmptyVec::try_from(values).map_err(|_| self.expected("identifier"))?;
+
+        Ok(Enum::Definition {
+            doc: None,
+            name,
+            underlying_ty,
+            values,
+            trailing_comma,
+        }
+        .into())
+    }
+
+    fn declarator(&mut self, kind: DeclaratorKind) -> Result<Declarator, Error> {
+        let mut pointers = Vec::new();
+        while self.eat("*") {
+            let mut is_const = false;
+            loop {
+                let token = self.peek().clone();
+                if self.eat_keyword("const") {
+                    is_const = true;
+                } else if let Some(name) = token.kind.qualifier() {
+                    return Err(self.unsupported(token.position, format!("`{name}` qualifiers")));
+                } else {
+                    break;
+                }
+            }
+
+            pointers.push(is_const);
+        }
+
+        let mut name = None;
+        let mut inner = None;
+        if kind != DeclaratorKind::Abstract && self.peek_identifier(0).is_some() {
+            name = Some(self.identifier()?);
+        } else if self.is_punctuator("(") && self.is_nested_declarator(kind) {
+            self.next();
+            inner = Some(Box::new(self.declarator(kind)?));
+            self.expect(")")?;
+        } else if kind == DeclaratorKind::Named {
+            return Err(self.expected("identifier"));
+        }
+
+        let mut suffixes = Vec::new();
+        loop {
+            if self.eat("[") {
+                suffixes.push(Suffix::Array(self.array_size()?));
+                self.expect("]")?;
+            } else if self.eat("(") {
+                suffixes.push(Suffix::Function(self.parameters()?));
+            } else {
+                break;
+            }
+        }
+
+        Ok(Declarator {
+            name,
+            pointers,
+            suffixes,
+            inner,
+        })
+    }
+
+    /// Returns whether the opening parenthesis that follows starts a nested declarator, rather than a parameter list.
+    fn is_nested_declarator(&self, kind: DeclaratorKind) -> bool {
+        self.is_punctuator_at(1, "*")
+            || (kind != DeclaratorKind::Abstract
+                && self
+                    .peek_identifier(1)
+                    .is_some_and(|name| !self.is_typedef_name(name)))
+    }
+
+    fn array_size(&mut self) -> Result<ArraySize, Error> {
+        if self.is_punctuator("]") {
+            return Ok(ArraySize::Unspecified);
+        }
+
+        if self.is_punctuator("*") && self.is_punctuator_at(1, "]") {
+            self.next();
+            return Ok(ArraySize::Star);
+        }
+
+        let token = self.peek().clone();
+        if self.eat_keyword("static") {
+            return Ok(ArraySize::Static(Box::new(self.assignment_expression()?)));
+        }
+        if let Some(name) = token
+            .kind
+            .qualifier()
+            .or_else(|| self.is_keyword("const").then_some("const"))
+        {
+            return Err(self.unsupported(
+                token.position,
+                format!("`{name}` qualifiers of array parameters"),
+            ));
+        }
+
+        Ok(ArraySize::Length(Box::new(self.assignment_expression()?)))
+    }
+
+    /// Parses a parameter list, up to and including the closing parenthesis.
+    fn parameters(&mut self) -> Result<Parameters, Error> {
+        let mut parameters = Parameters {
+            parameters: Vec::new(),
+            is_variadic: false,
+            is_unspecified: false,
+        };
+
+        // `()` leaves the parameters unspecified before C23, while `(void)` declares a function without parameters
+        if self.eat(")") {
+            parameters.is_unspecified = true;
+            return Ok(parameters);
+        }
+        if self.is_keyword("void") && self.is_punctuator_at(1, ")") {
+            self.next();
+            self.next();
+            return Ok(parameters);
+        }
+
+        loop {
+            if self.eat("...") {
+                parameters.is_variadic = true;
+                break;
+            }
+
+            let specifiers = self.specifiers()?;
+            self.reject_inline(&specifiers)?;
+            if specifiers.storage.is_some() || specifiers.alignment.is_some() {
+                return Err(self.unsupported(
+                    specifiers.position,
+                    "storage classes and alignment specifiers of parameters",
+                ));
+            }
+
+            let declarator = self.declarator(DeclaratorKind::Either)?;
+            let (name, ty) = declarator.apply(specifiers.ty.into(), specifiers.position)?;
+            let ty = match ty {
+                OpaqueType::ConcreteType(ty) => ty,
+                // A parameter of function type is adjusted to a pointer
+                OpaqueType::Function(function) => Pointer {
+                    pointer_ty: function.into(),
+                    is_const: false,
+                }
+                .into(),
+            };
+
+            parameters.parameters.push(FunctionParameter {
+                ty,
+                name: name.map(|(name, _)| name),
+            });
+
+            if !self.eat(",") {
+                break;
+            }
+        }
+
+        self.expect(")")?;
+
+        Ok(parameters)
+    }
+}
+
+/// Sets the documentation comment of an inline enumeration, structure or union definition.
+fn set_doc(ty: &mut ConcreteType, comment: Option<DocComment>) {
+    match ty {
+        ConcreteType::Enum(Enum::Definition { doc, .. })
+        | ConcreteType::Struct(Struct::Definition { doc, .. })
+        | ConcreteType::Union(Union::Definition { doc, .. }) => *doc = comment,
+        _ => {}
+    }
+}
+
+/// Returns whether the keyword starts declaration specifiers.
+fn is_specifier_keyword(name: &str) -> bool {
+    is_type_keyword(name)
+        || matches!(
+            name,
+            "typedef"
+                | "extern"
+                | "static"
+                | "auto"
+                | "register"
+                | "const"
+                | "volatile"
+                | "restrict"
+                | "_Atomic"
+                | "inline"
+                | "_Noreturn"
+                | "_Thread_local"
+                | "thread_local"
+                | "constexpr"
+                | "_Alignas"
+                | "alignas"
+                | "__attribute__"
+        )
+}
+
+fn is_type_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "void"
+            | "char"
+            | "short"
+            | "int"
+            | "long"
+            | "float"
+            | "double"
+            | "signed"
+            | "unsigned"
+            | "_Bool"
+            | "bool"
+            | "struct"
+            | "union"
+            | "enum"
+            | "_Complex"
+            | "_Imaginary"
+            | "typeof"
+    )
+}
+
+/// Returns the type of a combination of arithmetic type keywords, in any order.
+fn arithmetic_type(keywords: &[String]) -> Option<ConcreteType> {
+    let mut keywords = keywords.iter().map(String::as_str).collect::<Vec<_>>();
+    keywords.sort_unstable();
+
+    let signedness = keywords
+        .iter()
+        .filter(|keyword| matches!(**keyword, "signed" | "unsigned"))
+        .collect::<Vec<_>>();
+    let is_signed = match signedness.as_slice() {
+        [] => None,
+        [signedness] => Some(**signedness == "signed"),
+        _ => return None,
+    };
+    keywords.retain(|keyword| !matches!(*keyword, "signed" | "unsigned"));
+
+    let integer = |kind| {
+        Some(ConcreteType::Integer(Integer {
+            kind,
+            is_signed: is_signed.unwrap_or(true),
+        }))
+    };
+
+    match (keywords.as_slice(), is_signed) {
+        (["void"], None) => Some(ConcreteType::Void),
+        (["_Bool" | "bool"], None) => Some(ConcreteType::Bool),
+        (["float"], None) => Some(ConcreteType::Real(Real::Float)),
+        (["double"], None) => Some(ConcreteType::Real(Real::Double)),
+        (["double", "long"], None) => Some(ConcreteType::Real(Real::LongDouble)),
+        (["char"], None) => Some(ConcreteType::Char),
+        (["char"], Some(_)) => integer(IntegerKind::Char),
+        (["int", "short"] | ["short"], _) => integer(IntegerKind::Short),
+        (["int"], _) | ([], Some(_)) => integer(IntegerKind::Int),
+        (["int", "long"] | ["long"], _) => integer(IntegerKind::Long),
+        (["int", "long", "long"] | ["long", "long"], _) => integer(IntegerKind::LongLong),
+        _ => None,
+    }
+}
+
+/// Returns the type of a typedef name of a standard header that has a dedicated representation, e.g. `size_t`.
+pub(super) fn builtin_type(name: &str) -> Option<ConcreteType> {
+    let strong_int = match name {
+        "size_t" => return Some(ConcreteType::Size),
+        "int16_t" => StrongInt::Int16,
+        "int32_t" => StrongInt::Int32,
+        "int64_t" => StrongInt::Int64,
+        "int8_t" => StrongInt::Int8,
+        "int_fast16_t" => StrongInt::IntFast16,
+        "int_fast32_t" => StrongInt::IntFast32,
+        "int_fast64_t" => StrongInt::IntFast64,
+        "int_fast8_t" => StrongInt::IntFast8,
+        "int_least16_t" => StrongInt::IntLeast16,
+        "int_least32_t" => StrongInt::IntLeast32,
+        "int_least64_t" => StrongInt::IntLeast64,
+        "int_least8_t" => StrongInt::IntLeast8,
+        "intmax_t" => StrongInt::IntMax,
+        "intptr_t" => StrongInt::IntPtr,
+        "uint16_t" => StrongInt::Uint16,
+        "uint32_t" => StrongInt::Uint32,
+        "uint64_t" => StrongInt::Uint64,
+        "uint8_t" => StrongInt::Uint8,
+        "uint_fast16_t" => StrongInt::UintFast16,
+        "uint_fast32_t" => StrongInt::UintFast32,
+        "uint_fast64_t" => StrongInt::UintFast64,
+        "uint_fast8_t" => StrongInt::UintFast8,
+        "uint_least16_t" => StrongInt::UintLeast16,
+        "uint_least32_t" => StrongInt::UintLeast32,
+        "uint_least64_t" => StrongInt::UintLeast64,
+        "uint_least8_t" => StrongInt::UintLeast8,
+        "uintmax_t" => StrongInt::UintMax,
+        "uintptr_t" => StrongInt::UintPtr,
+        _ => return None,
+    };
+
+    Some(ConcreteType::StrongInt(strong_int))
+}
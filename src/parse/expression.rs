@@ -0,0 +1,685 @@
+use crate::{
+    function::FunctionCall,
+    operator::{
+        ArraySubscript, Assignment, BinaryOperator, BinaryOperatorKind, Cast, CommaOperator,
+        CompoundAssignment, CompoundAssignmentOperator, Conditional, OffsetOf, PostfixOperator,
+        PostfixOperatorKind, PrefixOperator, PrefixOperatorKind, SizeOf,
+    },
+    r#type::{
+        member::{IndirectMemberAccess, MemberAccess},
+        InitializerList, IntegerKind, Real, Scalar,
+    },
+    ConcreteType, Expression, Value,
+};
+
+use super::{
+    lexer::TokenKind,
+    template::{self, Conversion},
+    Error, ErrorKind, Fragment, Parser,
+};
+
+impl Parser {
+    /// Parses an expression, including comma operators.
+    pub(super) fn expression(&mut self) -> Result<Expression, Error> {
+        let start = self.lookahead(0);
+
+        let mut left = self.assignment_expression()?;
+        while self.eat(",") {
+            let right = self.assignment_expression()?;
+            left = CommaOperator { left, right }.into();
+        }
+
+        Ok(self.unwrap_fragment(start, left, true))
+    }
+
+    pub(super) fn assignment_expression(&mut self) -> Result<Expression, Error> {
+        let start = self.lookahead(0);
+        let expression = self.assignment_operators()?;
+
+        Ok(self.unwrap_fragment(start, expression, false))
+    }
+
+    /// Removes the parentheses around a fragment that forms the whole expression starting at the token index.
+    ///
+    /// Only an expression that allows comma operators can be a comma operator without parentheses.
+    fn unwrap_fragment(
+        &mut self,
+        start: usize,
+        expression: Expression,
+        allow_comma: bool,
+    ) -> Expression {
+        let is_placeholder =
+            matches!(self.tokens[start].kind, TokenKind::Placeholder(_)) && self.index == start + 1;
+
+        // The value of a template placeholder is only known, and converted, when the template is expanded
+        if let (TokenKind::Placeholder(name), true) = (&self.tokens[start].kind, is_placeholder) {
+            if let Some(conversion) = self.conversions.get_mut(name) {
+                *conversion = if allow_comma {
+                    Conversion::Expression
+                } else {
+                    Conversion::AssignmentExpression
+                };
+            }
+        }
+
+        match expression {
+            Expression::Parentheses(inner)
+                if is_placeholder
+                    && (allow_comma || !matches!(*inner, Expression::CommaOperator(_))) =>
+            {
+                *inner
+            }
+            expression => expression,
+        }
+    }
+
+    fn assignment_operators(&mut self) -> Result<Expression, Error> {
+        let left = self.conditional_expression()?;
+
+        if self.eat("=") {
+            let right = self.assignment_expression()?;
+            return Ok(Assignment { left, right }.into());
+        }
+
+        let TokenKind::Punctuator(punctuator) = self.peek().kind else {
+            return Ok(left);
+        };
+        let Some(operator) = compound_assignment_operator(punctuator) else {
+            return Ok(left);
+        };
+        self.next();
+
+        let right = self.assignment_expression()?;
+        Ok(CompoundAssignment {
+            left,
+            operator,
+            right,
+        }
+        .into())
+    }
+
+    /// Parses a conditional expression, which is also the syntax of constant expressions.
+    pub(super) fn conditional_expression(&mut self) -> Result<Expression, Error> {
+        let condition = self.binary_expression(0)?;
+        if !self.eat("?") {
+            return Ok(condition);
+        }
+
+        let then_branch = self.expression()?;
+        self.expect(":")?;
+        let else_branch = self.conditional_expression()?;
+
+        Ok(Conditional {
+            condition,
+            then_branch,
+            else_branch,
+        }
+        .into())
+    }
+
+    /// Parses binary operators that bind at least as tightly as the given precedence.
+    fn binary_expression(&mut self, precedence: u8) -> Result<Expression, Error> {
+        let mut left = self.cast_expression()?;
+
+        loop {
+            let TokenKind::Punctuator(punctuator) = self.peek().kind else {
+                return Ok(left);
+            };
+            let Some((operator, operator_precedence)) = binary_operator(punctuator) else {
+                return Ok(left);
+            };
+            if operator_precedence < precedence {
+                return Ok(left);
+            }
+            self.next();
+
+            // Binary operators are left-associative
+            let right = self.binary_expression(operator_precedence + 1)?;
+            left = BinaryOperator {
+                left,
+                operator,
+                right,
+            }
+            .into();
+        }
+    }
+
+    fn cast_expression(&mut self) -> Result<Expression, Error> {
+        if !(self.is_punctuator("(") && self.is_type_name_start(1)) {
+            return self.unary_expression();
+        }
+
+        let position = self.next().position;
+        let ty = self.type_name()?;
+        self.expect(")")?;
+
+        if self.is_punctuator("{") {
+            return Err(self.unsupported(position, "compound literals"));
+        }
+
+        let new_ty =
+            scalar(ty).ok_or_else(|| self.unsupported(position, "casts to non-scalar types"))?;
+        let expression = self.cast_expression()?;
+
+        Ok(Cast { new_ty, expression }.into())
+    }
+
+    fn unary_expression(&mut self) -> Result<Expression, Error> {
+        let token = self.peek().clone();
+
+        let operator = match &token.kind {
+            TokenKind::Punctuator("++") => Some(PrefixOperatorKind::Increment),
+            TokenKind::Punctuator("--") => Some(PrefixOperatorKind::Decrement),
+            TokenKind::Punctuator("+") => Some(PrefixOperatorKind::Positive),
+            TokenKind::Punctuator("-") => Some(PrefixOperatorKind::Negative),
+            TokenKind::Punctuator("&") => Some(PrefixOperatorKind::Address),
+            TokenKind::Punctuator("*") => Some(PrefixOperatorKind::Indirection),
+            TokenKind::Punctuator(punctuator @ ("!" | "~")) => {
+                return Err(self.unsupported(token.position, format!("`{punctuator}` operators")));
+            }
+            _ => None,
+        };
+        if let Some(operator) = operator {
+            self.next();
+
+            // Increments apply to unary expressions, the other operators to cast expressions
+            let operand = match operator {
+                PrefixOperatorKind::Increment | PrefixOperatorKind::Decrement => {
+                    self.unary_expression()?
+                }
+                _ => self.cast_expression()?,
+            };
+
+            return Ok(PrefixOperator { operand, operator }.into());
+        }
+
+        if self.eat_keyword("sizeof") {
+            let size_of = if self.is_punctuator("(") && self.is_type_name_start(1) {
+                self.next();
+                let ty = self.type_name()?;
+                self.expect(")")?;
+
+                SizeOf::Type(ty)
+            } else {
+                SizeOf::Expression(self.unary_expression()?)
+            };
+
+            return Ok(size_of.into());
+        }
+
+        if self.eat_keyword("_Alignof")
+            || self.eat_keyword("alignof")
+            || self.eat_keyword("__alignof__")
+        {
+            self.expect("(")?;
+            let ty = self.type_name()?;
+            self.expect(")")?;
+
+            return Ok(Expression::AlignOf(ty));
+        }
+
+        self.postfix_expression()
+    }
+
+    fn postfix_expression(&mut self) -> Result<Expression, Error> {
+        let mut expression = self.primary_expression()?;
+
+        loop {
+            expression = if self.eat("[") {
+                let index = self.expression()?;
+                self.expect("]")?;
+
+                ArraySubscript {
+                    array: expression,
+                    index,
+                }
+                .into()
+            } else if self.eat("(") {
+                let mut arguments = Vec::new();
+                if !self.eat(")") {
+                    loop {
+                        arguments.push(self.assignment_expression()?);
+                        if !self.eat(",") {
+                            break;
+                        }
+                    }
+                    self.expect(")")?;
+                }
+
+                FunctionCall {
+                    callee: expression,
+                    arguments,
+                }
+                .into()
+            } else if self.eat(".") {
+                let (member, _) = self.identifier()?;
+
+                MemberAccess {
+                    left: expression,
+                    member,
+                }
+                .into()
+            } else if self.eat("->") {
+                let (member, _) = self.identifier()?;
+
+                IndirectMemberAccess {
+                    left: expression,
+                    member,
+                }
+                .into()
+            } else if self.eat("++") {
+                PostfixOperator {
+                    operand: expression,
+                    operator: PostfixOperatorKind::Increment,
+                }
+                .into()
+            } else if self.eat("--") {
+                PostfixOperator {
+                    operand: expression,
+                    operator: PostfixOperatorKind::Decrement,
+                }
+                .into()
+            } else {
+                return Ok(expression);
+            };
+        }
+    }
+
+    fn primary_expression(&mut self) -> Result<Expression, Error> {
+        let token = self.peek().clone();
+        let invalid_constant = |text: &str| Error {
+            position: token.position,
+            kind: ErrorKind::InvalidConstant(text.to_string()),
+        };
+
+        match &token.kind {
+            TokenKind::Identifier(name)
+                if matches!(name.as_str(), "offsetof" | "__builtin_offsetof") =>
+            {
+                self.next();
+                self.expect("(")?;
+                let ty = self.type_name()?;
+                self.expect(",")?;
+                let (member, _) = self.identifier()?;
+                if self.is_punctuator(".") || self.is_punctuator("[") {
+                    return Err(self.unsupported(token.position, "nested `offsetof` designators"));
+                }
+                self.expect(")")?;
+
+                Ok(OffsetOf { ty, member }.into())
+            }
+            TokenKind::Identifier(name)
+                if !super::is_keyword(name) && !self.is_typedef_name(name) =>
+            {
+                let (variable, _) = self.identifier()?;
+
+                Ok(Expression::Variable(variable))
+            }
+            TokenKind::Placeholder(name) => {
+                self.next();
+
+                match self.placeholders.get(name) {
+                    Some(Fragment::Expression(expression)) => {
+                        Ok(template::operand(expression.clone()))
+                    }
+                    Some(_) => Err(Error {
+                        position: token.position,
+                        kind: ErrorKind::MisplacedPlaceholder(name.clone()),
+                    }),
+                    None if self.is_template => {
+                        self.conversions.insert(name.clone(), Conversion::Operand);
+                        Ok(Expression::Variable(template::marker(name)))
+                    }
+                    None => Err(Error {
+                        position: token.position,
+                        kind: ErrorKind::UnknownPlaceholder(name.clone()),
+                    }),
+                }
+            }
+            TokenKind::Integer(text) => {
+                self.next();
+
+                integer_constant(text)
+                    .map(Expression::Value)
+                    .ok_or_else(|| invalid_constant(text))
+            }
+            TokenKind::Floating(text) => {
+                self.next();
+
+                floating_constant(text)
+                    .map(Expression::Value)
+                    .ok_or_else(|| invalid_constant(text))
+            }
+            TokenKind::Char(text) => {
+                self.next();
+
+                let value = unescape(text)
+                    .filter(|value| value.chars().count() == 1)
+                    .and_then(|value| value.chars().next())
+                    .ok_or_else(|| invalid_constant(&format!("'{text}'")))?;
+
+                Ok(Value::Char { value }.into())
+            }
+            TokenKind::String(text) => {
+                self.next();
+
+                // Adjacent string literals are concatenated
+                let mut text = text.clone();
+                while let TokenKind::String(next) = &self.peek().kind {
+                    concatenate(&mut text, next);
+                    self.next();
+                }
+
+                Ok(Value::String(text).into())
+            }
+            TokenKind::Punctuator("(") => {
+                self.next();
+                if self.is_punctuator("{") {
+                    return Err(self.unsupported(token.position, "statement expressions"));
+                }
+
+                let expression = self.expression()?;
+                self.expect(")")?;
+
+                Ok(Expression::Parentheses(Box::new(expression)))
+            }
+            _ => Err(self.expected("expression")),
+        }
+    }
+
+    /// Parses the initializer of a variable, which can be an initializer list.
+    pub(super) fn initializer(&mut self) -> Result<Expression, Error> {
+        if !self.is_punctuator("{") {
+            return self.assignment_expression();
+        }
+        let position = self.next().position;
+
+        let mut named = Vec::new();
+        let mut ordered = Vec::new();
+        while !self.eat("}") {
+            if self.is_punctuator("[") {
+                return Err(self.unsupported(self.peek().position, "array designators"));
+            }
+
+            if self.eat(".") {
+                let (name, _) = self.identifier()?;
+                self.expect("=")?;
+                named.push((name, self.initializer()?));
+            } else {
+                ordered.push(self.initializer()?);
+            }
+
+            if !self.eat(",") {
+                self.expect("}")?;
+                break;
+            }
+        }
+
+        let list = match (named.is_empty(), ordered.is_empty()) {
+            (_, true) if !named.is_empty() => InitializerList::Named(named),
+            (true, _) => InitializerList::Ordered(ordered),
+            _ => {
+                return Err(self.unsupported(
+                    position,
+                    "initializer lists that mix designated and positional initializers",
+                ))
+            }
+        };
+
+        Ok(list.into())
+    }
+}
+
+fn compound_assignment_operator(punctuator: &str) -> Option<CompoundAssignmentOperator> {
+    Some(match punctuator {
+        "+=" => CompoundAssignmentOperator::Add,
+        "-=" => CompoundAssignmentOperator::Sub,
+        "*=" => CompoundAssignmentOperator::Mul,
+        "/=" => CompoundAssignmentOperator::Div,
+        "%=" => CompoundAssignmentOperator::Mod,
+        "&=" => CompoundAssignmentOperator::And,
+        "|=" => CompoundAssignmentOperator::Or,
+        "^=" => CompoundAssignmentOperator::Xor,
+        "<<=" => CompoundAssignmentOperator::Shl,
+        ">>=" => CompoundAssignmentOperator::Shr,
+        _ => return None,
+    })
+}
+
+/// Returns the binary operator and its precedence, where higher binds more tightly.
+fn binary_operator(punctuator: &str) -> Option<(BinaryOperatorKind, u8)> {
+    let operator = match punctuator {
+        "||" => BinaryOperatorKind::Or,
+        "&&" => BinaryOperatorKind::And,
+        "|" => BinaryOperatorKind::BitOr,
+        "^" => BinaryOperatorKind::BitXor,
+        "&" => BinaryOperatorKind::BitAnd,
+        "==" => BinaryOperatorKind::Eq,
+        "!=" => BinaryOperatorKind::Ne,
+        "<" => BinaryOperatorKind::Lt,
+        "<=" => BinaryOperatorKind::Le,
+        ">" => BinaryOperatorKind::Gt,
+        ">=" => BinaryOperatorKind::Ge,
+        "<<" => BinaryOperatorKind::LShift,
+        ">>" => BinaryOperatorKind::RShift,
+        "+" => BinaryOperatorKind::Add,
+        "-" => BinaryOperatorKind::Sub,
+        "*" => BinaryOperatorKind::Mul,
+        "/" => BinaryOperatorKind::Div,
+        "%" => BinaryOperatorKind::Mod,
+        _ => return None,
+    };
+
+    Some((operator, operator.precedence()))
+}
+
+fn scalar(ty: ConcreteType) -> Option<Scalar> {
+    Some(match ty {
+        ConcreteType::Bool => Scalar::Bool,
+        ConcreteType::Char => Scalar::Char,
+        ConcreteType::Integer(integer) => Scalar::Integer(integer),
+        ConcreteType::Pointer(pointer) => Scalar::Pointer(*pointer),
+        ConcreteType::StrongInt(strong_int) => Scalar::StrongInt(strong_int),
+        ConcreteType::Real(real) => Scalar::Real(real),
+        ConcreteType::Void => Scalar::Void,
+        _ => return None,
+    })
+}
+
+fn integer_constant(text: &str) -> Option<Value> {
+    let lowercase = text.to_ascii_lowercase();
+    let digits = lowercase.trim_end_matches(['u', 'l']);
+
+    let (is_unsigned, kind) = match &lowercase[digits.len()..] {
+        "" => (false, None),
+        "u" => (true, None),
+        "l" => (false, Some(IntegerKind::Long)),
+        "ul" | "lu" => (true, Some(IntegerKind::Long)),
+        "ll" => (false, Some(IntegerKind::LongLong)),
+        "ull" | "llu" => (true, Some(IntegerKind::LongLong)),
+        _ => return None,
+    };
+
+    let (value, is_decimal) = if let Some(hex) = digits.strip_prefix("0x") {
+        (u64::from_str_radix(hex, 16), false)
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        (u64::from_str_radix(binary, 2), false)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (u64::from_str_radix(&digits[1..], 8), false)
+    } else {
+        (digits.parse(), true)
+    };
+    let value = value.ok()?;
+
+    // The radix is not kept, so the type has to be spelled out instead: constants that do not fit into `long long`
+    // are unsigned, and so are hexadecimal and octal constants that only fit into `unsigned int`, e.g. `0xFFFFFFFF`
+    let is_unsigned = is_unsigned
+        || (!is_decimal
+            && kind.is_none()
+            && i32::try_from(value).is_err()
+            && u32::try_from(value).is_ok());
+    match i64::try_from(value) {
+        Ok(value) if !is_unsigned => Some(Value::SignedInteger { value, kind }),
+        _ => Some(Value::UnsignedInteger { value, kind }),
+    }
+}
+
+fn floating_constant(text: &str) -> Option<Value> {
+    let is_hex = text.starts_with("0x") || text.starts_with("0X");
+    let (digits, kind) = match text.chars().last()? {
+        // `f` is a digit of hexadecimal constants, but their exponent is decimal
+        'f' | 'F' => (&text[..text.len() - 1], Real::Float),
+        'l' | 'L' => (&text[..text.len() - 1], Real::LongDouble),
+        _ => (text, Real::Double),
+    };
+
+    let value = if is_hex {
+        hex_floating_constant(&digits[2..])?
+    } else {
+        digits.parse().ok()?
+    };
+    // Constants beyond the range of `double`, e.g. `1e999`, have no finite representation
+    if !f64::is_finite(value) {
+        return None;
+    }
+
+    Some(Value::Real {
+        value,
+        kind: Some(kind),
+    })
+}
+
+/// Parses the digits of a hexadecimal floating constant after the `0x` prefix, e.g. `1.8p3`.
+fn hex_floating_constant(digits: &str) -> Option<f64> {
+    let (mantissa, exponent) = digits.split_once(['p', 'P'])?;
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let mut value = 0.0;
+    for digit in integer.chars().chain(fraction.chars()) {
+        value = value * 16.0 + f64::from(digit.to_digit(16)?);
+    }
+
+    let exponent: i32 = exponent.parse().ok()?;
+    let fraction_bits = i32::try_from(fraction.len() * 4).ok()?;
+
+    Some(value * 2f64.powi(exponent.checked_sub(fraction_bits)?))
+}
+
+/// Appends the text of an adjacent string literal.
+///
+/// An octal or hexadecimal escape sequence at the end of the text is rewritten as three octal digits if the next
+/// literal starts with a digit that would extend it, e.g. `"\1" "2"` becomes `"\0012"` rather than `"\12"`.
+fn concatenate(text: &mut String, next: &str) {
+    let mut escape = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+
+        escape = match chars.next() {
+            Some((_, 'x')) => {
+                let mut value = 0u32;
+                while let Some(digit) = chars.peek().and_then(|(_, c)| c.to_digit(16)) {
+                    value = value.saturating_mul(16).saturating_add(digit);
+                    chars.next();
+                }
+                Some((start, value, 16))
+            }
+            Some((_, c @ '0'..='7')) => {
+                let mut value = c.to_digit(8).unwrap_or_default();
+                let mut length = 1;
+                while length < 3 {
+                    match chars.peek().and_then(|(_, c)| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            length += 1;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                // Three octal digits cannot be extended
+                (length < 3).then_some((start, value, 8))
+            }
+            _ => None,
+        };
+        if chars.peek().is_some() {
+            escape = None;
+        }
+    }
+
+    if let Some((start, value, radix)) = escape {
+        let extends = next.chars().next().is_some_and(|c| c.is_digit(radix));
+        if extends && value <= 0o777 {
+            text.truncate(start);
+            text.push_str(&format!("\\{value:03o}"));
+        }
+    }
+
+    text.push_str(next);
+}
+
+/// Replaces the simple, octal and hexadecimal escape sequences of a character constant or string literal.
+pub(super) fn unescape(text: &str) -> Option<String> {
+    let mut unescaped = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'a' => '\x07',
+            'b' => '\x08',
+            'f' => '\x0c',
+            'v' => '\x0b',
+            c @ ('\\' | '\'' | '"' | '?') => c,
+            'x' => {
+                let mut value = 0u32;
+                let mut length = 0;
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(16)) {
+                    value = value.checked_mul(16)?.checked_add(digit)?;
+                    length += 1;
+                    chars.next();
+                }
+
+                if length == 0 {
+                    return None;
+                }
+                byte(value)?
+            }
+            c @ '0'..='7' => {
+                let mut value = c.to_digit(8)?;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+
+                byte(value)?
+            }
+            _ => return None,
+        };
+        unescaped.push(escaped);
+    }
+
+    Some(unescaped)
+}
+
+/// Returns the character of a numeric escape, which must fit in a single byte.
+fn byte(value: u32) -> Option<char> {
+    u8::try_from(value).ok().map(char::from)
+}
@@ -0,0 +1,443 @@
+//! Splits preprocessed C source into tokens.
+//!
+//! Comments and preprocessor directives are kept as tokens, because they are part of the AST.
+
+use super::{Error, ErrorKind, Position};
+
+/// Punctuators, with longer ones before their prefixes.
+const PUNCTUATORS: &[&str] = &[
+    "...", "<<=", ">>=", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "*=",
+    "/=", "%=", "+=", "-=", "&=", "^=", "|=", "::", "[", "]", "(", ")", "{", "}", ".", "&", "*",
+    "+", "-", "~", "!", "/", "%", "<", ">", "^", "|", "?", ":", ";", "=", ",",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum TokenKind {
+    /// An identifier or keyword.
+    Identifier(String),
+    /// An integer constant, including its prefix and suffix.
+    Integer(String),
+    /// A floating constant, including its suffix.
+    Floating(String),
+    /// A character constant, without quotes and with escape sequences.
+    Char(String),
+    /// A string literal, without quotes and with escape sequences.
+    String(String),
+    Punctuator(&'static str),
+    /// A placeholder for an expression, e.g. `$value`, without the leading `$`.
+    Placeholder(String),
+    Comment(RawComment),
+    /// A preprocessor directive, without the leading `#`. Line continuations are kept.
+    Directive(String),
+    Eof,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct RawComment {
+    pub style: CommentStyle,
+    /// The text between the delimiters.
+    pub text: String,
+    /// The line of the end of the comment.
+    pub end_line: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum CommentStyle {
+    /// `/* ... */`
+    Block,
+    /// `/** ... */`
+    Doc,
+    /// `// ...`
+    Line,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Token {
+    pub kind: TokenKind,
+    pub position: Position,
+}
+
+impl TokenKind {
+    /// Returns the type qualifier that the token is, other than `const`.
+    pub fn qualifier(&self) -> Option<&'static str> {
+        match self {
+            TokenKind::Identifier(name) => ["volatile", "restrict", "_Atomic"]
+                .into_iter()
+                .find(|qualifier| name == qualifier),
+            _ => None,
+        }
+    }
+}
+
+impl Token {
+    /// Returns a description of the token for error messages.
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            TokenKind::Identifier(name) => format!("`{name}`"),
+            TokenKind::Integer(text) | TokenKind::Floating(text) => format!("`{text}`"),
+            TokenKind::Char(text) => format!("`'{text}'`"),
+            TokenKind::String(text) => format!("`\"{text}\"`"),
+            TokenKind::Punctuator(punctuator) => format!("`{punctuator}`"),
+            TokenKind::Placeholder(name) => format!("`${name}`"),
+            TokenKind::Comment(_) => "comment".to_string(),
+            TokenKind::Directive(_) => "preprocessor directive".to_string(),
+            TokenKind::Eof => "end of input".to_string(),
+        }
+    }
+}
+
+pub(super) fn tokenize(source: &str) -> Result<Vec<Token>, Error> {
+    let mut lexer = Lexer {
+        chars: source.chars().collect(),
+        index: 0,
+        line: 1,
+        column: 1,
+        is_line_start: true,
+    };
+
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.token()?;
+        let is_eof = token.kind == TokenKind::Eof;
+        tokens.push(token);
+
+        if is_eof {
+            return Ok(tokens);
+        }
+    }
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+    /// Whether only whitespace precedes the current character on its line.
+    is_line_start: bool,
+}
+
+impl Lexer {
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.index += 1;
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+            self.is_line_start = true;
+        } else {
+            self.column += 1;
+            if !c.is_whitespace() {
+                self.is_line_start = false;
+            }
+        }
+
+        Some(c)
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(offset, c)| self.peek(offset) == Some(c))
+    }
+
+    fn token(&mut self) -> Result<Token, Error> {
+        while self.peek(0).is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+
+        let position = self.position();
+        let is_line_start = self.is_line_start;
+        let kind = match self.peek(0) {
+            None => TokenKind::Eof,
+            Some('#') if is_line_start => {
+                self.bump();
+                TokenKind::Directive(self.directive())
+            }
+            Some('/') if self.peek(1) == Some('*') => self.block_comment(position)?,
+            Some('/') if self.peek(1) == Some('/') => {
+                self.bump();
+                self.bump();
+
+                let text = self.rest_of_line();
+                TokenKind::Comment(RawComment {
+                    style: CommentStyle::Line,
+                    text,
+                    end_line: position.line,
+                })
+            }
+            Some('\'') => TokenKind::Char(self.quoted('\'', position)?),
+            Some('"') => TokenKind::String(self.quoted('"', position)?),
+            Some(c) if c.is_ascii_digit() => self.number(),
+            Some('.') if self.peek(1).is_some_and(|c| c.is_ascii_digit()) => self.number(),
+            Some('$') if self.peek(1).is_some_and(is_identifier_char) => {
+                self.bump();
+                TokenKind::Placeholder(self.identifier())
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                let name = self.identifier();
+
+                if matches!(self.peek(0), Some('\'' | '"'))
+                    && matches!(name.as_str(), "L" | "u" | "U" | "u8")
+                {
+                    return Err(Error {
+                        position,
+                        kind: ErrorKind::Unsupported(
+                            "prefixed character and string literals".to_string(),
+                        ),
+                    });
+                }
+
+                TokenKind::Identifier(name)
+            }
+            Some(c) => {
+                let Some(punctuator) = PUNCTUATORS
+                    .iter()
+                    .find(|punctuator| self.starts_with(punctuator))
+                else {
+                    return Err(Error {
+                        position,
+                        kind: ErrorKind::UnexpectedCharacter(c),
+                    });
+                };
+
+                for _ in 0..punctuator.len() {
+                    self.bump();
+                }
+
+                TokenKind::Punctuator(punctuator)
+            }
+        };
+
+        Ok(Token { kind, position })
+    }
+
+    fn identifier(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.peek(0).filter(|c| is_identifier_char(*c)) {
+            name.push(c);
+            self.bump();
+        }
+
+        name
+    }
+
+    fn rest_of_line(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.peek(0).filter(|c| *c != '\n') {
+            text.push(c);
+            self.bump();
+        }
+
+        text
+    }
+
+    /// Reads a directive up to the end of its logical line.
+    fn directive(&mut self) -> String {
+        let mut text = String::new();
+        loop {
+            text.push_str(&self.rest_of_line());
+
+            // A backslash at the end of a line continues the directive on the next line
+            if text.ends_with('\\') && self.peek(0) == Some('\n') {
+                self.bump();
+                text.push('\n');
+            } else {
+                return text;
+            }
+        }
+    }
+
+    fn block_comment(&mut self, position: Position) -> Result<TokenKind, Error> {
+        self.bump();
+        self.bump();
+
+        // `/**/` is an empty block comment, rather than an unterminated documentation comment
+        let style = if self.peek(0) == Some('*') && self.peek(1) != Some('/') {
+            self.bump();
+            CommentStyle::Doc
+        } else {
+            CommentStyle::Block
+        };
+
+        let mut text = String::new();
+        loop {
+            if self.starts_with("*/") {
+                self.bump();
+                self.bump();
+
+                return Ok(TokenKind::Comment(RawComment {
+                    style,
+                    text,
+                    end_line: self.line,
+                }));
+            }
+
+            let Some(c) = self.bump() else {
+                return Err(Error {
+                    position,
+                    kind: ErrorKind::Unterminated("comment"),
+                });
+            };
+            text.push(c);
+        }
+    }
+
+    /// Reads a character constant or string literal, keeping escape sequences.
+    fn quoted(&mut self, quote: char, position: Position) -> Result<String, Error> {
+        self.bump();
+
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some(c) if c == quote => return Ok(text),
+                Some('\\') => {
+                    text.push('\\');
+                    if let Some(c) = self.bump() {
+                        text.push(c);
+                    }
+                }
+                Some(c) if c != '\n' => text.push(c),
+                _ => {
+                    return Err(Error {
+                        position,
+                        kind: ErrorKind::Unterminated(if quote == '"' {
+                            "string literal"
+                        } else {
+                            "character constant"
+                        }),
+                    })
+                }
+            }
+        }
+    }
+
+    fn number(&mut self) -> TokenKind {
+        let is_hex = self.starts_with("0x") || self.starts_with("0X");
+
+        let mut text = String::new();
+        while let Some(c) = self.peek(0) {
+            let is_exponent_sign = matches!(c, '+' | '-')
+                && text.ends_with(|previous: char| {
+                    if is_hex {
+                        matches!(previous, 'p' | 'P')
+                    } else {
+                        matches!(previous, 'e' | 'E')
+                    }
+                });
+
+            if c.is_ascii_alphanumeric() || c == '_' || c == '.' || is_exponent_sign {
+                text.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        let is_floating = text.contains('.')
+            || if is_hex {
+                text.contains(['p', 'P'])
+            } else {
+                text.contains(['e', 'E'])
+            };
+
+        if is_floating {
+            TokenKind::Floating(text)
+        } else {
+            TokenKind::Integer(text)
+        }
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Result<Vec<TokenKind>, Error> {
+        Ok(tokenize(source)?
+            .into_iter()
+            .map(|token| token.kind)
+            .collect())
+    }
+
+    #[test]
+    fn tokens() -> anyhow::Result<()> {
+        assert_eq!(
+            kinds("x <<= 0x1Fu + 1.5e-3f;")?,
+            [
+                TokenKind::Identifier("x".to_string()),
+                TokenKind::Punctuator("<<="),
+                TokenKind::Integer("0x1Fu".to_string()),
+                TokenKind::Punctuator("+"),
+                TokenKind::Floating("1.5e-3f".to_string()),
+                TokenKind::Punctuator(";"),
+                TokenKind::Eof,
+            ]
+        );
+
+        assert_eq!(
+            kinds(r#"'\'' "a\"b""#)?,
+            [
+                TokenKind::Char(r"\'".to_string()),
+                TokenKind::String(r#"a\"b"#.to_string()),
+                TokenKind::Eof,
+            ]
+        );
+
+        assert_eq!(
+            kinds("$value_1")?,
+            [
+                TokenKind::Placeholder("value_1".to_string()),
+                TokenKind::Eof
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn comments_and_directives() -> anyhow::Result<()> {
+        let tokens = tokenize("/** doc */ x // line\n  #define A \\\n  1\ny")?;
+        assert_eq!(
+            tokens.iter().map(|token| &token.kind).collect::<Vec<_>>(),
+            [
+                &TokenKind::Comment(RawComment {
+                    style: CommentStyle::Doc,
+                    text: " doc ".to_string(),
+                    end_line: 1,
+                }),
+                &TokenKind::Identifier("x".to_string()),
+                &TokenKind::Comment(RawComment {
+                    style: CommentStyle::Line,
+                    text: " line".to_string(),
+                    end_line: 1,
+                }),
+                &TokenKind::Directive("define A \\\n  1".to_string()),
+                &TokenKind::Identifier("y".to_string()),
+                &TokenKind::Eof,
+            ]
+        );
+        assert_eq!(tokens[3].position, Position { line: 2, column: 3 });
+
+        // A `#` that does not start a line is not a directive
+        let error = tokenize("y # z").unwrap_err();
+        assert_eq!(error.to_string(), "1:3: unexpected character `#`");
+
+        Ok(())
+    }
+}
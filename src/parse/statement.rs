@@ -0,0 +1,736 @@
+use crate::{
+    file::FileLevelStatement,
+    statement::{
+        Define, Do, For, ForDeclaration, Goto, If, Include, Label, Pragma, Return, Switch, While,
+    },
+    Block, Comment, DocComment, Identifier, Statement,
+};
+
+use super::{
+    declaration::Declaration,
+    lexer::{CommentStyle, RawComment, TokenKind},
+    template::{self, Conversion},
+    Error, ErrorKind, Fragment, Parser, Position,
+};
+
+/// An item of a file or block.
+enum Item {
+    Comment(Comment),
+    Declaration(Declaration),
+    Directive(Directive),
+    /// A file-level statement that replaces a placeholder, which only occurs at file level.
+    FileLevelStatement(FileLevelStatement),
+    /// A statement other than a declaration, which only occurs in blocks.
+    Statement(Statement),
+}
+
+enum Directive {
+    Define(Define),
+    Include(Include),
+    Pragma(Pragma),
+}
+
+impl Item {
+    fn into_file_level_statement(self) -> FileLevelStatement {
+        match self {
+            Item::Comment(comment) => comment.into(),
+            Item::Declaration(declaration) => match declaration {
+                Declaration::FunctionDeclaration(declaration) => declaration.into(),
+                Declaration::FunctionDefinition(definition) => definition.into(),
+                Declaration::StaticAssert(assertion) => assertion.into(),
+                Declaration::TypeDefinition(definition) => {
+                    FileLevelStatement::TypeDefinition(definition)
+                }
+                Declaration::Typedef(typedef) => typedef.into(),
+                Declaration::VariableDeclaration(declaration) => declaration.into(),
+                Declaration::VariableDeclarationList(declaration) => declaration.into(),
+            },
+            Item::Directive(Directive::Define(define)) => define.into(),
+            Item::Directive(Directive::Include(include)) => include.into(),
+            Item::Directive(Directive::Pragma(pragma)) => pragma.into(),
+            Item::FileLevelStatement(statement) => statement,
+            Item::Statement(_) => unreachable!("files only contain declarations"),
+        }
+    }
+
+    fn into_statement(self) -> Statement {
+        match self {
+            Item::Comment(comment) => comment.into(),
+            Item::Declaration(declaration) => match declaration {
+                Declaration::FunctionDeclaration(declaration) => declaration.into(),
+                Declaration::FunctionDefinition(_) => {
+                    unreachable!("functions are not defined in blocks")
+                }
+                Declaration::StaticAssert(assertion) => assertion.into(),
+                Declaration::TypeDefinition(definition) => Statement::TypeDefinition(definition),
+                Declaration::Typedef(typedef) => typedef.into(),
+                Declaration::VariableDeclaration(declaration) => declaration.into(),
+                Declaration::VariableDeclarationList(declaration) => declaration.into(),
+            },
+            Item::Directive(Directive::Define(define)) => define.into(),
+            Item::Directive(Directive::Include(include)) => include.into(),
+            Item::Directive(Directive::Pragma(pragma)) => pragma.into(),
+            Item::FileLevelStatement(_) => {
+                unreachable!("blocks do not contain file-level statements")
+            }
+            Item::Statement(statement) => statement,
+        }
+    }
+}
+
+impl Parser {
+    pub(super) fn translation_unit(&mut self) -> Result<Vec<FileLevelStatement>, Error> {
+        self.at("statements", |parser| {
+            let mut statements = Vec::new();
+            while let Some(item) = parser.at_index(statements.len(), |parser| parser.item(true))? {
+                statements.push(item.into_file_level_statement());
+            }
+
+            Ok(statements)
+        })
+    }
+
+    /// Parses a block, including its braces.
+    pub(super) fn block(&mut self) -> Result<Block, Error> {
+        self.expect("{")?;
+
+        self.push_scope();
+        let statements = self.at("statements", Self::statements);
+        self.pop_scope();
+
+        let statements = statements?;
+        self.expect("}")?;
+
+        Ok(Block { statements })
+    }
+
+    /// Parses statements up to a closing brace, `case` or `default`, at the indices of the current path.
+    fn statements(&mut self) -> Result<Vec<Statement>, Error> {
+        let mut statements = Vec::new();
+        while let Some(item) = self.at_index(statements.len(), |parser| parser.item(false))? {
+            statements.push(item.into_statement());
+        }
+
+        Ok(statements)
+    }
+
+    /// Parses the next item of a file or block, or returns `None` at its end. Line markers are skipped.
+    fn item(&mut self, is_file_scope: bool) -> Result<Option<Item>, Error> {
+        loop {
+            if let Some((comment, position)) = self.comment() {
+                self.record(position);
+
+                // A documentation comment directly followed by a declaration documents it
+                let is_doc = comment.style == CommentStyle::Doc
+                    && !matches!(self.tokens[self.index].kind, TokenKind::Comment(_))
+                    && self.is_declaration_start(0);
+                if is_doc {
+                    self.record(self.peek().position);
+                    let declaration =
+                        self.declaration(Some(doc_comment(&comment.text)), is_file_scope)?;
+
+                    return Ok(Some(Item::Declaration(declaration)));
+                }
+
+                return Ok(Some(Item::Comment(self.free_comment(comment))));
+            }
+
+            let token = self.peek().clone();
+            match &token.kind {
+                TokenKind::Eof => return Ok(None),
+                TokenKind::Punctuator("}") if !is_file_scope => return Ok(None),
+                TokenKind::Identifier(name)
+                    if !is_file_scope && matches!(name.as_str(), "case" | "default") =>
+                {
+                    return Ok(None)
+                }
+                TokenKind::Directive(text) => {
+                    self.next();
+                    if let Some(directive) = self.directive(text, token.position)? {
+                        self.record(token.position);
+
+                        return Ok(Some(Item::Directive(directive)));
+                    }
+                }
+                _ if is_file_scope => {
+                    if let Some(name) = self.statement_placeholder(true) {
+                        self.record(token.position);
+
+                        return self.placeholder_item(name, true).map(Some);
+                    }
+
+                    if !self.is_declaration_start(0) {
+                        return Err(self.expected("declaration"));
+                    }
+                    self.record(token.position);
+
+                    return Ok(Some(Item::Declaration(self.declaration(None, true)?)));
+                }
+                _ => return Ok(Some(Item::Statement(self.statement()?))),
+            }
+        }
+    }
+
+    /// Parses a statement, including comments and directives.
+    pub(super) fn single_statement(&mut self) -> Result<Statement, Error> {
+        match self.item(false)? {
+            Some(item) => Ok(item.into_statement()),
+            None => Err(self.expected("statement")),
+        }
+    }
+
+    /// Parses a file-level statement, including comments and directives.
+    pub(super) fn single_file_level_statement(&mut self) -> Result<FileLevelStatement, Error> {
+        match self.item(true)? {
+            Some(item) => Ok(item.into_file_level_statement()),
+            None => Err(self.expected("declaration")),
+        }
+    }
+
+    /// Returns the name of the placeholder that follows, if it replaces a statement or file-level statement.
+    fn statement_placeholder(&self, is_file_scope: bool) -> Option<String> {
+        let TokenKind::Placeholder(name) = &self.peek().kind else {
+            return None;
+        };
+
+        let is_statement = match self.placeholders.get(name) {
+            Some(Fragment::Expression(_)) => false,
+            Some(Fragment::FileLevelStatement(_) | Fragment::Statement(_)) => true,
+            // Unlike a statement, an expression is continued by an operator or terminated by a semicolon
+            None => {
+                self.is_template
+                    && (is_file_scope
+                        || !matches!(
+                            self.peek_nth(1).kind,
+                            TokenKind::Punctuator(punctuator) if punctuator != "{" && punctuator != "}"
+                        ))
+            }
+        };
+
+        is_statement.then(|| name.clone())
+    }
+
+    /// Parses a placeholder that replaces a statement or file-level statement.
+    fn placeholder_item(&mut self, name: String, is_file_scope: bool) -> Result<Item, Error> {
+        let position = self.next().position;
+
+        match (self.placeholders.get(&name), is_file_scope) {
+            (Some(Fragment::FileLevelStatement(statement)), true) => {
+                Ok(Item::FileLevelStatement(statement.clone()))
+            }
+            (Some(Fragment::Statement(statement)), false) => Ok(Item::Statement(statement.clone())),
+            (None, _) => {
+                let conversion = if is_file_scope {
+                    Conversion::FileLevelStatement
+                } else {
+                    Conversion::Statement
+                };
+                self.conversions.insert(name.clone(), conversion);
+
+                Ok(Item::Comment(Comment::line(
+                    template::marker(&name).to_string(),
+                )))
+            }
+            _ => Err(Error {
+                position,
+                kind: ErrorKind::MisplacedPlaceholder(name),
+            }),
+        }
+    }
+
+    /// Parses a statement, including declarations.
+    pub(super) fn statement(&mut self) -> Result<Statement, Error> {
+        let token = self.peek().clone();
+        self.record(token.position);
+
+        if let Some(name) = self.statement_placeholder(false) {
+            return self.placeholder_item(name, false).map(Item::into_statement);
+        }
+
+        // An expression that replaces a placeholder as a whole statement does not need parentheses
+        if let TokenKind::Placeholder(name) = &token.kind {
+            if let (Some(Fragment::Expression(expression)), true) =
+                (self.placeholders.get(name), self.is_punctuator_at(1, ";"))
+            {
+                let expression = expression.clone();
+                self.next();
+                self.next();
+
+                return Ok(Statement::Expression(expression));
+            }
+        }
+
+        if self.peek_identifier(0).is_some() && self.is_punctuator_at(1, ":") {
+            return self.label();
+        }
+
+        if let TokenKind::Identifier(keyword) = &token.kind {
+            match keyword.as_str() {
+                "if" => return self.if_statement(),
+                "switch" => return self.switch(),
+                "while" => return self.while_statement(),
+                "do" => return self.do_statement(),
+                "for" => return self.for_statement(token.position),
+                "goto" => {
+                    self.next();
+                    let (label, _) = self.identifier()?;
+                    self.expect(";")?;
+
+                    return Ok(Goto { label }.into());
+                }
+                "break" | "continue" => {
+                    self.next();
+                    self.expect(";")?;
+
+                    return Ok(if keyword == "break" {
+                        Statement::Break
+                    } else {
+                        Statement::Continue
+                    });
+                }
+                "return" => {
+                    self.next();
+                    let expression = if self.is_punctuator(";") {
+                        None
+                    } else {
+                        Some(self.expression()?)
+                    };
+                    self.expect(";")?;
+
+                    return Ok(Return { expression }.into());
+                }
+                "case" | "default" => return Err(self.expected("statement")),
+                _ => {}
+            }
+        }
+
+        if self.is_punctuator("{") {
+            return self.block().map(Statement::Block);
+        }
+
+        if self.eat(";") {
+            return Ok(Statement::Null);
+        }
+
+        if matches!(token.kind, TokenKind::Directive(_)) {
+            return Err(self.expected("statement"));
+        }
+
+        if self.is_declaration_start(0) {
+            return self
+                .declaration(None, false)
+                .map(|declaration| Item::Declaration(declaration).into_statement());
+        }
+
+        let expression = self.expression()?;
+        self.expect(";")?;
+
+        Ok(Statement::Expression(expression))
+    }
+
+    fn label(&mut self) -> Result<Statement, Error> {
+        let (identifier, _) = self.identifier()?;
+        self.expect(":")?;
+
+        // A label at the end of a block is followed by no statement
+        let statement = if self.is_punctuator("}") {
+            None
+        } else {
+            Some(self.at("statement", Self::statement)?)
+        };
+
+        Ok(Label {
+            identifier,
+            statement,
+        }
+        .into())
+    }
+
+    fn parenthesized_expression(&mut self) -> Result<crate::Expression, Error> {
+        self.expect("(")?;
+        let expression = self.expression()?;
+        self.expect(")")?;
+
+        Ok(expression)
+    }
+
+    fn if_statement(&mut self) -> Result<Statement, Error> {
+        self.next();
+        let condition = self.parenthesized_expression()?;
+        let then_statement = self.at("then_statement", Self::statement)?;
+        let else_statement = if self.eat_keyword("else") {
+            Some(self.at("else_statement", Self::statement)?)
+        } else {
+            None
+        };
+
+        Ok(If {
+            condition,
+            then_statement,
+            else_statement,
+        }
+        .into())
+    }
+
+    fn switch(&mut self) -> Result<Statement, Error> {
+        let position = self.next().position;
+        let condition = self.parenthesized_expression()?;
+        if !self.eat("{") {
+            return Err(self.unsupported(position, "`switch` statements without a block"));
+        }
+
+        self.push_scope();
+        let switch = self.switch_body(condition);
+        self.pop_scope();
+
+        Ok(Statement::Switch(Box::new(switch?)))
+    }
+
+    /// Parses the `case` and `default` groups of a switch, up to and including the closing brace.
+    fn switch_body(&mut self, condition: crate::Expression) -> Result<Switch, Error> {
+        let mut switch = Switch {
+            condition,
+            cases: Vec::new(),
+            default: None,
+        };
+
+        loop {
+            // Comments before the first label have no representation
+            while self.comment().is_some() {}
+
+            let position = self.peek().position;
+            if self.eat("}") {
+                return Ok(switch);
+            }
+
+            if self.eat_keyword("case") {
+                if switch.default.is_some() {
+                    return Err(self.unsupported(position, "`case` labels after `default`"));
+                }
+
+                let value = self.conditional_expression()?;
+                self.expect(":")?;
+
+                let index = switch.cases.len();
+                let statements = self.at("cases", |parser| {
+                    parser.at_index(index, |parser| parser.at("statements", Self::statements))
+                })?;
+                switch.cases.push((value, statements));
+            } else if self.eat_keyword("default") {
+                if switch.default.is_some() {
+                    return Err(self.unsupported(position, "multiple `default` labels"));
+                }
+                self.expect(":")?;
+
+                switch.default = Some(self.at("default", Self::statements)?);
+            } else {
+                return Err(self.expected("`case` or `default`"));
+            }
+        }
+    }
+
+    fn while_statement(&mut self) -> Result<Statement, Error> {
+        self.next();
+        let condition = self.parenthesized_expression()?;
+        let body = self.at("body", Self::statement)?;
+
+        Ok(Statement::While(Box::new(While { condition, body })))
+    }
+
+    fn do_statement(&mut self) -> Result<Statement, Error> {
+        self.next();
+        let body = self.at("body", Self::statement)?;
+        if !self.eat_keyword("while") {
+            return Err(self.expected("`while`"));
+        }
+        let condition = self.parenthesized_expression()?;
+        self.expect(";")?;
+
+        Ok(Do { body, condition }.into())
+    }
+
+    fn for_statement(&mut self, position: Position) -> Result<Statement, Error> {
+        self.next();
+        self.expect("(")?;
+
+        // A declaration in the first clause is scoped to the loop
+        self.push_scope();
+        let statement = self.for_clauses(position);
+        self.pop_scope();
+
+        statement
+    }
+
+    fn for_clauses(&mut self, position: Position) -> Result<Statement, Error> {
+        let init = if self.eat(";") {
+            None
+        } else if self.is_declaration_start(0) {
+            match self.declaration(None, false)? {
+                Declaration::VariableDeclaration(declaration) => Some(declaration.into()),
+                Declaration::VariableDeclarationList(declaration) => Some(declaration.into()),
+                _ => {
+                    return Err(self.unsupported(
+                        position,
+                        "declarations other than variables in `for` statements",
+                    ))
+                }
+            }
+        } else {
+            let expression = self.expression()?;
+            self.expect(";")?;
+
+            Some(ForDeclaration::Expression(expression))
+        };
+
+        if self.is_punctuator(";") {
+            return Err(self.unsupported(position, "`for` statements without condition"));
+        }
+        let condition = self.expression()?;
+        self.expect(";")?;
+
+        let step = if self.is_punctuator(")") {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.expect(")")?;
+
+        let body = self.at("body", Self::statement)?;
+
+        Ok(For {
+            init,
+            condition,
+            step,
+            body,
+        }
+        .into())
+    }
+
+    /// Consumes the comments that directly follow and returns the last documentation comment among them.
+    pub(super) fn leading_doc(&mut self) -> Option<DocComment> {
+        let mut doc = None;
+        while let Some((comment, _)) = self.comment() {
+            if comment.style == CommentStyle::Doc {
+                doc = Some(doc_comment(&comment.text));
+            }
+        }
+
+        doc
+    }
+
+    /// Converts a comment that does not document a declaration, merging it with line comments on the following lines.
+    fn free_comment(&mut self, comment: RawComment) -> Comment {
+        match comment.style {
+            CommentStyle::Block | CommentStyle::Doc => {
+                Comment::block(block_comment_text(&comment.text))
+            }
+            CommentStyle::Line => {
+                let mut lines = vec![comment.text];
+                let mut end_line = comment.end_line;
+
+                while let TokenKind::Comment(
+                    next @ RawComment {
+                        style: CommentStyle::Line,
+                        ..
+                    },
+                ) = &self.tokens[self.index].kind
+                {
+                    if next.end_line != end_line + 1 {
+                        break;
+                    }
+
+                    end_line = next.end_line;
+                    lines.push(next.text.clone());
+                    self.index += 1;
+                }
+
+                // Blank comment lines separate paragraphs
+                let paragraphs = lines
+                    .split(|line| line.trim().is_empty())
+                    .filter(|paragraph| !paragraph.is_empty())
+                    .map(|paragraph| {
+                        paragraph
+                            .iter()
+                            .map(|line| line.trim())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect::<Vec<_>>();
+
+                Comment::line(paragraphs.join("\n"))
+            }
+        }
+    }
+
+    /// Parses a directive, or returns `None` for line markers.
+    fn directive(&self, text: &str, position: Position) -> Result<Option<Directive>, Error> {
+        let text = text.trim_start();
+        let (name, rest) = text.split_at(identifier_length(text));
+
+        let expected = |expected: &str| Error {
+            position,
+            kind: ErrorKind::Expected {
+                expected: expected.to_string(),
+                found: format!("`#{}`", text.trim_end()),
+            },
+        };
+
+        match name {
+            // Line markers, e.g. `# 1 "file.c"`, and null directives
+            "" | "line" => Ok(None),
+            "include" => {
+                let path = rest.trim();
+                let include = if let Some(path) = path
+                    .strip_prefix('<')
+                    .and_then(|path| path.strip_suffix('>'))
+                {
+                    Include::with_angle_brackets(path)
+                } else if let Some(path) = path
+                    .strip_prefix('"')
+                    .and_then(|path| path.strip_suffix('"'))
+                {
+                    Include::with_quotes(path)
+                } else {
+                    return Err(expected("header name"));
+                };
+
+                Ok(Some(Directive::Include(include)))
+            }
+            "define" => {
+                let rest = rest.trim_start_matches([' ', '\t']);
+                let (name, rest) = rest.split_at(identifier_length(rest));
+                let name = Identifier::new(name).map_err(|_| expected("macro name"))?;
+
+                // The parameter list has to follow the name without whitespace
+                let (parameters, rest) = if let Some(rest) = rest.strip_prefix('(') {
+                    let (list, rest) = rest.split_once(')').ok_or_else(|| expected("`)`"))?;
+
+                    let parameters = if list.trim().is_empty() {
+                        Vec::new()
+                    } else {
+                        list.split(',')
+                            .map(|parameter| match parameter.trim() {
+                                "..." => Err(self.unsupported(position, "variadic macros")),
+                                parameter => Identifier::new(parameter)
+                                    .map_err(|_| expected("macro parameter")),
+                            })
+                            .collect::<Result<Vec<_>, _>>()?
+                    };
+
+                    (Some(parameters), rest)
+                } else {
+                    (None, rest)
+                };
+
+                // Escaped line breaks become line breaks of the body
+                let body = rest
+                    .trim_start_matches([' ', '\t'])
+                    .trim_end()
+                    .split("\\\n")
+                    .map(|line| line.strip_suffix(' ').unwrap_or(line))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                Ok(Some(Directive::Define(Define {
+                    name,
+                    parameters,
+                    body,
+                })))
+            }
+            "pragma" => {
+                let pragma = rest
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect::<String>();
+
+                if pragma == "pack(pop)" {
+                    return Ok(Some(Directive::Pragma(Pragma::PopPack)));
+                }
+
+                let Some(alignment) = pragma
+                    .strip_prefix("pack(push,")
+                    .and_then(|pragma| pragma.strip_suffix(')'))
+                else {
+                    return Err(self.unsupported(
+                        position,
+                        "pragmas other than `pack(push, N)` and `pack(pop)`",
+                    ));
+                };
+
+                let alignment = alignment.parse().map_err(|_| Error {
+                    position,
+                    kind: ErrorKind::InvalidConstant(alignment.to_string()),
+                })?;
+
+                Ok(Some(Directive::Pragma(Pragma::PushPack(alignment))))
+            }
+            _ => Err(self.unsupported(position, format!("`#{name}` directives"))),
+        }
+    }
+}
+
+fn identifier_length(text: &str) -> usize {
+    text.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+/// Returns the lines of a block comment without their leading `*`.
+fn comment_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(|line| {
+            let line = line.trim();
+            line.strip_prefix('*').unwrap_or(line).trim()
+        })
+        .filter(|line| !line.is_empty())
+}
+
+fn block_comment_text(text: &str) -> String {
+    comment_lines(text).collect::<Vec<_>>().join(" ")
+}
+
+/// Converts the text of a documentation comment, including its `@param` and `@return` commands.
+fn doc_comment(text: &str) -> DocComment {
+    let mut doc = DocComment::new("");
+
+    // Continuation lines belong to the preceding command
+    for line in comment_lines(text) {
+        if let Some(rest) = command(line, "param") {
+            let (name, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if let Ok(name) = Identifier::new(name) {
+                doc.parameters.push((name, description.trim().to_string()));
+                continue;
+            }
+        }
+
+        if let Some(rest) = command(line, "return").or_else(|| command(line, "returns")) {
+            doc.returns = Some(rest.to_string());
+            continue;
+        }
+
+        let text = match (&mut doc.returns, doc.parameters.last_mut()) {
+            (Some(returns), _) => returns,
+            (None, Some((_, description))) => description,
+            (None, None) => &mut doc.text,
+        };
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(line);
+    }
+
+    doc
+}
+
+/// Returns the rest of the line if it starts with the Doxygen command.
+fn command<'a>(line: &'a str, command: &str) -> Option<&'a str> {
+    let rest = line
+        .strip_prefix('@')
+        .or_else(|| line.strip_prefix('\\'))?
+        .strip_prefix(command)?;
+
+    if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        Some(rest.trim_start())
+    } else {
+        None
+    }
+}
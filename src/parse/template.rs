@@ -0,0 +1,1000 @@
+//! Expansion of templates into the Rust code that constructs their AST, for the quasi-quoting macros of
+//! `c_codegen_macros`.
+//!
+//! A template is parsed once at compile time, with each placeholder accepted as whatever fragment its position
+//! requires. [`Template::expand`] then writes the constructors of the parsed AST, which convert the value of each
+//! placeholder into what its position requires, and turn the list elements that contain a repetition into a loop.
+
+use core::mem;
+use std::collections::{BTreeSet, HashMap};
+
+use super::{Error, Fragment, Parser};
+use crate::{
+    comment::{Comment, DocComment, Style as CommentStyle},
+    function::{
+        Declaration as FunctionDeclaration, Definition as FunctionDefinition, FunctionCall,
+        FunctionParameter,
+    },
+    non_empty_vec::NonEmptyVec,
+    operator::{
+        ArraySubscript, Assignment, BinaryOperator, BinaryOperatorKind, Cast, CommaOperator,
+        CompoundAssignment, CompoundAssignmentOperator, Conditional, OffsetOf, PostfixOperator,
+        PostfixOperatorKind, PrefixOperator, PrefixOperatorKind, SizeOf,
+    },
+    r#type::{
+        enumeration::{Enum, Enumerator},
+        member::{IndirectMemberAccess, Member, MemberAccess},
+        Array, ArraySize, ConcreteType, Definition as TypeDefinition, Function, InitializerList,
+        Integer, IntegerKind, OpaqueType, Pointer, Real, Scalar, StrongInt, Struct,
+        StructAttributes, Union,
+    },
+    statement::{
+        Define, Do, For, ForDeclaration, Goto, If, Include, IncludeStyle, Label, Pragma, Return,
+        StaticAssert, Switch, Typedef, While,
+    },
+    variable::{Declaration as VariableDeclaration, DeclarationList, Declarator},
+    Block, Expression, FileLevelStatement, Identifier, Statement, StorageClass, Value,
+};
+
+/// The syntax of a template.
+#[derive(Clone, Copy, Debug)]
+pub enum Syntax {
+    Expression,
+    FileLevelStatement,
+    Statement,
+}
+
+/// How the value of a placeholder is converted at its position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Conversion {
+    /// An operand, which is parenthesized unless it is a primary or postfix expression, see [`operand`].
+    Operand,
+    /// A whole expression.
+    Expression,
+    /// A whole expression that cannot be a comma operator, e.g. an argument, see [`assignment_expression`].
+    AssignmentExpression,
+    Statement,
+    FileLevelStatement,
+}
+
+/// A template that has been parsed with unknown placeholders.
+#[derive(Clone, Debug)]
+pub struct Template {
+    fragment: Fragment,
+    /// The conversions of the placeholders, by name.
+    conversions: HashMap<String, Conversion>,
+}
+
+/// A placeholder of a template.
+#[derive(Clone, Debug)]
+pub struct Placeholder {
+    /// The interpolation that the placeholder stands for, which is the same for each copy of a repetition.
+    pub id: usize,
+    /// The Rust expression of the value, e.g. a variable.
+    pub value: String,
+    /// The index of the repetition that contains the placeholder.
+    pub repetition: Option<usize>,
+    /// The copy of the repetition that contains the placeholder, see [`Template::extent`].
+    pub copy: usize,
+}
+
+/// A repetition of a template.
+#[derive(Clone, Debug)]
+pub struct Repetition {
+    /// The variables of the iterables, which are iterated in lockstep and whose items shadow them.
+    pub variables: Vec<String>,
+    /// The elements without placeholders around the elements of the repetition that belong to it, see
+    /// [`Template::extent`].
+    pub extent: Extent,
+}
+
+/// The numbers of elements without placeholders before and after the elements of a repetition that belong to it,
+/// e.g. the call in `#(x += #values; f(x);)*`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Extent {
+    pub before: usize,
+    pub after: usize,
+}
+
+/// A repetition that cannot be expanded.
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct RepetitionError {
+    /// The index of the repetition.
+    pub repetition: usize,
+    message: &'static str,
+}
+
+const NOT_LIST_ELEMENTS: &str =
+    "repetitions must expand to whole elements of a list, e.g. arguments or statements";
+
+/// An extent that reaches beyond the list or into other elements, which is only tried by [`Template::extent`].
+const OUTSIDE_LIST: &str = "the elements of a repetition are outside of its list";
+
+/// Parses a template whose placeholders are not known yet.
+pub fn parse(source: &str, syntax: Syntax) -> Result<Template, Error> {
+    let mut parser = Parser::new(source)?;
+    parser.is_template = true;
+
+    let fragment = match syntax {
+        Syntax::Expression => parser.expression()?.into(),
+        Syntax::FileLevelStatement => parser.single_file_level_statement()?.into(),
+        Syntax::Statement => parser.single_statement()?.into(),
+    };
+    parser.expect_end()?;
+
+    Ok(Template {
+        fragment,
+        conversions: parser.conversions,
+    })
+}
+
+impl Template {
+    /// Writes the Rust expression that constructs the template, with the placeholders by name.
+    pub fn expand(
+        &self,
+        placeholders: &HashMap<String, Placeholder>,
+        repetitions: &[Repetition],
+    ) -> Result<String, RepetitionError> {
+        Writer::new(self, placeholders, repetitions, Mode::Construct).root(&self.fragment)
+    }
+
+    /// Finds the extent of a repetition, which makes the template with the repetition unrolled twice equal to the
+    /// template that has been parsed with two copies of the repetition.
+    ///
+    /// This also checks that the repetition expands to whole list elements: a repetition like `f(#(#a +)* 1)`
+    /// parses as a single argument, but a second copy of it does not add an argument.
+    pub fn extent(
+        &self,
+        placeholders: &HashMap<String, Placeholder>,
+        repetition: usize,
+        repeated: &Template,
+        repeated_placeholders: &HashMap<String, Placeholder>,
+    ) -> Result<Extent, RepetitionError> {
+        let not_list_elements = RepetitionError {
+            repetition,
+            message: NOT_LIST_ELEMENTS,
+        };
+        let expected = Writer::new(
+            repeated,
+            repeated_placeholders,
+            &[],
+            Mode::Unroll { repetition: None },
+        )
+        .root(&repeated.fragment)
+        .map_err(|_| not_list_elements.clone())?;
+
+        // The smallest extent is tried first, until every extent of a size reaches outside of the list
+        let mut first_error = None;
+        for size in 0.. {
+            let mut is_inside = false;
+
+            for before in 0..=size {
+                let extent = Extent {
+                    before,
+                    after: size - before,
+                };
+                let repetitions: Vec<_> = (0..=repetition)
+                    .map(|index| Repetition {
+                        variables: Vec::new(),
+                        extent: if index == repetition {
+                            extent
+                        } else {
+                            Extent::default()
+                        },
+                    })
+                    .collect();
+
+                match Writer::new(
+                    self,
+                    placeholders,
+                    &repetitions,
+                    Mode::Unroll {
+                        repetition: Some(repetition),
+                    },
+                )
+                .root(&self.fragment)
+                {
+                    Ok(unrolled) if unrolled == expected => return Ok(extent),
+                    Ok(_) => is_inside = true,
+                    Err(error) if error.message == OUTSIDE_LIST => {}
+                    Err(error) => {
+                        is_inside = true;
+                        first_error.get_or_insert(error);
+                    }
+                }
+            }
+
+            if !is_inside {
+                break;
+            }
+        }
+
+        Err(first_error.unwrap_or(not_list_elements))
+    }
+}
+
+/// Parenthesizes an expression that replaces a placeholder within operators, unless it is a primary or postfix
+/// expression, since the printer does not add parentheses.
+pub fn operand<E: Into<Expression>>(expression: E) -> Expression {
+    match expression.into() {
+        expression @ (Expression::ArraySubscript(_)
+        | Expression::FunctionCall(_)
+        | Expression::IndirectMemberAccess(_)
+        | Expression::MemberAccess(_)
+        | Expression::Parentheses(_)
+        | Expression::Value(_)
+        | Expression::Variable(_)) => expression,
+        expression => Expression::Parentheses(Box::new(expression)),
+    }
+}
+
+/// Parenthesizes a comma operator that replaces a placeholder where an assignment expression is expected, e.g. an
+/// argument.
+pub fn assignment_expression<E: Into<Expression>>(expression: E) -> Expression {
+    match expression.into() {
+        expression @ Expression::CommaOperator(_) => Expression::Parentheses(Box::new(expression)),
+        expression => expression,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    /// Writes Rust code.
+    Construct,
+    /// Writes each list with the elements of the repetition twice, and each placeholder as its interpolation and
+    /// copy, which is only compared.
+    Unroll { repetition: Option<usize> },
+}
+
+struct Writer<'a> {
+    conversions: &'a HashMap<String, Conversion>,
+    placeholders: &'a HashMap<String, Placeholder>,
+    repetitions: &'a [Repetition],
+    mode: Mode,
+    /// The copy of the repetition that is being unrolled.
+    iteration: usize,
+    /// The repetitions whose placeholders have been written, but that no list has expanded yet.
+    pending: BTreeSet<usize>,
+    /// The number of placeholders that have been written.
+    written: usize,
+}
+
+/// Adjacent elements of a list, which contain the same repetition if any.
+struct Run<'t, T> {
+    repetition: Option<usize>,
+    elements: Vec<(&'t T, String)>,
+}
+
+impl<'a> Writer<'a> {
+    fn new(
+        template: &'a Template,
+        placeholders: &'a HashMap<String, Placeholder>,
+        repetitions: &'a [Repetition],
+        mode: Mode,
+    ) -> Self {
+        Self {
+            conversions: &template.conversions,
+            placeholders,
+            repetitions,
+            mode,
+            iteration: 0,
+            pending: BTreeSet::new(),
+            written: 0,
+        }
+    }
+
+    fn root(mut self, fragment: &Fragment) -> Result<String, RepetitionError> {
+        let code = match fragment {
+            Fragment::Expression(expression) => expression.to_rust(&mut self)?,
+            Fragment::FileLevelStatement(statement) => statement.to_rust(&mut self)?,
+            Fragment::Statement(statement) => statement.to_rust(&mut self)?,
+        };
+
+        match self.pending.first() {
+            Some(&repetition) => Err(RepetitionError {
+                repetition,
+                message: NOT_LIST_ELEMENTS,
+            }),
+            None => Ok(code),
+        }
+    }
+
+    /// Writes the placeholder that a marker stands for, see [`marker`].
+    fn placeholder(&mut self, marker: &str) -> Option<String> {
+        let name = marker.strip_prefix('$')?;
+        let conversion = *self.conversions.get(name)?;
+        let placeholder = &self.placeholders[name];
+
+        self.written += 1;
+        if let Some(repetition) = placeholder.repetition {
+            self.pending.insert(repetition);
+        }
+
+        Some(match self.mode {
+            Mode::Construct => {
+                let value = format!("::core::clone::Clone::clone(&{})", placeholder.value);
+
+                match conversion {
+                    Conversion::Operand => {
+                        format!("::c_codegen::__private::operand({value})")
+                    }
+                    Conversion::Expression => {
+                        format!("::core::convert::Into::<::c_codegen::Expression>::into({value})")
+                    }
+                    Conversion::AssignmentExpression => {
+                        format!("::c_codegen::__private::assignment_expression({value})")
+                    }
+                    Conversion::Statement => {
+                        format!("::core::convert::Into::<::c_codegen::Statement>::into({value})")
+                    }
+                    Conversion::FileLevelStatement => format!(
+                        "::core::convert::Into::<::c_codegen::FileLevelStatement>::into({value})"
+                    ),
+                }
+            }
+            Mode::Unroll { repetition } => {
+                let iteration =
+                    if placeholder.repetition.is_some() && placeholder.repetition == repetition {
+                        self.iteration
+                    } else {
+                        0
+                    };
+
+                format!(
+                    "${}@{}:{conversion:?}",
+                    placeholder.id,
+                    iteration + placeholder.copy
+                )
+            }
+        })
+    }
+
+    /// Writes the elements of a list, and groups them into runs by the repetition that they belong to.
+    fn runs<'t, T: ToRust>(
+        &mut self,
+        elements: &'t [T],
+    ) -> Result<Vec<Run<'t, T>>, RepetitionError> {
+        let outer = mem::take(&mut self.pending);
+        let mut codes = Vec::new();
+        // The repetition of each element, if any, and whether it is without placeholders
+        let mut repetitions = Vec::new();
+
+        for element in elements {
+            let written = self.written;
+            codes.push(element.to_rust(self)?);
+
+            let mut pending = mem::take(&mut self.pending).into_iter();
+            let repetition = pending.next();
+            if let Some(repetition) = pending.next() {
+                return Err(RepetitionError {
+                    repetition,
+                    message: "an element of a list cannot contain several repetitions",
+                });
+            }
+            repetitions.push((repetition, self.written == written));
+        }
+        self.pending = outer;
+
+        // Each repetition spans from its first to its last element, and its extent
+        let mut spanned: BTreeSet<usize> = repetitions
+            .iter()
+            .filter_map(|(repetition, _)| *repetition)
+            .collect();
+        while let Some(repetition) = spanned.pop_first() {
+            let first = repetitions
+                .iter()
+                .position(|(other, _)| *other == Some(repetition))
+                .expect("the repetition has an element");
+            let last = repetitions
+                .iter()
+                .rposition(|(other, _)| *other == Some(repetition))
+                .expect("the repetition has an element");
+            let extent = self
+                .repetitions
+                .get(repetition)
+                .map_or_else(Extent::default, |repetition| repetition.extent);
+            let outside_list = RepetitionError {
+                repetition,
+                message: OUTSIDE_LIST,
+            };
+
+            let start = first
+                .checked_sub(extent.before)
+                .ok_or(outside_list.clone())?;
+            let end = last + extent.after;
+            if end >= repetitions.len() {
+                return Err(outside_list);
+            }
+
+            for (index, (other, is_constant)) in
+                repetitions.iter_mut().enumerate().take(end + 1).skip(start)
+            {
+                match other {
+                    Some(other) if *other == repetition => {}
+                    None if *is_constant => *other = Some(repetition),
+                    _ if index < first || index > last => return Err(outside_list),
+                    _ => {
+                        return Err(RepetitionError {
+                            repetition,
+                            message: NOT_LIST_ELEMENTS,
+                        })
+                    }
+                }
+            }
+        }
+
+        let mut runs: Vec<Run<'t, T>> = Vec::new();
+        for ((element, code), (repetition, _)) in elements.iter().zip(codes).zip(repetitions) {
+            match runs.last_mut() {
+                Some(run) if run.repetition == repetition => run.elements.push((element, code)),
+                _ => runs.push(Run {
+                    repetition,
+                    elements: vec![(element, code)],
+                }),
+            }
+        }
+
+        Ok(runs)
+    }
+
+    /// Writes a list, which starts with the first element if it must not be empty.
+    fn list<T: ToRust>(
+        &mut self,
+        runs: Vec<Run<'_, T>>,
+        is_non_empty: bool,
+    ) -> Result<String, RepetitionError> {
+        if let Mode::Unroll { repetition } = self.mode {
+            let outer = mem::take(&mut self.pending);
+            let mut elements = Vec::new();
+
+            for run in runs {
+                if run.repetition.is_some() && run.repetition == repetition {
+                    for iteration in 0..2 {
+                        self.iteration = iteration;
+                        for (element, _) in &run.elements {
+                            elements.push(element.to_rust(self)?);
+                        }
+                    }
+                    self.iteration = 0;
+                } else {
+                    elements.extend(run.elements.into_iter().map(|(_, code)| code));
+                }
+            }
+
+            self.pending = outer;
+            return Ok(format!("[{}]", elements.join(", ")));
+        }
+
+        if !is_non_empty && runs.iter().all(|run| run.repetition.is_none()) {
+            let elements: Vec<_> = runs
+                .into_iter()
+                .flat_map(|run| run.elements)
+                .map(|(_, code)| code)
+                .collect();
+
+            return Ok(format!("::std::vec![{}]", elements.join(", ")));
+        }
+
+        let mut runs = runs.into_iter();
+        let mut code = String::from("{ let mut __c_codegen_values = ");
+        if is_non_empty {
+            let mut first = runs.next().expect("the list is not empty");
+            if let Some(repetition) = first.repetition {
+                return Err(RepetitionError {
+                    repetition,
+                    message: "a repetition cannot start a list that must not be empty",
+                });
+            }
+
+            let (_, element) = first.elements.remove(0);
+            code.push_str(&format!(
+                "::c_codegen::non_empty_vec::NonEmptyVec::new({element}); "
+            ));
+            push_elements(&mut code, first.elements);
+        } else {
+            code.push_str("::std::vec::Vec::new(); ");
+        }
+
+        for run in runs {
+            match run.repetition {
+                Some(repetition) => {
+                    let variables = &self.repetitions[repetition].variables;
+                    let mut iterator =
+                        format!("::core::iter::IntoIterator::into_iter({})", variables[0]);
+                    let mut pattern = variables[0].clone();
+                    for variable in &variables[1..] {
+                        iterator = format!("::core::iter::Iterator::zip({iterator}, {variable})");
+                        pattern = format!("({pattern}, {variable})");
+                    }
+
+                    code.push_str(&format!("for {pattern} in {iterator} {{ "));
+                    push_elements(&mut code, run.elements);
+                    code.push_str("} ");
+                }
+                None => push_elements(&mut code, run.elements),
+            }
+        }
+
+        code.push_str("__c_codegen_values }");
+        Ok(code)
+    }
+}
+
+fn push_elements<T>(code: &mut String, elements: Vec<(&T, String)>) {
+    for (_, element) in elements {
+        code.push_str(&format!("__c_codegen_values.push({element}); "));
+    }
+}
+
+/// Returns the identifier that stands for a placeholder in the parsed template.
+pub(super) fn marker(name: &str) -> Identifier {
+    Identifier::new_unchecked(&format!("${name}"))
+}
+
+/// Writes the Rust expression that constructs a value.
+trait ToRust {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError>;
+}
+
+impl<T: ToRust> ToRust for Vec<T> {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        let runs = writer.runs(self)?;
+        writer.list(runs, false)
+    }
+}
+
+impl<T: ToRust> ToRust for NonEmptyVec<T> {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        let runs = writer.runs(self.as_ref())?;
+        writer.list(runs, true)
+    }
+}
+
+impl<T: ToRust> ToRust for Option<T> {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        Ok(match self {
+            Some(value) => format!("::core::option::Option::Some({})", value.to_rust(writer)?),
+            None => "::core::option::Option::None".to_string(),
+        })
+    }
+}
+
+impl<T: ToRust> ToRust for Box<T> {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        Ok(format!(
+            "::std::boxed::Box::new({})",
+            (**self).to_rust(writer)?
+        ))
+    }
+}
+
+impl<A: ToRust, B: ToRust> ToRust for (A, B) {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        Ok(format!(
+            "({}, {})",
+            self.0.to_rust(writer)?,
+            self.1.to_rust(writer)?
+        ))
+    }
+}
+
+impl ToRust for String {
+    fn to_rust(&self, _: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        Ok(format!("::std::string::String::from({self:?})"))
+    }
+}
+
+impl ToRust for Identifier {
+    fn to_rust(&self, _: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        Ok(format!(
+            "::c_codegen::Identifier::new({:?}).unwrap()",
+            self.as_ref()
+        ))
+    }
+}
+
+impl ToRust for f64 {
+    fn to_rust(&self, _: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        Ok(format!(
+            "::core::primitive::f64::from_bits({}u64)",
+            self.to_bits()
+        ))
+    }
+}
+
+macro_rules! literal_to_rust {
+    ($($ty:ty => $format:literal),* $(,)?) => {
+        $(
+            impl ToRust for $ty {
+                fn to_rust(&self, _: &mut Writer<'_>) -> Result<String, RepetitionError> {
+                    Ok(format!($format, self))
+                }
+            }
+        )*
+    };
+}
+
+literal_to_rust!(bool => "{}", char => "{:?}", i32 => "{}i32", i64 => "{}i64", u64 => "{}u64", usize => "{}usize");
+
+macro_rules! struct_to_rust {
+    ($($ty:ty = $path:literal { $($field:ident),* $(,)? })*) => {
+        $(
+            impl ToRust for $ty {
+                fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+                    let Self { $($field),* } = self;
+
+                    let mut code = format!("{} {{", $path);
+                    $(code.push_str(&format!(" {}: {},", stringify!($field), $field.to_rust(writer)?));)*
+                    code.push_str(" }");
+
+                    Ok(code)
+                }
+            }
+        )*
+    };
+}
+
+/// Writes the constructor of the variant of an enum within its [`ToRust`] implementation.
+macro_rules! variant_to_rust {
+    ($this:expr, $writer:expr, $path:literal { $($variant:ident $(($value:ident))? $({ $($field:ident),* })?),* $(,)? }) => {
+        match $this {
+            $(
+                Self::$variant $(($value))? $({ $($field),* })? => {
+                    #[allow(unused_mut)]
+                    let mut code = concat!($path, "::", stringify!($variant)).to_string();
+                    $(code.push_str(&format!("({})", $value.to_rust($writer)?));)?
+                    $(
+                        code.push_str(" {");
+                        $(code.push_str(&format!(" {}: {},", stringify!($field), $field.to_rust($writer)?));)*
+                        code.push_str(" }");
+                    )?
+                    code
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! enum_to_rust {
+    ($($ty:ty = $path:literal { $($variants:tt)* })*) => {
+        $(
+            impl ToRust for $ty {
+                // Enums without fields do not need the writer
+                #[allow(unused_variables)]
+                fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+                    Ok(variant_to_rust!(self, writer, $path { $($variants)* }))
+                }
+            }
+        )*
+    };
+}
+
+struct_to_rust! {
+    ArraySubscript = "::c_codegen::operator::ArraySubscript" { array, index }
+    Array = "::c_codegen::r#type::Array" { element_type, size }
+    Assignment = "::c_codegen::operator::Assignment" { left, right }
+    BinaryOperator = "::c_codegen::operator::BinaryOperator" { left, operator, right }
+    Block = "::c_codegen::Block" { statements }
+    Cast = "::c_codegen::operator::Cast" { new_ty, expression }
+    CommaOperator = "::c_codegen::operator::CommaOperator" { left, right }
+    Comment = "::c_codegen::comment::Comment" { text, style }
+    CompoundAssignment = "::c_codegen::operator::CompoundAssignment" { left, operator, right }
+    Conditional = "::c_codegen::operator::Conditional" { condition, then_branch, else_branch }
+    DeclarationList = "::c_codegen::variable::DeclarationList" {
+        doc, storage_class, alignment, ty, declarators
+    }
+    Declarator = "::c_codegen::variable::Declarator" { pointers, identifier, dimensions, initializer }
+    Define = "::c_codegen::statement::Define" { name, parameters, body }
+    Do = "::c_codegen::statement::Do" { body, condition }
+    DocComment = "::c_codegen::comment::DocComment" { text, parameters, returns }
+    Enumerator = "::c_codegen::r#type::Enumerator" { doc, name, value }
+    For = "::c_codegen::statement::For" { init, condition, step, body }
+    Function = "::c_codegen::r#type::Function" {
+        parameters, return_ty, is_variadic, has_unspecified_parameters
+    }
+    FunctionCall = "::c_codegen::function::FunctionCall" { callee, arguments }
+    FunctionDeclaration = "::c_codegen::function::Declaration" { doc, is_static, is_inline, name, ty }
+    FunctionDefinition = "::c_codegen::function::Definition" {
+        doc, is_static, is_inline, name, ty, body
+    }
+    FunctionParameter = "::c_codegen::function::FunctionParameter" { ty, name }
+    Goto = "::c_codegen::statement::Goto" { label }
+    If = "::c_codegen::statement::If" { condition, then_statement, else_statement }
+    Include = "::c_codegen::statement::Include" { path, style }
+    IndirectMemberAccess = "::c_codegen::r#type::member::IndirectMemberAccess" { left, member }
+    Integer = "::c_codegen::r#type::Integer" { kind, is_signed }
+    Label = "::c_codegen::statement::Label" { identifier, statement }
+    Member = "::c_codegen::r#type::member::Member" { doc, ty, name, bit_field_size, alignment }
+    MemberAccess = "::c_codegen::r#type::member::MemberAccess" { left, member }
+    OffsetOf = "::c_codegen::operator::OffsetOf" { ty, member }
+    Pointer = "::c_codegen::r#type::Pointer" { pointer_ty, is_const }
+    PostfixOperator = "::c_codegen::operator::PostfixOperator" { operand, operator }
+    PrefixOperator = "::c_codegen::operator::PrefixOperator" { operand, operator }
+    Return = "::c_codegen::statement::Return" { expression }
+    StaticAssert = "::c_codegen::statement::StaticAssert" { condition, message }
+    StructAttributes = "::c_codegen::r#type::StructAttributes" { packed, aligned }
+    Switch = "::c_codegen::statement::Switch" { condition, cases, default }
+    Typedef = "::c_codegen::statement::Typedef" { ty, alias }
+    VariableDeclaration = "::c_codegen::variable::Declaration" {
+        doc, storage_class, alignment, ty, identifier, initializer
+    }
+    While = "::c_codegen::statement::While" { condition, body }
+}
+
+enum_to_rust! {
+    ArraySize = "::c_codegen::r#type::ArraySize" { Unspecified, Length(length), Star, Static(length) }
+    BinaryOperatorKind = "::c_codegen::operator::BinaryOperatorKind" {
+        Add, Sub, Mul, Div, Mod, Eq, Ne, Lt, Le, Gt, Ge, And, Or, LShift, RShift, BitAnd, BitOr, BitXor,
+    }
+    CommentStyle = "::c_codegen::comment::Style" { Block, Line }
+    CompoundAssignmentOperator = "::c_codegen::operator::CompoundAssignmentOperator" {
+        Add, Sub, Mul, Div, Mod, And, Or, Xor, Shl, Shr,
+    }
+    ConcreteType = "::c_codegen::ConcreteType" {
+        Array(array),
+        Bool,
+        Char,
+        Const(ty),
+        Enum(ty),
+        Integer(integer),
+        Pointer(pointer),
+        Real(real),
+        Size,
+        StrongInt(integer),
+        Struct(ty),
+        Typedef(typedef),
+        TypedefName(name),
+        Union(ty),
+        Void,
+    }
+    Enum = "::c_codegen::r#type::Enum" {
+        Definition { doc, name, underlying_ty, values, trailing_comma },
+        Tag { name },
+    }
+    ForDeclaration = "::c_codegen::statement::ForDeclaration" {
+        Expression(expression),
+        VariableDeclaration(declaration),
+        VariableDeclarationList(declaration),
+    }
+    IncludeStyle = "::c_codegen::statement::IncludeStyle" { AngleBrackets, Quotes }
+    InitializerList = "::c_codegen::r#type::InitializerList" { Named(values), Ordered(values) }
+    IntegerKind = "::c_codegen::r#type::IntegerKind" { Char, Short, Int, Long, LongLong }
+    OpaqueType = "::c_codegen::r#type::OpaqueType" { ConcreteType(ty), Function(function) }
+    PostfixOperatorKind = "::c_codegen::operator::PostfixOperatorKind" { Increment, Decrement }
+    Pragma = "::c_codegen::statement::Pragma" { PushPack(alignment), PopPack }
+    PrefixOperatorKind = "::c_codegen::operator::PrefixOperatorKind" {
+        Increment, Decrement, Positive, Negative, Address, Indirection,
+    }
+    Real = "::c_codegen::r#type::Real" { Float, Double, LongDouble }
+    Scalar = "::c_codegen::r#type::Scalar" {
+        Bool,
+        Char,
+        Integer(integer),
+        Pointer(pointer),
+        StrongInt(integer),
+        Real(real),
+        Void,
+    }
+    SizeOf = "::c_codegen::operator::SizeOf" { Expression(expression), Type(ty) }
+    StorageClass = "::c_codegen::StorageClass" { Auto, Extern, Register, Static }
+    StrongInt = "::c_codegen::r#type::StrongInt" {
+        Int16, Int32, Int64, Int8, IntFast16, IntFast32, IntFast64, IntFast8, IntLeast16, IntLeast32, IntLeast64,
+        IntLeast8, IntMax, IntPtr, Uint16, Uint32, Uint64, Uint8, UintFast16, UintFast32, UintFast64, UintFast8,
+        UintLeast16, UintLeast32, UintLeast64, UintLeast8, UintMax, UintPtr,
+    }
+    Struct = "::c_codegen::r#type::Struct" {
+        Definition { doc, attributes, name, members },
+        Tag { name },
+    }
+    TypeDefinition = "::c_codegen::r#type::Definition" { Enum(ty), Struct(ty), Union(ty) }
+    Union = "::c_codegen::r#type::Union" {
+        Definition { doc, attributes, name, members },
+        Tag { name },
+    }
+    Value = "::c_codegen::Value" {
+        Array { values, base_type },
+        Char { value },
+        Enum { value, name },
+        Pointer { address },
+        Real { value, kind },
+        SignedInteger { value, kind },
+        Size { value },
+        String(value),
+        Struct { fields },
+        UnsignedInteger { value, kind },
+    }
+}
+
+impl ToRust for Expression {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        if let Expression::Variable(variable) = self {
+            if let Some(code) = writer.placeholder(variable.as_ref()) {
+                return Ok(code);
+            }
+        }
+
+        Ok(variant_to_rust!(self, writer, "::c_codegen::Expression" {
+            AlignOf(ty),
+            ArraySubscript(subscript),
+            Assignment(assignment),
+            BinaryOperator(operator),
+            Cast(cast),
+            CommaOperator(operator),
+            CompoundAssignment(assignment),
+            Conditional(conditional),
+            FunctionCall(call),
+            IndirectMemberAccess(access),
+            InitializerList(list),
+            MemberAccess(access),
+            OffsetOf(offset_of),
+            Parentheses(expression),
+            PostfixOperator(operator),
+            PrefixOperator(operator),
+            SizeOf(size_of),
+            Value(value),
+            Variable(variable),
+        }))
+    }
+}
+
+impl ToRust for Statement {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        if let Statement::Comment(comment) = self {
+            if let Some(code) = comment_placeholder(comment, writer) {
+                return Ok(code);
+            }
+        }
+
+        Ok(variant_to_rust!(self, writer, "::c_codegen::Statement" {
+            Expression(expression),
+            Label(label),
+            If(statement),
+            Switch(statement),
+            While(statement),
+            Do(statement),
+            For(statement),
+            Block(block),
+            Comment(comment),
+            Null,
+            Goto(goto),
+            Break,
+            Continue,
+            Return(statement),
+            StaticAssert(assert),
+            Typedef(typedef),
+            VariableDeclaration(declaration),
+            VariableDeclarationList(declaration),
+            FunctionDeclaration(declaration),
+            TypeDefinition(definition),
+            Include(include),
+            Define(define),
+            Pragma(pragma),
+        }))
+    }
+}
+
+impl ToRust for FileLevelStatement {
+    fn to_rust(&self, writer: &mut Writer<'_>) -> Result<String, RepetitionError> {
+        if let FileLevelStatement::Comment(comment) = self {
+            if let Some(code) = comment_placeholder(comment, writer) {
+                return Ok(code);
+            }
+        }
+
+        Ok(
+            variant_to_rust!(self, writer, "::c_codegen::FileLevelStatement" {
+                Comment(comment),
+                Define(define),
+                FunctionDeclaration(declaration),
+                FunctionDefinition(definition),
+                Include(include),
+                Pragma(pragma),
+                StaticAssert(assert),
+                TypeDefinition(definition),
+                Typedef(typedef),
+                VariableDeclaration(declaration),
+                VariableDeclarationList(declaration),
+            }),
+        )
+    }
+}
+
+/// Writes the placeholder that a line comment stands for in place of a statement.
+fn comment_placeholder(comment: &Comment, writer: &mut Writer<'_>) -> Option<String> {
+    match comment.style {
+        CommentStyle::Line => writer.placeholder(&comment.text),
+        CommentStyle::Block => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Placeholders for the copies of the interpolation of `a` in the first repetition.
+    fn copies(names: &[&str]) -> HashMap<String, Placeholder> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(copy, name)| {
+                let placeholder = Placeholder {
+                    id: 0,
+                    value: "a".to_string(),
+                    repetition: Some(0),
+                    copy,
+                };
+                (name.to_string(), placeholder)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn conversions() -> anyhow::Result<()> {
+        let template = parse("f($a + $b, $c) * 2", Syntax::Expression)?;
+        assert_eq!(template.conversions["a"], Conversion::Operand);
+        assert_eq!(template.conversions["c"], Conversion::AssignmentExpression);
+
+        let template = parse("{ $body $step; }", Syntax::Statement)?;
+        assert_eq!(template.conversions["body"], Conversion::Statement);
+        assert_eq!(template.conversions["step"], Conversion::Expression);
+
+        let template = parse("$item", Syntax::FileLevelStatement)?;
+        assert_eq!(template.conversions["item"], Conversion::FileLevelStatement);
+
+        assert_eq!(
+            parse("$a +", Syntax::Expression).unwrap_err().to_string(),
+            "1:5: expected expression, found end of input"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn repetitions() -> anyhow::Result<()> {
+        let placeholders = copies(&["a"]);
+        let repeated_placeholders = copies(&["a", "a_1"]);
+
+        let template = parse("f($a, 1)", Syntax::Expression)?;
+        let repeated = parse("f($a, $a_1, 1)", Syntax::Expression)?;
+        let extent = template.extent(&placeholders, 0, &repeated, &repeated_placeholders)?;
+        assert_eq!(extent, Extent::default());
+
+        let repetitions = [Repetition {
+            variables: vec!["a".to_string()],
+            extent,
+        }];
+        let code = template.expand(&placeholders, &repetitions)?;
+        assert!(code.contains("for a in ::core::iter::IntoIterator::into_iter(a)"));
+
+        // The statements without placeholders after the first one belong to the repetition
+        let template = parse("{ x += $a; f(x); return; }", Syntax::Statement)?;
+        let repeated = parse(
+            "{ x += $a; f(x); x += $a_1; f(x); return; }",
+            Syntax::Statement,
+        )?;
+        assert_eq!(
+            template.extent(&placeholders, 0, &repeated, &repeated_placeholders)?,
+            Extent {
+                before: 0,
+                after: 1
+            }
+        );
+
+        // A repetition like `f(#(#a +)* 1)` does not expand to whole arguments
+        let template = parse("f($a + 1)", Syntax::Expression)?;
+        let repeated = parse("f($a + $a_1 + 1)", Syntax::Expression)?;
+        assert_eq!(
+            template
+                .extent(&placeholders, 0, &repeated, &repeated_placeholders)
+                .unwrap_err()
+                .to_string(),
+            NOT_LIST_ELEMENTS
+        );
+
+        let template = parse("$a * 2", Syntax::Expression)?;
+        assert!(template.expand(&placeholders, &repetitions).is_err());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,28 @@
+use std::cell::Cell;
+
+thread_local! {
+    static LINE_WIDTH: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Returns the line width that overrides the default width of each node, if any.
+pub(crate) fn line_width() -> Option<usize> {
+    LINE_WIDTH.with(Cell::get)
+}
+
+/// Renders code with the given line width within the given closure.
+pub(crate) fn with_line_width<R>(line_width: usize, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<usize>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            LINE_WIDTH.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(LINE_WIDTH.with(|current| current.replace(Some(line_width))));
+    f()
+}
+
 macro_rules! impl_display_via_pretty {
     ($type:ty, $text_width:literal) => {
         impl core::fmt::Display for $type {
@@ -9,8 +34,11 @@ macro_rules! impl_display_via_pretty {
                     let doc = self.clone().pretty(&allocator);
 
                     let mut output = String::new();
-                    doc.render_fmt($text_width, &mut output)
-                        .map_err(|_| core::fmt::Error)?;
+                    doc.render_fmt(
+                        $crate::pretty::line_width().unwrap_or($text_width),
+                        &mut output,
+                    )
+                    .map_err(|_| core::fmt::Error)?;
 
                     output
                 };
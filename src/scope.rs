@@ -0,0 +1,1412 @@
+//! Resolution of ordinary identifiers to their declarations.
+//!
+//! Names that are declared by included headers are unknown, so their uses are reported as undeclared.
+
+use core::fmt;
+use std::collections::HashMap;
+
+use crate::{
+    file::FileLevelStatement,
+    function::FunctionParameter,
+    inference::{pointer_to, Environment},
+    operator::{BinaryOperator, BinaryOperatorKind, SizeOf},
+    r#type::{
+        ArraySize, Definition as TypeDefinition, Enum, Function, InitializerList, OpaqueType,
+        Pointer, Struct, Union,
+    },
+    statement::{Define, ForDeclaration, Pragma, Typedef},
+    validate::{Diagnostic, Kind, Path, Segment},
+    Block, ConcreteType, Expression, FunctionDefinition, Identifier, Statement, StorageClass,
+    Value, VariableDeclaration, VariableDeclarationList,
+};
+
+/// The symbol tables of all scopes in a file.
+#[derive(Clone, Debug)]
+pub struct SymbolTable {
+    scopes: Vec<Scope>,
+    /// The scope and position of every statement and expression.
+    points: HashMap<Path, Point>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl SymbolTable {
+    /// Returns the file scope.
+    pub fn root(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0]
+    }
+
+    /// Returns the innermost scope of the statement or expression at the given path.
+    pub fn scope_at(&self, path: &Path) -> Option<ScopeId> {
+        self.points.get(path).map(|point| point.scope)
+    }
+
+    /// Returns the declaration that the name refers to in the statement or expression at the given path.
+    ///
+    /// Returns `None` if the name is not declared before that point, or if the path does not refer to a statement or
+    /// expression.
+    pub fn lookup(&self, name: &Identifier, path: &Path) -> Option<&Symbol> {
+        let point = self.points.get(path)?;
+
+        self.visible(name, *point)
+    }
+
+    /// Returns the declarations that are visible to the statement or expression at the given path, for computing the
+    /// types of expressions.
+    pub fn environment(&self, path: &Path) -> Option<Environment> {
+        let point = self.points.get(path)?;
+
+        let mut environment = Environment::default();
+
+        // Inner declarations are added first, so they hide outer declarations
+        let mut scope = Some(point.scope);
+        while let Some(id) = scope {
+            let current = self.scope(id);
+
+            // Later declarations replace earlier ones, e.g. `int a[10];` after `extern int a[];`
+            for symbol in current
+                .symbols
+                .iter()
+                .rev()
+                .filter(|symbol| symbol.order <= point.order)
+            {
+                let name = symbol.name.clone();
+                match &symbol.kind {
+                    SymbolKind::Enumerator { value, ty } => {
+                        environment.declare_constant(name, ty.clone(), value.clone());
+                    }
+                    SymbolKind::Function(function) => {
+                        environment.declare(name, function.clone());
+                    }
+                    SymbolKind::Macro => {}
+                    SymbolKind::Parameter(ty) | SymbolKind::Variable(ty) => {
+                        environment.declare(name, ty.clone());
+                    }
+                    SymbolKind::Typedef(ty) => {
+                        if let Some(pack) = symbol.pack {
+                            environment.set_pack(name.clone(), pack);
+                        }
+                        environment.declare_typedef(name, ty.clone());
+                    }
+                }
+            }
+
+            for tag in current.tags.iter().filter(|tag| tag.order <= point.order) {
+                // A hidden tag must not pass its pack on to the definition that hides it
+                if let Some(pack) = tag.pack.filter(|_| environment.tag(&tag.name).is_none()) {
+                    environment.set_pack(tag.name.clone(), pack);
+                }
+                environment.define_tag(tag.definition.clone());
+            }
+
+            scope = current.parent;
+        }
+
+        Some(environment)
+    }
+
+    /// Returns the undeclared identifiers, redeclarations, shadowed declarations and declarations of reserved names in
+    /// the file.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn visible(&self, name: &Identifier, point: Point) -> Option<&Symbol> {
+        let mut scope = Some(point.scope);
+        while let Some(id) = scope {
+            let current = self.scope(id);
+            if let Some(symbol) = current
+                .symbols
+                .iter()
+                .rev()
+                .find(|symbol| symbol.name == *name && symbol.order <= point.order)
+            {
+                return Some(symbol);
+            }
+
+            scope = current.parent;
+        }
+
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ScopeId(usize);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScopeKind {
+    File,
+    /// The parameters and outermost block of a function definition.
+    Function,
+    Block,
+    /// The declarations in the first clause of a `for` statement.
+    For,
+}
+
+#[derive(Clone, Debug)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub parent: Option<ScopeId>,
+    symbols: Vec<Symbol>,
+    tags: Vec<Tag>,
+}
+
+impl Scope {
+    /// Returns the symbols that are declared in this scope, in order of declaration.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+}
+
+/// A named structure, union or enumeration definition.
+#[derive(Clone, Debug)]
+struct Tag {
+    name: Identifier,
+    definition: TypeDefinition,
+    order: usize,
+    /// The alignment of the enclosing `#pragma pack` region.
+    pack: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    pub name: Identifier,
+    pub kind: SymbolKind,
+    /// The location of the declaration.
+    pub path: Path,
+    /// The position of the declaration, relative to the statements and expressions of the file.
+    order: usize,
+    /// Whether the declaration is a definition, i.e. a function body or an initialized variable.
+    is_definition: bool,
+    /// Whether the function or variable has internal linkage, i.e. it is declared `static` at file scope.
+    is_internal: bool,
+    /// The alignment of the enclosing `#pragma pack` region.
+    pack: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub enum SymbolKind {
+    /// An enumeration constant with its value and type, which is `int` or the fixed underlying type of its
+    /// enumeration.
+    Enumerator {
+        value: Expression,
+        ty: ConcreteType,
+    },
+    Function(Function),
+    /// A preprocessor macro, which is visible until the end of the file.
+    Macro,
+    Parameter(ConcreteType),
+    Typedef(OpaqueType),
+    Variable(ConcreteType),
+}
+
+pub(crate) fn resolve(statements: &[FileLevelStatement]) -> SymbolTable {
+    let mut resolver = Resolver {
+        table: SymbolTable {
+            scopes: vec![Scope {
+                kind: ScopeKind::File,
+                parent: None,
+                symbols: Vec::new(),
+                tags: Vec::new(),
+            }],
+            points: HashMap::new(),
+            diagnostics: Vec::new(),
+        },
+        path: Vec::new(),
+        scope: ScopeId(0),
+        order: 0,
+        packs: Vec::new(),
+    };
+
+    resolver.at_index("statements", statements, |resolver, statement| {
+        resolver.file_level_statement(statement)
+    });
+
+    resolver.table
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Point {
+    scope: ScopeId,
+    order: usize,
+}
+
+struct Resolver {
+    table: SymbolTable,
+    path: Vec<Segment>,
+    scope: ScopeId,
+    /// The number of statements, expressions and declarations visited so far.
+    order: usize,
+    /// The stack of `#pragma pack` alignments.
+    packs: Vec<u64>,
+}
+
+impl Resolver {
+    fn report(&mut self, kind: Kind, snippet: impl fmt::Display) {
+        self.table.diagnostics.push(Diagnostic {
+            kind,
+            path: Path(self.path.clone()),
+            snippet: snippet.to_string(),
+        });
+    }
+
+    fn at<R>(&mut self, field: &'static str, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.path.push(Segment::Field(field));
+        let result = f(self);
+        self.path.pop();
+
+        result
+    }
+
+    fn at_index<T>(&mut self, field: &'static str, items: &[T], mut f: impl FnMut(&mut Self, &T)) {
+        self.path.push(Segment::Field(field));
+
+        for (index, item) in items.iter().enumerate() {
+            self.path.push(Segment::Index(index));
+            f(self, item);
+            self.path.pop();
+        }
+
+        self.path.pop();
+    }
+
+    /// Records the current path as a point in the current scope.
+    fn point(&mut self) -> Point {
+        self.order += 1;
+
+        let point = Point {
+            scope: self.scope,
+            order: self.order,
+        };
+        self.table.points.insert(Path(self.path.clone()), point);
+
+        point
+    }
+
+    fn in_scope(&mut self, kind: ScopeKind, f: impl FnOnce(&mut Self)) {
+        let parent = self.scope;
+
+        self.scope = ScopeId(self.table.scopes.len());
+        self.table.scopes.push(Scope {
+            kind,
+            parent: Some(parent),
+            symbols: Vec::new(),
+            tags: Vec::new(),
+        });
+
+        f(self);
+
+        self.scope = parent;
+    }
+
+    /// Reports a declaration whose name is reserved in the current scope.
+    fn check_reserved(&mut self, name: &Identifier, snippet: impl fmt::Display) {
+        let is_reserved = if self.table.scopes[self.scope.0].kind == ScopeKind::File {
+            name.is_reserved_at_file_scope()
+        } else {
+            name.is_reserved()
+        };
+
+        if is_reserved {
+            self.report(Kind::ReservedIdentifier(name.clone()), snippet);
+        }
+    }
+
+    fn declare(
+        &mut self,
+        name: &Identifier,
+        kind: SymbolKind,
+        is_definition: bool,
+        linkage: Linkage,
+        snippet: impl fmt::Display,
+    ) {
+        self.order += 1;
+
+        let scope = &self.table.scopes[self.scope.0];
+        let previous = scope
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.name == *name)
+            .collect::<Vec<_>>();
+        // Functions and variables can be declared multiple times at file scope, but only defined once
+        let may_redeclare = scope.kind == ScopeKind::File
+            && previous.iter().all(|previous| {
+                matches!(
+                    (&previous.kind, &kind),
+                    (SymbolKind::Function(_), SymbolKind::Function(_))
+                        | (SymbolKind::Variable(_), SymbolKind::Variable(_))
+                ) && !(previous.is_definition && is_definition)
+            });
+        let last = previous.last().copied();
+        let is_first = last.is_none();
+        let is_internal = match linkage {
+            Linkage::Internal => true,
+            Linkage::Inherited => last.is_some_and(|last| last.is_internal),
+            Linkage::External | Linkage::None => false,
+        };
+
+        let error = match last {
+            None => None,
+            Some(_) if !may_redeclare => Some(Kind::Redeclaration(name.clone())),
+            Some(last) if !self.is_compatible(&last.kind, &kind) => {
+                Some(Kind::IncompatibleRedeclaration(name.clone()))
+            }
+            Some(last) if last.is_internal != is_internal => {
+                Some(Kind::LinkageRedeclaration(name.clone()))
+            }
+            Some(_) => None,
+        };
+        // A redeclaration can complete the type of a previous declaration, e.g. `int a[10];` after `extern int a[];`
+        let kind = match last {
+            Some(last) if error.is_none() => composite(&last.kind, kind),
+            _ => kind,
+        };
+
+        if !matches!(kind, SymbolKind::Macro) {
+            self.check_reserved(name, &snippet);
+        }
+
+        if let Some(error) = error {
+            self.report(error, snippet);
+        } else if is_first {
+            let parent = self.table.scopes[self.scope.0].parent;
+            let is_shadowing = parent.is_some_and(|parent| {
+                self.table
+                    .visible(
+                        name,
+                        Point {
+                            scope: parent,
+                            order: self.order,
+                        },
+                    )
+                    .is_some_and(|symbol| !matches!(symbol.kind, SymbolKind::Macro))
+            });
+
+            if is_shadowing {
+                self.report(Kind::Shadowing(name.clone()), snippet);
+            }
+        }
+
+        let symbol = Symbol {
+            name: name.clone(),
+            kind,
+            path: Path(self.path.clone()),
+            order: self.order,
+            is_definition,
+            is_internal,
+            pack: self.packs.last().copied(),
+        };
+        self.table.scopes[self.scope.0].symbols.push(symbol);
+    }
+
+    /// Whether two declarations of the same function or variable have compatible types, e.g. `int[]` and `int[10]`.
+    fn is_compatible(&self, previous: &SymbolKind, kind: &SymbolKind) -> bool {
+        match (previous, kind) {
+            (SymbolKind::Function(previous), SymbolKind::Function(function)) => {
+                self.compatible_functions(previous, function)
+            }
+            (SymbolKind::Variable(previous), SymbolKind::Variable(ty)) => {
+                self.compatible_types(previous, ty)
+            }
+            _ => true,
+        }
+    }
+
+    fn compatible_functions(&self, left: &Function, right: &Function) -> bool {
+        if !self.compatible_types(&left.return_ty, &right.return_ty) {
+            return false;
+        }
+
+        // A declaration without a prototype is compatible with any parameters
+        left.has_unspecified_parameters
+            || right.has_unspecified_parameters
+            || (left.is_variadic == right.is_variadic
+                && left.parameters.len() == right.parameters.len()
+                && left
+                    .parameters
+                    .iter()
+                    .zip(&right.parameters)
+                    .all(|(left, right)| {
+                        self.compatible_types(&adjust(&left.ty), &adjust(&right.ty))
+                    }))
+    }
+
+    /// Whether two types are compatible.
+    ///
+    /// Type aliases are resolved, and aliases that are declared elsewhere, e.g. in headers, are compatible with any
+    /// type. Array lengths only conflict if both are different literals.
+    fn compatible_types(&self, left: &ConcreteType, right: &ConcreteType) -> bool {
+        let (left, right) = (self.resolve_alias(left), self.resolve_alias(right));
+
+        match (&left, &right) {
+            (ConcreteType::TypedefName(_), _) | (_, ConcreteType::TypedefName(_)) => true,
+            (ConcreteType::Const(left), ConcreteType::Const(right)) => {
+                self.compatible_types(left, right)
+            }
+            (ConcreteType::Array(left), ConcreteType::Array(right)) => {
+                let lengths_conflict = matches!(
+                    (&left.size, &right.size),
+                    (ArraySize::Length(left), ArraySize::Length(right))
+                        if matches!(**left, Expression::Value(_))
+                            && matches!(**right, Expression::Value(_))
+                            && left != right
+                );
+
+                !lengths_conflict && self.compatible_types(&left.element_type, &right.element_type)
+            }
+            (ConcreteType::Pointer(left), ConcreteType::Pointer(right)) => {
+                left.is_const == right.is_const
+                    && match (&left.pointer_ty, &right.pointer_ty) {
+                        (OpaqueType::ConcreteType(left), OpaqueType::ConcreteType(right)) => {
+                            self.compatible_types(left, right)
+                        }
+                        (OpaqueType::Function(left), OpaqueType::Function(right)) => {
+                            self.compatible_functions(left, right)
+                        }
+                        _ => false,
+                    }
+            }
+            // Definitions and tags of the same structure, union or enumeration are compatible
+            (ConcreteType::Enum(_), ConcreteType::Enum(_))
+            | (ConcreteType::Struct(_), ConcreteType::Struct(_))
+            | (ConcreteType::Union(_), ConcreteType::Union(_)) => {
+                match (tag_name(&left), tag_name(&right)) {
+                    (Some(left), Some(right)) => left == right,
+                    _ => left == right,
+                }
+            }
+            _ => left == right,
+        }
+    }
+
+    /// Returns the type that a type alias that is visible at this point refers to.
+    fn resolve_alias(&self, ty: &ConcreteType) -> ConcreteType {
+        let ConcreteType::TypedefName(alias) = ty else {
+            return ty.clone();
+        };
+        let point = Point {
+            scope: self.scope,
+            order: self.order,
+        };
+
+        match self.table.visible(alias, point) {
+            Some(Symbol {
+                kind: SymbolKind::Typedef(OpaqueType::ConcreteType(ty)),
+                ..
+            }) => self.resolve_alias(ty),
+            _ => ty.clone(),
+        }
+    }
+
+    fn file_level_statement(&mut self, statement: &FileLevelStatement) {
+        self.point();
+
+        match statement {
+            FileLevelStatement::Define(define) => self.define(define),
+            FileLevelStatement::FunctionDeclaration(declaration) => self.function(
+                &declaration.name,
+                &declaration.ty,
+                declaration.is_static,
+                false,
+                statement,
+            ),
+            FileLevelStatement::FunctionDefinition(definition) => {
+                self.function_definition(definition)
+            }
+            FileLevelStatement::StaticAssert(assertion) => {
+                self.at("condition", |resolver| {
+                    resolver.expression(&assertion.condition)
+                });
+            }
+            FileLevelStatement::TypeDefinition(definition) => {
+                self.type_definition(definition, statement)
+            }
+            FileLevelStatement::Typedef(typedef) => self.typedef(typedef, statement),
+            FileLevelStatement::VariableDeclaration(declaration) => {
+                self.variable_declaration(declaration, statement)
+            }
+            FileLevelStatement::VariableDeclarationList(declaration) => {
+                self.variable_declaration_list(declaration, statement)
+            }
+            FileLevelStatement::Pragma(pragma) => self.pragma(pragma),
+            FileLevelStatement::Comment(_) | FileLevelStatement::Include(_) => {}
+        }
+    }
+
+    fn pragma(&mut self, pragma: &Pragma) {
+        match pragma {
+            Pragma::PushPack(alignment) => self.packs.push(*alignment),
+            Pragma::PopPack => {
+                self.packs.pop();
+            }
+        }
+    }
+
+    fn define(&mut self, define: &Define) {
+        // Macros are not scoped, so they are visible in all following code
+        let scope = core::mem::replace(&mut self.scope, ScopeId(0));
+        self.order += 1;
+
+        let symbol = Symbol {
+            name: define.name.clone(),
+            kind: SymbolKind::Macro,
+            path: Path(self.path.clone()),
+            order: self.order,
+            is_definition: true,
+            is_internal: false,
+            pack: None,
+        };
+        self.table.scopes[0].symbols.push(symbol);
+
+        self.scope = scope;
+    }
+
+    fn function(
+        &mut self,
+        name: &Identifier,
+        ty: &Function,
+        is_static: bool,
+        is_definition: bool,
+        snippet: impl fmt::Display,
+    ) {
+        let linkage = if is_static {
+            Linkage::Internal
+        } else {
+            Linkage::Inherited
+        };
+
+        self.declare(
+            name,
+            SymbolKind::Function(ty.clone()),
+            is_definition,
+            linkage,
+            snippet,
+        );
+    }
+
+    fn function_definition(&mut self, definition: &FunctionDefinition) {
+        // The function is declared before its body, so it can call itself
+        self.function(
+            &definition.name,
+            &definition.ty,
+            definition.is_static,
+            true,
+            definition.declaration(),
+        );
+
+        self.in_scope(ScopeKind::Function, |resolver| {
+            resolver.at("ty", |resolver| {
+                resolver.at_index(
+                    "parameters",
+                    &definition.ty.parameters,
+                    |resolver, parameter| resolver.parameter(parameter),
+                )
+            });
+
+            resolver.at("body", |resolver| {
+                resolver.at_index("statements", &definition.body.statements, Self::statement)
+            });
+        });
+    }
+
+    fn parameter(&mut self, parameter: &FunctionParameter) {
+        self.at("ty", |resolver| resolver.array_sizes(&parameter.ty));
+
+        if let Some(name) = &parameter.name {
+            self.declare(
+                name,
+                SymbolKind::Parameter(parameter.ty.clone()),
+                true,
+                Linkage::None,
+                parameter,
+            );
+        }
+    }
+
+    fn typedef(&mut self, typedef: &Typedef, snippet: impl fmt::Display + Clone) {
+        if let OpaqueType::ConcreteType(ty) = &typedef.ty {
+            self.at("ty", |resolver| resolver.array_sizes(ty));
+            self.type_declarations(ty, snippet.clone());
+        }
+
+        self.declare(
+            &typedef.alias,
+            SymbolKind::Typedef(typedef.ty.clone()),
+            true,
+            Linkage::None,
+            snippet,
+        );
+    }
+
+    fn type_definition(&mut self, definition: &TypeDefinition, snippet: impl fmt::Display + Clone) {
+        let ty = match definition {
+            TypeDefinition::Enum(enumeration) => ConcreteType::Enum(enumeration.clone()),
+            TypeDefinition::Struct(structure) => ConcreteType::Struct(structure.clone()),
+            TypeDefinition::Union(union) => ConcreteType::Union(union.clone()),
+        };
+
+        self.type_declarations(&ty, snippet);
+    }
+
+    /// Declares the tags and enumeration constants that are defined as part of the type.
+    fn type_declarations(&mut self, ty: &ConcreteType, snippet: impl fmt::Display + Clone) {
+        let definition = match ty {
+            ConcreteType::Enum(
+                definition @ Enum::Definition {
+                    name: Some(name), ..
+                },
+            ) => Some((name, TypeDefinition::Enum(definition.clone()))),
+            ConcreteType::Struct(
+                definition @ Struct::Definition {
+                    name: Some(name), ..
+                },
+            ) => Some((name, TypeDefinition::Struct(definition.clone()))),
+            ConcreteType::Union(
+                definition @ Union::Definition {
+                    name: Some(name), ..
+                },
+            ) => Some((name, TypeDefinition::Union(definition.clone()))),
+            _ => None,
+        };
+        if let Some((name, definition)) = definition {
+            self.check_reserved(name, &snippet);
+            self.order += 1;
+            self.table.scopes[self.scope.0].tags.push(Tag {
+                name: name.clone(),
+                definition,
+                order: self.order,
+                pack: self.packs.last().copied(),
+            });
+        }
+
+        match ty {
+            ConcreteType::Array(array) => self.type_declarations(&array.element_type, snippet),
+            ConcreteType::Const(ty) => self.type_declarations(ty, snippet),
+            ConcreteType::Enum(Enum::Definition {
+                underlying_ty,
+                values,
+                ..
+            }) => {
+                let ty = underlying_ty
+                    .as_deref()
+                    .cloned()
+                    .unwrap_or_else(ConcreteType::int);
+                let mut previous: Option<&Identifier> = None;
+                for enumerator in values.iter() {
+                    // An enumerator without a value is one greater than the previous one
+                    let value = match (&enumerator.value, previous) {
+                        (Some(value), _) => value.clone(),
+                        (None, Some(previous)) => BinaryOperator {
+                            left: previous.clone().into(),
+                            operator: BinaryOperatorKind::Add,
+                            right: Value::int(1).into(),
+                        }
+                        .into(),
+                        (None, None) => Value::int(0).into(),
+                    };
+
+                    self.declare(
+                        &enumerator.name,
+                        SymbolKind::Enumerator {
+                            value,
+                            ty: ty.clone(),
+                        },
+                        true,
+                        Linkage::None,
+                        snippet.clone(),
+                    );
+                    previous = Some(&enumerator.name);
+                }
+            }
+            ConcreteType::Struct(Struct::Definition { members, .. }) => {
+                for member in members {
+                    self.type_declarations(&member.ty, snippet.clone());
+                }
+            }
+            ConcreteType::Union(Union::Definition { members, .. }) => {
+                for member in members.iter() {
+                    self.type_declarations(&member.ty, snippet.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolves the identifiers in the sizes of (variable-length) arrays.
+    fn array_sizes(&mut self, ty: &ConcreteType) {
+        match ty {
+            ConcreteType::Array(array) => {
+                self.array_size(&array.size);
+                self.at("element_type", |resolver| {
+                    resolver.array_sizes(&array.element_type)
+                });
+            }
+            ConcreteType::Const(ty) => self.array_sizes(ty),
+            _ => {}
+        }
+    }
+
+    fn array_size(&mut self, size: &ArraySize) {
+        if let ArraySize::Length(size) | ArraySize::Static(size) = size {
+            self.at("size", |resolver| resolver.expression(size));
+        }
+    }
+
+    /// Returns the linkage that a variable declaration with the storage class asks for.
+    fn linkage(&self, storage_class: &Option<StorageClass>) -> Linkage {
+        if self.table.scopes[self.scope.0].kind != ScopeKind::File {
+            return Linkage::None;
+        }
+
+        match storage_class {
+            Some(StorageClass::Static) => Linkage::Internal,
+            Some(StorageClass::Extern) => Linkage::Inherited,
+            _ => Linkage::External,
+        }
+    }
+
+    fn variable_declaration(
+        &mut self,
+        declaration: &VariableDeclaration,
+        snippet: impl fmt::Display + Clone,
+    ) {
+        // Array sizes are evaluated before the variable is in scope
+        self.at("ty", |resolver| resolver.array_sizes(&declaration.ty));
+        self.type_declarations(&declaration.ty, snippet.clone());
+        self.declare(
+            &declaration.identifier,
+            SymbolKind::Variable(declaration.ty.clone()),
+            is_definition(&declaration.storage_class, &declaration.initializer),
+            self.linkage(&declaration.storage_class),
+            snippet,
+        );
+
+        // The scope of a variable starts before its initializer
+        if let Some(initializer) = &declaration.initializer {
+            self.at("initializer", |resolver| resolver.expression(initializer));
+        }
+    }
+
+    fn variable_declaration_list(
+        &mut self,
+        declaration: &VariableDeclarationList,
+        snippet: impl fmt::Display + Clone,
+    ) {
+        self.at("ty", |resolver| resolver.array_sizes(&declaration.ty));
+        self.type_declarations(&declaration.ty, snippet.clone());
+
+        let declarators = declaration.declarators.iter().collect::<Vec<_>>();
+        self.at_index("declarators", &declarators, |resolver, declarator| {
+            resolver.at_index("dimensions", &declarator.dimensions, Self::array_size);
+            resolver.declare(
+                &declarator.identifier,
+                SymbolKind::Variable(declarator.declared_type(declaration.ty.clone())),
+                is_definition(&declaration.storage_class, &declarator.initializer),
+                resolver.linkage(&declaration.storage_class),
+                snippet.clone(),
+            );
+
+            if let Some(initializer) = &declarator.initializer {
+                resolver.at("initializer", |resolver| resolver.expression(initializer));
+            }
+        });
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.in_scope(ScopeKind::Block, |resolver| {
+            resolver.at_index("statements", &block.statements, Self::statement)
+        });
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        self.point();
+
+        match statement {
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::Pragma(pragma) => self.pragma(pragma),
+            Statement::Label(label) => {
+                if let Some(statement) = &label.statement {
+                    self.at("statement", |resolver| resolver.statement(statement));
+                }
+            }
+            Statement::If(if_stmt) => {
+                self.at("condition", |resolver| {
+                    resolver.expression(&if_stmt.condition)
+                });
+                self.at("then_statement", |resolver| {
+                    resolver.statement(&if_stmt.then_statement)
+                });
+                if let Some(else_statement) = &if_stmt.else_statement {
+                    self.at("else_statement", |resolver| {
+                        resolver.statement(else_statement)
+                    });
+                }
+            }
+            Statement::Switch(switch) => {
+                self.at("condition", |resolver| {
+                    resolver.expression(&switch.condition)
+                });
+
+                // All cases share the block of the `switch` body
+                self.in_scope(ScopeKind::Block, |resolver| {
+                    resolver.at_index("cases", &switch.cases, |resolver, (case, statements)| {
+                        resolver.at("case", |resolver| resolver.expression(case));
+                        resolver.at_index("statements", statements, Self::statement);
+                    });
+                    if let Some(default) = &switch.default {
+                        resolver.at_index("default", default, Self::statement);
+                    }
+                });
+            }
+            Statement::While(while_stmt) => {
+                self.at("condition", |resolver| {
+                    resolver.expression(&while_stmt.condition)
+                });
+                self.at("body", |resolver| resolver.statement(&while_stmt.body));
+            }
+            Statement::Do(do_stmt) => {
+                self.at("body", |resolver| resolver.statement(&do_stmt.body));
+                self.at("condition", |resolver| {
+                    resolver.expression(&do_stmt.condition)
+                });
+            }
+            Statement::For(for_stmt) => self.in_scope(ScopeKind::For, |resolver| {
+                if let Some(init) = &for_stmt.init {
+                    resolver.at("init", |resolver| match init {
+                        ForDeclaration::Expression(expression) => resolver.expression(expression),
+                        ForDeclaration::VariableDeclaration(declaration) => {
+                            resolver.variable_declaration(declaration, declaration)
+                        }
+                        ForDeclaration::VariableDeclarationList(declaration) => {
+                            resolver.variable_declaration_list(declaration, declaration)
+                        }
+                    });
+                }
+                resolver.at("condition", |resolver| {
+                    resolver.expression(&for_stmt.condition)
+                });
+                if let Some(step) = &for_stmt.step {
+                    resolver.at("step", |resolver| resolver.expression(step));
+                }
+                resolver.at("body", |resolver| resolver.statement(&for_stmt.body));
+            }),
+            Statement::Block(block) => self.block(block),
+            Statement::Return(return_stmt) => {
+                if let Some(expression) = &return_stmt.expression {
+                    self.at("expression", |resolver| resolver.expression(expression));
+                }
+            }
+            Statement::StaticAssert(assertion) => {
+                self.at("condition", |resolver| {
+                    resolver.expression(&assertion.condition)
+                });
+            }
+            Statement::Typedef(typedef) => self.typedef(typedef, statement),
+            Statement::VariableDeclaration(declaration) => {
+                self.variable_declaration(declaration, statement)
+            }
+            Statement::VariableDeclarationList(declaration) => {
+                self.variable_declaration_list(declaration, statement)
+            }
+            Statement::FunctionDeclaration(declaration) => self.function(
+                &declaration.name,
+                &declaration.ty,
+                declaration.is_static,
+                false,
+                statement,
+            ),
+            Statement::TypeDefinition(definition) => self.type_definition(definition, statement),
+            Statement::Define(define) => self.define(define),
+            Statement::Break
+            | Statement::Comment(_)
+            | Statement::Continue
+            | Statement::Goto(_)
+            | Statement::Include(_)
+            | Statement::Null => {}
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        let point = self.point();
+
+        match expression {
+            Expression::Variable(variable) => {
+                if self.table.visible(variable, point).is_none() {
+                    self.report(Kind::UndeclaredIdentifier(variable.clone()), variable);
+                }
+            }
+            Expression::AlignOf(_) | Expression::OffsetOf(_) | Expression::Value(_) => {}
+            Expression::ArraySubscript(subscript) => {
+                self.at("array", |resolver| resolver.expression(&subscript.array));
+                self.at("index", |resolver| resolver.expression(&subscript.index));
+            }
+            Expression::Assignment(assignment) => {
+                self.at("left", |resolver| resolver.expression(&assignment.left));
+                self.at("right", |resolver| resolver.expression(&assignment.right));
+            }
+            Expression::BinaryOperator(operation) => {
+                self.at("left", |resolver| resolver.expression(&operation.left));
+                self.at("right", |resolver| resolver.expression(&operation.right));
+            }
+            Expression::Cast(cast) => {
+                self.at("expression", |resolver| {
+                    resolver.expression(&cast.expression)
+                });
+            }
+            Expression::CommaOperator(comma) => {
+                self.at("left", |resolver| resolver.expression(&comma.left));
+                self.at("right", |resolver| resolver.expression(&comma.right));
+            }
+            Expression::CompoundAssignment(assignment) => {
+                self.at("left", |resolver| resolver.expression(&assignment.left));
+                self.at("right", |resolver| resolver.expression(&assignment.right));
+            }
+            Expression::Conditional(conditional) => {
+                self.at("condition", |resolver| {
+                    resolver.expression(&conditional.condition)
+                });
+                self.at("then_branch", |resolver| {
+                    resolver.expression(&conditional.then_branch)
+                });
+                self.at("else_branch", |resolver| {
+                    resolver.expression(&conditional.else_branch)
+                });
+            }
+            Expression::FunctionCall(call) => {
+                self.at("callee", |resolver| resolver.expression(&call.callee));
+                self.at_index("arguments", &call.arguments, Self::expression);
+            }
+            Expression::IndirectMemberAccess(access) => {
+                self.at("left", |resolver| resolver.expression(&access.left));
+            }
+            Expression::InitializerList(initializer_list) => {
+                let initializers = match initializer_list {
+                    InitializerList::Named(initializers) => initializers
+                        .iter()
+                        .map(|(_, initializer)| initializer.clone())
+                        .collect::<Vec<_>>(),
+                    InitializerList::Ordered(initializers) => initializers.clone(),
+                };
+
+                self.at_index("initializers", &initializers, Self::expression);
+            }
+            Expression::MemberAccess(access) => {
+                self.at("left", |resolver| resolver.expression(&access.left));
+            }
+            Expression::Parentheses(inner) => self.expression(inner),
+            Expression::PostfixOperator(operation) => {
+                self.at("operand", |resolver| {
+                    resolver.expression(&operation.operand)
+                });
+            }
+            Expression::PrefixOperator(operation) => {
+                self.at("operand", |resolver| {
+                    resolver.expression(&operation.operand)
+                });
+            }
+            Expression::SizeOf(sizeof) => {
+                if let SizeOf::Expression(inner) = sizeof.as_ref() {
+                    self.at("expression", |resolver| resolver.expression(inner));
+                }
+            }
+        }
+    }
+}
+
+/// The linkage that a declaration asks for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Linkage {
+    /// `static` at file scope.
+    Internal,
+    /// A variable without storage class at file scope.
+    External,
+    /// `extern`, or a function without `static`: the linkage of a previous declaration, or external linkage.
+    Inherited,
+    /// Parameters, type aliases, enumeration constants and declarations in blocks.
+    None,
+}
+
+/// Returns the tag of a structure, union or enumeration type, which is `None` for anonymous definitions.
+fn tag_name(ty: &ConcreteType) -> Option<&Identifier> {
+    match ty {
+        ConcreteType::Enum(Enum::Definition { name, .. })
+        | ConcreteType::Struct(Struct::Definition { name, .. })
+        | ConcreteType::Union(Union::Definition { name, .. }) => name.as_ref(),
+        ConcreteType::Enum(Enum::Tag { name })
+        | ConcreteType::Struct(Struct::Tag { name })
+        | ConcreteType::Union(Union::Tag { name }) => Some(name),
+        _ => None,
+    }
+}
+
+/// Applies the adjustments of parameter types, e.g. `int *` for `int a[]`, and drops the top-level qualifier.
+fn adjust(ty: &ConcreteType) -> ConcreteType {
+    match ty {
+        ConcreteType::Array(array) => pointer_to((*array.element_type).clone()),
+        ConcreteType::Const(ty) => adjust(ty),
+        ConcreteType::Pointer(pointer) => ConcreteType::Pointer(Box::new(Pointer {
+            is_const: false,
+            ..(**pointer).clone()
+        })),
+        ty => ty.clone(),
+    }
+}
+
+/// Returns the more complete of the compatible types of two declarations, e.g. `int[10]` for `int[]` and `int[10]`,
+/// or a prototype rather than a declaration without one.
+fn composite(previous: &SymbolKind, kind: SymbolKind) -> SymbolKind {
+    match (previous, &kind) {
+        (
+            SymbolKind::Variable(ConcreteType::Array(previous_array)),
+            SymbolKind::Variable(ConcreteType::Array(array)),
+        ) if array.size == ArraySize::Unspecified
+            && previous_array.size != ArraySize::Unspecified =>
+        {
+            previous.clone()
+        }
+        (SymbolKind::Function(previous_function), SymbolKind::Function(function))
+            if function.has_unspecified_parameters
+                && !previous_function.has_unspecified_parameters =>
+        {
+            previous.clone()
+        }
+        _ => kind,
+    }
+}
+
+/// Whether a variable declaration is a definition, rather than a tentative definition or `extern` declaration.
+fn is_definition(storage_class: &Option<StorageClass>, initializer: &Option<Expression>) -> bool {
+    initializer.is_some() && !matches!(storage_class, Some(StorageClass::Extern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operator::{Assignment, BinaryOperator, BinaryOperatorKind},
+        parse::parse_file,
+        CFileBuilder, Value,
+    };
+
+    use Segment::{Field, Index};
+
+    fn variable(
+        storage_class: Option<StorageClass>,
+        name: &str,
+        initializer: Option<Expression>,
+    ) -> anyhow::Result<VariableDeclaration> {
+        Ok(VariableDeclaration {
+            doc: None,
+            storage_class,
+            alignment: None,
+            ty: ConcreteType::int(),
+            identifier: Identifier::new(name)?,
+            initializer,
+        })
+    }
+
+    fn function(
+        name: &str,
+        parameters: Vec<FunctionParameter>,
+        statements: Vec<Statement>,
+    ) -> anyhow::Result<FunctionDefinition> {
+        Ok(FunctionDefinition {
+            doc: None,
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new(name)?,
+            ty: Function {
+                parameters,
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+            body: Block { statements },
+        })
+    }
+
+    #[test]
+    fn lookup() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(variable(
+            None,
+            "counter",
+            Some(Value::signed_integer(0).into()),
+        )?)
+        .add_statement(function(
+            "f",
+            vec![FunctionParameter {
+                ty: ConcreteType::int(),
+                name: Some(Identifier::new("x")?),
+            }],
+            vec![
+                Expression::from(Assignment {
+                    left: Identifier::new("y")?.into(),
+                    right: Value::signed_integer(1).into(),
+                })
+                .into(),
+                variable(
+                    None,
+                    "y",
+                    Some(
+                        BinaryOperator {
+                            left: Identifier::new("x")?.into(),
+                            operator: BinaryOperatorKind::Add,
+                            right: Identifier::new("counter")?.into(),
+                        }
+                        .into(),
+                    ),
+                )?
+                .into(),
+            ],
+        )?);
+
+        let symbols = file.symbols();
+
+        let diagnostics = symbols.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind,
+            Kind::UndeclaredIdentifier(Identifier::new("y")?)
+        );
+        assert_eq!(
+            diagnostics[0].path.to_string(),
+            "statements[1].body.statements[0].left"
+        );
+
+        let initializer = Path::from(vec![
+            Field("statements"),
+            Index(1),
+            Field("body"),
+            Field("statements"),
+            Index(1),
+            Field("initializer"),
+        ]);
+        assert!(matches!(
+            symbols.lookup(&Identifier::new("x")?, &initializer),
+            Some(Symbol {
+                kind: SymbolKind::Parameter(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            symbols.lookup(&Identifier::new("y")?, &initializer),
+            Some(Symbol {
+                kind: SymbolKind::Variable(_),
+                ..
+            })
+        ));
+        assert_eq!(
+            symbols
+                .lookup(&Identifier::new("counter")?, &initializer)
+                .map(|symbol| symbol.path.to_string()),
+            Some("statements[0]".to_string())
+        );
+
+        let environment = symbols
+            .environment(&initializer)
+            .expect("initializer is visited");
+        assert_eq!(
+            environment
+                .type_of(&Identifier::new("counter")?.into())?
+                .to_string(),
+            "int"
+        );
+
+        let scope = symbols
+            .scope_at(&initializer)
+            .expect("initializer is visited");
+        assert_eq!(symbols.scope(scope).kind, ScopeKind::Function);
+        assert_eq!(symbols.scope(scope).parent, Some(symbols.root()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn redeclarations() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(variable(Some(StorageClass::Extern), "x", None)?)
+            .add_statement(variable(None, "x", Some(Value::signed_integer(1).into()))?)
+            .add_statement(function(
+                "f",
+                Vec::new(),
+                vec![
+                    variable(None, "y", None)?.into(),
+                    variable(None, "y", None)?.into(),
+                    Block {
+                        statements: vec![variable(None, "x", None)?.into()],
+                    }
+                    .into(),
+                ],
+            )?)
+            .add_statement(function("f", Vec::new(), Vec::new())?)
+            // The tentative definition is compatible with both, but the second definition is not
+            .add_statement(variable(None, "z", None)?)
+            .add_statement(variable(None, "z", Some(Value::signed_integer(1).into()))?)
+            .add_statement(variable(None, "z", Some(Value::signed_integer(2).into()))?);
+
+        let diagnostics = file
+            .symbols()
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| (diagnostic.kind.clone(), diagnostic.path.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    Kind::Redeclaration(Identifier::new("y")?),
+                    "statements[2].body.statements[1]".to_string()
+                ),
+                (
+                    Kind::Shadowing(Identifier::new("x")?),
+                    "statements[2].body.statements[2].statements[0]".to_string()
+                ),
+                (
+                    Kind::Redeclaration(Identifier::new("f")?),
+                    "statements[3]".to_string()
+                ),
+                (
+                    Kind::Redeclaration(Identifier::new("z")?),
+                    "statements[6]".to_string()
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn file_scope_redeclarations() -> anyhow::Result<()> {
+        let diagnostics = |source| -> anyhow::Result<Vec<(Kind, String)>> {
+            Ok(parse_file(source)?
+                .file
+                .symbols()
+                .diagnostics()
+                .iter()
+                .map(|diagnostic| (diagnostic.kind.clone(), diagnostic.path.to_string()))
+                .collect())
+        };
+
+        assert!(diagnostics(
+            "typedef int T;
+            extern T t;
+            int t;
+            int h();
+            int h(int);
+            void p(int a[]);
+            void p(int *const a);
+            struct s { int m; } v;
+            extern struct s v;
+            static int z;
+            extern int z;
+            static int f(void);
+            int f(void) { return z; }"
+        )?
+        .is_empty());
+
+        assert_eq!(
+            diagnostics("int x;\ndouble x;\nint g(int);\nint g(long);\nint a[2];\nint a[3];")?,
+            [
+                (
+                    Kind::IncompatibleRedeclaration(Identifier::new("x")?),
+                    "statements[1]".to_string()
+                ),
+                (
+                    Kind::IncompatibleRedeclaration(Identifier::new("g")?),
+                    "statements[3]".to_string()
+                ),
+                (
+                    Kind::IncompatibleRedeclaration(Identifier::new("a")?),
+                    "statements[5]".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            diagnostics("extern int y;\nstatic int y;\nstatic int w;\nint w;\nint f(void);\nstatic int f(void);")?,
+            [
+                (
+                    Kind::LinkageRedeclaration(Identifier::new("y")?),
+                    "statements[1]".to_string()
+                ),
+                (
+                    Kind::LinkageRedeclaration(Identifier::new("w")?),
+                    "statements[3]".to_string()
+                ),
+                (
+                    Kind::LinkageRedeclaration(Identifier::new("f")?),
+                    "statements[5]".to_string()
+                ),
+            ]
+        );
+
+        // The completing declaration replaces the incomplete one, but not the other way around
+        let file = parse_file(
+            "extern int a[];\nint a[10];\nextern int a[];\nint f();\nint f(int);\nint f();\nint x;",
+        )?
+        .file;
+        let symbols = file.symbols();
+        assert!(symbols.diagnostics().is_empty());
+        let environment = symbols
+            .environment(&Path::from(vec![Field("statements"), Index(6)]))
+            .expect("statement is visited");
+        assert_eq!(
+            environment
+                .type_of(&Identifier::new("a")?.into())?
+                .to_string(),
+            "int[10]"
+        );
+        assert_eq!(
+            environment
+                .type_of(&Identifier::new("f")?.into())?
+                .to_string(),
+            "int (*)(int)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn reserved_identifiers() -> anyhow::Result<()> {
+        let file = parse_file(
+            "static int _count;
+            struct _Node { int x; };
+            int strdup_all(void) { int __tmp = 0; int _local = 1; return __tmp + _local; }",
+        )?
+        .file;
+
+        let diagnostics = file
+            .symbols()
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| (diagnostic.kind.clone(), diagnostic.path.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    Kind::ReservedIdentifier(Identifier::new("_count")?),
+                    "statements[0]".to_string()
+                ),
+                (
+                    Kind::ReservedIdentifier(Identifier::new("_Node")?),
+                    "statements[1]".to_string()
+                ),
+                (
+                    Kind::ReservedIdentifier(Identifier::new("strdup_all")?),
+                    "statements[2]".to_string()
+                ),
+                (
+                    Kind::ReservedIdentifier(Identifier::new("__tmp")?),
+                    "statements[2].body.statements[0]".to_string()
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+}
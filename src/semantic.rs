@@ -0,0 +1,473 @@
+//! Equality that ignores differences which only affect the formatting of the generated code.
+//!
+//! The derived [`PartialEq`] and [`Hash`] of the syntax tree are structural, e.g. `(x) + 1` differs from `x + 1`,
+//! because the parentheses are a node of their own. [`SemanticEq::normalized`] removes these differences, so that
+//! normalized nodes can be compared, hashed and deduplicated. The differences are
+//!
+//! - redundant parentheses, as the structure of the tree already determines the grouping; normalization keeps only
+//!   the parentheses that the printed code needs for that grouping, e.g. around the `x + 1` of `(x + 1) * y` or
+//!   around a comma operator that is a function argument,
+//! - the trailing comma of an enumeration, and
+//! - the style of a comment, i.e. `// ...` or `/* ... */`.
+
+use crate::{
+    comment::Style,
+    fold::{self, Fold},
+    function::FunctionCall,
+    operator::{
+        ArraySubscript, Assignment, BinaryOperator, Cast, CommaOperator, CompoundAssignment,
+        Conditional, PostfixOperator, PrefixOperator, PrefixOperatorKind, SizeOf,
+    },
+    r#type::{
+        member::{IndirectMemberAccess, Member, MemberAccess},
+        ArraySize, Enum, Enumerator, InitializerList,
+    },
+    statement::{StaticAssert, Switch},
+    variable::Declarator,
+    Block, Comment, ConcreteType, Expression, FileLevelStatement, FunctionDefinition, Statement,
+    Value, VariableDeclaration,
+};
+
+pub trait SemanticEq: Clone + PartialEq {
+    /// Removes the differences that only affect the formatting.
+    fn normalized(self) -> Self;
+
+    /// Compares the normalized nodes.
+    fn semantic_eq(&self, other: &Self) -> bool {
+        self.clone().normalized() == other.clone().normalized()
+    }
+}
+
+impl SemanticEq for Block {
+    fn normalized(self) -> Self {
+        Normalize.fold_block(self)
+    }
+}
+
+impl SemanticEq for ConcreteType {
+    fn normalized(self) -> Self {
+        Normalize.fold_concrete_type(self)
+    }
+}
+
+impl SemanticEq for Expression {
+    fn normalized(self) -> Self {
+        Normalize.fold_expression(self)
+    }
+}
+
+impl SemanticEq for FileLevelStatement {
+    fn normalized(self) -> Self {
+        Normalize.fold_file_level_statement(self)
+    }
+}
+
+impl SemanticEq for FunctionDefinition {
+    fn normalized(self) -> Self {
+        Normalize.fold_function_definition(self)
+    }
+}
+
+impl SemanticEq for Statement {
+    fn normalized(self) -> Self {
+        Normalize.fold_statement(self)
+    }
+}
+
+/// Binding strengths of the expressions, where higher binds more tightly. Binary operators lie between conditional
+/// and cast expressions.
+const COMMA: u8 = 0;
+const ASSIGNMENT: u8 = 1;
+const CONDITIONAL: u8 = 2;
+const CAST: u8 = 13;
+const UNARY: u8 = 14;
+const POSTFIX: u8 = 15;
+const PRIMARY: u8 = 16;
+
+fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::CommaOperator(_) => COMMA,
+        Expression::Assignment(_) | Expression::CompoundAssignment(_) => ASSIGNMENT,
+        Expression::Conditional(_) => CONDITIONAL,
+        Expression::BinaryOperator(operator) => CONDITIONAL + operator.operator.precedence(),
+        Expression::Cast(_) => CAST,
+        Expression::AlignOf(_) | Expression::PrefixOperator(_) | Expression::SizeOf(_) => UNARY,
+        // Negative constants are printed with a sign
+        Expression::Value(Value::SignedInteger { value, .. }) if *value < 0 => UNARY,
+        Expression::Value(Value::Real { value, .. }) if value.is_sign_negative() => UNARY,
+        Expression::ArraySubscript(_)
+        | Expression::FunctionCall(_)
+        | Expression::IndirectMemberAccess(_)
+        | Expression::MemberAccess(_)
+        | Expression::PostfixOperator(_) => POSTFIX,
+        Expression::InitializerList(_)
+        | Expression::OffsetOf(_)
+        | Expression::Parentheses(_)
+        | Expression::Value(_)
+        | Expression::Variable(_) => PRIMARY,
+    }
+}
+
+/// Parenthesizes the expression if it binds less tightly than its position requires.
+fn group(expression: Expression, required: u8) -> Expression {
+    if precedence(&expression) < required {
+        Expression::Parentheses(Box::new(expression))
+    } else {
+        expression
+    }
+}
+
+/// Removes all parentheses, then adds back those that keep the grouping of the tree when it is printed.
+struct Normalize;
+
+impl Fold for Normalize {
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        match expression {
+            Expression::Parentheses(expression) => self.fold_expression(*expression),
+            expression => fold::walk_expression(self, expression),
+        }
+    }
+
+    fn fold_array_subscript(&mut self, subscript: ArraySubscript) -> ArraySubscript {
+        let subscript = fold::walk_array_subscript(self, subscript);
+
+        ArraySubscript {
+            array: group(subscript.array, POSTFIX),
+            index: subscript.index,
+        }
+    }
+
+    fn fold_assignment(&mut self, assignment: Assignment) -> Assignment {
+        let assignment = fold::walk_assignment(self, assignment);
+
+        Assignment {
+            left: group(assignment.left, UNARY),
+            right: group(assignment.right, ASSIGNMENT),
+        }
+    }
+
+    fn fold_binary_operator(&mut self, operator: BinaryOperator) -> BinaryOperator {
+        let operator = fold::walk_binary_operator(self, operator);
+        let precedence = CONDITIONAL + operator.operator.precedence();
+
+        // Binary operators are left-associative
+        BinaryOperator {
+            left: group(operator.left, precedence),
+            operator: operator.operator,
+            right: group(operator.right, precedence + 1),
+        }
+    }
+
+    fn fold_cast(&mut self, cast: Cast) -> Cast {
+        let cast = fold::walk_cast(self, cast);
+
+        Cast {
+            new_ty: cast.new_ty,
+            expression: group(cast.expression, CAST),
+        }
+    }
+
+    fn fold_comma_operator(&mut self, operator: CommaOperator) -> CommaOperator {
+        let operator = fold::walk_comma_operator(self, operator);
+
+        CommaOperator {
+            left: operator.left,
+            right: group(operator.right, ASSIGNMENT),
+        }
+    }
+
+    fn fold_compound_assignment(&mut self, assignment: CompoundAssignment) -> CompoundAssignment {
+        let assignment = fold::walk_compound_assignment(self, assignment);
+
+        CompoundAssignment {
+            left: group(assignment.left, UNARY),
+            operator: assignment.operator,
+            right: group(assignment.right, ASSIGNMENT),
+        }
+    }
+
+    fn fold_conditional(&mut self, conditional: Conditional) -> Conditional {
+        let conditional = fold::walk_conditional(self, conditional);
+
+        Conditional {
+            condition: group(conditional.condition, CONDITIONAL + 1),
+            then_branch: conditional.then_branch,
+            else_branch: group(conditional.else_branch, CONDITIONAL),
+        }
+    }
+
+    fn fold_function_call(&mut self, call: FunctionCall) -> FunctionCall {
+        let call = fold::walk_function_call(self, call);
+
+        FunctionCall {
+            callee: group(call.callee, POSTFIX),
+            arguments: call
+                .arguments
+                .into_iter()
+                .map(|argument| group(argument, ASSIGNMENT))
+                .collect(),
+        }
+    }
+
+    fn fold_indirect_member_access(
+        &mut self,
+        access: IndirectMemberAccess,
+    ) -> IndirectMemberAccess {
+        let access = fold::walk_indirect_member_access(self, access);
+
+        IndirectMemberAccess {
+            left: group(access.left, POSTFIX),
+            member: access.member,
+        }
+    }
+
+    fn fold_initializer_list(&mut self, list: InitializerList) -> InitializerList {
+        match fold::walk_initializer_list(self, list) {
+            InitializerList::Named(fields) => InitializerList::Named(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, group(value, ASSIGNMENT)))
+                    .collect(),
+            ),
+            InitializerList::Ordered(values) => InitializerList::Ordered(
+                values
+                    .into_iter()
+                    .map(|value| group(value, ASSIGNMENT))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn fold_member_access(&mut self, access: MemberAccess) -> MemberAccess {
+        let access = fold::walk_member_access(self, access);
+
+        MemberAccess {
+            left: group(access.left, POSTFIX),
+            member: access.member,
+        }
+    }
+
+    fn fold_postfix_operator(&mut self, operator: PostfixOperator) -> PostfixOperator {
+        let operator = fold::walk_postfix_operator(self, operator);
+
+        PostfixOperator {
+            operand: group(operator.operand, POSTFIX),
+            operator: operator.operator,
+        }
+    }
+
+    fn fold_prefix_operator(&mut self, operator: PrefixOperator) -> PrefixOperator {
+        let operator = fold::walk_prefix_operator(self, operator);
+
+        // Increments apply to unary expressions, the other operators to cast expressions
+        let required = match operator.operator {
+            PrefixOperatorKind::Increment | PrefixOperatorKind::Decrement => UNARY,
+            _ => CAST,
+        };
+
+        PrefixOperator {
+            operand: group(operator.operand, required),
+            operator: operator.operator,
+        }
+    }
+
+    fn fold_size_of(&mut self, size_of: SizeOf) -> SizeOf {
+        match fold::walk_size_of(self, size_of) {
+            SizeOf::Expression(expression) => SizeOf::Expression(group(expression, UNARY)),
+            size_of @ SizeOf::Type(_) => size_of,
+        }
+    }
+
+    fn fold_static_assert(&mut self, assertion: StaticAssert) -> StaticAssert {
+        let assertion = fold::walk_static_assert(self, assertion);
+
+        StaticAssert {
+            condition: group(assertion.condition, CONDITIONAL),
+            message: assertion.message,
+        }
+    }
+
+    fn fold_switch(&mut self, switch: Switch) -> Switch {
+        let switch = fold::walk_switch(self, switch);
+
+        Switch {
+            cases: switch
+                .cases
+                .into_iter()
+                .map(|(value, statements)| (group(value, CONDITIONAL), statements))
+                .collect(),
+            ..switch
+        }
+    }
+
+    fn fold_variable_declaration(
+        &mut self,
+        declaration: VariableDeclaration,
+    ) -> VariableDeclaration {
+        let declaration = fold::walk_variable_declaration(self, declaration);
+
+        VariableDeclaration {
+            alignment: declaration
+                .alignment
+                .map(|alignment| group(alignment, CONDITIONAL)),
+            initializer: declaration
+                .initializer
+                .map(|initializer| group(initializer, ASSIGNMENT)),
+            ..declaration
+        }
+    }
+
+    fn fold_declarator(&mut self, declarator: Declarator) -> Declarator {
+        let declarator = fold::walk_declarator(self, declarator);
+
+        Declarator {
+            initializer: declarator
+                .initializer
+                .map(|initializer| group(initializer, ASSIGNMENT)),
+            ..declarator
+        }
+    }
+
+    fn fold_array_size(&mut self, size: ArraySize) -> ArraySize {
+        match fold::walk_array_size(self, size) {
+            ArraySize::Length(length) => ArraySize::Length(Box::new(group(*length, ASSIGNMENT))),
+            ArraySize::Static(length) => ArraySize::Static(Box::new(group(*length, ASSIGNMENT))),
+            size @ (ArraySize::Unspecified | ArraySize::Star) => size,
+        }
+    }
+
+    fn fold_enumerator(&mut self, enumerator: Enumerator) -> Enumerator {
+        let enumerator = fold::walk_enumerator(self, enumerator);
+
+        Enumerator {
+            value: enumerator.value.map(|value| group(value, CONDITIONAL)),
+            ..enumerator
+        }
+    }
+
+    fn fold_member(&mut self, member: Member) -> Member {
+        let member = fold::walk_member(self, member);
+
+        Member {
+            alignment: member
+                .alignment
+                .map(|alignment| group(alignment, CONDITIONAL)),
+            ..member
+        }
+    }
+
+    fn fold_comment(&mut self, comment: Comment) -> Comment {
+        Comment {
+            style: Style::Line,
+            ..comment
+        }
+    }
+
+    fn fold_enum(&mut self, enumeration: Enum) -> Enum {
+        match fold::walk_enum(self, enumeration) {
+            Enum::Definition {
+                doc,
+                name,
+                underlying_ty,
+                values,
+                ..
+            } => Enum::Definition {
+                doc,
+                name,
+                underlying_ty,
+                values,
+                trailing_comma: false,
+            },
+            tag @ Enum::Tag { .. } => tag,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::Value;
+
+    #[test]
+    fn parentheses() -> anyhow::Result<()> {
+        let parenthesized = Expression::parse("((x)) + f((1))")?;
+        let plain = Expression::parse("x + f(1)")?;
+        assert_ne!(parenthesized, plain);
+        assert!(parenthesized.semantic_eq(&plain));
+
+        // The parentheses of `(x + 1) * y` are part of the tree structure, not a node that can be removed.
+        let grouped = Expression::parse("(x + 1) * y")?;
+        let ungrouped = Expression::parse("x + 1 * y")?;
+        assert!(!grouped.semantic_eq(&ungrouped));
+
+        Ok(())
+    }
+
+    #[test]
+    fn necessary_parentheses() -> anyhow::Result<()> {
+        for source in [
+            "(x + 1) * y",
+            "x - (y - 1)",
+            "*(p + 1)",
+            "(int) (x + y)",
+            "sizeof (x + 1)",
+            "f((a, b), c)",
+            "(a ? b : c) ? d : e",
+            "(p + 1)->x",
+        ] {
+            let expression = Expression::parse(source)?;
+            assert_eq!(expression.clone().normalized(), expression, "{source}");
+            assert_eq!(
+                Expression::parse(&expression.normalized().to_string())?,
+                Expression::parse(source)?,
+                "{source}"
+            );
+        }
+
+        let redundant = Expression::parse("((x) * (y)) + (f((a)))")?;
+        assert_eq!(redundant.normalized().to_string(), "x * y + f(a)");
+
+        let initializer = Statement::parse("int x = ((a, b));")?;
+        assert_eq!(initializer.normalized().to_string(), "int x = (a, b);");
+
+        Ok(())
+    }
+
+    #[test]
+    fn formatting() -> anyhow::Result<()> {
+        let trailing_comma = FileLevelStatement::parse("enum color { RED, GREEN, };")?;
+        let no_trailing_comma = FileLevelStatement::parse("enum color { RED, GREEN };")?;
+        assert_ne!(trailing_comma, no_trailing_comma);
+        assert!(trailing_comma.semantic_eq(&no_trailing_comma));
+
+        let block_comment = Statement::parse("/* colors */")?;
+        let line_comment = Statement::parse("// colors")?;
+        assert_ne!(block_comment, line_comment);
+        assert!(block_comment.semantic_eq(&line_comment));
+
+        let other = FileLevelStatement::parse("enum color { RED, BLUE };")?;
+        assert!(!trailing_comma.semantic_eq(&other));
+
+        Ok(())
+    }
+
+    #[test]
+    fn deduplication() -> anyhow::Result<()> {
+        let helpers = [
+            "static int square(int x) { return x * x; }",
+            "static int square(int x) { return (x) * (x); }",
+            "static int cube(int x) { return x * x * x; }",
+        ]
+        .into_iter()
+        .map(|source| FileLevelStatement::parse(source).map(SemanticEq::normalized))
+        .collect::<Result<HashSet<_>, _>>()?;
+        assert_eq!(helpers.len(), 2);
+
+        let nan = Expression::from(Value::double(f64::NAN));
+        assert_eq!(nan, nan.clone());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1134 @@
+use core::{cell::Cell, fmt};
+
+use crate::{
+    comment::{self, Comment},
+    constant::EvaluationError,
+    file::FileLevelStatement,
+    function::{Declaration as FunctionDeclaration, FunctionParameter},
+    inference::Environment,
+    r#type::{
+        member::Member, Array, ArraySize, Definition as TypeDefinition, Enum, Function,
+        InitializerList, IntegerKind, OpaqueType, Pointer, Scalar, Struct, StructAttributes, Union,
+    },
+    scope,
+    statement::ForDeclaration,
+    validate::{Path, Segment},
+    Block, ConcreteType, Expression, Statement, Value,
+};
+
+/// A revision of the C language, optionally with GNU extensions.
+///
+/// # Source
+///
+/// https://gcc.gnu.org/onlinedocs/gcc/C-Dialect-Options.html
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Standard {
+    C89,
+    C99,
+    C11,
+    #[default]
+    C17,
+    C23,
+    Gnu89,
+    Gnu99,
+    Gnu11,
+    Gnu17,
+    Gnu23,
+}
+
+thread_local! {
+    static CURRENT: Cell<Standard> = Cell::new(Standard::default());
+}
+
+impl Standard {
+    /// Returns the ISO revision of the standard, without GNU extensions.
+    pub const fn iso(self) -> Self {
+        match self {
+            Standard::C89 | Standard::Gnu89 => Standard::C89,
+            Standard::C99 | Standard::Gnu99 => Standard::C99,
+            Standard::C11 | Standard::Gnu11 => Standard::C11,
+            Standard::C17 | Standard::Gnu17 => Standard::C17,
+            Standard::C23 | Standard::Gnu23 => Standard::C23,
+        }
+    }
+
+    /// Whether GNU extensions are enabled.
+    pub const fn is_gnu(self) -> bool {
+        matches!(
+            self,
+            Standard::Gnu89 | Standard::Gnu99 | Standard::Gnu11 | Standard::Gnu17 | Standard::Gnu23
+        )
+    }
+
+    /// Whether the standard is the given ISO revision or a later one.
+    pub const fn is_at_least(self, revision: Standard) -> bool {
+        self.iso().year() >= revision.iso().year()
+    }
+
+    /// Whether the given language feature can be used.
+    pub const fn supports(self, feature: Feature) -> bool {
+        self.is_at_least(feature.introduced_in()) || (self.is_gnu() && feature.is_gnu_extension())
+    }
+
+    /// Whether the name is a keyword, which cannot be used as an identifier.
+    pub fn is_keyword(self, name: &str) -> bool {
+        const C89: &[&str] = &[
+            "auto", "break", "case", "char", "const", "continue", "default", "do", "double",
+            "else", "enum", "extern", "float", "for", "goto", "if", "int", "long", "register",
+            "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+            "union", "unsigned", "void", "volatile", "while",
+        ];
+        const C99: &[&str] = &["inline", "restrict", "_Bool", "_Complex", "_Imaginary"];
+        const C11: &[&str] = &[
+            "_Alignas",
+            "_Alignof",
+            "_Atomic",
+            "_Generic",
+            "_Noreturn",
+            "_Static_assert",
+            "_Thread_local",
+        ];
+        const C23: &[&str] = &[
+            "alignas",
+            "alignof",
+            "bool",
+            "constexpr",
+            "false",
+            "nullptr",
+            "static_assert",
+            "thread_local",
+            "true",
+            "typeof",
+            "typeof_unqual",
+            "_BitInt",
+            "_Decimal32",
+            "_Decimal64",
+            "_Decimal128",
+        ];
+        const GNU: &[&str] = &["asm", "inline", "typeof"];
+
+        C89.contains(&name)
+            || (self.is_at_least(Standard::C99) && C99.contains(&name))
+            || (self.is_at_least(Standard::C11) && C11.contains(&name))
+            || (self.is_at_least(Standard::C23) && C23.contains(&name))
+            || (self.is_gnu() && GNU.contains(&name))
+    }
+
+    /// Returns the spelling of the alignment query operator.
+    pub const fn alignof_keyword(self) -> &'static str {
+        if self.is_at_least(Standard::C23) {
+            "alignof"
+        } else if self.is_at_least(Standard::C11) {
+            "_Alignof"
+        } else {
+            "__alignof__"
+        }
+    }
+
+    /// Returns the alignment specifier of a member or variable, e.g. `_Alignas(64)`.
+    ///
+    /// Before C11, GNU dialects spell it as `__attribute__((aligned(64)))`.
+    pub(crate) fn alignment_specifier(self, alignment: &Expression) -> String {
+        if self.is_at_least(Standard::C23) {
+            format!("alignas({alignment})")
+        } else if self.is_at_least(Standard::C11) {
+            format!("_Alignas({alignment})")
+        } else {
+            self.gnu_attributes(&[format!("aligned({alignment})")])
+        }
+    }
+
+    /// Returns the spelling of GNU attributes like `packed`, e.g. `__attribute__((packed))`.
+    ///
+    /// Every standard keeps this spelling: GNU compilers accept it in strict modes too, while other compilers may
+    /// silently ignore the C23 spelling `[[gnu::packed]]`. `[[...]]` is reserved for standard attributes.
+    pub(crate) fn gnu_attributes(self, attributes: &[String]) -> String {
+        format!("__attribute__(({}))", attributes.join(", "))
+    }
+
+    /// Returns the spelling of the boolean type.
+    pub const fn bool_keyword(self) -> &'static str {
+        if self.is_at_least(Standard::C23) {
+            "bool"
+        } else {
+            "_Bool"
+        }
+    }
+
+    /// Returns the spelling of the static assertion declaration.
+    pub const fn static_assert_keyword(self) -> &'static str {
+        if self.is_at_least(Standard::C23) {
+            "static_assert"
+        } else {
+            "_Static_assert"
+        }
+    }
+
+    /// Returns the standard that code is currently being generated for.
+    pub(crate) fn current() -> Self {
+        CURRENT.with(Cell::get)
+    }
+
+    /// Generates code for this standard within the given closure.
+    pub(crate) fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Standard);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(self)));
+        f()
+    }
+
+    const fn year(self) -> u16 {
+        match self.iso() {
+            Standard::C89 => 1989,
+            Standard::C99 => 1999,
+            Standard::C11 => 2011,
+            Standard::C17 => 2017,
+            _ => 2023,
+        }
+    }
+}
+
+impl fmt::Display for Standard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Standard::C89 => "C89",
+            Standard::C99 => "C99",
+            Standard::C11 => "C11",
+            Standard::C17 => "C17",
+            Standard::C23 => "C23",
+            Standard::Gnu89 => "GNU89",
+            Standard::Gnu99 => "GNU99",
+            Standard::Gnu11 => "GNU11",
+            Standard::Gnu17 => "GNU17",
+            Standard::Gnu23 => "GNU23",
+        })
+    }
+}
+
+/// A language feature that is not available in every revision of the C language.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Feature {
+    /// `_Alignas(N)`
+    AlignAs,
+    /// `_Alignof(T)`
+    AlignOf,
+    /// `_Bool`
+    Bool,
+    /// Declarations following statements in a block.
+    MixedDeclarations,
+    /// `{ .x = 1 }`
+    DesignatedInitializers,
+    /// `enum e : uint8_t { ... }`
+    EnumUnderlyingType,
+    /// `int8_t`, `uint32_t`, ...
+    FixedWidthIntegers,
+    /// `struct s { int n; int data[]; }`
+    FlexibleArrayMembers,
+    /// `for (int i = 0; ...)`
+    ForDeclarations,
+    /// `inline int f(void) { ... }`
+    Inline,
+    /// `{ ... done: }`
+    LabelsWithoutStatements,
+    /// `// ...`
+    LineComments,
+    /// `long long`
+    LongLong,
+    /// `_Static_assert(condition, message)`
+    StaticAssert,
+    /// `static_assert(condition)`
+    StaticAssertWithoutMessage,
+    /// `void f(int a[static 10])`
+    StaticArrayParameters,
+    /// `int f(int) { ... }`
+    UnnamedParameters,
+    /// `int buffer[n]` and `void f(int n, double row[*])`
+    VariableLengthArrays,
+    /// `void f(...)`
+    VariadicFunctionsWithoutParameters,
+}
+
+impl Feature {
+    /// Returns the ISO revision that introduced the feature.
+    pub const fn introduced_in(self) -> Standard {
+        match self {
+            Feature::Bool
+            | Feature::MixedDeclarations
+            | Feature::DesignatedInitializers
+            | Feature::FixedWidthIntegers
+            | Feature::FlexibleArrayMembers
+            | Feature::ForDeclarations
+            | Feature::Inline
+            | Feature::LineComments
+            | Feature::LongLong
+            | Feature::StaticArrayParameters
+            | Feature::VariableLengthArrays => Standard::C99,
+            Feature::AlignAs | Feature::AlignOf | Feature::StaticAssert => Standard::C11,
+            Feature::EnumUnderlyingType
+            | Feature::LabelsWithoutStatements
+            | Feature::StaticAssertWithoutMessage
+            | Feature::UnnamedParameters
+            | Feature::VariadicFunctionsWithoutParameters => Standard::C23,
+        }
+    }
+
+    /// Returns the ISO revision since which implementations may leave out the feature.
+    ///
+    /// C11 made variable-length arrays optional: implementations without them define `__STDC_NO_VLA__`.
+    pub const fn optional_since(self) -> Option<Standard> {
+        match self {
+            Feature::VariableLengthArrays => Some(Standard::C11),
+            _ => None,
+        }
+    }
+
+    /// Whether GNU dialects support the feature before it was standardized.
+    pub const fn is_gnu_extension(self) -> bool {
+        match self {
+            Feature::AlignAs
+            | Feature::AlignOf
+            | Feature::MixedDeclarations
+            | Feature::DesignatedInitializers
+            | Feature::FixedWidthIntegers
+            | Feature::FlexibleArrayMembers
+            | Feature::Inline
+            | Feature::LineComments
+            | Feature::LongLong
+            | Feature::StaticAssert
+            | Feature::VariableLengthArrays => true,
+            Feature::Bool
+            | Feature::EnumUnderlyingType
+            | Feature::ForDeclarations
+            | Feature::LabelsWithoutStatements
+            | Feature::StaticAssertWithoutMessage
+            | Feature::StaticArrayParameters
+            | Feature::UnnamedParameters
+            | Feature::VariadicFunctionsWithoutParameters => false,
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Feature::AlignAs => "an alignment specifier",
+            Feature::AlignOf => "an alignment query",
+            Feature::Bool => "the boolean type",
+            Feature::MixedDeclarations => "a declaration after a statement",
+            Feature::DesignatedInitializers => "a designated initializer",
+            Feature::EnumUnderlyingType => "an enumeration with a fixed underlying type",
+            Feature::FixedWidthIntegers => "a fixed-width integer type",
+            Feature::FlexibleArrayMembers => "a flexible array member",
+            Feature::ForDeclarations => "a declaration in a for loop",
+            Feature::Inline => "an inline function",
+            Feature::LabelsWithoutStatements => "a label at the end of a block",
+            Feature::LineComments => "a line comment",
+            Feature::LongLong => "the long long type",
+            Feature::StaticAssert => "a static assertion",
+            Feature::StaticAssertWithoutMessage => "a static assertion without a message",
+            Feature::StaticArrayParameters => "an array parameter with a minimum size",
+            Feature::UnnamedParameters => "an unnamed parameter in a function definition",
+            Feature::VariableLengthArrays => "a variable-length array",
+            Feature::VariadicFunctionsWithoutParameters => "a variadic function without parameters",
+        })
+    }
+}
+
+/// Errors that can occur when generating code for a specific standard
+#[derive(Debug, thiserror::Error)]
+#[error("{feature} requires {required} or later, but the target standard is {standard}", required = feature.introduced_in())]
+pub struct Error {
+    pub feature: Feature,
+    pub standard: Standard,
+}
+
+/// Verifies that the statements only use features that are available in the standard.
+pub(crate) fn check(statements: &[FileLevelStatement], standard: Standard) -> Result<(), Error> {
+    let symbols = scope::resolve(statements);
+
+    statements
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, statement)| {
+            let path = Path::from(vec![Segment::Field("statements"), Segment::Index(index)]);
+            let checker = Checker {
+                standard,
+                environment: symbols.environment(&path).unwrap_or_default(),
+            };
+
+            checker.file_level_statement(statement)
+        })
+}
+
+struct Checker {
+    standard: Standard,
+    /// The file-scope declarations that precede the statement, which tell constant array lengths apart from
+    /// variable ones.
+    environment: Environment,
+}
+
+impl Checker {
+    fn require(&self, feature: Feature) -> Result<(), Error> {
+        if self.standard.supports(feature) {
+            Ok(())
+        } else {
+            Err(Error {
+                feature,
+                standard: self.standard,
+            })
+        }
+    }
+
+    fn file_level_statement(&self, statement: &FileLevelStatement) -> Result<(), Error> {
+        match statement {
+            FileLevelStatement::FunctionDeclaration(declaration) => {
+                self.function_declaration(declaration)
+            }
+            FileLevelStatement::FunctionDefinition(definition) => {
+                if definition.is_inline {
+                    self.require(Feature::Inline)?;
+                }
+                if definition
+                    .ty
+                    .parameters
+                    .iter()
+                    .any(|parameter| parameter.name.is_none())
+                {
+                    self.require(Feature::UnnamedParameters)?;
+                }
+
+                self.function(&definition.ty)?;
+                self.block(&definition.body)
+            }
+            FileLevelStatement::Comment(comment) => self.comment(comment),
+            FileLevelStatement::Define(_)
+            | FileLevelStatement::Include(_)
+            | FileLevelStatement::Pragma(_) => Ok(()),
+            FileLevelStatement::StaticAssert(assertion) => self.static_assert(assertion),
+            FileLevelStatement::TypeDefinition(definition) => self.type_definition(definition),
+            FileLevelStatement::Typedef(typedef) => self.opaque_type(&typedef.ty),
+            FileLevelStatement::VariableDeclaration(declaration) => {
+                self.variable_declaration(declaration)
+            }
+            FileLevelStatement::VariableDeclarationList(declaration) => {
+                self.variable_declaration_list(declaration)
+            }
+        }
+    }
+
+    fn block(&self, block: &Block) -> Result<(), Error> {
+        let mut has_statements = false;
+
+        for statement in &block.statements {
+            if let Statement::Comment(comment) = statement {
+                self.comment(comment)?;
+                continue;
+            }
+
+            let is_declaration = matches!(
+                statement,
+                Statement::VariableDeclaration(_)
+                    | Statement::VariableDeclarationList(_)
+                    | Statement::Typedef(_)
+                    | Statement::FunctionDeclaration(_)
+                    | Statement::TypeDefinition(_)
+            );
+
+            if is_declaration && has_statements {
+                self.require(Feature::MixedDeclarations)?;
+            }
+            has_statements |= !is_declaration;
+
+            self.statement(statement)?;
+        }
+
+        Ok(())
+    }
+
+    fn statement(&self, statement: &Statement) -> Result<(), Error> {
+        match statement {
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::Label(label) => match &label.statement {
+                Some(statement) => self.statement(statement),
+                None => self.require(Feature::LabelsWithoutStatements),
+            },
+            Statement::If(if_stmt) => {
+                self.expression(&if_stmt.condition)?;
+                self.statement(&if_stmt.then_statement)?;
+                if_stmt
+                    .else_statement
+                    .as_ref()
+                    .map_or(Ok(()), |statement| self.statement(statement))
+            }
+            Statement::Switch(switch) => {
+                self.expression(&switch.condition)?;
+                for (case, statements) in &switch.cases {
+                    self.expression(case)?;
+                    statements
+                        .iter()
+                        .try_for_each(|statement| self.statement(statement))?;
+                }
+                switch
+                    .default
+                    .iter()
+                    .flatten()
+                    .try_for_each(|statement| self.statement(statement))
+            }
+            Statement::While(while_stmt) => {
+                self.expression(&while_stmt.condition)?;
+                self.statement(&while_stmt.body)
+            }
+            Statement::Do(do_stmt) => {
+                self.statement(&do_stmt.body)?;
+                self.expression(&do_stmt.condition)
+            }
+            Statement::For(for_stmt) => {
+                match &for_stmt.init {
+                    Some(ForDeclaration::Expression(expression)) => self.expression(expression)?,
+                    Some(ForDeclaration::VariableDeclaration(declaration)) => {
+                        self.require(Feature::ForDeclarations)?;
+                        self.variable_declaration(declaration)?;
+                    }
+                    Some(ForDeclaration::VariableDeclarationList(declaration)) => {
+                        self.require(Feature::ForDeclarations)?;
+                        self.variable_declaration_list(declaration)?;
+                    }
+                    None => {}
+                }
+                self.expression(&for_stmt.condition)?;
+                for_stmt
+                    .step
+                    .as_ref()
+                    .map_or(Ok(()), |step| self.expression(step))?;
+                self.statement(&for_stmt.body)
+            }
+            Statement::Block(block) => self.block(block),
+            Statement::Comment(comment) => self.comment(comment),
+            Statement::Null
+            | Statement::Goto(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Include(_)
+            | Statement::Pragma(_)
+            | Statement::Define(_) => Ok(()),
+            Statement::Return(statement) => statement
+                .expression
+                .as_ref()
+                .map_or(Ok(()), |expression| self.expression(expression)),
+            Statement::StaticAssert(assertion) => self.static_assert(assertion),
+            Statement::Typedef(typedef) => self.opaque_type(&typedef.ty),
+            Statement::VariableDeclaration(declaration) => self.variable_declaration(declaration),
+            Statement::VariableDeclarationList(declaration) => {
+                self.variable_declaration_list(declaration)
+            }
+            Statement::FunctionDeclaration(declaration) => self.function_declaration(declaration),
+            Statement::TypeDefinition(definition) => self.type_definition(definition),
+        }
+    }
+
+    fn comment(&self, comment: &Comment) -> Result<(), Error> {
+        if matches!(comment.style, comment::Style::Line) {
+            self.require(Feature::LineComments)?;
+        }
+
+        Ok(())
+    }
+
+    fn static_assert(&self, assertion: &crate::statement::StaticAssert) -> Result<(), Error> {
+        self.require(Feature::StaticAssert)?;
+        if assertion.message.is_none() {
+            self.require(Feature::StaticAssertWithoutMessage)?;
+        }
+
+        self.expression(&assertion.condition)
+    }
+
+    fn variable_declaration(&self, declaration: &crate::VariableDeclaration) -> Result<(), Error> {
+        self.alignment(declaration.alignment.as_ref())?;
+        self.ty(&declaration.ty)?;
+        declaration
+            .initializer
+            .as_ref()
+            .map_or(Ok(()), |initializer| self.expression(initializer))
+    }
+
+    fn variable_declaration_list(
+        &self,
+        declaration: &crate::VariableDeclarationList,
+    ) -> Result<(), Error> {
+        self.alignment(declaration.alignment.as_ref())?;
+        self.ty(&declaration.ty)?;
+        declaration.declarators.iter().try_for_each(|declarator| {
+            declarator
+                .dimensions
+                .iter()
+                .try_for_each(|size| self.array_size(size))?;
+
+            declarator
+                .initializer
+                .as_ref()
+                .map_or(Ok(()), |initializer| self.expression(initializer))
+        })
+    }
+
+    fn expression(&self, expression: &Expression) -> Result<(), Error> {
+        match expression {
+            Expression::AlignOf(ty) => {
+                self.require(Feature::AlignOf)?;
+                self.ty(ty)
+            }
+            Expression::OffsetOf(offsetof) => self.ty(&offsetof.ty),
+            Expression::ArraySubscript(subscript) => {
+                self.expression(&subscript.array)?;
+                self.expression(&subscript.index)
+            }
+            Expression::Assignment(assignment) => {
+                self.expression(&assignment.left)?;
+                self.expression(&assignment.right)
+            }
+            Expression::BinaryOperator(operator) => {
+                self.expression(&operator.left)?;
+                self.expression(&operator.right)
+            }
+            Expression::Cast(cast) => {
+                self.scalar(&cast.new_ty)?;
+                self.expression(&cast.expression)
+            }
+            Expression::CommaOperator(comma) => {
+                self.expression(&comma.left)?;
+                self.expression(&comma.right)
+            }
+            Expression::CompoundAssignment(assignment) => {
+                self.expression(&assignment.left)?;
+                self.expression(&assignment.right)
+            }
+            Expression::Conditional(conditional) => {
+                self.expression(&conditional.condition)?;
+                self.expression(&conditional.then_branch)?;
+                self.expression(&conditional.else_branch)
+            }
+            Expression::FunctionCall(call) => {
+                self.expression(&call.callee)?;
+                call.arguments
+                    .iter()
+                    .try_for_each(|argument| self.expression(argument))
+            }
+            Expression::IndirectMemberAccess(access) => self.expression(&access.left),
+            Expression::InitializerList(initializer_list) => {
+                self.initializer_list(initializer_list)
+            }
+            Expression::MemberAccess(access) => self.expression(&access.left),
+            Expression::Parentheses(expression) => self.expression(expression),
+            Expression::PostfixOperator(operator) => self.expression(&operator.operand),
+            Expression::PrefixOperator(operator) => self.expression(&operator.operand),
+            Expression::SizeOf(sizeof) => match sizeof.as_ref() {
+                crate::operator::SizeOf::Expression(expression) => self.expression(expression),
+                crate::operator::SizeOf::Type(ty) => self.ty(ty),
+            },
+            Expression::Value(value) => self.value(value),
+            Expression::Variable(_) => Ok(()),
+        }
+    }
+
+    fn initializer_list(&self, initializer_list: &InitializerList) -> Result<(), Error> {
+        match initializer_list {
+            InitializerList::Named(initializers) => {
+                self.require(Feature::DesignatedInitializers)?;
+                initializers
+                    .iter()
+                    .try_for_each(|(_, initializer)| self.expression(initializer))
+            }
+            InitializerList::Ordered(initializers) => initializers
+                .iter()
+                .try_for_each(|initializer| self.expression(initializer)),
+        }
+    }
+
+    fn value(&self, value: &Value) -> Result<(), Error> {
+        match value {
+            Value::Array { values, base_type } => {
+                self.ty(base_type)?;
+                values.iter().try_for_each(|value| self.value(value))
+            }
+            Value::SignedInteger {
+                kind: Some(IntegerKind::LongLong),
+                ..
+            }
+            | Value::UnsignedInteger {
+                kind: Some(IntegerKind::LongLong),
+                ..
+            } => self.require(Feature::LongLong),
+            Value::Struct { fields } => fields.iter().try_for_each(|(_, value)| self.value(value)),
+            _ => Ok(()),
+        }
+    }
+
+    fn type_definition(&self, definition: &TypeDefinition) -> Result<(), Error> {
+        match definition {
+            TypeDefinition::Enum(enumeration) => self.enumeration(enumeration),
+            TypeDefinition::Struct(structure) => self.structure(structure),
+            TypeDefinition::Union(union) => self.union(union),
+        }
+    }
+
+    fn opaque_type(&self, ty: &OpaqueType) -> Result<(), Error> {
+        match ty {
+            OpaqueType::ConcreteType(ty) => self.ty(ty),
+            OpaqueType::Function(function) => self.function(function),
+        }
+    }
+
+    fn ty(&self, ty: &ConcreteType) -> Result<(), Error> {
+        match ty {
+            ConcreteType::Array(array) => self.array(array),
+            ConcreteType::Bool => self.require(Feature::Bool),
+            ConcreteType::Const(ty) => self.ty(ty),
+            ConcreteType::Enum(enumeration) => self.enumeration(enumeration),
+            ConcreteType::Integer(integer) => self.integer_kind(integer.kind),
+            ConcreteType::Pointer(pointer) => self.pointer(pointer),
+            ConcreteType::StrongInt(_) => self.require(Feature::FixedWidthIntegers),
+            ConcreteType::Struct(structure) => self.structure(structure),
+            ConcreteType::Typedef(typedef) => self.opaque_type(&typedef.ty),
+            ConcreteType::Union(union) => self.union(union),
+            ConcreteType::Char
+            | ConcreteType::Real(_)
+            | ConcreteType::Size
+            | ConcreteType::TypedefName(_)
+            | ConcreteType::Void => Ok(()),
+        }
+    }
+
+    fn scalar(&self, scalar: &Scalar) -> Result<(), Error> {
+        match scalar {
+            Scalar::Bool => self.require(Feature::Bool),
+            Scalar::Integer(integer) => self.integer_kind(integer.kind),
+            Scalar::Pointer(pointer) => self.pointer(pointer),
+            Scalar::StrongInt(_) => self.require(Feature::FixedWidthIntegers),
+            Scalar::Char | Scalar::Real(_) | Scalar::Void => Ok(()),
+        }
+    }
+
+    fn integer_kind(&self, kind: IntegerKind) -> Result<(), Error> {
+        if matches!(kind, IntegerKind::LongLong) {
+            self.require(Feature::LongLong)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn array(&self, array: &Array) -> Result<(), Error> {
+        self.array_size(&array.size)?;
+        self.ty(&array.element_type)
+    }
+
+    fn array_size(&self, size: &ArraySize) -> Result<(), Error> {
+        match size {
+            ArraySize::Length(length) | ArraySize::Static(length) => {
+                // Only lengths that read objects make variable-length arrays, whereas overflows, unknown types and the like are
+                // left for the compiler to report
+                if let Err(EvaluationError::NotConstant(_)) = self.environment.evaluate(length) {
+                    self.require(Feature::VariableLengthArrays)?;
+                }
+
+                self.expression(length)
+            }
+            ArraySize::Star => self.require(Feature::VariableLengthArrays),
+            ArraySize::Unspecified => Ok(()),
+        }
+    }
+
+    fn pointer(&self, pointer: &Pointer) -> Result<(), Error> {
+        self.opaque_type(&pointer.pointer_ty)
+    }
+
+    fn function_declaration(&self, declaration: &FunctionDeclaration) -> Result<(), Error> {
+        if declaration.is_inline {
+            self.require(Feature::Inline)?;
+        }
+
+        self.function(&declaration.ty)
+    }
+
+    fn function(&self, function: &Function) -> Result<(), Error> {
+        if function.is_variadic && function.parameters.is_empty() {
+            self.require(Feature::VariadicFunctionsWithoutParameters)?;
+        }
+
+        self.ty(&function.return_ty)?;
+        function
+            .parameters
+            .iter()
+            .try_for_each(|FunctionParameter { ty, .. }| {
+                if let ConcreteType::Array(Array {
+                    size: ArraySize::Static(_),
+                    ..
+                }) = ty
+                {
+                    self.require(Feature::StaticArrayParameters)?;
+                }
+
+                self.ty(ty)
+            })
+    }
+
+    fn enumeration(&self, enumeration: &Enum) -> Result<(), Error> {
+        match enumeration {
+            Enum::Definition {
+                underlying_ty,
+                values,
+                ..
+            } => {
+                if let Some(underlying_ty) = underlying_ty {
+                    self.require(Feature::EnumUnderlyingType)?;
+                    self.ty(underlying_ty)?;
+                }
+
+                values
+                    .iter()
+                    .filter_map(|enumerator| enumerator.value.as_ref())
+                    .try_for_each(|value| self.expression(value))
+            }
+            Enum::Tag { .. } => Ok(()),
+        }
+    }
+
+    fn structure(&self, structure: &Struct) -> Result<(), Error> {
+        match structure {
+            Struct::Definition {
+                attributes,
+                members,
+                ..
+            } => {
+                self.attributes(attributes)?;
+
+                if let Some(Member {
+                    ty:
+                        ConcreteType::Array(Array {
+                            size: ArraySize::Unspecified,
+                            ..
+                        }),
+                    ..
+                }) = members.last()
+                {
+                    self.require(Feature::FlexibleArrayMembers)?;
+                }
+
+                members.iter().try_for_each(|member| self.member(member))
+            }
+            Struct::Tag { .. } => Ok(()),
+        }
+    }
+
+    fn union(&self, union: &Union) -> Result<(), Error> {
+        match union {
+            Union::Definition {
+                attributes,
+                members,
+                ..
+            } => {
+                self.attributes(attributes)?;
+                members.iter().try_for_each(|member| self.member(member))
+            }
+            Union::Tag { .. } => Ok(()),
+        }
+    }
+
+    fn attributes(&self, attributes: &StructAttributes) -> Result<(), Error> {
+        attributes
+            .aligned
+            .as_ref()
+            .map_or(Ok(()), |aligned| self.expression(aligned))
+    }
+
+    fn member(&self, member: &Member) -> Result<(), Error> {
+        self.alignment(member.alignment.as_ref())?;
+        self.ty(&member.ty)
+    }
+
+    fn alignment(&self, alignment: Option<&Expression>) -> Result<(), Error> {
+        alignment.map_or(Ok(()), |alignment| {
+            self.require(Feature::AlignAs)?;
+            self.expression(alignment)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        function::Declaration as FunctionDeclaration, parse, statement::For, CFileBuilder,
+        FunctionDefinition, Identifier, StorageClass, VariableDeclaration,
+    };
+
+    #[test]
+    fn spellings() {
+        assert_eq!(Standard::Gnu99.alignof_keyword(), "__alignof__");
+        assert_eq!(Standard::C11.alignof_keyword(), "_Alignof");
+        assert_eq!(Standard::C23.alignof_keyword(), "alignof");
+        assert_eq!(Standard::C17.bool_keyword(), "_Bool");
+        assert_eq!(Standard::Gnu23.bool_keyword(), "bool");
+        assert_eq!(Standard::C11.static_assert_keyword(), "_Static_assert");
+        assert_eq!(Standard::C23.static_assert_keyword(), "static_assert");
+    }
+
+    #[test]
+    fn supported_features() {
+        assert!(!Standard::C89.supports(Feature::LongLong));
+        assert!(Standard::Gnu89.supports(Feature::LongLong));
+        assert!(Standard::C99.supports(Feature::LongLong));
+        assert!(!Standard::C17.supports(Feature::EnumUnderlyingType));
+        assert!(Standard::C23.supports(Feature::EnumUnderlyingType));
+    }
+
+    #[test]
+    fn generation() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(VariableDeclaration {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: ConcreteType::Bool,
+            identifier: Identifier::new("is_aligned")?,
+            initializer: Some(Expression::AlignOf(ConcreteType::int())),
+        });
+
+        file.set_standard(Standard::C17);
+        assert_eq!(
+            file.write_to_string()?,
+            "_Bool is_aligned = _Alignof(int);\n"
+        );
+
+        file.set_standard(Standard::C23);
+        assert_eq!(file.write_to_string()?, "bool is_aligned = alignof(int);\n");
+
+        file.set_standard(Standard::C99);
+        let error = file.write_to_string().unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::Standard(Error {
+                feature: Feature::AlignOf,
+                standard: Standard::C99,
+            })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn for_declaration() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.set_standard(Standard::C89)
+            .add_statement(FunctionDefinition {
+                doc: None,
+                is_static: false,
+                is_inline: false,
+                name: Identifier::new("main")?,
+                ty: Function {
+                    parameters: Vec::new(),
+                    return_ty: ConcreteType::int(),
+                    is_variadic: false,
+                    has_unspecified_parameters: false,
+                },
+                body: Block {
+                    statements: vec![For {
+                        init: Some(
+                            VariableDeclaration {
+                                doc: None,
+                                storage_class: None,
+                                alignment: None,
+                                ty: ConcreteType::int(),
+                                identifier: Identifier::new("i")?,
+                                initializer: Some(Value::signed_integer(0).into()),
+                            }
+                            .into(),
+                        ),
+                        condition: Value::signed_integer(0).into(),
+                        step: None,
+                        body: Statement::Null,
+                    }
+                    .into()],
+                },
+            });
+
+        let error = file.write_to_string().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "a declaration in a for loop requires C99 or later, but the target standard is C89"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn line_comments() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(Comment::line("Generated code, do not edit."));
+
+        file.set_standard(Standard::C89);
+        assert!(file.write_to_string().is_err());
+
+        file.set_standard(Standard::Gnu89);
+        assert_eq!(file.write_to_string()?, "// Generated code, do not edit.\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn unnamed_parameters() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(FunctionDefinition {
+            doc: None,
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new("ignore")?,
+            ty: Function {
+                parameters: vec![FunctionParameter {
+                    ty: ConcreteType::int(),
+                    name: None,
+                }],
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+            body: Block {
+                statements: Vec::new(),
+            },
+        });
+
+        file.set_standard(Standard::C17);
+        assert!(file.write_to_string().is_err());
+
+        file.set_standard(Standard::C23);
+        assert!(file.write_to_string().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn variable_length_arrays() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(FunctionDeclaration {
+            doc: None,
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new("clear")?,
+            ty: Function {
+                parameters: vec![FunctionParameter {
+                    ty: Array {
+                        element_type: Box::new(ConcreteType::double()),
+                        size: ArraySize::Star,
+                    }
+                    .into(),
+                    name: None,
+                }],
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+        });
+
+        file.set_standard(Standard::C89);
+        assert_eq!(
+            file.write_to_string().unwrap_err().to_string(),
+            "a variable-length array requires C99 or later, but the target standard is C89"
+        );
+
+        file.set_standard(Standard::Gnu89);
+        assert_eq!(file.write_to_string()?, "void clear (double[*]);\n");
+
+        assert_eq!(
+            Feature::VariableLengthArrays.optional_since(),
+            Some(Standard::C11)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn variable_length_array_lengths() -> anyhow::Result<()> {
+        let file =
+            parse::parse_file("enum { N = 4 };\nint counts[N * 2];\nvoid f(int rows[N]);")?.file;
+        file.clone().set_standard(Standard::C89).write_to_string()?;
+
+        let mut file = parse::parse_file("void f(int n) { int buffer[n + 1]; }")?.file;
+        file.set_standard(Standard::C89);
+        assert_eq!(
+            file.write_to_string().unwrap_err().to_string(),
+            "a variable-length array requires C99 or later, but the target standard is C89"
+        );
+
+        file.set_standard(Standard::C99);
+        file.write_to_string()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn alignment_specifiers() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(VariableDeclaration {
+            doc: None,
+            storage_class: Some(StorageClass::Static),
+            alignment: Some(Value::signed_integer(64).into()),
+            ty: Array {
+                element_type: Box::new(ConcreteType::Char),
+                size: 256.into(),
+            }
+            .into(),
+            identifier: Identifier::new("buffer")?,
+            initializer: None,
+        });
+
+        file.set_standard(Standard::C99);
+        assert_eq!(
+            file.write_to_string().unwrap_err().to_string(),
+            "an alignment specifier requires C11 or later, but the target standard is C99"
+        );
+
+        file.set_standard(Standard::Gnu99);
+        assert_eq!(
+            file.write_to_string()?,
+            "static __attribute__((aligned(64))) char buffer[256];\n"
+        );
+
+        file.set_standard(Standard::C11);
+        assert_eq!(
+            file.write_to_string()?,
+            "static _Alignas(64) char buffer[256];\n"
+        );
+
+        file.set_standard(Standard::C23);
+        assert_eq!(
+            file.write_to_string()?,
+            "static alignas(64) char buffer[256];\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn attributes() -> anyhow::Result<()> {
+        let mut file = parse::parse_file(
+            "union value { long integer; double real; } __attribute__((packed, aligned(2)));",
+        )?
+        .file;
+
+        // GNU attributes keep their spelling, even in strict modes and C23
+        for standard in [Standard::Gnu89, Standard::C17, Standard::C23] {
+            file.set_standard(standard);
+            assert!(file
+                .write_to_string()?
+                .starts_with("union __attribute__((packed, aligned(2))) value {"));
+        }
+
+        Ok(())
+    }
+}
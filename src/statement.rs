@@ -1,37 +1,49 @@
+mod define;
 mod r#do;
 mod r#for;
 mod goto;
 mod r#if;
 mod include;
 mod label;
+mod pragma;
 mod r#return;
+mod static_assert;
 mod switch;
 mod typedef;
 mod r#while;
 
+use std::collections::HashMap;
+
 use pretty::Pretty;
 
 pub use self::{
+    define::Define,
     goto::Goto,
-    include::Include,
+    include::{Include, Style as IncludeStyle},
     label::Label,
+    pragma::Pragma,
     r#do::Do,
     r#for::{For, ForDeclaration},
     r#if::If,
     r#return::Return,
     r#while::While,
+    static_assert::StaticAssert,
     switch::Switch,
     typedef::Typedef,
 };
 use crate::{
-    macros::impl_froms, pretty::impl_display_via_pretty, Block, ConcreteType, Expression,
-    Identifier, Value, VariableDeclaration,
+    macros::impl_froms,
+    parse,
+    pretty::impl_display_via_pretty,
+    r#type::{Definition as TypeDefinition, Enum, Struct, Union},
+    Block, Comment, Expression, FunctionDeclaration, VariableDeclaration, VariableDeclarationList,
 };
 
 /// # Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Statements
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Statement {
     Expression(Expression),
     Label(Box<Label>),
@@ -41,37 +53,21 @@ pub enum Statement {
     Do(Box<Do>),
     For(Box<For>),
     Block(Block),
+    Comment(Comment),
     Null,
     Goto(Goto),
     Break,
     Continue,
     Return(Return),
+    StaticAssert(StaticAssert),
     Typedef(Typedef),
     VariableDeclaration(VariableDeclaration),
-    FunctionDeclaration {
-        return_type: ConcreteType,
-        name: Identifier,
-        parameters: Vec<(ConcreteType, Option<Identifier>)>,
-    },
-    FunctionDefinition {
-        return_type: ConcreteType,
-        name: Identifier,
-        parameters: Vec<(ConcreteType, Option<Identifier>)>,
-        body: Vec<Statement>,
-    },
-    StructDeclaration {
-        name: Identifier,
-        fields: Vec<(ConcreteType, Identifier)>,
-    },
-    EnumDeclaration {
-        name: Identifier,
-        variants: Vec<(Identifier, Option<Value>)>,
-    },
+    VariableDeclarationList(VariableDeclarationList),
+    FunctionDeclaration(FunctionDeclaration),
+    TypeDefinition(TypeDefinition),
     Include(Include),
-    MacroDefinition {
-        name: Identifier,
-        body: String,
-    },
+    Define(Define),
+    Pragma(Pragma),
 }
 
 impl Statement {
@@ -82,9 +78,28 @@ impl Statement {
     pub fn is_block(&self) -> bool {
         matches!(self, Statement::Block(_))
     }
+
+    /// Parses a statement, e.g. `if (x > 0) return x;`, including declarations, comments and directives.
+    ///
+    /// Typedef names are only known if they are declared within the statement, or come from a standard header.
+    pub fn parse(source: &str) -> Result<Self, parse::Error> {
+        parse::parse_statement(source, HashMap::new())
+    }
+
+    /// Parses a statement, replacing each placeholder, e.g. `$value`, with the fragment of the same name.
+    ///
+    /// A placeholder can replace a whole statement, e.g. `{ $body }`, or an expression within one.
+    pub fn parse_with<I, S, F>(source: &str, placeholders: I) -> Result<Self, parse::Error>
+    where
+        I: IntoIterator<Item = (S, F)>,
+        S: Into<String>,
+        F: Into<parse::Fragment>,
+    {
+        parse::parse_statement(source, parse::placeholders(placeholders))
+    }
 }
 
-impl_froms!(Statement: Block, box Do, Expression, box For, box If, Include, box Label, Goto, Return, Typedef, VariableDeclaration);
+impl_froms!(Statement: Block, Comment, Define, box Do, Expression, box For, FunctionDeclaration, box If, Include, box Label, Goto, Pragma, Return, StaticAssert, Typedef, TypeDefinition(Enum, Struct, Union), VariableDeclaration, VariableDeclarationList);
 
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Statement
 where
@@ -104,95 +119,25 @@ where
             Statement::Do(do_stmt) => do_stmt.pretty(allocator),
             Statement::For(for_stmt) => for_stmt.pretty(allocator),
             Statement::Block(block) => block.pretty(allocator),
+            Statement::Comment(comment) => comment.pretty(allocator),
             Statement::Null => allocator.text(";"),
             Statement::Goto(goto) => goto.pretty(allocator),
             Statement::Break => allocator.text("break;"),
             Statement::Continue => allocator.text("continue;"),
             Statement::Return(statement) => statement.pretty(allocator),
+            Statement::StaticAssert(assertion) => assertion.pretty(allocator),
             Statement::VariableDeclaration(declaration) => {
                 declaration.pretty(allocator).append(allocator.text(";"))
             }
-            Statement::Typedef(typedef) => typedef.pretty(allocator),
-            Statement::FunctionDeclaration {
-                return_type,
-                name,
-                parameters,
-            } => {
-                let param_str = parameters
-                    .iter()
-                    .map(|(param_type, param_name)| match param_name {
-                        Some(name) => format!("{} {}", param_type, name),
-                        None => format!("{}", param_type),
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", ");
-
-                allocator.text(format!("{} {}({});", return_type, name, param_str))
-            }
-            Statement::FunctionDefinition {
-                return_type,
-                name,
-                parameters,
-                body,
-            } => {
-                let param_str = parameters
-                    .iter()
-                    .map(|(param_type, param_name)| match param_name {
-                        Some(name) => format!("{} {}", param_type, name),
-                        None => format!("{}", param_type),
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", ");
-
-                let mut doc = allocator
-                    .text(format!("{} {}({}) {{", return_type, name, param_str))
-                    .append(allocator.hardline());
-
-                for stmt in body {
-                    doc = doc
-                        .append(stmt.pretty(allocator))
-                        .append(allocator.hardline());
-                }
-
-                doc.append(allocator.text("}")).nest(2)
-            }
-            Statement::StructDeclaration { name, fields } => {
-                let mut doc = allocator
-                    .text(format!("struct {} {{", name))
-                    .append(allocator.hardline());
-
-                for (field_type, field_name) in fields {
-                    doc = doc
-                        .append(allocator.text(format!("{} {};", field_type, field_name)))
-                        .append(allocator.hardline());
-                }
-
-                doc.append(allocator.text("};")).nest(2)
-            }
-            Statement::EnumDeclaration { name, variants } => {
-                let mut doc = allocator
-                    .text(format!("enum {} {{", name))
-                    .append(allocator.hardline());
-
-                for (variant, value) in variants {
-                    let variant_str = if let Some(value) = value {
-                        format!("{}__{} = {},", name, variant, value)
-                    } else {
-                        format!("{}__{},", name, variant)
-                    };
-                    doc = doc
-                        .append(allocator.text(variant_str))
-                        .append(allocator.hardline());
-                }
-
-                doc.append(allocator.text("};")).nest(2)
-            }
-            Statement::Include(header_file) => {
-                allocator.text(format!("#include <{}>", header_file))
-            }
-            Statement::MacroDefinition { name, body } => {
-                allocator.text(format!("#define {} {}", name, body))
+            Statement::VariableDeclarationList(declaration) => {
+                declaration.pretty(allocator).append(allocator.text(";"))
             }
+            Statement::Typedef(typedef) => typedef.pretty(allocator),
+            Statement::FunctionDeclaration(declaration) => declaration.pretty(allocator),
+            Statement::TypeDefinition(definition) => definition.pretty(allocator),
+            Statement::Include(include) => include.pretty(allocator),
+            Statement::Define(define) => define.pretty(allocator),
+            Statement::Pragma(pragma) => pragma.pretty(allocator),
         }
     }
 }
@@ -0,0 +1,111 @@
+use pretty::Pretty;
+
+use crate::Identifier;
+
+use super::impl_display_via_pretty;
+
+/// A preprocessor macro definition.
+///
+/// # Source
+///
+/// https://gcc.gnu.org/onlinedocs/cpp/Macros.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Define {
+    pub name: Identifier,
+    /// The parameters of a function-like macro, or `None` for an object-like macro.
+    pub parameters: Option<Vec<Identifier>>,
+    /// The replacement list, which is emitted verbatim. Line breaks are escaped.
+    pub body: String,
+}
+
+impl Define {
+    /// Creates an object-like macro.
+    pub fn object<S: Into<String>>(name: Identifier, body: S) -> Self {
+        Self {
+            name,
+            parameters: None,
+            body: body.into(),
+        }
+    }
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Define
+where
+    AnnotationT: Clone + 'a,
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let builder = allocator
+            .text("#define")
+            .append(allocator.space())
+            .append(allocator.text(self.name));
+
+        // The parameter list has to follow the name without whitespace
+        let builder = if let Some(parameters) = self.parameters {
+            builder.append(
+                allocator
+                    .intersperse(
+                        parameters
+                            .into_iter()
+                            .map(|parameter| allocator.text(parameter)),
+                        allocator.text(",").append(allocator.space()),
+                    )
+                    .parens(),
+            )
+        } else {
+            builder
+        };
+
+        if self.body.is_empty() {
+            builder
+        } else {
+            builder.append(allocator.space()).append(
+                allocator.intersperse(
+                    self.body
+                        .lines()
+                        .map(|line| allocator.text(line.to_string()))
+                        .collect::<Vec<_>>(),
+                    allocator.text(" \\").append(allocator.hardline()),
+                ),
+            )
+        }
+    }
+}
+
+impl_display_via_pretty!(Define, 80);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_like() -> anyhow::Result<()> {
+        let generated = Define::object(Identifier::new("BUFFER_SIZE")?, "1024").to_string();
+        assert_eq!(generated, "#define BUFFER_SIZE 1024");
+
+        let generated = Define::object(Identifier::new("NDEBUG")?, "").to_string();
+        assert_eq!(generated, "#define NDEBUG");
+
+        Ok(())
+    }
+
+    #[test]
+    fn function_like() -> anyhow::Result<()> {
+        let generated = Define {
+            name: Identifier::new("MAX")?,
+            parameters: Some(vec![Identifier::new("a")?, Identifier::new("b")?]),
+            body: "((a) > (b)\n  ? (a)\n  : (b))".to_string(),
+        }
+        .to_string();
+        assert_eq!(
+            generated,
+            r#"#define MAX(a, b) ((a) > (b) \
+  ? (a) \
+  : (b))"#
+        );
+
+        Ok(())
+    }
+}
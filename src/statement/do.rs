@@ -1,7 +1,8 @@
 use crate::{pretty::impl_display_via_pretty, Expression, Statement};
 use pretty::Pretty;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Do {
     pub body: Statement,
     pub condition: Expression,
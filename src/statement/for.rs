@@ -1,16 +1,18 @@
 use pretty::Pretty;
 
-use crate::{Expression, VariableDeclaration};
+use crate::{Expression, VariableDeclaration, VariableDeclarationList};
 
 use super::{impl_display_via_pretty, impl_froms, Statement};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ForDeclaration {
     Expression(Expression),
-    VariableDeclaration(VariableDeclaration),
+    VariableDeclaration(Box<VariableDeclaration>),
+    VariableDeclarationList(Box<VariableDeclarationList>),
 }
 
-impl_froms!(ForDeclaration: VariableDeclaration, Expression);
+impl_froms!(ForDeclaration: box VariableDeclaration, box VariableDeclarationList, Expression);
 
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for ForDeclaration
 where
@@ -22,11 +24,13 @@ where
         match self {
             ForDeclaration::Expression(expression) => expression.pretty(allocator),
             ForDeclaration::VariableDeclaration(declaration) => declaration.pretty(allocator),
+            ForDeclaration::VariableDeclarationList(declaration) => declaration.pretty(allocator),
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct For {
     pub init: Option<ForDeclaration>,
     pub condition: Expression,
@@ -90,6 +94,7 @@ mod tests {
             Assignment, BinaryOperator, BinaryOperatorKind, CommaOperator, CompoundAssignment,
             CompoundAssignmentOperator, PrefixOperator, PrefixOperatorKind,
         },
+        variable::Declarator,
         Block, ConcreteType, Expression, Identifier, Value, Variable,
     };
 
@@ -137,12 +142,16 @@ mod tests {
     #[test]
     fn init_condition_step() -> anyhow::Result<()> {
         let generated = For {
-            init: Some(ForDeclaration::VariableDeclaration(VariableDeclaration {
-                storage_class: None,
-                ty: ConcreteType::Size,
-                identifier: Identifier::new("i")?,
-                initializer: Some(Value::signed_integer(0).into()),
-            })),
+            init: Some(ForDeclaration::VariableDeclaration(Box::new(
+                VariableDeclaration {
+                    doc: None,
+                    storage_class: None,
+                    alignment: None,
+                    ty: ConcreteType::Size,
+                    identifier: Identifier::new("i")?,
+                    initializer: Some(Value::signed_integer(0).into()),
+                },
+            ))),
             condition: BinaryOperator {
                 left: Variable::new("i")?.into(),
                 operator: BinaryOperatorKind::Lt,
@@ -243,4 +252,52 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn multiple_declarators() -> anyhow::Result<()> {
+        let generated = For {
+            init: Some(
+                VariableDeclarationList {
+                    doc: None,
+                    storage_class: None,
+                    alignment: None,
+                    ty: ConcreteType::int(),
+                    declarators: vec![
+                        Declarator {
+                            initializer: Some(Value::signed_integer(0).into()),
+                            ..Declarator::new(Identifier::new("i")?)
+                        },
+                        Declarator {
+                            initializer: Some(Variable::new("len")?.into()),
+                            ..Declarator::new(Identifier::new("n")?)
+                        },
+                    ]
+                    .try_into()?,
+                }
+                .into(),
+            ),
+            condition: BinaryOperator {
+                left: Variable::new("i")?.into(),
+                operator: BinaryOperatorKind::Lt,
+                right: Variable::new("n")?.into(),
+            }
+            .into(),
+            step: Some(
+                PrefixOperator {
+                    operand: Variable::new("i")?.into(),
+                    operator: PrefixOperatorKind::Increment,
+                }
+                .into(),
+            ),
+            body: Expression::Variable(Variable::new("x")?).into(),
+        }
+        .to_string();
+        assert_eq!(
+            generated,
+            r#"for (int i = 0, n = len; i < n; ++i)
+  x;"#
+        );
+
+        Ok(())
+    }
 }
@@ -4,7 +4,8 @@ use crate::Identifier;
 
 use super::impl_display_via_pretty;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Goto {
     pub label: Identifier,
 }
@@ -4,7 +4,8 @@ use crate::Identifier;
 
 use super::{impl_display_via_pretty, Statement};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Label {
     pub identifier: Identifier,
     pub statement: Option<Statement>,
@@ -48,15 +49,21 @@ impl_display_via_pretty!(Label, 80);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{function, Block, ConcreteType, Value};
+    use crate::{function, r#type::Function, Block, ConcreteType, Value};
 
     #[test]
     fn generation() -> anyhow::Result<()> {
         let generated = function::Definition {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("main")?,
-            parameters: Vec::new(),
-            return_ty: ConcreteType::Void,
+            ty: Function {
+                parameters: Vec::new(),
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Label {
                     identifier: Identifier::new("loop_start")?,
@@ -69,7 +76,7 @@ mod tests {
         assert_eq!(
             generated,
             r#"void
-main () {
+main (void) {
 loop_start:
   42;
 }"#
@@ -81,10 +88,16 @@ loop_start:
     #[test]
     fn inside_block() -> anyhow::Result<()> {
         let generated = function::Definition {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("main")?,
-            parameters: Vec::new(),
-            return_ty: ConcreteType::Void,
+            ty: Function {
+                parameters: Vec::new(),
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Block {
                     statements: vec![Label {
@@ -100,7 +113,7 @@ loop_start:
         assert_eq!(
             generated,
             r#"void
-main () {
+main (void) {
   {
 loop_start:
     42;
@@ -114,10 +127,16 @@ loop_start:
     #[test]
     fn double_label() -> anyhow::Result<()> {
         let generated = function::Definition {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("main")?,
-            parameters: Vec::new(),
-            return_ty: ConcreteType::Void,
+            ty: Function {
+                parameters: Vec::new(),
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Block {
                     statements: vec![
@@ -141,7 +160,7 @@ loop_start:
         assert_eq!(
             generated,
             r#"void
-main () {
+main (void) {
   {
 loop_start:
 loop_start2:
@@ -156,10 +175,16 @@ loop_start2:
     #[test]
     fn nested_labels() -> anyhow::Result<()> {
         let generated = function::Definition {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("main")?,
-            parameters: Vec::new(),
-            return_ty: ConcreteType::Void,
+            ty: Function {
+                parameters: Vec::new(),
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Block {
                     statements: vec![
@@ -184,7 +209,7 @@ loop_start2:
         assert_eq!(
             generated,
             r#"void
-main () {
+main (void) {
   {
 loop_start:
 loop_start2:
@@ -0,0 +1,50 @@
+use pretty::Pretty;
+
+use crate::pretty::impl_display_via_pretty;
+
+/// A `#pragma` directive.
+///
+/// # Source
+///
+/// https://gcc.gnu.org/onlinedocs/gcc/Structure-Layout-Pragmas.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Pragma {
+    /// `#pragma pack(push, N)`: the members of structures that are defined until the matching [`Pragma::PopPack`] are
+    /// aligned to at most `N` bytes.
+    PushPack(u64),
+    /// `#pragma pack(pop)`
+    PopPack,
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Pragma
+where
+    AnnotationT: Clone + 'a,
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let arguments = match self {
+            Pragma::PushPack(alignment) => format!("push, {alignment}"),
+            Pragma::PopPack => "pop".to_string(),
+        };
+
+        allocator
+            .text("#pragma")
+            .append(allocator.space())
+            .append(allocator.text(format!("pack({arguments})")))
+    }
+}
+
+impl_display_via_pretty!(Pragma, 80);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack() {
+        assert_eq!(Pragma::PushPack(1).to_string(), "#pragma pack(push, 1)");
+        assert_eq!(Pragma::PopPack.to_string(), "#pragma pack(pop)");
+    }
+}
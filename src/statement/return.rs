@@ -1,7 +1,8 @@
 use crate::Expression;
 use pretty::Pretty;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Return {
     pub expression: Option<Expression>,
 }
@@ -30,18 +31,28 @@ where
 mod tests {
     use super::*;
     use crate::{
-        function,
+        function::{self, FunctionParameter},
         operator::{BinaryOperator, BinaryOperatorKind},
+        r#type::Function,
         Block, ConcreteType, Identifier, Variable,
     };
 
     #[test]
     fn void() -> anyhow::Result<()> {
         let generated = function::Definition {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("print_plus_five")?,
-            parameters: vec![(ConcreteType::int(), Identifier::new("x")?)],
-            return_ty: ConcreteType::Void,
+            ty: Function {
+                parameters: vec![FunctionParameter {
+                    ty: ConcreteType::int(),
+                    name: Some(Identifier::new("x")?),
+                }],
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Return { expression: None }.into()],
             },
@@ -61,10 +72,19 @@ print_plus_five (int x) {
     #[test]
     fn with_expression() -> anyhow::Result<()> {
         let generated = function::Definition {
+            doc: None,
             is_static: false,
+            is_inline: false,
             name: Identifier::new("square_value")?,
-            parameters: vec![(ConcreteType::int(), Identifier::new("x")?)],
-            return_ty: ConcreteType::int(),
+            ty: Function {
+                parameters: vec![FunctionParameter {
+                    ty: ConcreteType::int(),
+                    name: Some(Identifier::new("x")?),
+                }],
+                return_ty: ConcreteType::int(),
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
             body: Block {
                 statements: vec![Return {
                     expression: Some(
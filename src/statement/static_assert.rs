@@ -0,0 +1,88 @@
+use pretty::Pretty;
+
+use crate::{Expression, Standard, Value};
+
+use super::impl_display_via_pretty;
+
+/// # Source
+///
+/// https://en.cppreference.com/w/c/language/_Static_assert
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct StaticAssert {
+    pub condition: Expression,
+    /// The diagnostic message. It can only be omitted since C23.
+    pub message: Option<String>,
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for StaticAssert
+where
+    AnnotationT: Clone + 'a,
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let arguments = self.condition.pretty(allocator);
+
+        let arguments = if let Some(message) = self.message {
+            arguments
+                .append(allocator.text(","))
+                .append(allocator.space())
+                .append(allocator.text(Value::String(message).to_string()))
+        } else {
+            arguments
+        };
+
+        allocator
+            .text(Standard::current().static_assert_keyword())
+            .append(arguments.parens())
+            .append(allocator.text(";"))
+    }
+}
+
+impl_display_via_pretty!(StaticAssert, 80);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operator::{BinaryOperator, BinaryOperatorKind, SizeOf},
+        CFileBuilder, ConcreteType,
+    };
+
+    #[test]
+    fn with_message() -> anyhow::Result<()> {
+        let generated = StaticAssert {
+            condition: BinaryOperator {
+                left: SizeOf::Type(ConcreteType::int()).into(),
+                operator: BinaryOperatorKind::Eq,
+                right: Value::signed_integer(4).into(),
+            }
+            .into(),
+            message: Some("int must be 32 bits".to_string()),
+        }
+        .to_string();
+        assert_eq!(
+            generated,
+            r#"_Static_assert(sizeof (int) == 4, "int must be 32 bits");"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn without_message() -> anyhow::Result<()> {
+        let mut file = CFileBuilder::default();
+        file.set_standard(Standard::C23)
+            .add_statement(StaticAssert {
+                condition: Value::signed_integer(1).into(),
+                message: None,
+            });
+        assert_eq!(file.write_to_string()?, "static_assert(1);\n");
+
+        file.set_standard(Standard::C11);
+        assert!(file.write_to_string().is_err());
+
+        Ok(())
+    }
+}
@@ -1,12 +1,22 @@
-use crate::{r#type::OpaqueType, Identifier};
+use crate::{r#type::OpaqueType, ConcreteType, DocComment, Identifier};
 use pretty::Pretty;
 
-#[derive(Clone, Debug)]
+use super::impl_display_via_pretty;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Typedef {
     pub ty: OpaqueType,
     pub alias: Identifier,
 }
 
+impl Typedef {
+    /// Returns a reference to the alias, for use in place of the aliased type.
+    pub fn type_name(&self) -> ConcreteType {
+        ConcreteType::TypedefName(self.alias.clone())
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Typedef
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
@@ -14,29 +24,37 @@ where
     AnnotationT: Clone + 'a,
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
-        let definition = match self.ty {
-            OpaqueType::ConcreteType(concrete) => concrete.pretty_definition(self.alias, allocator),
-            OpaqueType::Function(function) => function
-                .pretty_signature_start(allocator)
-                .append(allocator.text(self.alias))
-                .append(function.pretty_signature_end(allocator)),
+        let (doc, definition) = match self.ty {
+            OpaqueType::ConcreteType(mut concrete) => (
+                concrete.take_doc(),
+                concrete.pretty_definition(self.alias, allocator),
+            ),
+            OpaqueType::Function(function) => (
+                None,
+                function
+                    .pretty_signature_start(allocator)
+                    .append(allocator.text(self.alias))
+                    .append(function.pretty_signature_end(allocator)),
+            ),
         };
 
-        allocator
-            .text("typedef")
+        DocComment::pretty_preceding(doc, allocator)
+            .append(allocator.text("typedef"))
             .append(allocator.space())
             .append(definition)
             .append(allocator.text(";"))
     }
 }
 
+impl_display_via_pretty!(Typedef, 80);
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         function::FunctionParameter,
-        r#type::{member::Member, structure::Struct, Array, Function, Pointer},
-        ConcreteType, Statement,
+        r#type::{member::Member, structure::Struct, Array, ArraySize, Function, Pointer},
+        CFileBuilder, Statement, VariableDeclaration,
     };
 
     #[test]
@@ -55,22 +73,30 @@ mod tests {
     fn structure() -> anyhow::Result<()> {
         let typedef = Statement::from(Typedef {
             ty: Struct::Definition {
+                doc: None,
+                attributes: Default::default(),
                 name: Some(Identifier::new("fish")?),
                 members: vec![
                     Member {
+                        doc: None,
                         ty: ConcreteType::float(),
                         name: Identifier::new("weight")?,
                         bit_field_size: None,
+                        alignment: None,
                     },
                     Member {
+                        doc: None,
                         ty: ConcreteType::float(),
                         name: Identifier::new("length")?,
                         bit_field_size: None,
+                        alignment: None,
                     },
                     Member {
+                        doc: None,
                         ty: ConcreteType::float(),
                         name: Identifier::new("probability_of_being_caught")?,
                         bit_field_size: None,
+                        alignment: None,
                     },
                 ],
             }
@@ -89,12 +115,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn documented_structure() -> anyhow::Result<()> {
+        let typedef = Statement::from(Typedef {
+            ty: Struct::Definition {
+                doc: Some(DocComment::new("A fish that can be caught.")),
+                attributes: Default::default(),
+                name: None,
+                members: vec![Member {
+                    doc: None,
+                    ty: ConcreteType::float(),
+                    name: Identifier::new("weight")?,
+                    bit_field_size: None,
+                    alignment: None,
+                }],
+            }
+            .into(),
+            alias: Identifier::new("fish_type")?,
+        });
+        assert_eq!(
+            typedef.to_string(),
+            r#"/** A fish that can be caught. */
+typedef struct {
+  float weight;
+} fish_type;"#
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn array() -> anyhow::Result<()> {
         let typedef = Statement::from(Typedef {
             ty: Array {
                 element_type: Box::new(ConcreteType::Char),
-                size: Some(5),
+                size: 5.into(),
             }
             .into(),
             alias: Identifier::new("array_of_bytes")?,
@@ -117,13 +172,15 @@ mod tests {
                                 name: Some(Identifier::new("x")?),
                             }],
                             return_ty: ConcreteType::Void,
+                            is_variadic: false,
+                            has_unspecified_parameters: false,
                         }
                         .into(),
                         is_const: false,
                     }
                     .into(),
                 ),
-                size: None,
+                size: ArraySize::Unspecified,
             }
             .into(),
             alias: Identifier::new("func_ptr_arr")?,
@@ -147,6 +204,8 @@ mod tests {
                                     name: Some(Identifier::new("x")?),
                                 }],
                                 return_ty: ConcreteType::Void,
+                                is_variadic: false,
+                                has_unspecified_parameters: false,
                             }
                             .into(),
                             is_const: false,
@@ -156,7 +215,7 @@ mod tests {
                     }
                     .into(),
                 ),
-                size: None,
+                size: ArraySize::Unspecified,
             }
             .into(),
             alias: Identifier::new("func_ptr_arr")?,
@@ -176,6 +235,8 @@ mod tests {
                     name: Some(Identifier::new("x")?),
                 }],
                 return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
             }
             .into(),
             alias: Identifier::new("func_type")?,
@@ -196,6 +257,8 @@ mod tests {
                         name: Some(Identifier::new("x")?),
                     }],
                     return_ty: ConcreteType::Void,
+                    is_variadic: false,
+                    has_unspecified_parameters: false,
                 }
                 .into(),
                 is_const: false,
@@ -208,4 +271,51 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn file_level() -> anyhow::Result<()> {
+        let typedef = Typedef {
+            ty: Struct::Definition {
+                doc: None,
+                attributes: Default::default(),
+                name: None,
+                members: vec![Member {
+                    doc: None,
+                    ty: ConcreteType::int(),
+                    name: Identifier::new("x")?,
+                    bit_field_size: None,
+                    alignment: None,
+                }],
+            }
+            .into(),
+            alias: Identifier::new("foo_t")?,
+        };
+
+        let mut file = CFileBuilder::default();
+        let foo_t = typedef.type_name();
+        file.add_statement(typedef)
+            .add_statement(VariableDeclaration {
+                doc: None,
+                storage_class: None,
+                alignment: None,
+                ty: Pointer {
+                    pointer_ty: foo_t.into(),
+                    is_const: false,
+                }
+                .into(),
+                identifier: Identifier::new("foo")?,
+                initializer: None,
+            });
+
+        assert_eq!(
+            file.write_to_string()?,
+            r#"typedef struct {
+  int x;
+} foo_t;
+foo_t *foo;
+"#
+        );
+
+        Ok(())
+    }
 }
@@ -4,7 +4,8 @@ use crate::Expression;
 
 use super::{impl_display_via_pretty, Statement};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct While {
     pub condition: Expression,
     pub body: Statement,
@@ -3,7 +3,8 @@ use core::fmt;
 /// # Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Storage-Class-Specifiers
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum StorageClass {
     Auto,
     Extern,
@@ -33,7 +34,9 @@ mod tests {
     #[test]
     fn with_storage_class_extern() -> anyhow::Result<()> {
         let generated = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: Some(StorageClass::Extern),
+            alignment: None,
             ty: ConcreteType::int(),
             identifier: Identifier::new("numberOfClients")?,
             initializer: None,
@@ -43,7 +46,9 @@ mod tests {
         assert_eq!(generated, "extern int numberOfClients;");
 
         let generated = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: ConcreteType::int(),
             identifier: Identifier::new("numberOfClients")?,
             initializer: Some(Value::signed_integer(0).into()),
@@ -14,12 +14,12 @@ pub mod union;
 
 use pretty::Pretty;
 
-use crate::{macros::impl_froms, pretty::impl_display_via_pretty, statement::Typedef};
+use crate::{macros::impl_froms, pretty::impl_display_via_pretty, statement::Typedef, DocComment};
 
 pub use self::{
-    array::Array,
+    array::{Array, ArraySize},
     concrete::ConcreteType,
-    enumeration::Enum,
+    enumeration::{Enum, Enumerator},
     function::Function,
     initializer_list::InitializerList,
     integer::{Integer, IntegerKind, StrongInt},
@@ -27,11 +27,12 @@ pub use self::{
     pointer::Pointer,
     real::Real,
     scalar::Scalar,
-    structure::Struct,
+    structure::{Struct, StructAttributes},
     union::Union,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Definition {
     Enum(Enum),
     Struct(Struct),
@@ -40,20 +41,36 @@ pub enum Definition {
 
 impl_froms!(Definition: Enum, Struct, Union);
 
+impl Definition {
+    /// Removes the documentation comment of the definition.
+    pub(crate) fn take_doc(&mut self) -> Option<DocComment> {
+        match self {
+            Definition::Enum(enumeration) => enumeration.take_doc(),
+            Definition::Struct(structure) => structure.take_doc(),
+            Definition::Union(union) => union.take_doc(),
+        }
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Definition
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
     AllocatorT::Doc: Clone,
     AnnotationT: Clone + 'a,
 {
-    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+    fn pretty(
+        mut self,
+        allocator: &'a AllocatorT,
+    ) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let doc = DocComment::pretty_preceding(self.take_doc(), allocator);
+
         let builder = match self {
             Definition::Enum(enumeration) => enumeration.pretty(allocator),
             Definition::Struct(structure) => structure.pretty(allocator),
             Definition::Union(union) => union.pretty(allocator),
         };
 
-        builder.append(allocator.text(";"))
+        doc.append(builder).append(allocator.text(";"))
     }
 }
 
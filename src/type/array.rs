@@ -1,16 +1,69 @@
 use pretty::Pretty;
 
-use crate::{pretty::impl_display_via_pretty, ConcreteType};
+use crate::{pretty::impl_display_via_pretty, ConcreteType, Expression, Value};
 
 use super::OpaqueType;
 
 /// Represents a C array type with its base type and size
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Array {
     /// The base type of the array elements
     pub element_type: Box<ConcreteType>,
-    /// The size of the array (optional for flexible arrays)
-    pub size: Option<usize>,
+    /// The size of the array
+    pub size: ArraySize,
+}
+
+/// The size of an array dimension.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ArraySize {
+    /// `[]`, for flexible array members, parameters and arrays sized by their initializer.
+    Unspecified,
+    /// `[N]`, where `N` is an integer constant expression, e.g. `[BUF_SIZE]` or `[ROWS * COLS]`.
+    ///
+    /// Any other expression declares a variable-length array, e.g. `[n]`, which is only valid in a block or a
+    /// parameter list.
+    Length(Box<Expression>),
+    /// `[*]`, a variable-length array of unspecified size. Only valid for parameters of a function declaration that is
+    /// not a definition.
+    Star,
+    /// `[static N]`, a parameter that points to at least `N` elements. Only valid for the outermost dimension of a
+    /// function parameter.
+    Static(Box<Expression>),
+}
+
+impl From<usize> for ArraySize {
+    fn from(size: usize) -> Self {
+        ArraySize::Length(Box::new(Value::size(size).into()))
+    }
+}
+
+impl From<Expression> for ArraySize {
+    fn from(size: Expression) -> Self {
+        ArraySize::Length(Box::new(size))
+    }
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for ArraySize
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let size = match self {
+            ArraySize::Unspecified => allocator.nil(),
+            ArraySize::Length(size) => size.pretty(allocator),
+            ArraySize::Star => allocator.text("*"),
+            ArraySize::Static(size) => allocator
+                .text("static")
+                .append(allocator.space())
+                .append(size.pretty(allocator)),
+        };
+
+        size.brackets()
+    }
 }
 
 impl Array {
@@ -38,8 +91,8 @@ impl Array {
         }
     }
 
-    pub fn dimensions(&self) -> Vec<Option<usize>> {
-        let mut dimensions = vec![self.size];
+    pub fn dimensions(&self) -> Vec<ArraySize> {
+        let mut dimensions = vec![self.size.clone()];
 
         if let ConcreteType::Array(array) = self.element_type.as_ref() {
             dimensions.extend(array.dimensions());
@@ -60,12 +113,7 @@ impl Array {
         self.dimensions()
             .into_iter()
             .fold(allocator.nil(), |builder, dimension| {
-                let dimension = match dimension {
-                    Some(size) => allocator.text(size.to_string()),
-                    None => allocator.nil(),
-                };
-
-                builder.append(dimension.brackets())
+                builder.append(dimension.pretty(allocator))
             })
     }
 }
@@ -90,18 +138,24 @@ impl_display_via_pretty!(Array, 80);
 mod tests {
     use super::*;
     use crate::{
-        operator::{ArraySubscript, Assignment},
-        r#type::{member::MemberAccess, structure::Struct, union::Union, InitializerList},
-        variable, Identifier, Statement, Value, Variable,
+        function::{self, FunctionParameter},
+        operator::{ArraySubscript, Assignment, BinaryOperator, BinaryOperatorKind},
+        r#type::{
+            member::MemberAccess, structure::Struct, union::Union, Function, InitializerList,
+            StrongInt,
+        },
+        variable, Block, CFileBuilder, FunctionDefinition, Identifier, Statement, Value, Variable,
     };
 
     #[test]
     fn fixed_width() -> anyhow::Result<()> {
         let definition = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::int()),
-                size: Some(10),
+                size: 10.into(),
             }
             .into(),
             identifier: Identifier::new("my_array")?,
@@ -110,10 +164,12 @@ mod tests {
         assert_eq!(definition.to_string(), "int my_array[10];");
 
         let initialization = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::int()),
-                size: Some(10),
+                size: 10.into(),
             }
             .into(),
             identifier: Identifier::new("my_array")?,
@@ -139,10 +195,12 @@ mod tests {
     #[test]
     fn flex_width() -> anyhow::Result<()> {
         let definition = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::int()),
-                size: None,
+                size: ArraySize::Unspecified,
             }
             .into(),
             identifier: Identifier::new("flex")?,
@@ -151,10 +209,12 @@ mod tests {
         assert_eq!(definition.to_string(), "int flex[];");
 
         let initialization = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::int()),
-                size: None,
+                size: ArraySize::Unspecified,
             }
             .into(),
             identifier: Identifier::new("flex")?,
@@ -177,20 +237,126 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn expression_sizes() -> anyhow::Result<()> {
+        let buffer = Statement::from(variable::Declaration {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: Array {
+                element_type: Box::new(StrongInt::Uint8.into()),
+                size: Expression::from(Identifier::new("BUF_SIZE")?).into(),
+            }
+            .into(),
+            identifier: Identifier::new("buf")?,
+            initializer: None,
+        });
+        assert_eq!(buffer.to_string(), "uint8_t buf[BUF_SIZE];");
+
+        let grid = Statement::from(variable::Declaration {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: Array {
+                element_type: Box::new(ConcreteType::int()),
+                size: Expression::from(BinaryOperator {
+                    left: Identifier::new("ROWS")?.into(),
+                    operator: BinaryOperatorKind::Mul,
+                    right: Identifier::new("COLS")?.into(),
+                })
+                .into(),
+            }
+            .into(),
+            identifier: Identifier::new("grid")?,
+            initializer: None,
+        });
+        assert_eq!(grid.to_string(), "int grid[ROWS * COLS];");
+
+        Ok(())
+    }
+
+    #[test]
+    fn variable_length() -> anyhow::Result<()> {
+        let row = |size: ArraySize| -> anyhow::Result<FunctionParameter> {
+            Ok(FunctionParameter {
+                ty: Array {
+                    element_type: Box::new(ConcreteType::double()),
+                    size,
+                }
+                .into(),
+                name: Some(Identifier::new("row")?),
+            })
+        };
+        let n = FunctionParameter {
+            ty: ConcreteType::int(),
+            name: Some(Identifier::new("n")?),
+        };
+
+        let prototype = function::Declaration {
+            doc: None,
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new("clear")?,
+            ty: Function {
+                parameters: vec![n.clone(), row(ArraySize::Star)?],
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+        };
+        assert_eq!(prototype.to_string(), "void clear (int n, double row[*]);");
+
+        let copy = variable::Declaration {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: Array {
+                element_type: Box::new(ConcreteType::double()),
+                size: Expression::from(Identifier::new("n")?).into(),
+            }
+            .into(),
+            identifier: Identifier::new("copy")?,
+            initializer: None,
+        };
+        assert_eq!(Statement::from(copy.clone()).to_string(), "double copy[n];");
+
+        let mut file = CFileBuilder::default();
+        file.add_statement(FunctionDefinition {
+            doc: None,
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new("clear")?,
+            ty: Function {
+                parameters: vec![n, row(Expression::from(Identifier::new("n")?).into())?],
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+            body: Block {
+                statements: vec![copy.into()],
+            },
+        });
+        assert!(file.symbols().diagnostics().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn two_dimensional() -> anyhow::Result<()> {
         let inner_array = Array {
             element_type: Box::new(ConcreteType::int()),
-            size: Some(5),
+            size: 5.into(),
         };
 
         let outer_array = Array {
             element_type: Box::new(inner_array.into()),
-            size: Some(2),
+            size: 2.into(),
         };
 
         let definition = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: outer_array.clone().into(),
             identifier: Identifier::new("two_dimensions")?,
             initializer: None,
@@ -198,7 +364,9 @@ mod tests {
         assert_eq!(definition.to_string(), "int two_dimensions[2][5];");
 
         let declaration = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: outer_array.into(),
             identifier: Identifier::new("two_dimensions")?,
             initializer: Some(
@@ -235,21 +403,23 @@ mod tests {
     fn three_dimensional() -> anyhow::Result<()> {
         let inner_array = Array {
             element_type: Box::new(ConcreteType::int()),
-            size: Some(4),
+            size: 4.into(),
         };
 
         let middle_array = Array {
             element_type: Box::new(inner_array.into()),
-            size: Some(3),
+            size: 3.into(),
         };
 
         let outer_array = Array {
             element_type: Box::new(middle_array.into()),
-            size: Some(2),
+            size: 2.into(),
         };
 
         let definition = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: outer_array.clone().into(),
             identifier: Identifier::new("three_dimensional")?,
             initializer: None,
@@ -257,7 +427,9 @@ mod tests {
         assert_eq!(definition.to_string(), "int three_dimensional[2][3][4];");
 
         let declaration = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: outer_array.into(),
             identifier: Identifier::new("three_dimensional")?,
             initializer: Some(
@@ -325,10 +497,12 @@ mod tests {
     #[test]
     fn array_of_strings() -> anyhow::Result<()> {
         let definition = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::Char),
-                size: Some(26),
+                size: 26.into(),
             }
             .into(),
             identifier: Identifier::new("blue")?,
@@ -337,10 +511,12 @@ mod tests {
         assert_eq!(definition.to_string(), "char blue[26];");
 
         let fixed_char = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::Char),
-                size: Some(26),
+                size: 26.into(),
             }
             .into(),
             identifier: Identifier::new("yellow")?,
@@ -356,14 +532,16 @@ mod tests {
         });
         assert_eq!(
             fixed_char.to_string(),
-            "char yellow[26] = { 'y', 'e', 'l', 'l', 'o', 'w', '\0' };"
+            "char yellow[26] = { 'y', 'e', 'l', 'l', 'o', 'w', '\\0' };"
         );
 
         let fixed_string = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::Char),
-                size: Some(26),
+                size: 26.into(),
             }
             .into(),
             identifier: Identifier::new("orange")?,
@@ -372,10 +550,12 @@ mod tests {
         assert_eq!(fixed_string.to_string(), r#"char orange[26] = "orange";"#);
 
         let flexible_char = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::Char),
-                size: None,
+                size: ArraySize::Unspecified,
             }
             .into(),
             identifier: Identifier::new("gray")?,
@@ -391,14 +571,16 @@ mod tests {
         });
         assert_eq!(
             flexible_char.to_string(),
-            "char gray[] = { 'g', 'r', 'a', 'y', '\0' };"
+            "char gray[] = { 'g', 'r', 'a', 'y', '\\0' };"
         );
 
         let flexible_string = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(ConcreteType::Char),
-                size: None,
+                size: ArraySize::Unspecified,
             }
             .into(),
             identifier: Identifier::new("salmon")?,
@@ -412,7 +594,9 @@ mod tests {
     #[test]
     fn array_of_structures() -> anyhow::Result<()> {
         let definition = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(
                     Struct::Tag {
@@ -421,7 +605,7 @@ mod tests {
                     .into(),
                 ),
 
-                size: Some(3),
+                size: 3.into(),
             }
             .into(),
             identifier: Identifier::new("point_array")?,
@@ -430,7 +614,9 @@ mod tests {
         assert_eq!(definition.to_string(), "struct point point_array[3];");
 
         let declaration = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(
                     Struct::Tag {
@@ -438,18 +624,27 @@ mod tests {
                     }
                     .into(),
                 ),
-                size: Some(3),
+                size: 3.into(),
             }
             .into(),
             identifier: Identifier::new("point_array")?,
             initializer: Some(
                 InitializerList::Ordered(vec![
-                    InitializerList::Ordered(vec![Value::signed_integer(2).into(), Value::signed_integer(3).into()])
-                        .into(),
-                    InitializerList::Ordered(vec![Value::signed_integer(4).into(), Value::signed_integer(5).into()])
-                        .into(),
-                    InitializerList::Ordered(vec![Value::signed_integer(6).into(), Value::signed_integer(7).into()])
-                        .into(),
+                    InitializerList::Ordered(vec![
+                        Value::signed_integer(2).into(),
+                        Value::signed_integer(3).into(),
+                    ])
+                    .into(),
+                    InitializerList::Ordered(vec![
+                        Value::signed_integer(4).into(),
+                        Value::signed_integer(5).into(),
+                    ])
+                    .into(),
+                    InitializerList::Ordered(vec![
+                        Value::signed_integer(6).into(),
+                        Value::signed_integer(7).into(),
+                    ])
+                    .into(),
                 ])
                 .into(),
             ),
@@ -482,7 +677,9 @@ mod tests {
     #[test]
     fn array_of_unions() -> anyhow::Result<()> {
         let definition = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(
                     Union::Tag {
@@ -490,7 +687,7 @@ mod tests {
                     }
                     .into(),
                 ),
-                size: Some(3),
+                size: 3.into(),
             }
             .into(),
             identifier: Identifier::new("number_array")?,
@@ -499,7 +696,9 @@ mod tests {
         assert_eq!(definition.to_string(), "union numbers number_array[3];");
 
         let declaration = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Array {
                 element_type: Box::new(
                     Union::Tag {
@@ -507,7 +706,7 @@ mod tests {
                     }
                     .into(),
                 ),
-                size: Some(3),
+                size: 3.into(),
             }
             .into(),
             identifier: Identifier::new("number_array")?,
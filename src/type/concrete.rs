@@ -1,6 +1,8 @@
 use pretty::Pretty;
 
-use crate::{macros::impl_froms, pretty::impl_display_via_pretty, Identifier};
+use crate::{
+    macros::impl_froms, pretty::impl_display_via_pretty, DocComment, Identifier, Standard,
+};
 
 use super::{
     Array, Enum, Integer, IntegerKind, OpaqueType, Pointer, Real, StrongInt, Struct, Typedef, Union,
@@ -9,10 +11,16 @@ use super::{
 /// Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Data-Types
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ConcreteType {
     Array(Array),
+    Bool,
     Char,
+    /// A const-qualified type, e.g. `const int`.
+    ///
+    /// A const-qualified pointer is printed as `*const`, like [`Pointer::is_const`].
+    Const(Box<ConcreteType>),
     Enum(Enum),
     Integer(Integer),
     Pointer(Box<Pointer>),
@@ -20,7 +28,12 @@ pub enum ConcreteType {
     Size,
     StrongInt(StrongInt),
     Struct(Struct),
+    /// A type alias, including its definition.
+    ///
+    /// Prefer [`ConcreteType::TypedefName`] to refer to an alias that is defined elsewhere.
     Typedef(Box<Typedef>),
+    /// A reference to a type alias by name, e.g. `foo_t`.
+    TypedefName(Identifier),
     Union(Union),
     Void,
 }
@@ -28,6 +41,29 @@ pub enum ConcreteType {
 impl_froms!(ConcreteType: Array, Enum, Integer, box Pointer, Real, Struct, StrongInt, box Typedef, Union);
 
 impl ConcreteType {
+    /// Returns the const-qualified version of the type.
+    pub fn into_const(self) -> Self {
+        match self {
+            ConcreteType::Pointer(pointer) => Pointer {
+                is_const: true,
+                ..*pointer
+            }
+            .into(),
+            ty @ ConcreteType::Const(_) => ty,
+            ty => ConcreteType::Const(Box::new(ty)),
+        }
+    }
+
+    /// Removes the documentation comment of an inline enumeration, structure or union definition.
+    pub(crate) fn take_doc(&mut self) -> Option<DocComment> {
+        match self {
+            ConcreteType::Enum(enumeration) => enumeration.take_doc(),
+            ConcreteType::Struct(structure) => structure.take_doc(),
+            ConcreteType::Union(union) => union.take_doc(),
+            _ => None,
+        }
+    }
+
     pub const fn float() -> Self {
         Self::Real(Real::Float)
     }
@@ -86,19 +122,6 @@ impl ConcreteType {
         }
     }
 
-    /// Whether the pointer needs a trailing whitespace.
-    ///
-    /// This is the case for:
-    /// - non-pointer types (e.g. `int x;`)
-    /// - pointers for which the last pointer is constant (e.g. `int *const x;`)
-    pub(crate) fn needs_trailing_whitespace(&self) -> bool {
-        if let ConcreteType::Pointer(pointer) = self {
-            pointer.needs_trailing_whitespace()
-        } else {
-            true
-        }
-    }
-
     pub fn pretty_definition<'a, 's, AllocatorT, AnnotationT>(
         self,
         alias: Identifier,
@@ -109,6 +132,18 @@ impl ConcreteType {
         AllocatorT::Doc: Clone,
         AnnotationT: Clone + 'a,
     {
+        if let ConcreteType::Const(ty) = self {
+            return match *ty {
+                ty @ ConcreteType::Pointer(_) => {
+                    ty.into_const().pretty_definition(alias, allocator)
+                }
+                ty => allocator
+                    .text("const")
+                    .append(allocator.space())
+                    .append(ty.pretty_definition(alias, allocator)),
+            };
+        }
+
         let base_type = self.base_type();
         match self {
             ConcreteType::Array(array) => {
@@ -126,6 +161,21 @@ impl ConcreteType {
                     builder
                         .append(alias)
                         .append(function.pretty_signature_end(allocator))
+                } else if let (
+                    OpaqueType::ConcreteType(ConcreteType::Array(pointee)),
+                    ConcreteType::Pointer(pointer),
+                ) = (base_type, array.innermost_element_type())
+                {
+                    // An array of pointers to arrays, e.g. `int (*rows[2])[3]`
+                    pointee
+                        .innermost_element_type()
+                        .pretty(allocator)
+                        .append(allocator.space())
+                        .append(allocator.text("("))
+                        .append(pointer.pretty_pointers(allocator))
+                        .append(alias)
+                        .append(allocator.text(")"))
+                        .append(pointee.pretty_dimensions(allocator))
                 } else {
                     array
                         .innermost_element_type()
@@ -151,6 +201,25 @@ impl ConcreteType {
                     builder
                         .append(allocator.text(alias))
                         .append(function.pretty_signature_end(allocator))
+                } else if let OpaqueType::ConcreteType(ConcreteType::Array(array)) = base_type {
+                    // A pointer to an array, e.g. `int (*row)[3]`
+                    let builder = array
+                        .innermost_element_type()
+                        .pretty(allocator)
+                        .append(allocator.space())
+                        .append(allocator.text("("))
+                        .append(pointer.pretty_pointers(allocator));
+
+                    let builder = if needs_trailing_whitespace {
+                        builder.append(allocator.space())
+                    } else {
+                        builder
+                    };
+
+                    builder
+                        .append(allocator.text(alias))
+                        .append(allocator.text(")"))
+                        .append(array.pretty_dimensions(allocator))
                 } else {
                     let builder = pointer.pretty(allocator);
 
@@ -169,23 +238,6 @@ impl ConcreteType {
                 .append(allocator.text(alias)),
         }
     }
-
-    /// Pretty prints the dimensions of the array type, if it is an array.
-    pub(crate) fn pretty_dimensions<'a, AllocatorT, AnnotationT>(
-        &self,
-        allocator: &'a AllocatorT,
-    ) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT>
-    where
-        AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
-        AllocatorT::Doc: Clone,
-        AnnotationT: Clone + 'a,
-    {
-        if let ConcreteType::Array(array) = self {
-            array.pretty_dimensions(allocator)
-        } else {
-            allocator.nil()
-        }
-    }
 }
 
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for ConcreteType
@@ -197,7 +249,15 @@ where
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
         match self {
             ConcreteType::Array(array) => array.pretty(allocator),
+            ConcreteType::Bool => allocator.text(Standard::current().bool_keyword()),
             ConcreteType::Char => allocator.text("char"),
+            ConcreteType::Const(ty) => match *ty {
+                ty @ ConcreteType::Pointer(_) => ty.into_const().pretty(allocator),
+                ty => allocator
+                    .text("const")
+                    .append(allocator.space())
+                    .append(ty.pretty(allocator)),
+            },
             ConcreteType::Enum(enumeration) => enumeration.pretty(allocator),
             ConcreteType::Integer(integer) => allocator.text(integer.to_string()),
             ConcreteType::Pointer(pointer) => allocator.text(pointer.to_string()),
@@ -206,6 +266,7 @@ where
             ConcreteType::StrongInt(integer) => allocator.text(integer.to_string()),
             ConcreteType::Struct(structure) => structure.pretty(allocator),
             ConcreteType::Typedef(typedef) => allocator.text(typedef.alias),
+            ConcreteType::TypedefName(name) => allocator.text(name),
             ConcreteType::Union(union) => union.pretty(allocator),
             ConcreteType::Void => allocator.text("void"),
         }
@@ -1,17 +1,72 @@
+use core::ops::RangeInclusive;
+
 use pretty::Pretty;
 
-use crate::{non_empty_vec::NonEmptyVec, pretty::impl_display_via_pretty, Expression, Identifier};
+use crate::{
+    inference::{self, Environment},
+    layout::DataModel,
+    non_empty_vec::{self, NonEmptyVec},
+    operator::{BinaryOperator, BinaryOperatorKind},
+    pretty::impl_display_via_pretty,
+    r#type::IntegerKind,
+    ConcreteType, DocComment, Expression, Identifier, Value,
+};
+
+/// Errors that can occur when creating enumerators
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    NonEmptyVec(#[from] non_empty_vec::Error),
+    #[error("the value of `{0}` is out of range")]
+    Overflow(Identifier),
+    #[error("the underlying type of an enumeration must be an integer type, but `{0}` is not")]
+    UnderlyingType(Box<ConcreteType>),
+}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Enum {
     Definition {
+        doc: Option<DocComment>,
         name: Option<Identifier>,
-        values: NonEmptyVec<(Identifier, Option<Expression>)>,
+        /// The fixed underlying type of the enumeration (C23), e.g. `enum color : uint8_t { ... }`.
+        underlying_ty: Option<Box<ConcreteType>>,
+        values: NonEmptyVec<Enumerator>,
+        /// Whether a comma is emitted after the last enumerator, if the enumerators are laid out one per line.
+        trailing_comma: bool,
     },
     /// An incomplete enumeration type, only useable as pointer type. Requires a complete definition elsewhere.
     Tag { name: Identifier },
 }
 
+impl Enum {
+    /// Returns an error if the fixed underlying type is not an integer type, e.g. `enum e : float`.
+    ///
+    /// Type aliases are resolved in the environment. Aliases that it does not know, e.g. `uint8_t`, are accepted.
+    pub(crate) fn check(&self, environment: &Environment) -> Result<(), Error> {
+        match self {
+            Enum::Definition {
+                underlying_ty: Some(underlying_ty),
+                ..
+            } if !environment
+                .resolve(underlying_ty)
+                .is_ok_and(|ty| is_underlying_type(&ty)) =>
+            {
+                Err(Error::UnderlyingType(underlying_ty.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes the documentation comment of a definition, so it can be emitted in front of the enclosing declaration.
+    pub(crate) fn take_doc(&mut self) -> Option<DocComment> {
+        match self {
+            Enum::Definition { doc, .. } => doc.take(),
+            Enum::Tag { .. } => None,
+        }
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Enum
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
@@ -22,7 +77,13 @@ where
         let builder = allocator.text("enum").append(allocator.space());
 
         match self {
-            Enum::Definition { name, values } => {
+            Enum::Definition {
+                name,
+                underlying_ty,
+                values,
+                trailing_comma,
+                ..
+            } => {
                 let builder = if let Some(name) = name {
                     builder
                         .append(allocator.text(name.to_string()))
@@ -31,25 +92,38 @@ where
                     builder
                 };
 
+                let builder = if let Some(underlying_ty) = underlying_ty {
+                    builder
+                        .append(allocator.text(":"))
+                        .append(allocator.space())
+                        .append(underlying_ty.pretty(allocator))
+                        .append(allocator.space())
+                } else {
+                    builder
+                };
+
+                let trailing_comma = if trailing_comma {
+                    allocator.text(",").flat_alt(allocator.nil())
+                } else {
+                    allocator.nil()
+                };
+
+                // Enumerators are laid out on a single line if they fit, and one per line otherwise.
                 builder
                     .append(allocator.text("{"))
-                    .append(allocator.intersperse(
-                        values.into_iter().map(|(identifier, value)| {
-                            let builder = allocator.text(identifier.to_string());
-
-                            if let Some(value) = value {
-                                builder
-                                    .append(allocator.space())
-                                    .append(allocator.text("="))
-                                    .append(allocator.space())
-                                    .append(value.pretty(allocator))
-                            } else {
-                                builder
-                            }
-                        }),
-                        allocator.text(",").append(allocator.space()),
-                    ))
+                    .append(
+                        allocator
+                            .line_()
+                            .append(allocator.intersperse(
+                                values.into_iter().map(|value| value.pretty(allocator)),
+                                allocator.text(",").append(allocator.line()),
+                            ))
+                            .append(trailing_comma)
+                            .nest(2),
+                    )
+                    .append(allocator.line_())
                     .append(allocator.text("}"))
+                    .group()
             }
             Enum::Tag { name } => builder.append(allocator.text(name.to_string())),
         }
@@ -58,12 +132,208 @@ where
 
 impl_display_via_pretty!(Enum, 80);
 
+/// A named constant of an enumeration, with an optional explicit value.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Enumerator {
+    pub doc: Option<DocComment>,
+    pub name: Identifier,
+    pub value: Option<Expression>,
+}
+
+impl Enumerator {
+    /// Creates an enumerator without an explicit value.
+    pub fn new(name: Identifier) -> Self {
+        Self {
+            doc: None,
+            name,
+            value: None,
+        }
+    }
+
+    /// Creates an enumerator with an explicit value.
+    pub fn with_value<E: Into<Expression>>(name: Identifier, value: E) -> Self {
+        Self {
+            doc: None,
+            name,
+            value: Some(value.into()),
+        }
+    }
+
+    /// Creates enumerators that are explicitly numbered in ascending order, starting at `start`.
+    ///
+    /// Fails if there are no names, or if a value is out of the range of the underlying type, which is `int` without a
+    /// fixed underlying type. See [`Enumerator::bit_flags`] for the range of underlying types.
+    ///
+    /// # Examples
+    ///
+    /// - For `[RED, GREEN]` and a `start` of `1`, it returns `RED = 1, GREEN = 2`.
+    pub fn numbered<I>(
+        names: I,
+        start: i64,
+        underlying_ty: Option<&ConcreteType>,
+    ) -> Result<NonEmptyVec<Self>, Error>
+    where
+        I: IntoIterator<Item = Identifier>,
+    {
+        let range = range(underlying_ty)?;
+        let enumerators = names
+            .into_iter()
+            .zip(i128::from(start)..)
+            .map(|(name, value)| {
+                if !range.contains(&value) {
+                    return Err(Error::Overflow(name));
+                }
+
+                let value = match i64::try_from(value) {
+                    Ok(value) => Value::signed_integer(value),
+                    Err(_) => Value::unsigned_integer(value as u64),
+                };
+                Ok(Self::with_value(name, value))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(enumerators.try_into()?)
+    }
+
+    /// Creates enumerators that each represent a single bit of a flag set.
+    ///
+    /// Fails if there are no names, or if a bit is out of the range of the underlying type, which is `int` without a
+    /// fixed underlying type. E.g. there can be at most 31 names for `int`, since `1 << 31` overflows it, and 32 for
+    /// `uint32_t`.
+    ///
+    /// The underlying type must be an integer type whose width is known: the widths of `long`, `size_t` and
+    /// `intptr_t` depend on the target, so the narrowest width of the data models is used, and aliases other than the
+    /// fixed-width integer types are rejected.
+    ///
+    /// # Examples
+    ///
+    /// - For `[READ, WRITE]`, it returns `READ = 1 << 0, WRITE = 1 << 1`.
+    /// - For bits beyond the range of `int`, the shifted constant has the type `long long` or `unsigned long long`,
+    ///   e.g. `1ull << 40`.
+    pub fn bit_flags<I>(
+        names: I,
+        underlying_ty: Option<&ConcreteType>,
+    ) -> Result<NonEmptyVec<Self>, Error>
+    where
+        I: IntoIterator<Item = Identifier>,
+    {
+        let range = range(underlying_ty)?;
+        let is_signed = *range.start() < 0;
+        let enumerators = names
+            .into_iter()
+            .zip(0..)
+            .map(|(name, bit)| {
+                if bit >= u64::BITS || 1 << bit > *range.end() {
+                    return Err(Error::Overflow(name));
+                }
+
+                // `1` is an `int`, so shifting it beyond the range of `int` overflows
+                let one = if bit < i32::BITS - 1 {
+                    Value::signed_integer(1)
+                } else if is_signed {
+                    Value::SignedInteger {
+                        value: 1,
+                        kind: Some(IntegerKind::LongLong),
+                    }
+                } else if bit < u32::BITS {
+                    Value::UnsignedInteger {
+                        value: 1,
+                        kind: None,
+                    }
+                } else {
+                    Value::UnsignedInteger {
+                        value: 1,
+                        kind: Some(IntegerKind::LongLong),
+                    }
+                };
+
+                Ok(Self::with_value(
+                    name,
+                    BinaryOperator {
+                        left: one.into(),
+                        operator: BinaryOperatorKind::LShift,
+                        right: Value::signed_integer(bit.into()).into(),
+                    },
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(enumerators.try_into()?)
+    }
+}
+
+/// Whether the type can be the fixed underlying type of an enumeration: an integer type other than an enumeration.
+///
+/// Expects a resolved type. Unknown type aliases are accepted.
+fn is_underlying_type(ty: &ConcreteType) -> bool {
+    matches!(
+        ty,
+        ConcreteType::Bool
+            | ConcreteType::Char
+            | ConcreteType::Integer(_)
+            | ConcreteType::Size
+            | ConcreteType::StrongInt(_)
+            | ConcreteType::TypedefName(_)
+    )
+}
+
+/// Returns the range of values of the underlying type, or of `int` without a fixed underlying type, with the narrowest
+/// width of the data models.
+fn range(underlying_ty: Option<&ConcreteType>) -> Result<RangeInclusive<i128>, Error> {
+    let Some(underlying_ty) = underlying_ty else {
+        return Ok(i32::MIN.into()..=i32::MAX.into());
+    };
+    let unsupported = || Error::UnderlyingType(Box::new(underlying_ty.clone()));
+
+    let ty = Environment::default()
+        .resolve(underlying_ty)
+        .map_err(|_| unsupported())?;
+    if !is_underlying_type(&ty) {
+        return Err(unsupported());
+    }
+
+    let ranks = [DataModel::LP64, DataModel::LLP64, DataModel::ILP32]
+        .iter()
+        .map(|data_model| inference::integer_rank(&ty, data_model))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(unsupported)?;
+    let width = ranks.iter().map(|rank| rank.width).min().unwrap_or(0);
+
+    Ok(if ranks[0].is_signed {
+        -(1 << (width - 1))..=(1 << (width - 1)) - 1
+    } else {
+        0..=(1 << width) - 1
+    })
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Enumerator
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let builder = DocComment::pretty_preceding(self.doc, allocator)
+            .append(allocator.text(self.name.to_string()));
+
+        if let Some(value) = self.value {
+            builder
+                .append(allocator.space())
+                .append(allocator.text("="))
+                .append(allocator.space())
+                .append(value.pretty(allocator))
+        } else {
+            builder
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        operator::{BinaryOperator, BinaryOperatorKind},
-        r#type::Definition,
-        variable, Statement, Value, Variable,
+        r#type::{Definition, Integer, StrongInt},
+        variable, Statement, Variable,
     };
 
     use super::*;
@@ -71,27 +341,33 @@ mod tests {
     #[test]
     fn complete_definitions() -> anyhow::Result<()> {
         let named = Definition::from(Enum::Definition {
+            doc: None,
             name: Some(Identifier::new("fruit")?),
+            underlying_ty: None,
             values: vec![
-                (Identifier::new("grape")?, None),
-                (Identifier::new("cherry")?, None),
-                (Identifier::new("lemon")?, None),
-                (Identifier::new("kiwi")?, None),
+                Enumerator::new(Identifier::new("grape")?),
+                Enumerator::new(Identifier::new("cherry")?),
+                Enumerator::new(Identifier::new("lemon")?),
+                Enumerator::new(Identifier::new("kiwi")?),
             ]
             .try_into()?,
+            trailing_comma: false,
         })
         .to_string();
         assert_eq!(named, "enum fruit {grape, cherry, lemon, kiwi};");
 
         let specified_value = Definition::from(Enum::Definition {
+            doc: None,
             name: Some(Identifier::new("more_fruit")?),
+            underlying_ty: None,
             values: vec![
-                (Identifier::new("banana")?, Some(Value::signed_integer(-17).into())),
-                (Identifier::new("apple")?, None),
-                (Identifier::new("blueberry")?, None),
-                (Identifier::new("mango")?, None),
+                Enumerator::with_value(Identifier::new("banana")?, Value::signed_integer(-17)),
+                Enumerator::new(Identifier::new("apple")?),
+                Enumerator::new(Identifier::new("blueberry")?),
+                Enumerator::new(Identifier::new("mango")?),
             ]
             .try_into()?,
+            trailing_comma: false,
         })
         .to_string();
         assert_eq!(
@@ -100,24 +376,24 @@ mod tests {
         );
 
         let specified_expression = Definition::from(Enum::Definition {
+            doc: None,
             name: Some(Identifier::new("yet_more_fruit")?),
+            underlying_ty: None,
             values: vec![
-                (Identifier::new("kumquat")?, None),
-                (Identifier::new("raspberry")?, None),
-                (Identifier::new("peach")?, None),
-                (
+                Enumerator::new(Identifier::new("kumquat")?),
+                Enumerator::new(Identifier::new("raspberry")?),
+                Enumerator::new(Identifier::new("peach")?),
+                Enumerator::with_value(
                     Identifier::new("plum")?,
-                    Some(
-                        BinaryOperator {
-                            left: Variable::new("peach")?.into(),
-                            operator: BinaryOperatorKind::Add,
-                            right: Value::signed_integer(2).into(),
-                        }
-                        .into(),
-                    ),
+                    BinaryOperator {
+                        left: Variable::new("peach")?.into(),
+                        operator: BinaryOperatorKind::Add,
+                        right: Value::signed_integer(2).into(),
+                    },
                 ),
             ]
             .try_into()?,
+            trailing_comma: false,
         })
         .to_string();
         assert_eq!(
@@ -142,16 +418,21 @@ mod tests {
     #[test]
     fn declarations() -> anyhow::Result<()> {
         let inline = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Enum::Definition {
+                doc: None,
                 name: Some(Identifier::new("fruit")?),
+                underlying_ty: None,
                 values: vec![
-                    (Identifier::new("banana")?, None),
-                    (Identifier::new("apple")?, None),
-                    (Identifier::new("blueberry")?, None),
-                    (Identifier::new("mango")?, None),
+                    Enumerator::new(Identifier::new("banana")?),
+                    Enumerator::new(Identifier::new("apple")?),
+                    Enumerator::new(Identifier::new("blueberry")?),
+                    Enumerator::new(Identifier::new("mango")?),
                 ]
                 .try_into()?,
+                trailing_comma: false,
             }
             .into(),
             identifier: Identifier::new("my_fruit")?,
@@ -164,7 +445,9 @@ mod tests {
         );
 
         let tag = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Enum::Tag {
                 name: Identifier::new("fruit")?,
             }
@@ -181,16 +464,21 @@ mod tests {
     #[test]
     fn initializers() -> anyhow::Result<()> {
         let generated = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Enum::Definition {
+                doc: None,
                 name: Some(Identifier::new("fruit")?),
+                underlying_ty: None,
                 values: vec![
-                    (Identifier::new("banana")?, None),
-                    (Identifier::new("apple")?, None),
-                    (Identifier::new("blueberry")?, None),
-                    (Identifier::new("mango")?, None),
+                    Enumerator::new(Identifier::new("banana")?),
+                    Enumerator::new(Identifier::new("apple")?),
+                    Enumerator::new(Identifier::new("blueberry")?),
+                    Enumerator::new(Identifier::new("mango")?),
                 ]
                 .try_into()?,
+                trailing_comma: false,
             }
             .into(),
             identifier: Identifier::new("my_fruit")?,
@@ -204,4 +492,253 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn underlying_type() -> anyhow::Result<()> {
+        let generated = Definition::from(Enum::Definition {
+            doc: None,
+            name: Some(Identifier::new("color")?),
+            underlying_ty: Some(Box::new(StrongInt::Uint8.into())),
+            values: vec![
+                Enumerator::new(Identifier::new("RED")?),
+                Enumerator::new(Identifier::new("GREEN")?),
+            ]
+            .try_into()?,
+            trailing_comma: false,
+        })
+        .to_string();
+        assert_eq!(generated, "enum color : uint8_t {RED, GREEN};");
+
+        Ok(())
+    }
+
+    #[test]
+    fn vertical_layout() -> anyhow::Result<()> {
+        let generated = Definition::from(Enum::Definition {
+            doc: None,
+            name: Some(Identifier::new("register_address")?),
+            underlying_ty: None,
+            values: Enumerator::numbered(
+                [
+                    "REGISTER_CONTROL",
+                    "REGISTER_STATUS",
+                    "REGISTER_INTERRUPT_MASK",
+                    "REGISTER_DATA",
+                ]
+                .into_iter()
+                .map(Identifier::new)
+                .collect::<Result<Vec<_>, _>>()?,
+                16,
+                None,
+            )?,
+            trailing_comma: true,
+        })
+        .to_string();
+        assert_eq!(
+            generated,
+            r#"enum register_address {
+  REGISTER_CONTROL = 16,
+  REGISTER_STATUS = 17,
+  REGISTER_INTERRUPT_MASK = 18,
+  REGISTER_DATA = 19,
+};"#
+        );
+
+        // The trailing comma is left out if the enumerators fit on one line
+        let generated = Definition::from(Enum::Definition {
+            doc: None,
+            name: Some(Identifier::new("color")?),
+            underlying_ty: None,
+            values: Enumerator::numbered(
+                [Identifier::new("RED")?, Identifier::new("GREEN")?],
+                0,
+                None,
+            )?,
+            trailing_comma: true,
+        })
+        .to_string();
+        assert_eq!(generated, "enum color {RED = 0, GREEN = 1};");
+
+        Ok(())
+    }
+
+    #[test]
+    fn bit_flags() -> anyhow::Result<()> {
+        let generated = Definition::from(Enum::Definition {
+            doc: None,
+            name: Some(Identifier::new("permission")?),
+            underlying_ty: None,
+            values: Enumerator::bit_flags(
+                [
+                    Identifier::new("READ")?,
+                    Identifier::new("WRITE")?,
+                    Identifier::new("EXECUTE")?,
+                ],
+                None,
+            )?,
+            trailing_comma: false,
+        })
+        .to_string();
+        assert_eq!(
+            generated,
+            "enum permission {READ = 1 << 0, WRITE = 1 << 1, EXECUTE = 1 << 2};"
+        );
+
+        let names = (0..32)
+            .map(|bit| Identifier::new(format!("FLAG_{bit}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert!(Enumerator::bit_flags(names[..31].to_vec(), None).is_ok());
+        assert_eq!(
+            Enumerator::bit_flags(names.clone(), None)
+                .unwrap_err()
+                .to_string(),
+            "the value of `FLAG_31` is out of range"
+        );
+        assert!(matches!(
+            Enumerator::bit_flags(Vec::new(), None),
+            Err(Error::NonEmptyVec(_))
+        ));
+
+        // Bits beyond the range of `int` shift wider constants
+        let last_flag = |count: usize, underlying_ty: ConcreteType| -> Result<String, Error> {
+            let values = Enumerator::bit_flags(names[..count].to_vec(), Some(&underlying_ty))?;
+            let value = values
+                .iter()
+                .last()
+                .and_then(|enumerator| enumerator.value.as_ref());
+            Ok(value.map(ToString::to_string).unwrap_or_default())
+        };
+        let integer = |kind, is_signed| ConcreteType::Integer(Integer { kind, is_signed });
+        assert_eq!(last_flag(32, StrongInt::Uint32.into())?, "1u << 31");
+        assert_eq!(
+            last_flag(32, integer(IntegerKind::LongLong, true))?,
+            "1ll << 31"
+        );
+        assert!(last_flag(8, StrongInt::Uint8.into()).is_ok());
+        assert!(last_flag(9, StrongInt::Uint8.into()).is_err());
+        // `long` has 32 bits on some targets
+        assert!(last_flag(32, integer(IntegerKind::Long, true)).is_err());
+
+        let names = (0..65)
+            .map(|bit| Identifier::new(format!("FLAG_{bit}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let unsigned_long_long = integer(IntegerKind::LongLong, false);
+        let values = Enumerator::bit_flags(names[..64].to_vec(), Some(&unsigned_long_long))?;
+        assert_eq!(
+            values
+                .iter()
+                .last()
+                .and_then(|enumerator| enumerator.value.as_ref())
+                .map(ToString::to_string),
+            Some("1ull << 63".to_string())
+        );
+        assert!(Enumerator::bit_flags(names.clone(), Some(&unsigned_long_long)).is_err());
+
+        // The underlying type must be an integer type of known width
+        for underlying_ty in [
+            ConcreteType::float(),
+            ConcreteType::TypedefName(Identifier::new("flags_t")?),
+        ] {
+            assert!(matches!(
+                Enumerator::bit_flags(names[..1].to_vec(), Some(&underlying_ty)),
+                Err(Error::UnderlyingType(_))
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn documented_enumerators() -> anyhow::Result<()> {
+        let generated = Definition::from(Enum::Definition {
+            doc: None,
+            name: Some(Identifier::new("status")?),
+            underlying_ty: None,
+            values: vec![
+                Enumerator {
+                    doc: Some(DocComment::new("The operation succeeded.")),
+                    ..Enumerator::new(Identifier::new("OK")?)
+                },
+                Enumerator::new(Identifier::new("FAILED")?),
+            ]
+            .try_into()?,
+            trailing_comma: false,
+        })
+        .to_string();
+        assert_eq!(
+            generated,
+            r#"enum status {
+  /** The operation succeeded. */
+  OK,
+  FAILED
+};"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn numbering() -> anyhow::Result<()> {
+        let names = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| Identifier::new(*name))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let values = Enumerator::numbered(names(&["MAX"])?, i32::MAX.into(), None)?;
+        assert_eq!(
+            values
+                .iter()
+                .next()
+                .and_then(|enumerator| enumerator.value.clone()),
+            Some(Value::signed_integer(i32::MAX.into()).into())
+        );
+
+        let result = Enumerator::numbered(names(&["MAX", "BEYOND"])?, i32::MAX.into(), None);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "the value of `BEYOND` is out of range"
+        );
+
+        // The range of the underlying type applies instead of that of `int`
+        let unsigned_long_long = ConcreteType::Integer(Integer {
+            kind: IntegerKind::LongLong,
+            is_signed: false,
+        });
+        let values = Enumerator::numbered(
+            names(&["BEFORE", "MAX", "BEYOND"])?,
+            i64::MAX - 1,
+            Some(&unsigned_long_long),
+        )?;
+        assert_eq!(
+            values
+                .iter()
+                .map(|enumerator| enumerator.value.clone())
+                .collect::<Vec<_>>(),
+            [
+                Some(Value::signed_integer(i64::MAX - 1).into()),
+                Some(Value::signed_integer(i64::MAX).into()),
+                Some(Value::unsigned_integer(i64::MAX as u64 + 1).into()),
+            ]
+        );
+        assert!(
+            Enumerator::numbered(names(&["NEGATIVE"])?, -1, Some(&unsigned_long_long)).is_err()
+        );
+        assert!(
+            Enumerator::numbered(names(&["MAX"])?, 255, Some(&StrongInt::Uint8.into())).is_ok()
+        );
+        assert!(Enumerator::numbered(
+            names(&["MAX", "BEYOND"])?,
+            255,
+            Some(&StrongInt::Uint8.into())
+        )
+        .is_err());
+        assert!(matches!(
+            Enumerator::numbered(Vec::new(), 0, None),
+            Err(Error::NonEmptyVec(_))
+        ));
+
+        Ok(())
+    }
 }
@@ -1,16 +1,60 @@
 use pretty::Pretty as _;
 
-use crate::function::FunctionParameter;
+use crate::function::{self, FunctionParameter};
 
-use super::ConcreteType;
+use super::{ConcreteType, OpaqueType};
 
-#[derive(Clone, Debug)]
+/// # Source
+///
+/// https://en.cppreference.com/w/c/language/function_declaration
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Function {
+    /// The parameters of the function. An empty list is printed as `(void)`, because `()` declares a function with
+    /// unspecified parameters before C23.
     pub parameters: Vec<FunctionParameter>,
     pub return_ty: ConcreteType,
+    /// Whether the function takes further arguments after its parameters, i.e. `(const char *format, ...)`. Variadic
+    /// functions without parameters, i.e. `(...)`, require C23.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_variadic: bool,
+    /// Whether the parameters are left unspecified, i.e. `()` rather than `(void)`. Only applies to functions without
+    /// parameters, and since C23 both declare a function without parameters.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub has_unspecified_parameters: bool,
 }
 
 impl Function {
+    /// Returns an error if the return type cannot be printed before the name, i.e. if it is an array or a pointer to
+    /// a function or array.
+    pub(crate) fn check(&self) -> Result<(), function::Error> {
+        let is_array = |ty: &ConcreteType| match ty {
+            ConcreteType::Const(ty) => matches!(**ty, ConcreteType::Array(_)),
+            ty => matches!(ty, ConcreteType::Array(_)),
+        };
+        let return_ty = match &self.return_ty {
+            ConcreteType::Const(ty) => ty,
+            ty => ty,
+        };
+
+        let is_supported = match return_ty {
+            ConcreteType::Array(_) => false,
+            ConcreteType::Pointer(pointer) => match pointer.base_type() {
+                OpaqueType::ConcreteType(ty) => !is_array(&ty),
+                OpaqueType::Function(_) => false,
+            },
+            _ => true,
+        };
+
+        if is_supported {
+            Ok(())
+        } else {
+            Err(function::Error::ReturnType(Box::new(
+                self.return_ty.clone(),
+            )))
+        }
+    }
+
     pub fn pretty_parameters<'a, AllocatorT, AnnotationT>(
         &self,
         allocator: &'a AllocatorT,
@@ -20,17 +64,24 @@ impl Function {
         AllocatorT::Doc: Clone,
         AnnotationT: Clone + 'a,
     {
-        allocator
-            .text("(")
-            .append(
-                allocator.intersperse(
-                    self.parameters
-                        .iter()
-                        .map(|parameter| parameter.clone().pretty(allocator)),
-                    allocator.text(",").append(allocator.space()),
-                ),
+        let ellipsis = self.is_variadic.then(|| allocator.text("..."));
+        let parameters = if self.parameters.is_empty() {
+            match ellipsis {
+                Some(ellipsis) => ellipsis,
+                None if self.has_unspecified_parameters => allocator.nil(),
+                None => allocator.text("void"),
+            }
+        } else {
+            allocator.intersperse(
+                self.parameters
+                    .iter()
+                    .map(|parameter| parameter.clone().pretty(allocator))
+                    .chain(ellipsis),
+                allocator.text(",").append(allocator.space()),
             )
-            .append(allocator.text(")"))
+        };
+
+        parameters.parens()
     }
 
     pub fn pretty_return_type<'a, AllocatorT, AnnotationT>(
@@ -2,7 +2,8 @@ use pretty::Pretty;
 
 use crate::{Expression, Identifier};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum InitializerList {
     // TODO: Use a map that maintains insertion order to avoid duplication
     Named(Vec<(Identifier, Expression)>),
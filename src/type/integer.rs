@@ -1,6 +1,7 @@
 use core::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Integer {
     pub kind: IntegerKind,
     pub is_signed: bool,
@@ -23,7 +24,8 @@ impl fmt::Display for Integer {
 /// # Source
 ///
 /// https://www.gnu.org/software/gnu-c-manual/gnu-c-manual.html#Integer-Types
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum IntegerKind {
     Char,
     Short,
@@ -56,7 +58,8 @@ impl fmt::Display for IntegerKind {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum StrongInt {
     Int16,
     Int32,
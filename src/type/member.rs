@@ -1,10 +1,11 @@
 use pretty::Pretty;
 
-use crate::{Expression, Identifier};
+use crate::{DocComment, Expression, Identifier, Standard};
 
 use super::ConcreteType;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct IndirectMemberAccess {
     pub left: Expression,
     pub member: Identifier,
@@ -24,7 +25,8 @@ where
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct MemberAccess {
     pub left: Expression,
     pub member: Identifier,
@@ -44,11 +46,15 @@ where
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Member {
+    pub doc: Option<DocComment>,
     pub ty: ConcreteType,
     pub name: Identifier,
     pub bit_field_size: Option<usize>,
+    /// The alignment specifier of the member, e.g. `_Alignas(64)`.
+    pub alignment: Option<Expression>,
 }
 
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Member
@@ -58,7 +64,17 @@ where
     AnnotationT: Clone + 'a,
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
-        let definition = self.ty.pretty_definition(self.name, allocator);
+        let builder = DocComment::pretty_preceding(self.doc, allocator);
+
+        let builder = if let Some(alignment) = self.alignment {
+            builder
+                .append(allocator.text(Standard::current().alignment_specifier(&alignment)))
+                .append(allocator.space())
+        } else {
+            builder
+        };
+
+        let definition = builder.append(self.ty.pretty_definition(self.name, allocator));
 
         let builder = if let Some(size) = self.bit_field_size {
             definition
@@ -4,7 +4,8 @@ use super::{
     Array, ConcreteType, Enum, Function, Integer, Pointer, Real, StrongInt, Struct, Union,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum OpaqueType {
     ConcreteType(ConcreteType),
     Function(Function),
@@ -4,15 +4,16 @@ use crate::{pretty::impl_display_via_pretty, ConcreteType};
 
 use super::OpaqueType;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Pointer {
     pub pointer_ty: OpaqueType,
     pub is_const: bool,
 }
 
 impl Pointer {
+    /// Returns the type that the pointer chain points to, e.g. `int[3]` for `int (**)[3]`.
     pub fn base_type(&self) -> OpaqueType {
-        // TODO: Handle array
         match &self.pointer_ty {
             OpaqueType::ConcreteType(ConcreteType::Pointer(pointer)) => pointer.base_type(),
             ty => ty.clone(),
@@ -78,6 +79,14 @@ where
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
         match self.base_type() {
+            OpaqueType::ConcreteType(ConcreteType::Array(array)) => array
+                .innermost_element_type()
+                .pretty(allocator)
+                .append(allocator.space())
+                .append(allocator.text("("))
+                .append(self.pretty_pointers(allocator))
+                .append(allocator.text(")"))
+                .append(array.pretty_dimensions(allocator)),
             OpaqueType::ConcreteType(base_type) => base_type
                 .pretty(allocator)
                 .append(allocator.space())
@@ -86,8 +95,8 @@ where
                 let return_type = function.pretty_return_type(allocator);
                 let parameters = function.pretty_parameters(allocator);
 
+                // The return type ends with a space
                 return_type
-                    .append(allocator.space())
                     .append(allocator.text("("))
                     .append(self.pretty_pointers(allocator))
                     .append(allocator.text(")"))
@@ -1,9 +1,13 @@
 use core::fmt;
 
+use crate::Standard;
+
 use super::{integer::Integer, IntegerKind, Pointer, Real, StrongInt};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Scalar {
+    Bool,
     Char,
     Integer(Integer),
     Pointer(Pointer),
@@ -24,6 +28,7 @@ impl Scalar {
 impl fmt::Display for Scalar {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Scalar::Bool => write!(f, "{}", Standard::current().bool_keyword()),
             Scalar::Char => write!(f, "char"),
             Scalar::Integer(integer) => write!(f, "{integer}"),
             Scalar::Pointer(pointer) => write!(f, "{pointer}"),
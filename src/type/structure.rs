@@ -1,12 +1,15 @@
 use pretty::Pretty;
 
-use crate::{pretty::impl_display_via_pretty, Identifier};
+use crate::{pretty::impl_display_via_pretty, DocComment, Expression, Identifier, Standard};
 
 use super::member::Member;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Struct {
     Definition {
+        doc: Option<DocComment>,
+        attributes: StructAttributes,
         name: Option<Identifier>,
         members: Vec<Member>,
     },
@@ -14,6 +17,47 @@ pub enum Struct {
     Tag { name: Identifier },
 }
 
+/// Controls the layout of a structure or union, which are spelled as GNU attributes, e.g.
+/// `struct __attribute__((packed)) s`.
+///
+/// To pack a region of definitions instead, use [`Pragma`](crate::statement::Pragma).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct StructAttributes {
+    /// `packed`: members are not aligned, unless they have an explicit alignment.
+    pub packed: bool,
+    /// `aligned(N)`: the minimum alignment of the structure or union.
+    pub aligned: Option<Box<Expression>>,
+}
+
+impl StructAttributes {
+    pub fn is_empty(&self) -> bool {
+        !self.packed && self.aligned.is_none()
+    }
+
+    pub(super) fn to_gnu_attributes(&self) -> Vec<String> {
+        let mut attributes = Vec::new();
+        if self.packed {
+            attributes.push("packed".to_string());
+        }
+        if let Some(aligned) = &self.aligned {
+            attributes.push(format!("aligned({aligned})"));
+        }
+
+        attributes
+    }
+}
+
+impl Struct {
+    /// Removes the documentation comment of a definition, so it can be emitted in front of the enclosing declaration.
+    pub(crate) fn take_doc(&mut self) -> Option<DocComment> {
+        match self {
+            Struct::Definition { doc, .. } => doc.take(),
+            Struct::Tag { .. } => None,
+        }
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Struct
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
@@ -24,7 +68,22 @@ where
         let builder = allocator.text("struct").append(allocator.space());
 
         match self {
-            Struct::Definition { name, members } => {
+            Struct::Definition {
+                attributes,
+                name,
+                members,
+                ..
+            } => {
+                let builder = if attributes.is_empty() {
+                    builder
+                } else {
+                    builder
+                        .append(allocator.text(
+                            Standard::current().gnu_attributes(&attributes.to_gnu_attributes()),
+                        ))
+                        .append(allocator.space())
+                };
+
                 let builder = if let Some(name) = name {
                     builder
                         .append(allocator.text(name.to_string()))
@@ -35,14 +94,14 @@ where
 
                 builder
                     .append(allocator.text("{"))
-                    .append(allocator.hardline())
                     .append(
-                        allocator.intersperse(
-                            members.into_iter().map(|member| {
-                                allocator.text("  ").append(member.pretty(allocator))
-                            }),
-                            allocator.hardline(),
-                        ),
+                        allocator
+                            .hardline()
+                            .append(allocator.intersperse(
+                                members.into_iter().map(|member| member.pretty(allocator)),
+                                allocator.hardline(),
+                            ))
+                            .nest(2),
                     )
                     .append(allocator.hardline())
                     .append(allocator.text("}"))
@@ -67,17 +126,23 @@ mod tests {
     #[test]
     fn complete_definitions() -> anyhow::Result<()> {
         let multi_line = Definition::from(Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
             name: Some(Identifier::new("point")?),
             members: vec![
                 Member {
+                    doc: None,
                     ty: ConcreteType::int(),
                     name: Identifier::new("x")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
                 Member {
+                    doc: None,
                     ty: ConcreteType::int(),
                     name: Identifier::new("y")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
             ],
         })
@@ -108,19 +173,27 @@ mod tests {
     fn declarations() -> anyhow::Result<()> {
         // Test inline declaration
         let inline = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Struct::Definition {
+                doc: None,
+                attributes: Default::default(),
                 name: Some(Identifier::new("point")?),
                 members: vec![
                     Member {
+                        doc: None,
                         ty: ConcreteType::int(),
                         name: Identifier::new("x")?,
                         bit_field_size: None,
+                        alignment: None,
                     },
                     Member {
+                        doc: None,
                         ty: ConcreteType::int(),
                         name: Identifier::new("y")?,
                         bit_field_size: None,
+                        alignment: None,
                     },
                 ],
             }
@@ -138,7 +211,9 @@ mod tests {
         );
 
         let tag = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Struct::Tag {
                 name: Identifier::new("point")?,
             }
@@ -155,22 +230,29 @@ mod tests {
     #[test]
     fn initializers() -> anyhow::Result<()> {
         let ordered = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Struct::Tag {
                 name: Identifier::new("point")?,
             }
             .into(),
             identifier: Identifier::new("first_point")?,
             initializer: Some(
-                InitializerList::Ordered(vec![Value::signed_integer(5).into(), Value::signed_integer(10).into()])
-                    .into(),
+                InitializerList::Ordered(vec![
+                    Value::signed_integer(5).into(),
+                    Value::signed_integer(10).into(),
+                ])
+                .into(),
             ),
         })
         .to_string();
         assert_eq!(ordered, "struct point first_point = { 5, 10 };");
 
         let named = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Struct::Tag {
                 name: Identifier::new("point")?,
             }
@@ -188,7 +270,9 @@ mod tests {
         assert_eq!(named, "struct point first_point = { .y = 10, .x = 5 };");
 
         let nested = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Struct::Tag {
                 name: Identifier::new("rectangle")?,
             }
@@ -222,17 +306,23 @@ mod tests {
     #[test]
     fn bit_fields() -> anyhow::Result<()> {
         let multi_line = Definition::from(Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
             name: Some(Identifier::new("card")?),
             members: vec![
                 Member {
+                    doc: None,
                     ty: ConcreteType::unsigned_int(),
                     name: Identifier::new("suit")?,
                     bit_field_size: Some(2),
+                    alignment: None,
                 },
                 Member {
+                    doc: None,
                     ty: ConcreteType::unsigned_int(),
                     name: Identifier::new("face_value")?,
                     bit_field_size: Some(4),
+                    alignment: None,
                 },
             ],
         })
@@ -251,9 +341,12 @@ mod tests {
     #[test]
     fn function_pointer_member() -> anyhow::Result<()> {
         let generated = Definition::from(Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
             name: Some(Identifier::new("with_pointers")?),
             members: vec![
                 Member {
+                    doc: None,
                     ty: Pointer {
                         pointer_ty: Function {
                             parameters: vec![
@@ -267,6 +360,8 @@ mod tests {
                                 },
                             ],
                             return_ty: ConcreteType::int(),
+                            is_variadic: false,
+                            has_unspecified_parameters: false,
                         }
                         .into(),
                         is_const: false,
@@ -274,12 +369,16 @@ mod tests {
                     .into(),
                     name: Identifier::new("mutable")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
                 Member {
+                    doc: None,
                     ty: Pointer {
                         pointer_ty: Function {
                             parameters: vec![],
                             return_ty: ConcreteType::Void,
+                            is_variadic: false,
+                            has_unspecified_parameters: false,
                         }
                         .into(),
                         is_const: true,
@@ -287,6 +386,7 @@ mod tests {
                     .into(),
                     name: Identifier::new("immutable")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
             ],
         })
@@ -295,7 +395,7 @@ mod tests {
             generated,
             r#"struct with_pointers {
   int (*mutable)(int, int);
-  void (*const immutable)();
+  void (*const immutable)(void);
 };"#
         );
 
@@ -305,9 +405,12 @@ mod tests {
     #[test]
     fn pointer_member() -> anyhow::Result<()> {
         let generated = Definition::from(Struct::Definition {
+            doc: None,
+            attributes: Default::default(),
             name: Some(Identifier::new("with_pointers")?),
             members: vec![
                 Member {
+                    doc: None,
                     ty: Pointer {
                         pointer_ty: ConcreteType::int().into(),
                         is_const: false,
@@ -315,8 +418,10 @@ mod tests {
                     .into(),
                     name: Identifier::new("mutable")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
                 Member {
+                    doc: None,
                     ty: Pointer {
                         pointer_ty: ConcreteType::Void.into(),
                         is_const: true,
@@ -324,6 +429,7 @@ mod tests {
                     .into(),
                     name: Identifier::new("immutable")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
             ],
         })
@@ -338,4 +444,90 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn documented_members() -> anyhow::Result<()> {
+        let generated = Definition::from(Struct::Definition {
+            doc: Some(DocComment::new("A point in two-dimensional space.")),
+            attributes: Default::default(),
+            name: Some(Identifier::new("point")?),
+            members: vec![
+                Member {
+                    doc: Some(DocComment::new("The horizontal position, measured in pixels from the left edge of the screen.")),
+                    ty: ConcreteType::int(),
+                    name: Identifier::new("x")?,
+                    bit_field_size: None,
+                    alignment: None,
+                },
+                Member {
+                    doc: Some(DocComment::new("The vertical position.")),
+                    ty: ConcreteType::int(),
+                    name: Identifier::new("y")?,
+                    bit_field_size: None,
+                    alignment: None,
+                },
+            ],
+        })
+        .to_string();
+        assert_eq!(
+            generated,
+            r#"/** A point in two-dimensional space. */
+struct point {
+  /**
+   * The horizontal position, measured in pixels from the left edge of the
+   * screen.
+   */
+  int x;
+  /** The vertical position. */
+  int y;
+};"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn attributes() -> anyhow::Result<()> {
+        let header = Definition::from(Struct::Definition {
+            doc: None,
+            attributes: StructAttributes {
+                packed: true,
+                aligned: Some(Box::new(Value::signed_integer(4).into())),
+            },
+            name: Some(Identifier::new("header")?),
+            members: vec![
+                Member {
+                    doc: None,
+                    ty: ConcreteType::Char,
+                    name: Identifier::new("tag")?,
+                    bit_field_size: None,
+                    alignment: None,
+                },
+                Member {
+                    doc: None,
+                    ty: ConcreteType::int(),
+                    name: Identifier::new("length")?,
+                    bit_field_size: None,
+                    alignment: Some(Value::signed_integer(8).into()),
+                },
+            ],
+        });
+
+        assert_eq!(
+            Standard::Gnu17.scope(|| header.to_string()),
+            r#"struct __attribute__((packed, aligned(4))) header {
+  char tag;
+  _Alignas(8) int length;
+};"#
+        );
+        assert_eq!(
+            Standard::C23.scope(|| header.to_string()),
+            r#"struct __attribute__((packed, aligned(4))) header {
+  char tag;
+  alignas(8) int length;
+};"#
+        );
+
+        Ok(())
+    }
 }
@@ -1,12 +1,17 @@
 use pretty::Pretty;
 
-use crate::{non_empty_vec::NonEmptyVec, pretty::impl_display_via_pretty, Identifier};
+use crate::{
+    non_empty_vec::NonEmptyVec, pretty::impl_display_via_pretty, DocComment, Identifier, Standard,
+};
 
-use super::member::Member;
+use super::{member::Member, StructAttributes};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Union {
     Definition {
+        doc: Option<DocComment>,
+        attributes: StructAttributes,
         name: Option<Identifier>,
         members: NonEmptyVec<Member>,
     },
@@ -14,6 +19,16 @@ pub enum Union {
     Tag { name: Identifier },
 }
 
+impl Union {
+    /// Removes the documentation comment of a definition, so it can be emitted in front of the enclosing declaration.
+    pub(crate) fn take_doc(&mut self) -> Option<DocComment> {
+        match self {
+            Union::Definition { doc, .. } => doc.take(),
+            Union::Tag { .. } => None,
+        }
+    }
+}
+
 impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Union
 where
     AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
@@ -24,7 +39,22 @@ where
         let builder = allocator.text("union").append(allocator.space());
 
         match self {
-            Union::Definition { name, members } => {
+            Union::Definition {
+                attributes,
+                name,
+                members,
+                ..
+            } => {
+                let builder = if attributes.is_empty() {
+                    builder
+                } else {
+                    builder
+                        .append(allocator.text(
+                            Standard::current().gnu_attributes(&attributes.to_gnu_attributes()),
+                        ))
+                        .append(allocator.space())
+                };
+
                 let builder = if let Some(name) = name {
                     builder
                         .append(allocator.text(name.to_string()))
@@ -35,14 +65,14 @@ where
 
                 builder
                     .append(allocator.text("{"))
-                    .append(allocator.hardline())
                     .append(
-                        allocator.intersperse(
-                            members.into_iter().map(|member| {
-                                allocator.text("  ").append(member.pretty(allocator))
-                            }),
-                            allocator.hardline(),
-                        ),
+                        allocator
+                            .hardline()
+                            .append(allocator.intersperse(
+                                members.into_iter().map(|member| member.pretty(allocator)),
+                                allocator.hardline(),
+                            ))
+                            .nest(2),
                     )
                     .append(allocator.hardline())
                     .append(allocator.text("}"))
@@ -68,17 +98,23 @@ mod tests {
     #[test]
     fn complete_definitions() -> anyhow::Result<()> {
         let multi_line = Definition::from(Union::Definition {
+            doc: None,
+            attributes: Default::default(),
             name: Some(Identifier::new("numbers")?),
             members: vec![
                 Member {
+                    doc: None,
                     ty: ConcreteType::int(),
                     name: Identifier::new("i")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
                 Member {
+                    doc: None,
                     ty: ConcreteType::float(),
                     name: Identifier::new("f")?,
                     bit_field_size: None,
+                    alignment: None,
                 },
             ]
             .try_into()?,
@@ -110,19 +146,27 @@ mod tests {
     fn declarations() -> anyhow::Result<()> {
         // Test inline declaration
         let inline = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Union::Definition {
+                doc: None,
+                attributes: Default::default(),
                 name: Some(Identifier::new("numbers")?),
                 members: vec![
                     Member {
+                        doc: None,
                         ty: ConcreteType::int(),
                         name: Identifier::new("i")?,
                         bit_field_size: None,
+                        alignment: None,
                     },
                     Member {
+                        doc: None,
                         ty: ConcreteType::float(),
                         name: Identifier::new("f")?,
                         bit_field_size: None,
+                        alignment: None,
                     },
                 ]
                 .try_into()?,
@@ -141,7 +185,9 @@ mod tests {
         );
 
         let tag = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Union::Tag {
                 name: Identifier::new("numbers")?,
             }
@@ -158,19 +204,25 @@ mod tests {
     #[test]
     fn initializers() -> anyhow::Result<()> {
         let ordered = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Union::Tag {
                 name: Identifier::new("numbers")?,
             }
             .into(),
             identifier: Identifier::new("first_number")?,
-            initializer: Some(InitializerList::Ordered(vec![Value::signed_integer(5).into()]).into()),
+            initializer: Some(
+                InitializerList::Ordered(vec![Value::signed_integer(5).into()]).into(),
+            ),
         })
         .to_string();
         assert_eq!(ordered, "union numbers first_number = { 5 };");
 
         let named = Statement::from(variable::Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Union::Tag {
                 name: Identifier::new("numbers")?,
             }
@@ -196,17 +248,23 @@ mod tests {
     #[test]
     fn bit_fields() -> anyhow::Result<()> {
         let multi_line = Definition::from(Union::Definition {
+            doc: None,
+            attributes: Default::default(),
             name: Some(Identifier::new("numbers")?),
             members: vec![
                 Member {
+                    doc: None,
                     ty: ConcreteType::unsigned_int(),
                     name: Identifier::new("ui")?,
                     bit_field_size: Some(2),
+                    alignment: None,
                 },
                 Member {
+                    doc: None,
                     ty: ConcreteType::int(),
                     name: Identifier::new("i")?,
                     bit_field_size: Some(4),
+                    alignment: None,
                 },
             ]
             .try_into()?,
@@ -0,0 +1,1454 @@
+//! Semantic checks for code that is well-formed in Rust, but not in C.
+
+use core::fmt;
+use std::collections::HashSet;
+
+use crate::{
+    constant::EvaluationError,
+    file::FileLevelStatement,
+    function::{self, FunctionParameter},
+    identifier,
+    inference::Environment,
+    operator::{PostfixOperatorKind, PrefixOperatorKind, SizeOf},
+    r#type::{self, enumeration, Array, ArraySize, Enum, InitializerList, OpaqueType},
+    scope::{self, SymbolTable},
+    statement::ForDeclaration,
+    value,
+    variable::{self, Declarator},
+    visit::{self, Visitor},
+    Block, ConcreteType, Expression, FunctionDefinition, Identifier, Standard, Statement,
+    StorageClass, Value, Variable, VariableDeclaration, VariableDeclarationList,
+};
+
+/// A semantic error in the generated code.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub kind: Kind,
+    /// The location of the offending node.
+    pub path: Path,
+    /// The generated C code of the offending construct.
+    pub snippet: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.path, self.kind)?;
+
+        for line in self.snippet.lines() {
+            writeln!(f, "    {line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Kind {
+    #[error("the operand of {operation} must be an lvalue")]
+    NotAnLvalue { operation: &'static str },
+    /// The operand is an lvalue, but an array, a function or `const`-qualified.
+    #[error("the operand of {operation} must be a modifiable lvalue")]
+    NotModifiable { operation: &'static str },
+    #[error("`break` must be inside a loop or `switch`")]
+    BreakOutsideLoop,
+    #[error("`continue` must be inside a loop")]
+    ContinueOutsideLoop,
+    #[error("label `{0}` is not defined in the enclosing function")]
+    UndefinedLabel(Identifier),
+    #[error("label `{0}` is defined more than once in the enclosing function")]
+    DuplicateLabel(Identifier),
+    #[error("`{0}` must be inside a function")]
+    OutsideFunction(&'static str),
+    #[error("a function returning `void` cannot return a value")]
+    ReturnValueInVoidFunction,
+    #[error("a function returning a value must return a value")]
+    MissingReturnValue,
+    #[error("`{0}` is not declared")]
+    UndeclaredIdentifier(Identifier),
+    #[error("`{0}` is already declared in the same scope")]
+    Redeclaration(Identifier),
+    /// A file-scope redeclaration whose type is not compatible with the previous declaration, e.g. `int x; double x;`.
+    #[error("`{0}` is redeclared with an incompatible type")]
+    IncompatibleRedeclaration(Identifier),
+    /// A file-scope redeclaration whose linkage differs from the previous declaration, e.g. `extern int x;` followed
+    /// by `static int x;`.
+    #[error("`{0}` is redeclared with a different linkage")]
+    LinkageRedeclaration(Identifier),
+    /// The declaration is legal, but hides a declaration of an enclosing scope.
+    #[error("`{0}` shadows a declaration of an enclosing scope")]
+    Shadowing(Identifier),
+    /// The identifier was created for another standard, in which it is not a keyword.
+    #[error("`{0}` is a keyword")]
+    Keyword(Identifier),
+    /// The declaration is legal, but the name is reserved for the implementation or the standard library, see
+    /// [`Identifier::is_reserved`].
+    #[error("`{0}` is a reserved identifier")]
+    ReservedIdentifier(Identifier),
+    /// An array dimension other than the outermost one has an unspecified size, e.g. `int a[2][]`.
+    #[error("only the outermost dimension of an array can have an unspecified size")]
+    IncompleteArrayElement,
+    /// An object with static storage duration, i.e. at file scope or declared `static` or `extern`, has a
+    /// variable-length array type, e.g. `int a[n];` at file scope.
+    #[error("`{0}` has static storage duration, so its array lengths must be constant")]
+    VariableLengthArrayWithStaticStorage(Identifier),
+    /// `[*]` outside the parameters of a function declaration that is not a definition.
+    #[error("`[*]` can only be used in the parameters of a function declaration")]
+    StarOutsidePrototype,
+    /// `[static N]` outside the outermost dimension of a function parameter.
+    #[error("`[static N]` can only be used in the outermost dimension of a function parameter")]
+    StaticOutsideParameter,
+    /// The return type of a function cannot be printed before its name, e.g. a pointer to a function. See
+    /// [`function::Error::ReturnType`].
+    #[error(
+        "functions returning `{0}` are not supported, use a type alias for the return type instead"
+    )]
+    UnsupportedReturnType(Box<ConcreteType>),
+    /// The fixed underlying type of an enumeration is not an integer type, e.g. `enum e : float`.
+    #[error("the underlying type of an enumeration must be an integer type, but `{0}` is not")]
+    UnderlyingType(Box<ConcreteType>),
+}
+
+/// The location of a node, as a sequence of field names and indices starting at the file.
+///
+/// # Examples
+///
+/// - `statements[1].body.statements[0].left`
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Path(pub(crate) Vec<Segment>);
+
+impl Path {
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+}
+
+impl From<Vec<Segment>> for Path {
+    fn from(segments: Vec<Segment>) -> Self {
+        Self(segments)
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Field(name) if index == 0 => write!(f, "{name}")?,
+                Segment::Field(name) => write!(f, ".{name}")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Segment {
+    Field(&'static str),
+    Index(usize),
+}
+
+pub(crate) fn validate(statements: &[FileLevelStatement]) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        diagnostics: Vec::new(),
+        path: Vec::new(),
+        symbols: scope::resolve(statements),
+        function: None,
+        loops: 0,
+        switches: 0,
+    };
+
+    validator.at_index("statements", |validator, index| {
+        if let Some(statement) = statements.get(index) {
+            validator.file_level_statement(statement);
+            validator.keywords(statement);
+            validator.array_dimensions(statement);
+            validator.array_placements(statement);
+            validator.underlying_types(statement);
+            validator.return_types(statement);
+            true
+        } else {
+            false
+        }
+    });
+
+    validator.diagnostics
+}
+
+/// The innermost function that is being validated.
+struct Function {
+    returns_void: bool,
+    labels: HashSet<Identifier>,
+    /// `goto` statements with their location, which are resolved at the end of the function.
+    gotos: Vec<(Identifier, Path)>,
+}
+
+struct Validator {
+    diagnostics: Vec<Diagnostic>,
+    path: Vec<Segment>,
+    /// The declarations of the file, for the types and values of the names that expressions refer to.
+    symbols: SymbolTable,
+    function: Option<Function>,
+    loops: usize,
+    switches: usize,
+}
+
+impl Validator {
+    fn report(&mut self, kind: Kind, snippet: impl fmt::Display) {
+        self.diagnostics.push(Diagnostic {
+            kind,
+            path: Path(self.path.clone()),
+            snippet: snippet.to_string(),
+        });
+    }
+
+    fn at<R>(&mut self, field: &'static str, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.path.push(Segment::Field(field));
+        let result = f(self);
+        self.path.pop();
+
+        result
+    }
+
+    /// Calls `f` with increasing indices of the field until it returns `false`.
+    fn at_index(&mut self, field: &'static str, mut f: impl FnMut(&mut Self, usize) -> bool) {
+        self.path.push(Segment::Field(field));
+
+        for index in 0.. {
+            self.path.push(Segment::Index(index));
+            let has_next = f(self, index);
+            self.path.pop();
+
+            if !has_next {
+                break;
+            }
+        }
+
+        self.path.pop();
+    }
+
+    fn file_level_statement(&mut self, statement: &FileLevelStatement) {
+        match statement {
+            FileLevelStatement::FunctionDefinition(definition) => self.function(definition),
+            FileLevelStatement::StaticAssert(assertion) => {
+                self.at("condition", |validator| {
+                    validator.expression(&assertion.condition)
+                });
+            }
+            FileLevelStatement::VariableDeclaration(declaration) => {
+                self.variable_declaration(declaration)
+            }
+            FileLevelStatement::VariableDeclarationList(declaration) => {
+                self.variable_declaration_list(declaration)
+            }
+            FileLevelStatement::Comment(_)
+            | FileLevelStatement::Define(_)
+            | FileLevelStatement::FunctionDeclaration(_)
+            | FileLevelStatement::Include(_)
+            | FileLevelStatement::Pragma(_)
+            | FileLevelStatement::TypeDefinition(_)
+            | FileLevelStatement::Typedef(_) => {}
+        }
+    }
+
+    /// Reports the identifiers in the statement that are keywords of the current standard.
+    fn keywords(&mut self, statement: &FileLevelStatement) {
+        let mut identifiers = Identifiers::default();
+        identifiers.visit_file_level_statement(statement);
+
+        for identifier in identifiers.0 {
+            if Standard::current().is_keyword(identifier.as_ref()) {
+                self.report(Kind::Keyword(identifier), statement);
+            }
+        }
+    }
+
+    /// Reports the arrays in the statement whose elements are arrays of unspecified size.
+    fn array_dimensions(&mut self, statement: &FileLevelStatement) {
+        let mut arrays = IncompleteArrayElements::default();
+        arrays.visit_file_level_statement(statement);
+
+        for _ in 0..arrays.0 {
+            self.report(Kind::IncompleteArrayElement, statement);
+        }
+    }
+
+    /// Reports the `[*]` and `[static N]` array sizes in the statement that are not in a function parameter.
+    fn array_placements(&mut self, statement: &FileLevelStatement) {
+        let mut placements = ArrayPlacements::default();
+        placements.visit_file_level_statement(statement);
+
+        for kind in placements.kinds {
+            self.report(kind, statement);
+        }
+    }
+
+    /// Reports the enumerations in the statement whose fixed underlying type is not an integer type.
+    fn underlying_types(&mut self, statement: &FileLevelStatement) {
+        let mut enumerations = UnderlyingTypes::new(self.environment());
+        enumerations.visit_file_level_statement(statement);
+
+        for error in enumerations.errors {
+            if let enumeration::Error::UnderlyingType(ty) = error {
+                self.report(Kind::UnderlyingType(ty), statement);
+            }
+        }
+    }
+
+    /// Reports the function types in the statement whose return type cannot be printed before the name.
+    fn return_types(&mut self, statement: &FileLevelStatement) {
+        let mut functions = ReturnTypes::default();
+        functions.visit_file_level_statement(statement);
+
+        for function::Error::ReturnType(ty) in functions.0 {
+            self.report(Kind::UnsupportedReturnType(ty), statement);
+        }
+    }
+
+    /// Returns the declarations that are visible to the statement or expression at the current path.
+    fn environment(&self) -> Environment {
+        self.symbols
+            .environment(&Path(self.path.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Reports an object with static storage duration whose type has a non-constant array length.
+    fn static_storage(
+        &mut self,
+        name: &Identifier,
+        storage_class: Option<&StorageClass>,
+        ty: &ConcreteType,
+        dimensions: &[ArraySize],
+        snippet: impl fmt::Display,
+    ) {
+        let has_static_storage = self.function.is_none()
+            || matches!(
+                storage_class,
+                Some(StorageClass::Static | StorageClass::Extern)
+            );
+        if !has_static_storage {
+            return;
+        }
+
+        let environment = self.environment();
+        let is_variable_length = array_lengths(ty)
+            .chain(dimensions.iter().filter_map(length))
+            .any(|length| {
+                matches!(
+                    environment.evaluate(length),
+                    Err(EvaluationError::NotConstant(_))
+                )
+            });
+        if is_variable_length {
+            self.report(
+                Kind::VariableLengthArrayWithStaticStorage(name.clone()),
+                snippet,
+            );
+        }
+    }
+
+    fn function(&mut self, definition: &FunctionDefinition) {
+        self.function = Some(Function {
+            returns_void: matches!(definition.ty.return_ty, ConcreteType::Void),
+            labels: HashSet::new(),
+            gotos: Vec::new(),
+        });
+
+        self.at("body", |validator| validator.block(&definition.body));
+
+        let function = self.function.take().expect("function is set above");
+        for (label, path) in function.gotos {
+            if !function.labels.contains(&label) {
+                self.diagnostics.push(Diagnostic {
+                    snippet: format!("goto {label};"),
+                    kind: Kind::UndefinedLabel(label),
+                    path,
+                });
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.at_index("statements", |validator, index| {
+            if let Some(statement) = block.statements.get(index) {
+                validator.statement(statement);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    fn statements(&mut self, field: &'static str, statements: &[Statement]) {
+        self.at_index(field, |validator, index| {
+            if let Some(statement) = statements.get(index) {
+                validator.statement(statement);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::Label(label) => {
+                if let Some(function) = &mut self.function {
+                    if !function.labels.insert(label.identifier.clone()) {
+                        self.report(
+                            Kind::DuplicateLabel(label.identifier.clone()),
+                            format!("{}:", label.identifier),
+                        );
+                    }
+                } else {
+                    self.report(
+                        Kind::OutsideFunction("label"),
+                        format!("{}:", label.identifier),
+                    );
+                }
+
+                if let Some(statement) = &label.statement {
+                    self.at("statement", |validator| validator.statement(statement));
+                }
+            }
+            Statement::If(if_stmt) => {
+                self.at("condition", |validator| {
+                    validator.expression(&if_stmt.condition)
+                });
+                self.at("then_statement", |validator| {
+                    validator.statement(&if_stmt.then_statement)
+                });
+                if let Some(else_statement) = &if_stmt.else_statement {
+                    self.at("else_statement", |validator| {
+                        validator.statement(else_statement)
+                    });
+                }
+            }
+            Statement::Switch(switch) => {
+                self.at("condition", |validator| {
+                    validator.expression(&switch.condition)
+                });
+
+                self.switches += 1;
+                self.at_index("cases", |validator, index| {
+                    if let Some((case, statements)) = switch.cases.get(index) {
+                        validator.at("case", |validator| validator.expression(case));
+                        validator.statements("statements", statements);
+                        true
+                    } else {
+                        false
+                    }
+                });
+                if let Some(default) = &switch.default {
+                    self.statements("default", default);
+                }
+                self.switches -= 1;
+            }
+            Statement::While(while_stmt) => {
+                self.at("condition", |validator| {
+                    validator.expression(&while_stmt.condition)
+                });
+                self.loop_body(&while_stmt.body);
+            }
+            Statement::Do(do_stmt) => {
+                self.loop_body(&do_stmt.body);
+                self.at("condition", |validator| {
+                    validator.expression(&do_stmt.condition)
+                });
+            }
+            Statement::For(for_stmt) => {
+                if let Some(init) = &for_stmt.init {
+                    self.at("init", |validator| match init {
+                        ForDeclaration::Expression(expression) => validator.expression(expression),
+                        ForDeclaration::VariableDeclaration(declaration) => {
+                            validator.variable_declaration(declaration)
+                        }
+                        ForDeclaration::VariableDeclarationList(declaration) => {
+                            validator.variable_declaration_list(declaration)
+                        }
+                    });
+                }
+                self.at("condition", |validator| {
+                    validator.expression(&for_stmt.condition)
+                });
+                if let Some(step) = &for_stmt.step {
+                    self.at("step", |validator| validator.expression(step));
+                }
+                self.loop_body(&for_stmt.body);
+            }
+            Statement::Block(block) => self.block(block),
+            Statement::Break => {
+                if self.loops == 0 && self.switches == 0 {
+                    self.report(Kind::BreakOutsideLoop, statement);
+                }
+            }
+            Statement::Continue => {
+                if self.loops == 0 {
+                    self.report(Kind::ContinueOutsideLoop, statement);
+                }
+            }
+            Statement::Goto(goto) => {
+                if let Some(function) = &mut self.function {
+                    function
+                        .gotos
+                        .push((goto.label.clone(), Path(self.path.clone())));
+                } else {
+                    self.report(Kind::OutsideFunction("goto"), statement);
+                }
+            }
+            Statement::Return(return_stmt) => {
+                match (&self.function, &return_stmt.expression) {
+                    (None, _) => self.report(Kind::OutsideFunction("return"), statement),
+                    (Some(function), Some(_)) if function.returns_void => {
+                        self.report(Kind::ReturnValueInVoidFunction, statement)
+                    }
+                    (Some(function), None) if !function.returns_void => {
+                        self.report(Kind::MissingReturnValue, statement)
+                    }
+                    _ => {}
+                }
+
+                if let Some(expression) = &return_stmt.expression {
+                    self.at("expression", |validator| validator.expression(expression));
+                }
+            }
+            Statement::StaticAssert(assertion) => {
+                self.at("condition", |validator| {
+                    validator.expression(&assertion.condition)
+                });
+            }
+            Statement::VariableDeclaration(declaration) => self.variable_declaration(declaration),
+            Statement::VariableDeclarationList(declaration) => {
+                self.variable_declaration_list(declaration)
+            }
+            Statement::Comment(_)
+            | Statement::Define(_)
+            | Statement::FunctionDeclaration(_)
+            | Statement::Include(_)
+            | Statement::Pragma(_)
+            | Statement::Null
+            | Statement::TypeDefinition(_)
+            | Statement::Typedef(_) => {}
+        }
+    }
+
+    fn loop_body(&mut self, body: &Statement) {
+        self.loops += 1;
+        // `break` inside a loop inside a `switch` belongs to the loop
+        let switches = core::mem::take(&mut self.switches);
+
+        self.at("body", |validator| validator.statement(body));
+
+        self.switches = switches;
+        self.loops -= 1;
+    }
+
+    fn variable_declaration(&mut self, declaration: &VariableDeclaration) {
+        self.static_storage(
+            &declaration.identifier,
+            declaration.storage_class.as_ref(),
+            &declaration.ty,
+            &[],
+            declaration,
+        );
+
+        if let Some(initializer) = &declaration.initializer {
+            self.at("initializer", |validator| validator.expression(initializer));
+        }
+    }
+
+    fn variable_declaration_list(&mut self, declaration: &VariableDeclarationList) {
+        let declarators = declaration.declarators.iter().collect::<Vec<_>>();
+
+        for declarator in &declarators {
+            self.static_storage(
+                &declarator.identifier,
+                declaration.storage_class.as_ref(),
+                &declaration.ty,
+                &declarator.dimensions,
+                declaration,
+            );
+        }
+
+        self.at_index("declarators", |validator, index| {
+            if let Some(declarator) = declarators.get(index) {
+                if let Some(initializer) = &declarator.initializer {
+                    validator.at("initializer", |validator| validator.expression(initializer));
+                }
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Reports an error if the operand of the expression is not an lvalue.
+    fn require_lvalue(
+        &mut self,
+        operand_field: &'static str,
+        operand: &Expression,
+        operation: &'static str,
+        expression: &Expression,
+    ) -> bool {
+        let is_lvalue = is_lvalue(operand);
+        if !is_lvalue {
+            self.at(operand_field, |validator| {
+                validator.report(Kind::NotAnLvalue { operation }, expression)
+            });
+        }
+
+        is_lvalue
+    }
+
+    /// Reports an error if the operand of the expression is not an lvalue, or is an array, a function or `const`.
+    ///
+    /// Operands whose type is unknown, e.g. because they are declared in a header, are assumed to be modifiable.
+    fn require_modifiable_lvalue(
+        &mut self,
+        operand_field: &'static str,
+        operand: &Expression,
+        operation: &'static str,
+        expression: &Expression,
+    ) {
+        if !self.require_lvalue(operand_field, operand, operation, expression) {
+            return;
+        }
+
+        if let Ok(false) = self.environment().is_modifiable(operand) {
+            self.at(operand_field, |validator| {
+                validator.report(Kind::NotModifiable { operation }, expression)
+            });
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::AlignOf(_)
+            | Expression::OffsetOf(_)
+            | Expression::Value(_)
+            | Expression::Variable(_) => {}
+            Expression::ArraySubscript(subscript) => {
+                self.at("array", |validator| validator.expression(&subscript.array));
+                self.at("index", |validator| validator.expression(&subscript.index));
+            }
+            Expression::Assignment(assignment) => {
+                self.require_modifiable_lvalue(
+                    "left",
+                    &assignment.left,
+                    "an assignment",
+                    expression,
+                );
+                self.at("left", |validator| validator.expression(&assignment.left));
+                self.at("right", |validator| validator.expression(&assignment.right));
+            }
+            Expression::BinaryOperator(operation) => {
+                self.at("left", |validator| validator.expression(&operation.left));
+                self.at("right", |validator| validator.expression(&operation.right));
+            }
+            Expression::Cast(cast) => {
+                self.at("expression", |validator| {
+                    validator.expression(&cast.expression)
+                });
+            }
+            Expression::CommaOperator(comma) => {
+                self.at("left", |validator| validator.expression(&comma.left));
+                self.at("right", |validator| validator.expression(&comma.right));
+            }
+            Expression::CompoundAssignment(assignment) => {
+                self.require_modifiable_lvalue(
+                    "left",
+                    &assignment.left,
+                    "a compound assignment",
+                    expression,
+                );
+                self.at("left", |validator| validator.expression(&assignment.left));
+                self.at("right", |validator| validator.expression(&assignment.right));
+            }
+            Expression::Conditional(conditional) => {
+                self.at("condition", |validator| {
+                    validator.expression(&conditional.condition)
+                });
+                self.at("then_branch", |validator| {
+                    validator.expression(&conditional.then_branch)
+                });
+                self.at("else_branch", |validator| {
+                    validator.expression(&conditional.else_branch)
+                });
+            }
+            Expression::FunctionCall(call) => {
+                self.at("callee", |validator| validator.expression(&call.callee));
+                self.at_index("arguments", |validator, index| {
+                    if let Some(argument) = call.arguments.get(index) {
+                        validator.expression(argument);
+                        true
+                    } else {
+                        false
+                    }
+                });
+            }
+            Expression::IndirectMemberAccess(access) => {
+                self.at("left", |validator| validator.expression(&access.left));
+            }
+            Expression::InitializerList(initializer_list) => {
+                self.initializer_list(initializer_list)
+            }
+            Expression::MemberAccess(access) => {
+                self.at("left", |validator| validator.expression(&access.left));
+            }
+            Expression::Parentheses(inner) => self.expression(inner),
+            Expression::PostfixOperator(operation) => {
+                let name = match operation.operator {
+                    PostfixOperatorKind::Increment => "an increment",
+                    PostfixOperatorKind::Decrement => "a decrement",
+                };
+                self.require_modifiable_lvalue("operand", &operation.operand, name, expression);
+                self.at("operand", |validator| {
+                    validator.expression(&operation.operand)
+                });
+            }
+            Expression::PrefixOperator(operation) => {
+                match operation.operator {
+                    PrefixOperatorKind::Increment => {
+                        self.require_modifiable_lvalue(
+                            "operand",
+                            &operation.operand,
+                            "an increment",
+                            expression,
+                        );
+                    }
+                    PrefixOperatorKind::Decrement => {
+                        self.require_modifiable_lvalue(
+                            "operand",
+                            &operation.operand,
+                            "a decrement",
+                            expression,
+                        );
+                    }
+                    // String literals are lvalues, but cannot be modified
+                    PrefixOperatorKind::Address
+                        if !matches!(operation.operand, Expression::Value(Value::String(_))) =>
+                    {
+                        self.require_lvalue(
+                            "operand",
+                            &operation.operand,
+                            "the address operator",
+                            expression,
+                        );
+                    }
+                    _ => {}
+                }
+                self.at("operand", |validator| {
+                    validator.expression(&operation.operand)
+                });
+            }
+            Expression::SizeOf(sizeof) => {
+                if let SizeOf::Expression(inner) = sizeof.as_ref() {
+                    self.at("expression", |validator| validator.expression(inner));
+                }
+            }
+        }
+    }
+
+    fn initializer_list(&mut self, initializer_list: &InitializerList) {
+        let initializers = match initializer_list {
+            InitializerList::Named(initializers) => initializers
+                .iter()
+                .map(|(_, initializer)| initializer)
+                .collect::<Vec<_>>(),
+            InitializerList::Ordered(initializers) => initializers.iter().collect(),
+        };
+
+        self.at_index("initializers", |validator, index| {
+            if let Some(initializer) = initializers.get(index) {
+                validator.expression(initializer);
+                true
+            } else {
+                false
+            }
+        });
+    }
+}
+
+/// Returns an error for the first identifier in the statements that is a keyword of the standard, e.g. `bool` in C23.
+pub(crate) fn keywords(
+    statements: &[FileLevelStatement],
+    standard: Standard,
+) -> Result<(), identifier::Error> {
+    let mut identifiers = Identifiers::default();
+    for statement in statements {
+        identifiers.visit_file_level_statement(statement);
+    }
+
+    match identifiers
+        .0
+        .into_iter()
+        .find(|identifier| standard.is_keyword(identifier.as_ref()))
+    {
+        Some(keyword) => Err(identifier::Error::Keyword(keyword.into())),
+        None => Ok(()),
+    }
+}
+
+/// Returns an error for the first declaration list in the statements whose shared type is a pointer or array type.
+pub(crate) fn declaration_lists(statements: &[FileLevelStatement]) -> Result<(), variable::Error> {
+    let mut lists = DeclarationLists(Ok(()));
+    for statement in statements {
+        lists.visit_file_level_statement(statement);
+    }
+
+    lists.0
+}
+
+/// Returns an error for the first value in the statements that cannot be written as a C constant, e.g. infinity.
+pub(crate) fn values(statements: &[FileLevelStatement]) -> Result<(), value::Error> {
+    let mut values = Values(Ok(()));
+    for statement in statements {
+        values.visit_file_level_statement(statement);
+    }
+
+    values.0
+}
+
+/// Returns an error for the first enumeration in the statements whose fixed underlying type is not an integer type.
+pub(crate) fn underlying_types(
+    statements: &[FileLevelStatement],
+) -> Result<(), enumeration::Error> {
+    let symbols = scope::resolve(statements);
+
+    for (index, statement) in statements.iter().enumerate() {
+        let path = Path::from(vec![Segment::Field("statements"), Segment::Index(index)]);
+        let mut enumerations = UnderlyingTypes::new(symbols.environment(&path).unwrap_or_default());
+        enumerations.visit_file_level_statement(statement);
+
+        if let Some(error) = enumerations.errors.into_iter().next() {
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns an error for the first function type in the statements whose return type cannot be printed before the
+/// name, e.g. a pointer to a function.
+pub(crate) fn return_types(statements: &[FileLevelStatement]) -> Result<(), function::Error> {
+    let mut functions = ReturnTypes::default();
+    for statement in statements {
+        functions.visit_file_level_statement(statement);
+    }
+
+    match functions.0.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Checks the values of constants, stopping at the first error.
+struct Values(Result<(), value::Error>);
+
+impl Visitor for Values {
+    fn visit_value(&mut self, value: &Value) {
+        if self.0.is_ok() {
+            self.0 = value.check();
+            visit::walk_value(self, value);
+        }
+    }
+}
+
+/// Checks the shared types of declaration lists, stopping at the first error.
+struct DeclarationLists(Result<(), variable::Error>);
+
+impl Visitor for DeclarationLists {
+    fn visit_variable_declaration_list(&mut self, declaration: &VariableDeclarationList) {
+        if self.0.is_ok() {
+            self.0 = declaration.check();
+            visit::walk_variable_declaration_list(self, declaration);
+        }
+    }
+}
+
+/// Checks the return types of function types.
+#[derive(Default)]
+struct ReturnTypes(Vec<function::Error>);
+
+impl Visitor for ReturnTypes {
+    fn visit_function_type(&mut self, function: &r#type::Function) {
+        if let Err(error) = function.check() {
+            self.0.push(error);
+        }
+
+        visit::walk_function_type(self, function);
+    }
+}
+
+/// Checks the fixed underlying types of enumerations, with the declarations that precede the statement.
+struct UnderlyingTypes {
+    environment: Environment,
+    errors: Vec<enumeration::Error>,
+}
+
+impl UnderlyingTypes {
+    fn new(environment: Environment) -> Self {
+        Self {
+            environment,
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Visitor for UnderlyingTypes {
+    fn visit_enum(&mut self, enumeration: &Enum) {
+        if let Err(error) = enumeration.check(&self.environment) {
+            self.errors.push(error);
+        }
+
+        visit::walk_enum(self, enumeration);
+    }
+}
+
+/// Collects the distinct identifiers of a statement, in source order.
+///
+/// Uses of `bool`, `true` and `false` are left out: they refer to `<stdbool.h>` before C23 and to the keywords of the
+/// same meaning since.
+#[derive(Default)]
+struct Identifiers(Vec<Identifier>);
+
+impl Identifiers {
+    fn is_boolean(name: &Identifier) -> bool {
+        matches!(name.as_ref(), "bool" | "true" | "false")
+    }
+}
+
+impl Visitor for Identifiers {
+    fn visit_identifier(&mut self, identifier: &Identifier) {
+        if !self.0.contains(identifier) {
+            self.0.push(identifier.clone());
+        }
+    }
+
+    fn visit_variable(&mut self, variable: &Variable) {
+        if !Self::is_boolean(variable) {
+            visit::walk_variable(self, variable);
+        }
+    }
+
+    fn visit_concrete_type(&mut self, ty: &ConcreteType) {
+        match ty {
+            ConcreteType::TypedefName(name) if Self::is_boolean(name) => {}
+            _ => visit::walk_concrete_type(self, ty),
+        }
+    }
+}
+
+/// Counts the arrays whose elements are arrays of unspecified size, including the dimensions of declarators.
+#[derive(Default)]
+struct IncompleteArrayElements(usize);
+
+impl Visitor for IncompleteArrayElements {
+    fn visit_array(&mut self, array: &Array) {
+        let mut element_type = &*array.element_type;
+        while let ConcreteType::Const(inner) = element_type {
+            element_type = inner;
+        }
+        if let ConcreteType::Array(Array {
+            size: ArraySize::Unspecified,
+            ..
+        }) = element_type
+        {
+            self.0 += 1;
+        }
+
+        visit::walk_array(self, array);
+    }
+
+    fn visit_declarator(&mut self, declarator: &Declarator) {
+        self.0 += declarator
+            .dimensions
+            .iter()
+            .skip(1)
+            .filter(|size| matches!(size, ArraySize::Unspecified))
+            .count();
+
+        visit::walk_declarator(self, declarator);
+    }
+}
+
+/// Reports `[*]` outside the parameters of function declarations, and `[static N]` outside the outermost dimension of
+/// function parameters.
+#[derive(Default)]
+struct ArrayPlacements {
+    kinds: Vec<Kind>,
+    /// Whether the visited types are in the parameters of a function declaration that is not a definition.
+    in_prototype: bool,
+}
+
+impl ArrayPlacements {
+    fn parameters(&mut self, function: &r#type::Function, in_prototype: bool) {
+        let outer = core::mem::replace(&mut self.in_prototype, false);
+        self.visit_concrete_type(&function.return_ty);
+
+        self.in_prototype = in_prototype;
+        for parameter in &function.parameters {
+            self.visit_function_parameter(parameter);
+        }
+
+        self.in_prototype = outer;
+    }
+}
+
+impl Visitor for ArrayPlacements {
+    fn visit_function_definition(&mut self, definition: &FunctionDefinition) {
+        // The parameters of a definition are not in a prototype, but can still be `[static N]`
+        self.parameters(&definition.ty, false);
+        self.visit_block(&definition.body);
+    }
+
+    fn visit_function_type(&mut self, function: &r#type::Function) {
+        self.parameters(function, true);
+    }
+
+    fn visit_function_parameter(&mut self, parameter: &FunctionParameter) {
+        match &parameter.ty {
+            ConcreteType::Array(Array {
+                element_type,
+                size: ArraySize::Static(length),
+            }) => {
+                self.visit_concrete_type(element_type);
+                self.visit_expression(length);
+            }
+            _ => visit::walk_function_parameter(self, parameter),
+        }
+    }
+
+    fn visit_array_size(&mut self, size: &ArraySize) {
+        match size {
+            ArraySize::Star if !self.in_prototype => self.kinds.push(Kind::StarOutsidePrototype),
+            ArraySize::Static(_) => self.kinds.push(Kind::StaticOutsideParameter),
+            _ => {}
+        }
+
+        visit::walk_array_size(self, size);
+    }
+}
+
+/// Returns the lengths of the arrays that the type consists of, including arrays that it points to.
+fn array_lengths(ty: &ConcreteType) -> Box<dyn Iterator<Item = &Expression> + '_> {
+    match ty {
+        ConcreteType::Array(array) => Box::new(
+            length(&array.size)
+                .into_iter()
+                .chain(array_lengths(&array.element_type)),
+        ),
+        ConcreteType::Const(ty) => array_lengths(ty),
+        ConcreteType::Pointer(pointer) => match &pointer.pointer_ty {
+            OpaqueType::ConcreteType(ty) => array_lengths(ty),
+            OpaqueType::Function(_) => Box::new(core::iter::empty()),
+        },
+        _ => Box::new(core::iter::empty()),
+    }
+}
+
+fn length(size: &ArraySize) -> Option<&Expression> {
+    match size {
+        ArraySize::Length(length) | ArraySize::Static(length) => Some(length),
+        ArraySize::Star | ArraySize::Unspecified => None,
+    }
+}
+
+/// Whether the expression designates an object.
+fn is_lvalue(expression: &Expression) -> bool {
+    match expression {
+        Expression::ArraySubscript(_)
+        | Expression::IndirectMemberAccess(_)
+        | Expression::Variable(_) => true,
+        Expression::MemberAccess(access) => is_lvalue(&access.left),
+        Expression::Parentheses(inner) => is_lvalue(inner),
+        Expression::PrefixOperator(operation) => {
+            matches!(operation.operator, PrefixOperatorKind::Indirection)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operator::{Assignment, PostfixOperator},
+        r#type::Function,
+        statement::{Goto, Label, Return, While},
+        CFileBuilder,
+    };
+
+    fn main(statements: Vec<Statement>) -> anyhow::Result<CFileBuilder> {
+        let mut file = CFileBuilder::default();
+        file.add_statement(FunctionDefinition {
+            doc: None,
+            is_static: false,
+            is_inline: false,
+            name: Identifier::new("main")?,
+            ty: Function {
+                parameters: Vec::new(),
+                return_ty: ConcreteType::Void,
+                is_variadic: false,
+                has_unspecified_parameters: false,
+            },
+            body: Block { statements },
+        });
+
+        Ok(file)
+    }
+
+    #[test]
+    fn lvalues() -> anyhow::Result<()> {
+        let file = main(vec![
+            Expression::from(Assignment {
+                left: Identifier::new("x")?.into(),
+                right: Value::signed_integer(1).into(),
+            })
+            .into(),
+            Expression::from(Assignment {
+                left: Value::signed_integer(5).into(),
+                right: Identifier::new("x")?.into(),
+            })
+            .into(),
+            Expression::from(PostfixOperator {
+                operand: Value::signed_integer(1).into(),
+                operator: PostfixOperatorKind::Increment,
+            })
+            .into(),
+        ])?;
+
+        let diagnostics = file.validate();
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(
+            diagnostics[0].kind,
+            Kind::NotAnLvalue {
+                operation: "an assignment"
+            }
+        );
+        assert_eq!(
+            diagnostics[0].path.to_string(),
+            "statements[0].body.statements[1].left"
+        );
+        assert_eq!(diagnostics[0].snippet, "5 = x");
+
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "statements[0].body.statements[2].operand: the operand of an increment must be an lvalue\n    1++\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn modifiable_lvalues() -> anyhow::Result<()> {
+        let file = crate::parse::parse_file(
+            "struct s { const int fixed; int free; };
+            typedef const int constant;
+            int g(int x);
+            void f(const int *p, int *q) {
+              int a[2]; const int c = 0; constant t = 0; struct s v; const struct s w;
+              a = 0; c = 1; g = 0; c++; --c; t += 1; *p = 1; p[0] = 1; v.fixed = 1; w.free = 1;
+              *q = 1; q[0] = 1; v.free = 1; a[0] = 1; p = q; int *r = &a[0]; r = &c;
+            }",
+        )?
+        .file;
+
+        let diagnostics = file.validate();
+        let operations = diagnostics
+            .iter()
+            .map(|diagnostic| match diagnostic.kind {
+                Kind::NotModifiable { operation } => Ok(operation),
+                ref kind => Err(kind.clone()),
+            })
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            operations,
+            Ok(vec![
+                "an assignment",
+                "an assignment",
+                "an assignment",
+                "an increment",
+                "a decrement",
+                "a compound assignment",
+                "an assignment",
+                "an assignment",
+                "an assignment",
+                "an assignment",
+            ])
+        );
+        assert_eq!(diagnostics[0].snippet, "a = 0");
+        assert_eq!(
+            diagnostics[0].path.to_string(),
+            "statements[3].body.statements[5].left"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn control_flow() -> anyhow::Result<()> {
+        let file = main(vec![
+            Statement::Break,
+            Statement::While(Box::new(While {
+                condition: Value::signed_integer(1).into(),
+                body: Block {
+                    statements: vec![Statement::Break, Statement::Continue],
+                }
+                .into(),
+            })),
+            Return {
+                expression: Some(Value::signed_integer(0).into()),
+            }
+            .into(),
+        ])?;
+
+        let kinds = file
+            .validate()
+            .into_iter()
+            .map(|diagnostic| diagnostic.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![Kind::BreakOutsideLoop, Kind::ReturnValueInVoidFunction]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn labels() -> anyhow::Result<()> {
+        let file = main(vec![
+            Goto {
+                label: Identifier::new("end")?,
+            }
+            .into(),
+            Goto {
+                label: Identifier::new("missing")?,
+            }
+            .into(),
+            Label {
+                identifier: Identifier::new("end")?,
+                statement: None,
+            }
+            .into(),
+            Label {
+                identifier: Identifier::new("end")?,
+                statement: None,
+            }
+            .into(),
+        ])?;
+
+        let diagnostics = file.validate();
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(
+            diagnostics[0].kind,
+            Kind::DuplicateLabel(Identifier::new("end")?)
+        );
+        assert_eq!(
+            diagnostics[0].path.to_string(),
+            "statements[0].body.statements[3]"
+        );
+
+        assert_eq!(
+            diagnostics[1].kind,
+            Kind::UndefinedLabel(Identifier::new("missing")?)
+        );
+        assert_eq!(diagnostics[1].snippet, "goto missing;");
+
+        Ok(())
+    }
+
+    #[test]
+    fn keywords() -> anyhow::Result<()> {
+        let mut file = main(vec![Expression::from(Identifier::new("typeof")?).into()])?;
+        assert!(file.validate().is_empty());
+
+        file.set_standard(Standard::Gnu11);
+        let diagnostics = file.validate();
+        assert_eq!(diagnostics.len(), 1);
+
+        assert_eq!(
+            diagnostics[0].kind,
+            Kind::Keyword(Identifier::new("typeof")?)
+        );
+        assert_eq!(diagnostics[0].path.to_string(), "statements[0]");
+        assert_eq!(
+            file.write_to_string().unwrap_err().to_string(),
+            "`typeof` is a keyword"
+        );
+
+        let mut file = crate::parse::parse_file("int nullptr; bool f(void) { return true; }")?.file;
+        file.set_standard(Standard::C23);
+        assert_eq!(
+            file.write_to_string().unwrap_err().to_string(),
+            "`nullptr` is a keyword"
+        );
+
+        file.set_standard(Standard::C17);
+        file.write_to_string()?;
+
+        let mut file = CFileBuilder::default();
+        file.add_statement(VariableDeclaration {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: ConcreteType::int(),
+            identifier: Identifier::new("bool")?,
+            initializer: None,
+        });
+        file.write_to_string()?;
+        file.set_standard(Standard::C23);
+        assert_eq!(
+            file.write_to_string().unwrap_err().to_string(),
+            "`bool` is a keyword"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn underlying_types() -> anyhow::Result<()> {
+        let mut file = crate::parse::parse_file(
+            "typedef unsigned char byte;
+            typedef float real;
+            typedef struct s { int x; } record;
+            enum a : byte { A };
+            enum b : uint8_t { B };
+            enum c : real { C };
+            void f(void) { enum d : record { D } d; }",
+        )?
+        .file;
+        file.set_standard(Standard::C23);
+
+        let diagnostics = file.validate();
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.kind.to_string(), diagnostic.path.to_string()))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "the underlying type of an enumeration must be an integer type, but `real` is not"
+                        .to_string(),
+                    "statements[5]".to_string()
+                ),
+                (
+                    "the underlying type of an enumeration must be an integer type, but `record` is not"
+                        .to_string(),
+                    "statements[6]".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            file.write_to_string().unwrap_err().to_string(),
+            diagnostics[0].kind.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn array_dimensions() -> anyhow::Result<()> {
+        let file = crate::parse::parse_file(
+            "extern char *names[][2];\ntypedef int rows[][3][4];\nvoid f(const char *names[][2]);",
+        )?
+        .file;
+        assert!(file.validate().is_empty());
+
+        let mut file = CFileBuilder::default();
+        file.add_statement(VariableDeclarationList {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: ConcreteType::Char,
+            declarators: vec![Declarator {
+                dimensions: vec![2.into(), ArraySize::Unspecified],
+                ..Declarator::new(Identifier::new("names")?)
+            }]
+            .try_into()?,
+        });
+        file.add_statement(VariableDeclaration {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: Array {
+                element_type: Box::new(
+                    Array {
+                        element_type: Box::new(ConcreteType::int()),
+                        size: ArraySize::Unspecified,
+                    }
+                    .into(),
+                ),
+                size: 2.into(),
+            }
+            .into(),
+            identifier: Identifier::new("rows")?,
+            initializer: None,
+        });
+
+        let diagnostics = file.validate();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].kind, Kind::IncompleteArrayElement);
+        assert_eq!(diagnostics[0].snippet, "char names[2][];");
+        assert_eq!(diagnostics[1].path.to_string(), "statements[1]");
+
+        Ok(())
+    }
+
+    #[test]
+    fn array_placements() -> anyhow::Result<()> {
+        let kinds = |source| -> anyhow::Result<Vec<Kind>> {
+            Ok(crate::parse::parse_file(source)?
+                .file
+                .validate()
+                .into_iter()
+                .map(|diagnostic| diagnostic.kind)
+                .collect())
+        };
+
+        assert!(kinds(
+            "enum { N = 4 };
+            int constant[N * 2];
+            void prototype(int n, int a[static 4], int m[*][n], void (*f)(int b[*]));
+            void definition(int n, int a[static 4]) { int v[n]; int (*p)[n]; static int s[N]; }"
+        )?
+        .is_empty());
+
+        // Objects with static storage duration cannot have variable lengths, even if the length is `const`
+        assert_eq!(
+            kinds("int n = 3;\nint a[n];\nconst int c = 3;\nint (*b)[c];\nint d[3], e[n];")?,
+            [
+                Kind::VariableLengthArrayWithStaticStorage(Identifier::new("a")?),
+                Kind::VariableLengthArrayWithStaticStorage(Identifier::new("b")?),
+                Kind::VariableLengthArrayWithStaticStorage(Identifier::new("e")?),
+            ]
+        );
+        assert_eq!(
+            kinds("void f(int n) { static int s[n]; extern int e[n]; }")?,
+            [
+                Kind::VariableLengthArrayWithStaticStorage(Identifier::new("s")?),
+                Kind::VariableLengthArrayWithStaticStorage(Identifier::new("e")?),
+            ]
+        );
+
+        // `[*]` only in prototypes, `[static N]` only in the outermost dimension of parameters
+        assert_eq!(
+            kinds("int g[*];\nvoid f(int n, int a[*]) { int b[static 4]; }")?,
+            [
+                Kind::StarOutsidePrototype,
+                Kind::StarOutsidePrototype,
+                Kind::StaticOutsideParameter,
+            ]
+        );
+        assert_eq!(
+            kinds("void f(int a[2][static 4]);\ntypedef int t[static 4];")?,
+            [Kind::StaticOutsideParameter, Kind::StaticOutsideParameter]
+        );
+
+        Ok(())
+    }
+}
@@ -1,11 +1,24 @@
-use core::fmt;
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+};
 
 use crate::{
     r#type::{IntegerKind, Real},
     ConcreteType,
 };
 
+/// Errors that can occur when generating a [`Value`]
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// C has no constants for infinity and NaN, only the `INFINITY` and `NAN` macros of `<math.h>`.
+    #[error("the real constant `{0}` is not finite")]
+    NonFiniteReal(f64),
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Value {
     Array {
         values: Vec<Value>,
@@ -43,6 +56,14 @@ pub enum Value {
 }
 
 impl Value {
+    /// Returns an error if the value cannot be written as a C constant.
+    pub fn check(&self) -> Result<(), Error> {
+        match self {
+            Value::Real { value, .. } if !value.is_finite() => Err(Error::NonFiniteReal(*value)),
+            _ => Ok(()),
+        }
+    }
+
     pub const fn double(value: f64) -> Self {
         Self::Real {
             value,
@@ -75,6 +96,10 @@ impl Value {
         Self::Real { value, kind: None }
     }
 
+    pub const fn size(value: usize) -> Self {
+        Self::Size { value }
+    }
+
     pub const fn signed_integer(value: i64) -> Self {
         Self::SignedInteger { value, kind: None }
     }
@@ -84,6 +109,92 @@ impl Value {
     }
 }
 
+/// Real values compare by their bit pattern, which keeps the equality reflexive and consistent with [`Hash`]: `NaN`
+/// equals itself, and `0.0` differs from `-0.0`.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Value::Array { values, base_type },
+                Value::Array {
+                    values: other_values,
+                    base_type: other_base_type,
+                },
+            ) => values == other_values && base_type == other_base_type,
+            (Value::Char { value }, Value::Char { value: other }) => value == other,
+            (
+                Value::Enum { value, name },
+                Value::Enum {
+                    value: other_value,
+                    name: other_name,
+                },
+            ) => value == other_value && name == other_name,
+            (Value::Pointer { address }, Value::Pointer { address: other }) => address == other,
+            (
+                Value::Real { value, kind },
+                Value::Real {
+                    value: other_value,
+                    kind: other_kind,
+                },
+            ) => value.to_bits() == other_value.to_bits() && kind == other_kind,
+            (
+                Value::SignedInteger { value, kind },
+                Value::SignedInteger {
+                    value: other_value,
+                    kind: other_kind,
+                },
+            ) => value == other_value && kind == other_kind,
+            (Value::Size { value }, Value::Size { value: other }) => value == other,
+            (Value::String(value), Value::String(other)) => value == other,
+            (Value::Struct { fields }, Value::Struct { fields: other }) => fields == other,
+            (
+                Value::UnsignedInteger { value, kind },
+                Value::UnsignedInteger {
+                    value: other_value,
+                    kind: other_kind,
+                },
+            ) => value == other_value && kind == other_kind,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+
+        match self {
+            Value::Array { values, base_type } => {
+                values.hash(state);
+                base_type.hash(state);
+            }
+            Value::Char { value } => value.hash(state),
+            Value::Enum { value, name } => {
+                value.hash(state);
+                name.hash(state);
+            }
+            Value::Pointer { address } => address.hash(state),
+            Value::Real { value, kind } => {
+                value.to_bits().hash(state);
+                kind.hash(state);
+            }
+            Value::SignedInteger { value, kind } => {
+                value.hash(state);
+                kind.hash(state);
+            }
+            Value::Size { value } => value.hash(state),
+            Value::String(value) => value.hash(state),
+            Value::Struct { fields } => fields.hash(state),
+            Value::UnsignedInteger { value, kind } => {
+                value.hash(state);
+                kind.hash(state);
+            }
+        }
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -96,11 +207,17 @@ impl fmt::Display for Value {
                 write!(f, "{}[] {{ {} }}", base_type, vals)
             }
             Value::Char { value } => {
-                write!(f, "'{value}'")
+                write!(f, "'{}'", escape_char(*value))
             }
             Value::Enum { value, name } => write!(f, "enum {} = {}", name, value),
             Value::Real { value, kind } => {
-                write!(f, "{value}{suffix}", suffix = kind.map_or("", Real::suffix))
+                // The debug representation keeps a fractional part or an exponent, e.g. `1.0` or `1e300`, so that
+                // the constant is not read as an integer
+                write!(
+                    f,
+                    "{value:?}{suffix}",
+                    suffix = kind.map_or("", Real::suffix)
+                )
             }
             Value::Pointer { address } => {
                 write!(f, "{address:#x}")
@@ -132,3 +249,66 @@ impl fmt::Display for Value {
         }
     }
 }
+
+/// Returns the escape sequence of a character in a character constant, or the character itself.
+///
+/// Control characters and characters of a single byte above `0x7F` are written as octal escapes, since the UTF-8
+/// encoding of the latter would form a multi-character constant.
+fn escape_char(c: char) -> String {
+    match c {
+        '\n' => "\\n".to_string(),
+        '\t' => "\\t".to_string(),
+        '\r' => "\\r".to_string(),
+        '\x07' => "\\a".to_string(),
+        '\x08' => "\\b".to_string(),
+        '\x0b' => "\\v".to_string(),
+        '\x0c' => "\\f".to_string(),
+        '\\' => "\\\\".to_string(),
+        '\'' => "\\'".to_string(),
+        c if c.is_ascii_control() || ('\u{80}'..='\u{ff}').contains(&c) => {
+            format!("\\{:o}", c as u32)
+        }
+        c => c.to_string(),
+    }
+}
+
+/// Returns the number of bytes that the text of a string literal encodes, where each escape sequence stands for one
+/// byte, or the UTF-8 encoding of a universal character name. The terminating null character is not included.
+pub(crate) fn string_length(text: &str) -> usize {
+    let mut length = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            length += c.len_utf8();
+            continue;
+        }
+
+        length += match chars.next() {
+            Some('x') => {
+                while chars.next_if(char::is_ascii_hexdigit).is_some() {}
+                1
+            }
+            Some('0'..='7') => {
+                for _ in 0..2 {
+                    chars.next_if(|c| c.is_digit(8));
+                }
+                1
+            }
+            Some(c @ ('u' | 'U')) => {
+                let digits = if c == 'u' { 4 } else { 8 };
+                let code = (0..digits)
+                    .map_while(|_| chars.next_if(char::is_ascii_hexdigit))
+                    .collect::<String>();
+
+                u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map_or(1, char::len_utf8)
+            }
+            _ => 1,
+        };
+    }
+
+    length
+}
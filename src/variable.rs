@@ -1,19 +1,32 @@
 use pretty::Pretty;
 
-use crate::{pretty::impl_display_via_pretty, ConcreteType, Expression, Identifier, StorageClass};
+use crate::{
+    non_empty_vec::NonEmptyVec,
+    pretty::impl_display_via_pretty,
+    r#type::{Array, ArraySize, Pointer},
+    ConcreteType, DocComment, Expression, Identifier, Standard, StorageClass,
+};
 
 pub type Variable = Identifier;
 
+/// Errors that can occur when generating a [`DeclarationList`]
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The shared type is derived, so it would only apply to the first declarator, e.g. `int * a, b;`.
+    #[error("the shared type `{0}` of a declaration list must not be a pointer or array type")]
+    DerivedType(Box<ConcreteType>),
+}
+
 /// Variable declaration
 ///
-/// Unsupported C language features:
-///
-/// ```c
-/// int test = 1, test2[2] = {1, 2};
-/// ```
-#[derive(Clone, Debug)]
+/// To declare multiple variables that share a type, use a [`DeclarationList`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Declaration {
+    pub doc: Option<DocComment>,
     pub storage_class: Option<StorageClass>,
+    /// The alignment specifier of the variable, e.g. `_Alignas(64)`.
+    pub alignment: Option<Expression>,
     pub ty: ConcreteType,
     pub identifier: Identifier,
     pub initializer: Option<Expression>,
@@ -27,12 +40,22 @@ where
     AnnotationT: Clone + 'a,
 {
     fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let builder = DocComment::pretty_preceding(self.doc, allocator);
+
         let builder = if let Some(storage_class) = self.storage_class {
-            allocator
-                .text(storage_class.to_string())
+            builder
+                .append(allocator.text(storage_class.to_string()))
+                .append(allocator.space())
+        } else {
+            builder
+        };
+
+        let builder = if let Some(alignment) = self.alignment {
+            builder
+                .append(allocator.text(Standard::current().alignment_specifier(&alignment)))
                 .append(allocator.space())
         } else {
-            allocator.nil()
+            builder
         };
 
         let builder = builder.append(self.ty.pretty_definition(self.identifier, allocator));
@@ -51,12 +74,181 @@ where
 
 impl_display_via_pretty!(Declaration, 80);
 
+/// Declaration of multiple variables that share their specifiers.
+///
+/// ```c
+/// int x = 0, *p = &x, buf[16];
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DeclarationList {
+    pub doc: Option<DocComment>,
+    pub storage_class: Option<StorageClass>,
+    /// The alignment specifier of all variables, e.g. `_Alignas(64)`.
+    pub alignment: Option<Expression>,
+    /// The type shared by all declarators, e.g. `int`.
+    ///
+    /// Generation fails if it is a pointer or array type: those belong to the [`Declarator`]s instead.
+    pub ty: ConcreteType,
+    pub declarators: NonEmptyVec<Declarator>,
+}
+
+impl DeclarationList {
+    /// Verifies that the shared type is not a pointer or array type.
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        let ty = match &self.ty {
+            ConcreteType::Const(ty) => ty,
+            ty => ty,
+        };
+
+        match ty {
+            ConcreteType::Array(_) | ConcreteType::Pointer(_) => {
+                Err(Error::DerivedType(Box::new(self.ty.clone())))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for DeclarationList
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let builder = DocComment::pretty_preceding(self.doc, allocator);
+
+        let builder = if let Some(storage_class) = self.storage_class {
+            builder
+                .append(allocator.text(storage_class.to_string()))
+                .append(allocator.space())
+        } else {
+            builder
+        };
+
+        let builder = if let Some(alignment) = self.alignment {
+            builder
+                .append(allocator.text(Standard::current().alignment_specifier(&alignment)))
+                .append(allocator.space())
+        } else {
+            builder
+        };
+
+        builder
+            .append(self.ty.pretty(allocator))
+            .append(allocator.space())
+            .append(
+                allocator.intersperse(
+                    self.declarators
+                        .into_iter()
+                        .map(|declarator| declarator.pretty(allocator)),
+                    allocator.text(",").append(allocator.space()),
+                ),
+            )
+    }
+}
+
+impl_display_via_pretty!(DeclarationList, 80);
+
+/// A single variable of a [`DeclarationList`], which derives its type from the shared type.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Declarator {
+    /// The constness of each pointer level, starting with the one closest to the shared type.
+    ///
+    /// # Examples
+    ///
+    /// - For `int *const *p`, it is `[true, false]`.
+    pub pointers: Vec<bool>,
+    pub identifier: Identifier,
+    /// The array dimensions, starting with the outermost one, which is the only one that can be
+    /// [unspecified](ArraySize::Unspecified).
+    pub dimensions: Vec<ArraySize>,
+    pub initializer: Option<Expression>,
+}
+
+impl Declarator {
+    /// Creates a declarator of a plain variable of the shared type, without initializer.
+    pub fn new(identifier: Identifier) -> Self {
+        Self {
+            pointers: Vec::new(),
+            identifier,
+            dimensions: Vec::new(),
+            initializer: None,
+        }
+    }
+
+    /// Returns the type of the declared variable, given the type shared by all declarators.
+    ///
+    /// # Examples
+    ///
+    /// - For `*p[4]` and `int`, it returns `int *[4]`.
+    pub fn declared_type(&self, ty: ConcreteType) -> ConcreteType {
+        let ty = self.pointers.iter().fold(ty, |ty, is_const| {
+            Pointer {
+                pointer_ty: ty.into(),
+                is_const: *is_const,
+            }
+            .into()
+        });
+
+        self.dimensions.iter().rev().fold(ty, |ty, size| {
+            Array {
+                element_type: Box::new(ty),
+                size: size.clone(),
+            }
+            .into()
+        })
+    }
+}
+
+impl<'a, AllocatorT, AnnotationT> Pretty<'a, AllocatorT, AnnotationT> for Declarator
+where
+    AllocatorT: pretty::DocAllocator<'a, AnnotationT>,
+    AllocatorT::Doc: Clone,
+    AnnotationT: Clone + 'a,
+{
+    fn pretty(self, allocator: &'a AllocatorT) -> pretty::DocBuilder<'a, AllocatorT, AnnotationT> {
+        let builder = self
+            .pointers
+            .into_iter()
+            .fold(allocator.nil(), |builder, is_const| {
+                let builder = builder.append(allocator.text("*"));
+
+                if is_const {
+                    builder
+                        .append(allocator.text("const"))
+                        .append(allocator.space())
+                } else {
+                    builder
+                }
+            });
+
+        let builder = self.dimensions.into_iter().fold(
+            builder.append(allocator.text(self.identifier)),
+            |builder, size| builder.append(size.pretty(allocator)),
+        );
+
+        if let Some(initializer) = self.initializer {
+            builder
+                .append(allocator.space())
+                .append(allocator.text("="))
+                .append(allocator.space())
+                .append(initializer.pretty(allocator))
+        } else {
+            builder
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         function::FunctionParameter,
-        r#type::{Function, Pointer},
-        Statement, Value,
+        operator::{PrefixOperator, PrefixOperatorKind},
+        r#type::{Function, OpaqueType},
+        CFileBuilder, Statement, Value,
     };
 
     use super::*;
@@ -64,7 +256,9 @@ mod tests {
     #[test]
     fn const_pointer() -> anyhow::Result<()> {
         let generated = Statement::from(Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Pointer {
                 pointer_ty: ConcreteType::int().into(),
                 is_const: true,
@@ -82,7 +276,9 @@ mod tests {
     #[test]
     fn function_pointer() -> anyhow::Result<()> {
         let immutable = Statement::from(Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Pointer {
                 pointer_ty: Function {
                     parameters: vec![
@@ -96,6 +292,8 @@ mod tests {
                         },
                     ],
                     return_ty: ConcreteType::int(),
+                    is_variadic: false,
+                    has_unspecified_parameters: false,
                 }
                 .into(),
                 is_const: true,
@@ -108,7 +306,9 @@ mod tests {
         assert_eq!(immutable, "int (*const immutable)(int, int);");
 
         let mutable = Statement::from(Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Pointer {
                 pointer_ty: Function {
                     parameters: vec![
@@ -122,6 +322,8 @@ mod tests {
                         },
                     ],
                     return_ty: ConcreteType::int(),
+                    is_variadic: false,
+                    has_unspecified_parameters: false,
                 }
                 .into(),
                 is_const: false,
@@ -139,7 +341,9 @@ mod tests {
     #[test]
     fn initializer() -> anyhow::Result<()> {
         let multiple = Statement::from(Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: ConcreteType::int(),
             identifier: Identifier::new("x")?,
             initializer: Some(Value::signed_integer(5).into()),
@@ -153,7 +357,9 @@ mod tests {
     #[test]
     fn pointer() -> anyhow::Result<()> {
         let generated = Statement::from(Declaration {
+            doc: None,
             storage_class: None,
+            alignment: None,
             ty: Pointer {
                 pointer_ty: ConcreteType::int().into(),
                 is_const: false,
@@ -167,4 +373,140 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn declaration_list() -> anyhow::Result<()> {
+        let generated = Statement::from(DeclarationList {
+            doc: None,
+            storage_class: None,
+            alignment: None,
+            ty: ConcreteType::int(),
+            declarators: vec![
+                Declarator {
+                    initializer: Some(Value::signed_integer(0).into()),
+                    ..Declarator::new(Identifier::new("x")?)
+                },
+                Declarator {
+                    pointers: vec![false],
+                    initializer: Some(
+                        PrefixOperator {
+                            operand: Variable::new("x")?.into(),
+                            operator: PrefixOperatorKind::Address,
+                        }
+                        .into(),
+                    ),
+                    ..Declarator::new(Identifier::new("p")?)
+                },
+                Declarator {
+                    dimensions: vec![16.into()],
+                    ..Declarator::new(Identifier::new("buf")?)
+                },
+            ]
+            .try_into()?,
+        })
+        .to_string();
+        assert_eq!(generated, "int x = 0, *p = &x, buf[16];");
+
+        let generated = Statement::from(DeclarationList {
+            doc: None,
+            storage_class: Some(StorageClass::Static),
+            alignment: None,
+            ty: ConcreteType::Char,
+            declarators: vec![
+                Declarator {
+                    pointers: vec![true, false],
+                    ..Declarator::new(Identifier::new("argv")?)
+                },
+                Declarator {
+                    pointers: vec![false],
+                    dimensions: vec![ArraySize::Unspecified, 2.into()],
+                    ..Declarator::new(Identifier::new("names")?)
+                },
+            ]
+            .try_into()?,
+        })
+        .to_string();
+        assert_eq!(generated, "static char *const *argv, *names[][2];");
+
+        Ok(())
+    }
+
+    #[test]
+    fn derived_shared_types() -> anyhow::Result<()> {
+        let list = |ty| -> anyhow::Result<CFileBuilder> {
+            let mut file = CFileBuilder::default();
+            file.add_statement(DeclarationList {
+                doc: None,
+                storage_class: None,
+                alignment: None,
+                ty,
+                declarators: vec![
+                    Declarator::new(Identifier::new("a")?),
+                    Declarator::new(Identifier::new("b")?),
+                ]
+                .try_into()?,
+            });
+
+            Ok(file)
+        };
+
+        let pointer = list(
+            Pointer {
+                pointer_ty: ConcreteType::int().into(),
+                is_const: false,
+            }
+            .into(),
+        )?;
+        assert_eq!(
+            pointer.write_to_string().unwrap_err().to_string(),
+            "the shared type `int *` of a declaration list must not be a pointer or array type"
+        );
+
+        let array = list(
+            Array {
+                element_type: Box::new(ConcreteType::int()),
+                size: 4.into(),
+            }
+            .into(),
+        )?;
+        assert_eq!(
+            array.write_to_string().unwrap_err().to_string(),
+            "the shared type `int[4]` of a declaration list must not be a pointer or array type"
+        );
+
+        list(ConcreteType::int().into_const())?.write_to_string()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn declared_type() -> anyhow::Result<()> {
+        let declarator = Declarator {
+            pointers: vec![true],
+            dimensions: vec![4.into(), 2.into()],
+            ..Declarator::new(Identifier::new("p")?)
+        };
+
+        let ConcreteType::Array(array) = declarator.declared_type(ConcreteType::int()) else {
+            panic!("expected an array");
+        };
+        assert!(matches!(
+            array.dimensions()[..],
+            [
+                ArraySize::Length(ref four),
+                ArraySize::Length(ref two)
+            ] if four.to_string() == "4" && two.to_string() == "2"
+        ));
+
+        let ConcreteType::Pointer(pointer) = array.innermost_element_type() else {
+            panic!("expected a pointer");
+        };
+        assert!(pointer.is_const);
+        assert!(matches!(
+            pointer.pointer_ty,
+            OpaqueType::ConcreteType(ConcreteType::Integer(_))
+        ));
+
+        Ok(())
+    }
 }
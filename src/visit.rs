@@ -0,0 +1,1057 @@
+//! Traversal of the syntax tree by shared reference.
+//!
+//! Each `visit_*` method of [`Visitor`] defaults to the `walk_*` function of the same name, which visits the children
+//! of the node in source order. An implementation overrides the methods of the nodes it is interested in, and calls
+//! the `walk_*` function from the override to continue into the children.
+//!
+//! See [`visit_mut`](crate::visit_mut) for rewrites in place, and [`fold`](crate::fold) for rewrites by value.
+
+use crate::{
+    function::{FunctionCall, FunctionParameter},
+    operator::{
+        ArraySubscript, Assignment, BinaryOperator, Cast, CommaOperator, CompoundAssignment,
+        Conditional, OffsetOf, PostfixOperator, PrefixOperator, SizeOf,
+    },
+    r#type::{
+        member::{IndirectMemberAccess, Member, MemberAccess},
+        Array, ArraySize, Definition as TypeDefinition, Enum, Enumerator, Function,
+        InitializerList, OpaqueType, Pointer, Scalar, Struct, Union,
+    },
+    statement::{
+        Define, Do, For, ForDeclaration, Goto, If, Include, Label, Pragma, Return, StaticAssert,
+        Switch, Typedef, While,
+    },
+    variable::Declarator,
+    Block, Comment, ConcreteType, DocComment, Expression, FileLevelStatement, FunctionDeclaration,
+    FunctionDefinition, Identifier, Statement, Value, Variable, VariableDeclaration,
+    VariableDeclarationList,
+};
+
+/// A traversal of the syntax tree by shared reference.
+pub trait Visitor {
+    fn visit_file_level_statement(&mut self, statement: &FileLevelStatement) {
+        walk_file_level_statement(self, statement);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_value(&mut self, value: &Value) {
+        walk_value(self, value);
+    }
+
+    /// Visits a variable that is used in an expression.
+    fn visit_variable(&mut self, variable: &Variable) {
+        walk_variable(self, variable);
+    }
+
+    fn visit_identifier(&mut self, _identifier: &Identifier) {}
+
+    fn visit_comment(&mut self, _comment: &Comment) {}
+
+    fn visit_doc_comment(&mut self, _doc: &DocComment) {}
+
+    fn visit_array_subscript(&mut self, subscript: &ArraySubscript) {
+        walk_array_subscript(self, subscript);
+    }
+
+    fn visit_assignment(&mut self, assignment: &Assignment) {
+        walk_assignment(self, assignment);
+    }
+
+    fn visit_binary_operator(&mut self, operator: &BinaryOperator) {
+        walk_binary_operator(self, operator);
+    }
+
+    fn visit_cast(&mut self, cast: &Cast) {
+        walk_cast(self, cast);
+    }
+
+    fn visit_comma_operator(&mut self, operator: &CommaOperator) {
+        walk_comma_operator(self, operator);
+    }
+
+    fn visit_compound_assignment(&mut self, assignment: &CompoundAssignment) {
+        walk_compound_assignment(self, assignment);
+    }
+
+    fn visit_conditional(&mut self, conditional: &Conditional) {
+        walk_conditional(self, conditional);
+    }
+
+    fn visit_function_call(&mut self, call: &FunctionCall) {
+        walk_function_call(self, call);
+    }
+
+    fn visit_indirect_member_access(&mut self, access: &IndirectMemberAccess) {
+        walk_indirect_member_access(self, access);
+    }
+
+    fn visit_initializer_list(&mut self, list: &InitializerList) {
+        walk_initializer_list(self, list);
+    }
+
+    fn visit_member_access(&mut self, access: &MemberAccess) {
+        walk_member_access(self, access);
+    }
+
+    fn visit_offset_of(&mut self, offset_of: &OffsetOf) {
+        walk_offset_of(self, offset_of);
+    }
+
+    fn visit_postfix_operator(&mut self, operator: &PostfixOperator) {
+        walk_postfix_operator(self, operator);
+    }
+
+    fn visit_prefix_operator(&mut self, operator: &PrefixOperator) {
+        walk_prefix_operator(self, operator);
+    }
+
+    fn visit_size_of(&mut self, size_of: &SizeOf) {
+        walk_size_of(self, size_of);
+    }
+
+    fn visit_define(&mut self, define: &Define) {
+        walk_define(self, define);
+    }
+
+    fn visit_do(&mut self, do_statement: &Do) {
+        walk_do(self, do_statement);
+    }
+
+    fn visit_for(&mut self, for_statement: &For) {
+        walk_for(self, for_statement);
+    }
+
+    fn visit_for_declaration(&mut self, declaration: &ForDeclaration) {
+        walk_for_declaration(self, declaration);
+    }
+
+    fn visit_goto(&mut self, goto: &Goto) {
+        walk_goto(self, goto);
+    }
+
+    fn visit_if(&mut self, if_statement: &If) {
+        walk_if(self, if_statement);
+    }
+
+    fn visit_include(&mut self, _include: &Include) {}
+
+    fn visit_label(&mut self, label: &Label) {
+        walk_label(self, label);
+    }
+
+    fn visit_pragma(&mut self, _pragma: &Pragma) {}
+
+    fn visit_return(&mut self, return_statement: &Return) {
+        walk_return(self, return_statement);
+    }
+
+    fn visit_static_assert(&mut self, assertion: &StaticAssert) {
+        walk_static_assert(self, assertion);
+    }
+
+    fn visit_switch(&mut self, switch: &Switch) {
+        walk_switch(self, switch);
+    }
+
+    fn visit_typedef(&mut self, typedef: &Typedef) {
+        walk_typedef(self, typedef);
+    }
+
+    fn visit_while(&mut self, while_statement: &While) {
+        walk_while(self, while_statement);
+    }
+
+    fn visit_function_declaration(&mut self, declaration: &FunctionDeclaration) {
+        walk_function_declaration(self, declaration);
+    }
+
+    fn visit_function_definition(&mut self, definition: &FunctionDefinition) {
+        walk_function_definition(self, definition);
+    }
+
+    fn visit_function_parameter(&mut self, parameter: &FunctionParameter) {
+        walk_function_parameter(self, parameter);
+    }
+
+    fn visit_variable_declaration(&mut self, declaration: &VariableDeclaration) {
+        walk_variable_declaration(self, declaration);
+    }
+
+    fn visit_variable_declaration_list(&mut self, declaration: &VariableDeclarationList) {
+        walk_variable_declaration_list(self, declaration);
+    }
+
+    fn visit_declarator(&mut self, declarator: &Declarator) {
+        walk_declarator(self, declarator);
+    }
+
+    fn visit_concrete_type(&mut self, ty: &ConcreteType) {
+        walk_concrete_type(self, ty);
+    }
+
+    fn visit_opaque_type(&mut self, ty: &OpaqueType) {
+        walk_opaque_type(self, ty);
+    }
+
+    fn visit_function_type(&mut self, function: &Function) {
+        walk_function_type(self, function);
+    }
+
+    fn visit_scalar(&mut self, scalar: &Scalar) {
+        walk_scalar(self, scalar);
+    }
+
+    fn visit_pointer(&mut self, pointer: &Pointer) {
+        walk_pointer(self, pointer);
+    }
+
+    fn visit_array(&mut self, array: &Array) {
+        walk_array(self, array);
+    }
+
+    fn visit_array_size(&mut self, size: &ArraySize) {
+        walk_array_size(self, size);
+    }
+
+    fn visit_type_definition(&mut self, definition: &TypeDefinition) {
+        walk_type_definition(self, definition);
+    }
+
+    fn visit_enum(&mut self, enumeration: &Enum) {
+        walk_enum(self, enumeration);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &Enumerator) {
+        walk_enumerator(self, enumerator);
+    }
+
+    fn visit_struct(&mut self, structure: &Struct) {
+        walk_struct(self, structure);
+    }
+
+    fn visit_union(&mut self, union: &Union) {
+        walk_union(self, union);
+    }
+
+    fn visit_member(&mut self, member: &Member) {
+        walk_member(self, member);
+    }
+}
+
+pub fn walk_file_level_statement<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    statement: &FileLevelStatement,
+) {
+    match statement {
+        FileLevelStatement::Comment(comment) => visitor.visit_comment(comment),
+        FileLevelStatement::Define(define) => visitor.visit_define(define),
+        FileLevelStatement::FunctionDeclaration(declaration) => {
+            visitor.visit_function_declaration(declaration)
+        }
+        FileLevelStatement::FunctionDefinition(definition) => {
+            visitor.visit_function_definition(definition)
+        }
+        FileLevelStatement::Include(include) => visitor.visit_include(include),
+        FileLevelStatement::Pragma(pragma) => visitor.visit_pragma(pragma),
+        FileLevelStatement::StaticAssert(assertion) => visitor.visit_static_assert(assertion),
+        FileLevelStatement::TypeDefinition(definition) => visitor.visit_type_definition(definition),
+        FileLevelStatement::Typedef(typedef) => visitor.visit_typedef(typedef),
+        FileLevelStatement::VariableDeclaration(declaration) => {
+            visitor.visit_variable_declaration(declaration)
+        }
+        FileLevelStatement::VariableDeclarationList(declaration) => {
+            visitor.visit_variable_declaration_list(declaration)
+        }
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Expression(expression) => visitor.visit_expression(expression),
+        Statement::Label(label) => visitor.visit_label(label),
+        Statement::If(if_statement) => visitor.visit_if(if_statement),
+        Statement::Switch(switch) => visitor.visit_switch(switch),
+        Statement::While(while_statement) => visitor.visit_while(while_statement),
+        Statement::Do(do_statement) => visitor.visit_do(do_statement),
+        Statement::For(for_statement) => visitor.visit_for(for_statement),
+        Statement::Block(block) => visitor.visit_block(block),
+        Statement::Comment(comment) => visitor.visit_comment(comment),
+        Statement::Null | Statement::Break | Statement::Continue => {}
+        Statement::Goto(goto) => visitor.visit_goto(goto),
+        Statement::Return(return_statement) => visitor.visit_return(return_statement),
+        Statement::StaticAssert(assertion) => visitor.visit_static_assert(assertion),
+        Statement::Typedef(typedef) => visitor.visit_typedef(typedef),
+        Statement::VariableDeclaration(declaration) => {
+            visitor.visit_variable_declaration(declaration)
+        }
+        Statement::VariableDeclarationList(declaration) => {
+            visitor.visit_variable_declaration_list(declaration)
+        }
+        Statement::FunctionDeclaration(declaration) => {
+            visitor.visit_function_declaration(declaration)
+        }
+        Statement::TypeDefinition(definition) => visitor.visit_type_definition(definition),
+        Statement::Include(include) => visitor.visit_include(include),
+        Statement::Define(define) => visitor.visit_define(define),
+        Statement::Pragma(pragma) => visitor.visit_pragma(pragma),
+    }
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    for statement in &block.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::AlignOf(ty) => visitor.visit_concrete_type(ty),
+        Expression::ArraySubscript(subscript) => visitor.visit_array_subscript(subscript),
+        Expression::Assignment(assignment) => visitor.visit_assignment(assignment),
+        Expression::BinaryOperator(operator) => visitor.visit_binary_operator(operator),
+        Expression::Cast(cast) => visitor.visit_cast(cast),
+        Expression::CommaOperator(operator) => visitor.visit_comma_operator(operator),
+        Expression::CompoundAssignment(assignment) => visitor.visit_compound_assignment(assignment),
+        Expression::Conditional(conditional) => visitor.visit_conditional(conditional),
+        Expression::FunctionCall(call) => visitor.visit_function_call(call),
+        Expression::IndirectMemberAccess(access) => visitor.visit_indirect_member_access(access),
+        Expression::InitializerList(list) => visitor.visit_initializer_list(list),
+        Expression::MemberAccess(access) => visitor.visit_member_access(access),
+        Expression::OffsetOf(offset_of) => visitor.visit_offset_of(offset_of),
+        Expression::Parentheses(expression) => visitor.visit_expression(expression),
+        Expression::PostfixOperator(operator) => visitor.visit_postfix_operator(operator),
+        Expression::PrefixOperator(operator) => visitor.visit_prefix_operator(operator),
+        Expression::SizeOf(size_of) => visitor.visit_size_of(size_of),
+        Expression::Value(value) => visitor.visit_value(value),
+        Expression::Variable(variable) => visitor.visit_variable(variable),
+    }
+}
+
+pub fn walk_value<V: Visitor + ?Sized>(visitor: &mut V, value: &Value) {
+    match value {
+        Value::Array { values, base_type } => {
+            visitor.visit_concrete_type(base_type);
+            for value in values {
+                visitor.visit_value(value);
+            }
+        }
+        Value::Struct { fields } => {
+            for (_, value) in fields {
+                visitor.visit_value(value);
+            }
+        }
+        Value::Char { .. }
+        | Value::Enum { .. }
+        | Value::Pointer { .. }
+        | Value::Real { .. }
+        | Value::SignedInteger { .. }
+        | Value::Size { .. }
+        | Value::String(_)
+        | Value::UnsignedInteger { .. } => {}
+    }
+}
+
+pub fn walk_variable<V: Visitor + ?Sized>(visitor: &mut V, variable: &Variable) {
+    visitor.visit_identifier(variable);
+}
+
+pub fn walk_array_subscript<V: Visitor + ?Sized>(visitor: &mut V, subscript: &ArraySubscript) {
+    visitor.visit_expression(&subscript.array);
+    visitor.visit_expression(&subscript.index);
+}
+
+pub fn walk_assignment<V: Visitor + ?Sized>(visitor: &mut V, assignment: &Assignment) {
+    visitor.visit_expression(&assignment.left);
+    visitor.visit_expression(&assignment.right);
+}
+
+pub fn walk_binary_operator<V: Visitor + ?Sized>(visitor: &mut V, operator: &BinaryOperator) {
+    visitor.visit_expression(&operator.left);
+    visitor.visit_expression(&operator.right);
+}
+
+pub fn walk_cast<V: Visitor + ?Sized>(visitor: &mut V, cast: &Cast) {
+    visitor.visit_scalar(&cast.new_ty);
+    visitor.visit_expression(&cast.expression);
+}
+
+pub fn walk_comma_operator<V: Visitor + ?Sized>(visitor: &mut V, operator: &CommaOperator) {
+    visitor.visit_expression(&operator.left);
+    visitor.visit_expression(&operator.right);
+}
+
+pub fn walk_compound_assignment<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    assignment: &CompoundAssignment,
+) {
+    visitor.visit_expression(&assignment.left);
+    visitor.visit_expression(&assignment.right);
+}
+
+pub fn walk_conditional<V: Visitor + ?Sized>(visitor: &mut V, conditional: &Conditional) {
+    visitor.visit_expression(&conditional.condition);
+    visitor.visit_expression(&conditional.then_branch);
+    visitor.visit_expression(&conditional.else_branch);
+}
+
+pub fn walk_function_call<V: Visitor + ?Sized>(visitor: &mut V, call: &FunctionCall) {
+    visitor.visit_expression(&call.callee);
+    for argument in &call.arguments {
+        visitor.visit_expression(argument);
+    }
+}
+
+pub fn walk_indirect_member_access<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    access: &IndirectMemberAccess,
+) {
+    visitor.visit_expression(&access.left);
+    visitor.visit_identifier(&access.member);
+}
+
+pub fn walk_initializer_list<V: Visitor + ?Sized>(visitor: &mut V, list: &InitializerList) {
+    match list {
+        InitializerList::Named(fields) => {
+            for (name, value) in fields {
+                visitor.visit_identifier(name);
+                visitor.visit_expression(value);
+            }
+        }
+        InitializerList::Ordered(values) => {
+            for value in values {
+                visitor.visit_expression(value);
+            }
+        }
+    }
+}
+
+pub fn walk_member_access<V: Visitor + ?Sized>(visitor: &mut V, access: &MemberAccess) {
+    visitor.visit_expression(&access.left);
+    visitor.visit_identifier(&access.member);
+}
+
+pub fn walk_offset_of<V: Visitor + ?Sized>(visitor: &mut V, offset_of: &OffsetOf) {
+    visitor.visit_concrete_type(&offset_of.ty);
+    visitor.visit_identifier(&offset_of.member);
+}
+
+pub fn walk_postfix_operator<V: Visitor + ?Sized>(visitor: &mut V, operator: &PostfixOperator) {
+    visitor.visit_expression(&operator.operand);
+}
+
+pub fn walk_prefix_operator<V: Visitor + ?Sized>(visitor: &mut V, operator: &PrefixOperator) {
+    visitor.visit_expression(&operator.operand);
+}
+
+pub fn walk_size_of<V: Visitor + ?Sized>(visitor: &mut V, size_of: &SizeOf) {
+    match size_of {
+        SizeOf::Expression(expression) => visitor.visit_expression(expression),
+        SizeOf::Type(ty) => visitor.visit_concrete_type(ty),
+    }
+}
+
+pub fn walk_define<V: Visitor + ?Sized>(visitor: &mut V, define: &Define) {
+    visitor.visit_identifier(&define.name);
+    for parameter in define.parameters.iter().flatten() {
+        visitor.visit_identifier(parameter);
+    }
+}
+
+pub fn walk_do<V: Visitor + ?Sized>(visitor: &mut V, do_statement: &Do) {
+    visitor.visit_statement(&do_statement.body);
+    visitor.visit_expression(&do_statement.condition);
+}
+
+pub fn walk_for<V: Visitor + ?Sized>(visitor: &mut V, for_statement: &For) {
+    if let Some(init) = &for_statement.init {
+        visitor.visit_for_declaration(init);
+    }
+    visitor.visit_expression(&for_statement.condition);
+    if let Some(step) = &for_statement.step {
+        visitor.visit_expression(step);
+    }
+    visitor.visit_statement(&for_statement.body);
+}
+
+pub fn walk_for_declaration<V: Visitor + ?Sized>(visitor: &mut V, declaration: &ForDeclaration) {
+    match declaration {
+        ForDeclaration::Expression(expression) => visitor.visit_expression(expression),
+        ForDeclaration::VariableDeclaration(declaration) => {
+            visitor.visit_variable_declaration(declaration)
+        }
+        ForDeclaration::VariableDeclarationList(declaration) => {
+            visitor.visit_variable_declaration_list(declaration)
+        }
+    }
+}
+
+pub fn walk_goto<V: Visitor + ?Sized>(visitor: &mut V, goto: &Goto) {
+    visitor.visit_identifier(&goto.label);
+}
+
+pub fn walk_if<V: Visitor + ?Sized>(visitor: &mut V, if_statement: &If) {
+    visitor.visit_expression(&if_statement.condition);
+    visitor.visit_statement(&if_statement.then_statement);
+    if let Some(else_statement) = &if_statement.else_statement {
+        visitor.visit_statement(else_statement);
+    }
+}
+
+pub fn walk_label<V: Visitor + ?Sized>(visitor: &mut V, label: &Label) {
+    visitor.visit_identifier(&label.identifier);
+    if let Some(statement) = &label.statement {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_return<V: Visitor + ?Sized>(visitor: &mut V, return_statement: &Return) {
+    if let Some(expression) = &return_statement.expression {
+        visitor.visit_expression(expression);
+    }
+}
+
+pub fn walk_static_assert<V: Visitor + ?Sized>(visitor: &mut V, assertion: &StaticAssert) {
+    visitor.visit_expression(&assertion.condition);
+}
+
+pub fn walk_switch<V: Visitor + ?Sized>(visitor: &mut V, switch: &Switch) {
+    visitor.visit_expression(&switch.condition);
+    for (value, statements) in &switch.cases {
+        visitor.visit_expression(value);
+        for statement in statements {
+            visitor.visit_statement(statement);
+        }
+    }
+    for statement in switch.default.iter().flatten() {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_typedef<V: Visitor + ?Sized>(visitor: &mut V, typedef: &Typedef) {
+    visitor.visit_opaque_type(&typedef.ty);
+    visitor.visit_identifier(&typedef.alias);
+}
+
+pub fn walk_while<V: Visitor + ?Sized>(visitor: &mut V, while_statement: &While) {
+    visitor.visit_expression(&while_statement.condition);
+    visitor.visit_statement(&while_statement.body);
+}
+
+pub fn walk_function_declaration<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declaration: &FunctionDeclaration,
+) {
+    if let Some(doc) = &declaration.doc {
+        visitor.visit_doc_comment(doc);
+    }
+    visitor.visit_identifier(&declaration.name);
+    visitor.visit_function_type(&declaration.ty);
+}
+
+pub fn walk_function_definition<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    definition: &FunctionDefinition,
+) {
+    if let Some(doc) = &definition.doc {
+        visitor.visit_doc_comment(doc);
+    }
+    visitor.visit_identifier(&definition.name);
+    visitor.visit_function_type(&definition.ty);
+    visitor.visit_block(&definition.body);
+}
+
+pub fn walk_function_parameter<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    parameter: &FunctionParameter,
+) {
+    visitor.visit_concrete_type(&parameter.ty);
+    if let Some(name) = &parameter.name {
+        visitor.visit_identifier(name);
+    }
+}
+
+pub fn walk_variable_declaration<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declaration: &VariableDeclaration,
+) {
+    if let Some(doc) = &declaration.doc {
+        visitor.visit_doc_comment(doc);
+    }
+    if let Some(alignment) = &declaration.alignment {
+        visitor.visit_expression(alignment);
+    }
+    visitor.visit_concrete_type(&declaration.ty);
+    visitor.visit_identifier(&declaration.identifier);
+    if let Some(initializer) = &declaration.initializer {
+        visitor.visit_expression(initializer);
+    }
+}
+
+pub fn walk_variable_declaration_list<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    declaration: &VariableDeclarationList,
+) {
+    if let Some(doc) = &declaration.doc {
+        visitor.visit_doc_comment(doc);
+    }
+    if let Some(alignment) = &declaration.alignment {
+        visitor.visit_expression(alignment);
+    }
+    visitor.visit_concrete_type(&declaration.ty);
+    for declarator in declaration.declarators.iter() {
+        visitor.visit_declarator(declarator);
+    }
+}
+
+pub fn walk_declarator<V: Visitor + ?Sized>(visitor: &mut V, declarator: &Declarator) {
+    visitor.visit_identifier(&declarator.identifier);
+    for size in &declarator.dimensions {
+        visitor.visit_array_size(size);
+    }
+    if let Some(initializer) = &declarator.initializer {
+        visitor.visit_expression(initializer);
+    }
+}
+
+pub fn walk_concrete_type<V: Visitor + ?Sized>(visitor: &mut V, ty: &ConcreteType) {
+    match ty {
+        ConcreteType::Array(array) => visitor.visit_array(array),
+        ConcreteType::Const(ty) => visitor.visit_concrete_type(ty),
+        ConcreteType::Enum(enumeration) => visitor.visit_enum(enumeration),
+        ConcreteType::Pointer(pointer) => visitor.visit_pointer(pointer),
+        ConcreteType::Struct(structure) => visitor.visit_struct(structure),
+        ConcreteType::Typedef(typedef) => visitor.visit_typedef(typedef),
+        ConcreteType::TypedefName(name) => visitor.visit_identifier(name),
+        ConcreteType::Union(union) => visitor.visit_union(union),
+        ConcreteType::Bool
+        | ConcreteType::Char
+        | ConcreteType::Integer(_)
+        | ConcreteType::Real(_)
+        | ConcreteType::Size
+        | ConcreteType::StrongInt(_)
+        | ConcreteType::Void => {}
+    }
+}
+
+pub fn walk_opaque_type<V: Visitor + ?Sized>(visitor: &mut V, ty: &OpaqueType) {
+    match ty {
+        OpaqueType::ConcreteType(ty) => visitor.visit_concrete_type(ty),
+        OpaqueType::Function(function) => visitor.visit_function_type(function),
+    }
+}
+
+pub fn walk_function_type<V: Visitor + ?Sized>(visitor: &mut V, function: &Function) {
+    visitor.visit_concrete_type(&function.return_ty);
+    for parameter in &function.parameters {
+        visitor.visit_function_parameter(parameter);
+    }
+}
+
+pub fn walk_scalar<V: Visitor + ?Sized>(visitor: &mut V, scalar: &Scalar) {
+    match scalar {
+        Scalar::Pointer(pointer) => visitor.visit_pointer(pointer),
+        Scalar::Bool
+        | Scalar::Char
+        | Scalar::Integer(_)
+        | Scalar::StrongInt(_)
+        | Scalar::Real(_)
+        | Scalar::Void => {}
+    }
+}
+
+pub fn walk_pointer<V: Visitor + ?Sized>(visitor: &mut V, pointer: &Pointer) {
+    visitor.visit_opaque_type(&pointer.pointer_ty);
+}
+
+pub fn walk_array<V: Visitor + ?Sized>(visitor: &mut V, array: &Array) {
+    visitor.visit_concrete_type(&array.element_type);
+    visitor.visit_array_size(&array.size);
+}
+
+pub fn walk_array_size<V: Visitor + ?Sized>(visitor: &mut V, size: &ArraySize) {
+    match size {
+        ArraySize::Length(length) | ArraySize::Static(length) => visitor.visit_expression(length),
+        ArraySize::Unspecified | ArraySize::Star => {}
+    }
+}
+
+pub fn walk_type_definition<V: Visitor + ?Sized>(visitor: &mut V, definition: &TypeDefinition) {
+    match definition {
+        TypeDefinition::Enum(enumeration) => visitor.visit_enum(enumeration),
+        TypeDefinition::Struct(structure) => visitor.visit_struct(structure),
+        TypeDefinition::Union(union) => visitor.visit_union(union),
+    }
+}
+
+pub fn walk_enum<V: Visitor + ?Sized>(visitor: &mut V, enumeration: &Enum) {
+    match enumeration {
+        Enum::Definition {
+            doc,
+            name,
+            underlying_ty,
+            values,
+            ..
+        } => {
+            if let Some(doc) = doc {
+                visitor.visit_doc_comment(doc);
+            }
+            if let Some(name) = name {
+                visitor.visit_identifier(name);
+            }
+            if let Some(underlying_ty) = underlying_ty {
+                visitor.visit_concrete_type(underlying_ty);
+            }
+            for enumerator in values.iter() {
+                visitor.visit_enumerator(enumerator);
+            }
+        }
+        Enum::Tag { name } => visitor.visit_identifier(name),
+    }
+}
+
+pub fn walk_enumerator<V: Visitor + ?Sized>(visitor: &mut V, enumerator: &Enumerator) {
+    if let Some(doc) = &enumerator.doc {
+        visitor.visit_doc_comment(doc);
+    }
+    visitor.visit_identifier(&enumerator.name);
+    if let Some(value) = &enumerator.value {
+        visitor.visit_expression(value);
+    }
+}
+
+pub fn walk_struct<V: Visitor + ?Sized>(visitor: &mut V, structure: &Struct) {
+    match structure {
+        Struct::Definition {
+            doc,
+            attributes,
+            name,
+            members,
+        } => {
+            if let Some(doc) = doc {
+                visitor.visit_doc_comment(doc);
+            }
+            if let Some(aligned) = &attributes.aligned {
+                visitor.visit_expression(aligned);
+            }
+            if let Some(name) = name {
+                visitor.visit_identifier(name);
+            }
+            for member in members {
+                visitor.visit_member(member);
+            }
+        }
+        Struct::Tag { name } => visitor.visit_identifier(name),
+    }
+}
+
+pub fn walk_union<V: Visitor + ?Sized>(visitor: &mut V, union: &Union) {
+    match union {
+        Union::Definition {
+            doc,
+            attributes,
+            name,
+            members,
+        } => {
+            if let Some(doc) = doc {
+                visitor.visit_doc_comment(doc);
+            }
+            if let Some(aligned) = &attributes.aligned {
+                visitor.visit_expression(aligned);
+            }
+            if let Some(name) = name {
+                visitor.visit_identifier(name);
+            }
+            for member in members.iter() {
+                visitor.visit_member(member);
+            }
+        }
+        Union::Tag { name } => visitor.visit_identifier(name),
+    }
+}
+
+pub fn walk_member<V: Visitor + ?Sized>(visitor: &mut V, member: &Member) {
+    if let Some(doc) = &member.doc {
+        visitor.visit_doc_comment(doc);
+    }
+    if let Some(alignment) = &member.alignment {
+        visitor.visit_expression(alignment);
+    }
+    visitor.visit_concrete_type(&member.ty);
+    visitor.visit_identifier(&member.name);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    /// A function that contains every variant of [`Expression`] and [`Statement`].
+    pub(crate) const EVERY_VARIANT: &str = r#"static int
+every_variant (struct pair *p, int n)
+{
+  // Comment
+  typedef int number;
+  struct pair { int first; int second; };
+  enum color { RED, GREEN = 2 };
+  number a = 1, b[2] = { 3, 4 };
+  long c = _Alignof (long) + offsetof (struct pair, second);
+  void g (void);
+  _Static_assert (sizeof (int) == 4, "int");
+#include <stddef.h>
+#define LIMIT 8
+#pragma pack(pop)
+  ;
+  if (n > 0) a = b[1]; else a += (long) n;
+  switch (n) { case 1: break; default: a = n ? p->first : (*p).second; }
+  while (a < LIMIT) a++;
+  do { --a; continue; } while (a, 0);
+  for (int i = 0; i < n; i++) g ();
+  goto done;
+done:
+  return -a + 'x' + sizeof "s";
+}"#;
+
+    /// The visited expressions and statements by variant, and the visited identifiers in order.
+    #[derive(Debug, Default, PartialEq)]
+    pub(crate) struct Counts {
+        pub(crate) expressions: BTreeMap<&'static str, usize>,
+        pub(crate) statements: BTreeMap<&'static str, usize>,
+        pub(crate) identifiers: Vec<String>,
+    }
+
+    impl Counts {
+        pub(crate) fn expression(&mut self, expression: &Expression) {
+            let variant = match expression {
+                Expression::AlignOf(_) => "AlignOf",
+                Expression::ArraySubscript(_) => "ArraySubscript",
+                Expression::Assignment(_) => "Assignment",
+                Expression::BinaryOperator(_) => "BinaryOperator",
+                Expression::Cast(_) => "Cast",
+                Expression::CommaOperator(_) => "CommaOperator",
+                Expression::CompoundAssignment(_) => "CompoundAssignment",
+                Expression::Conditional(_) => "Conditional",
+                Expression::FunctionCall(_) => "FunctionCall",
+                Expression::IndirectMemberAccess(_) => "IndirectMemberAccess",
+                Expression::InitializerList(_) => "InitializerList",
+                Expression::MemberAccess(_) => "MemberAccess",
+                Expression::OffsetOf(_) => "OffsetOf",
+                Expression::Parentheses(_) => "Parentheses",
+                Expression::PostfixOperator(_) => "PostfixOperator",
+                Expression::PrefixOperator(_) => "PrefixOperator",
+                Expression::SizeOf(_) => "SizeOf",
+                Expression::Value(_) => "Value",
+                Expression::Variable(_) => "Variable",
+            };
+            *self.expressions.entry(variant).or_default() += 1;
+        }
+
+        pub(crate) fn statement(&mut self, statement: &Statement) {
+            let variant = match statement {
+                Statement::Expression(_) => "Expression",
+                Statement::Label(_) => "Label",
+                Statement::If(_) => "If",
+                Statement::Switch(_) => "Switch",
+                Statement::While(_) => "While",
+                Statement::Do(_) => "Do",
+                Statement::For(_) => "For",
+                Statement::Block(_) => "Block",
+                Statement::Comment(_) => "Comment",
+                Statement::Null => "Null",
+                Statement::Goto(_) => "Goto",
+                Statement::Break => "Break",
+                Statement::Continue => "Continue",
+                Statement::Return(_) => "Return",
+                Statement::StaticAssert(_) => "StaticAssert",
+                Statement::Typedef(_) => "Typedef",
+                Statement::VariableDeclaration(_) => "VariableDeclaration",
+                Statement::VariableDeclarationList(_) => "VariableDeclarationList",
+                Statement::FunctionDeclaration(_) => "FunctionDeclaration",
+                Statement::TypeDefinition(_) => "TypeDefinition",
+                Statement::Include(_) => "Include",
+                Statement::Define(_) => "Define",
+                Statement::Pragma(_) => "Pragma",
+            };
+            *self.statements.entry(variant).or_default() += 1;
+        }
+    }
+
+    impl Visitor for Counts {
+        fn visit_expression(&mut self, expression: &Expression) {
+            self.expression(expression);
+            walk_expression(self, expression);
+        }
+
+        fn visit_statement(&mut self, statement: &Statement) {
+            self.statement(statement);
+            walk_statement(self, statement);
+        }
+
+        fn visit_identifier(&mut self, identifier: &Identifier) {
+            self.identifiers.push(identifier.to_string());
+        }
+    }
+
+    /// Returns the counts of a traversal of [`EVERY_VARIANT`] by [`Visitor`].
+    pub(crate) fn every_variant_counts() -> anyhow::Result<Counts> {
+        let function = FileLevelStatement::parse(EVERY_VARIANT)?;
+
+        let mut counts = Counts::default();
+        counts.visit_file_level_statement(&function);
+
+        Ok(counts)
+    }
+
+    /// Records the identifiers in the order they are visited.
+    #[derive(Default)]
+    struct Identifiers(Vec<String>);
+
+    impl Visitor for Identifiers {
+        fn visit_identifier(&mut self, identifier: &Identifier) {
+            self.0.push(identifier.to_string());
+        }
+    }
+
+    #[test]
+    fn source_order() -> anyhow::Result<()> {
+        let function = FileLevelStatement::parse(
+            "static int count(struct list *list, int limit) {
+                int n = 0;
+                for (struct list *node = list; node && n < limit; node = node->next) n++;
+                return n;
+            }",
+        )?;
+
+        let mut identifiers = Identifiers::default();
+        identifiers.visit_file_level_statement(&function);
+        assert_eq!(
+            identifiers.0,
+            [
+                "count", "list", "list", "limit", "n", "list", "node", "list", "node", "n",
+                "limit", "node", "node", "next", "n", "n",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn every_variant() -> anyhow::Result<()> {
+        let counts = every_variant_counts()?;
+
+        assert_eq!(
+            counts.expressions,
+            BTreeMap::from([
+                ("AlignOf", 1),
+                ("ArraySubscript", 1),
+                ("Assignment", 2),
+                ("BinaryOperator", 7),
+                ("Cast", 1),
+                ("CommaOperator", 1),
+                ("CompoundAssignment", 1),
+                ("Conditional", 1),
+                ("FunctionCall", 1),
+                ("IndirectMemberAccess", 1),
+                ("InitializerList", 1),
+                ("MemberAccess", 1),
+                ("OffsetOf", 1),
+                ("Parentheses", 1),
+                ("PostfixOperator", 2),
+                ("PrefixOperator", 3),
+                ("SizeOf", 2),
+                ("Value", 13),
+                ("Variable", 20),
+            ])
+        );
+        assert_eq!(
+            counts.statements,
+            BTreeMap::from([
+                ("Block", 1),
+                ("Break", 1),
+                ("Comment", 1),
+                ("Continue", 1),
+                ("Define", 1),
+                ("Do", 1),
+                ("Expression", 6),
+                ("For", 1),
+                ("FunctionDeclaration", 1),
+                ("Goto", 1),
+                ("If", 1),
+                ("Include", 1),
+                ("Label", 1),
+                ("Null", 1),
+                ("Pragma", 1),
+                ("Return", 1),
+                ("StaticAssert", 1),
+                ("Switch", 1),
+                ("TypeDefinition", 2),
+                ("Typedef", 1),
+                ("VariableDeclaration", 1),
+                ("VariableDeclarationList", 1),
+                ("While", 1),
+            ])
+        );
+        assert_eq!(
+            counts.identifiers,
+            [
+                "every_variant",
+                "pair",
+                "p",
+                "n",
+                "number",
+                "pair",
+                "first",
+                "second",
+                "color",
+                "RED",
+                "GREEN",
+                "number",
+                "a",
+                "b",
+                "c",
+                "pair",
+                "second",
+                "g",
+                "LIMIT",
+                "n",
+                "a",
+                "b",
+                "a",
+                "n",
+                "n",
+                "a",
+                "n",
+                "p",
+                "first",
+                "p",
+                "second",
+                "a",
+                "LIMIT",
+                "a",
+                "a",
+                "a",
+                "i",
+                "i",
+                "n",
+                "i",
+                "g",
+                "done",
+                "done",
+                "a",
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,888 @@
+//! Traversal of the syntax tree by mutable reference.
+//!
+//! Each `visit_*_mut` method of [`VisitorMut`] defaults to the `walk_*_mut` function of the same name, which visits
+//! the children of the node in source order. An implementation overrides the methods of the nodes it rewrites, and
+//! calls the `walk_*_mut` function from the override to continue into the children.
+
+use crate::{
+    function::{FunctionCall, FunctionParameter},
+    operator::{
+        ArraySubscript, Assignment, BinaryOperator, Cast, CommaOperator, CompoundAssignment,
+        Conditional, OffsetOf, PostfixOperator, PrefixOperator, SizeOf,
+    },
+    r#type::{
+        member::{IndirectMemberAccess, Member, MemberAccess},
+        Array, ArraySize, Definition as TypeDefinition, Enum, Enumerator, Function,
+        InitializerList, OpaqueType, Pointer, Scalar, Struct, Union,
+    },
+    statement::{
+        Define, Do, For, ForDeclaration, Goto, If, Include, Label, Pragma, Return, StaticAssert,
+        Switch, Typedef, While,
+    },
+    variable::Declarator,
+    Block, Comment, ConcreteType, DocComment, Expression, FileLevelStatement, FunctionDeclaration,
+    FunctionDefinition, Identifier, Statement, Value, Variable, VariableDeclaration,
+    VariableDeclarationList,
+};
+
+/// A traversal of the syntax tree by mutable reference, for rewrites in place.
+pub trait VisitorMut {
+    fn visit_file_level_statement_mut(&mut self, statement: &mut FileLevelStatement) {
+        walk_file_level_statement_mut(self, statement);
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+    }
+
+    fn visit_value_mut(&mut self, value: &mut Value) {
+        walk_value_mut(self, value);
+    }
+
+    /// Visits a variable that is used in an expression.
+    fn visit_variable_mut(&mut self, variable: &mut Variable) {
+        walk_variable_mut(self, variable);
+    }
+
+    fn visit_identifier_mut(&mut self, _identifier: &mut Identifier) {}
+
+    fn visit_comment_mut(&mut self, _comment: &mut Comment) {}
+
+    fn visit_doc_comment_mut(&mut self, _doc: &mut DocComment) {}
+
+    fn visit_array_subscript_mut(&mut self, subscript: &mut ArraySubscript) {
+        walk_array_subscript_mut(self, subscript);
+    }
+
+    fn visit_assignment_mut(&mut self, assignment: &mut Assignment) {
+        walk_assignment_mut(self, assignment);
+    }
+
+    fn visit_binary_operator_mut(&mut self, operator: &mut BinaryOperator) {
+        walk_binary_operator_mut(self, operator);
+    }
+
+    fn visit_cast_mut(&mut self, cast: &mut Cast) {
+        walk_cast_mut(self, cast);
+    }
+
+    fn visit_comma_operator_mut(&mut self, operator: &mut CommaOperator) {
+        walk_comma_operator_mut(self, operator);
+    }
+
+    fn visit_compound_assignment_mut(&mut self, assignment: &mut CompoundAssignment) {
+        walk_compound_assignment_mut(self, assignment);
+    }
+
+    fn visit_conditional_mut(&mut self, conditional: &mut Conditional) {
+        walk_conditional_mut(self, conditional);
+    }
+
+    fn visit_function_call_mut(&mut self, call: &mut FunctionCall) {
+        walk_function_call_mut(self, call);
+    }
+
+    fn visit_indirect_member_access_mut(&mut self, access: &mut IndirectMemberAccess) {
+        walk_indirect_member_access_mut(self, access);
+    }
+
+    fn visit_initializer_list_mut(&mut self, list: &mut InitializerList) {
+        walk_initializer_list_mut(self, list);
+    }
+
+    fn visit_member_access_mut(&mut self, access: &mut MemberAccess) {
+        walk_member_access_mut(self, access);
+    }
+
+    fn visit_offset_of_mut(&mut self, offset_of: &mut OffsetOf) {
+        walk_offset_of_mut(self, offset_of);
+    }
+
+    fn visit_postfix_operator_mut(&mut self, operator: &mut PostfixOperator) {
+        walk_postfix_operator_mut(self, operator);
+    }
+
+    fn visit_prefix_operator_mut(&mut self, operator: &mut PrefixOperator) {
+        walk_prefix_operator_mut(self, operator);
+    }
+
+    fn visit_size_of_mut(&mut self, size_of: &mut SizeOf) {
+        walk_size_of_mut(self, size_of);
+    }
+
+    fn visit_define_mut(&mut self, define: &mut Define) {
+        walk_define_mut(self, define);
+    }
+
+    fn visit_do_mut(&mut self, do_statement: &mut Do) {
+        walk_do_mut(self, do_statement);
+    }
+
+    fn visit_for_mut(&mut self, for_statement: &mut For) {
+        walk_for_mut(self, for_statement);
+    }
+
+    fn visit_for_declaration_mut(&mut self, declaration: &mut ForDeclaration) {
+        walk_for_declaration_mut(self, declaration);
+    }
+
+    fn visit_goto_mut(&mut self, goto: &mut Goto) {
+        walk_goto_mut(self, goto);
+    }
+
+    fn visit_if_mut(&mut self, if_statement: &mut If) {
+        walk_if_mut(self, if_statement);
+    }
+
+    fn visit_include_mut(&mut self, _include: &mut Include) {}
+
+    fn visit_label_mut(&mut self, label: &mut Label) {
+        walk_label_mut(self, label);
+    }
+
+    fn visit_pragma_mut(&mut self, _pragma: &mut Pragma) {}
+
+    fn visit_return_mut(&mut self, return_statement: &mut Return) {
+        walk_return_mut(self, return_statement);
+    }
+
+    fn visit_static_assert_mut(&mut self, assertion: &mut StaticAssert) {
+        walk_static_assert_mut(self, assertion);
+    }
+
+    fn visit_switch_mut(&mut self, switch: &mut Switch) {
+        walk_switch_mut(self, switch);
+    }
+
+    fn visit_typedef_mut(&mut self, typedef: &mut Typedef) {
+        walk_typedef_mut(self, typedef);
+    }
+
+    fn visit_while_mut(&mut self, while_statement: &mut While) {
+        walk_while_mut(self, while_statement);
+    }
+
+    fn visit_function_declaration_mut(&mut self, declaration: &mut FunctionDeclaration) {
+        walk_function_declaration_mut(self, declaration);
+    }
+
+    fn visit_function_definition_mut(&mut self, definition: &mut FunctionDefinition) {
+        walk_function_definition_mut(self, definition);
+    }
+
+    fn visit_function_parameter_mut(&mut self, parameter: &mut FunctionParameter) {
+        walk_function_parameter_mut(self, parameter);
+    }
+
+    fn visit_variable_declaration_mut(&mut self, declaration: &mut VariableDeclaration) {
+        walk_variable_declaration_mut(self, declaration);
+    }
+
+    fn visit_variable_declaration_list_mut(&mut self, declaration: &mut VariableDeclarationList) {
+        walk_variable_declaration_list_mut(self, declaration);
+    }
+
+    fn visit_declarator_mut(&mut self, declarator: &mut Declarator) {
+        walk_declarator_mut(self, declarator);
+    }
+
+    fn visit_concrete_type_mut(&mut self, ty: &mut ConcreteType) {
+        walk_concrete_type_mut(self, ty);
+    }
+
+    fn visit_opaque_type_mut(&mut self, ty: &mut OpaqueType) {
+        walk_opaque_type_mut(self, ty);
+    }
+
+    fn visit_function_type_mut(&mut self, function: &mut Function) {
+        walk_function_type_mut(self, function);
+    }
+
+    fn visit_scalar_mut(&mut self, scalar: &mut Scalar) {
+        walk_scalar_mut(self, scalar);
+    }
+
+    fn visit_pointer_mut(&mut self, pointer: &mut Pointer) {
+        walk_pointer_mut(self, pointer);
+    }
+
+    fn visit_array_mut(&mut self, array: &mut Array) {
+        walk_array_mut(self, array);
+    }
+
+    fn visit_array_size_mut(&mut self, size: &mut ArraySize) {
+        walk_array_size_mut(self, size);
+    }
+
+    fn visit_type_definition_mut(&mut self, definition: &mut TypeDefinition) {
+        walk_type_definition_mut(self, definition);
+    }
+
+    fn visit_enum_mut(&mut self, enumeration: &mut Enum) {
+        walk_enum_mut(self, enumeration);
+    }
+
+    fn visit_enumerator_mut(&mut self, enumerator: &mut Enumerator) {
+        walk_enumerator_mut(self, enumerator);
+    }
+
+    fn visit_struct_mut(&mut self, structure: &mut Struct) {
+        walk_struct_mut(self, structure);
+    }
+
+    fn visit_union_mut(&mut self, union: &mut Union) {
+        walk_union_mut(self, union);
+    }
+
+    fn visit_member_mut(&mut self, member: &mut Member) {
+        walk_member_mut(self, member);
+    }
+}
+
+pub fn walk_file_level_statement_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    statement: &mut FileLevelStatement,
+) {
+    match statement {
+        FileLevelStatement::Comment(comment) => visitor.visit_comment_mut(comment),
+        FileLevelStatement::Define(define) => visitor.visit_define_mut(define),
+        FileLevelStatement::FunctionDeclaration(declaration) => {
+            visitor.visit_function_declaration_mut(declaration)
+        }
+        FileLevelStatement::FunctionDefinition(definition) => {
+            visitor.visit_function_definition_mut(definition)
+        }
+        FileLevelStatement::Include(include) => visitor.visit_include_mut(include),
+        FileLevelStatement::Pragma(pragma) => visitor.visit_pragma_mut(pragma),
+        FileLevelStatement::StaticAssert(assertion) => visitor.visit_static_assert_mut(assertion),
+        FileLevelStatement::TypeDefinition(definition) => {
+            visitor.visit_type_definition_mut(definition)
+        }
+        FileLevelStatement::Typedef(typedef) => visitor.visit_typedef_mut(typedef),
+        FileLevelStatement::VariableDeclaration(declaration) => {
+            visitor.visit_variable_declaration_mut(declaration)
+        }
+        FileLevelStatement::VariableDeclarationList(declaration) => {
+            visitor.visit_variable_declaration_list_mut(declaration)
+        }
+    }
+}
+
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    match statement {
+        Statement::Expression(expression) => visitor.visit_expression_mut(expression),
+        Statement::Label(label) => visitor.visit_label_mut(label),
+        Statement::If(if_statement) => visitor.visit_if_mut(if_statement),
+        Statement::Switch(switch) => visitor.visit_switch_mut(switch),
+        Statement::While(while_statement) => visitor.visit_while_mut(while_statement),
+        Statement::Do(do_statement) => visitor.visit_do_mut(do_statement),
+        Statement::For(for_statement) => visitor.visit_for_mut(for_statement),
+        Statement::Block(block) => visitor.visit_block_mut(block),
+        Statement::Comment(comment) => visitor.visit_comment_mut(comment),
+        Statement::Null | Statement::Break | Statement::Continue => {}
+        Statement::Goto(goto) => visitor.visit_goto_mut(goto),
+        Statement::Return(return_statement) => visitor.visit_return_mut(return_statement),
+        Statement::StaticAssert(assertion) => visitor.visit_static_assert_mut(assertion),
+        Statement::Typedef(typedef) => visitor.visit_typedef_mut(typedef),
+        Statement::VariableDeclaration(declaration) => {
+            visitor.visit_variable_declaration_mut(declaration)
+        }
+        Statement::VariableDeclarationList(declaration) => {
+            visitor.visit_variable_declaration_list_mut(declaration)
+        }
+        Statement::FunctionDeclaration(declaration) => {
+            visitor.visit_function_declaration_mut(declaration)
+        }
+        Statement::TypeDefinition(definition) => visitor.visit_type_definition_mut(definition),
+        Statement::Include(include) => visitor.visit_include_mut(include),
+        Statement::Define(define) => visitor.visit_define_mut(define),
+        Statement::Pragma(pragma) => visitor.visit_pragma_mut(pragma),
+    }
+}
+
+pub fn walk_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut Block) {
+    for statement in &mut block.statements {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    match expression {
+        Expression::AlignOf(ty) => visitor.visit_concrete_type_mut(ty),
+        Expression::ArraySubscript(subscript) => visitor.visit_array_subscript_mut(subscript),
+        Expression::Assignment(assignment) => visitor.visit_assignment_mut(assignment),
+        Expression::BinaryOperator(operator) => visitor.visit_binary_operator_mut(operator),
+        Expression::Cast(cast) => visitor.visit_cast_mut(cast),
+        Expression::CommaOperator(operator) => visitor.visit_comma_operator_mut(operator),
+        Expression::CompoundAssignment(assignment) => {
+            visitor.visit_compound_assignment_mut(assignment)
+        }
+        Expression::Conditional(conditional) => visitor.visit_conditional_mut(conditional),
+        Expression::FunctionCall(call) => visitor.visit_function_call_mut(call),
+        Expression::IndirectMemberAccess(access) => {
+            visitor.visit_indirect_member_access_mut(access)
+        }
+        Expression::InitializerList(list) => visitor.visit_initializer_list_mut(list),
+        Expression::MemberAccess(access) => visitor.visit_member_access_mut(access),
+        Expression::OffsetOf(offset_of) => visitor.visit_offset_of_mut(offset_of),
+        Expression::Parentheses(expression) => visitor.visit_expression_mut(expression),
+        Expression::PostfixOperator(operator) => visitor.visit_postfix_operator_mut(operator),
+        Expression::PrefixOperator(operator) => visitor.visit_prefix_operator_mut(operator),
+        Expression::SizeOf(size_of) => visitor.visit_size_of_mut(size_of),
+        Expression::Value(value) => visitor.visit_value_mut(value),
+        Expression::Variable(variable) => visitor.visit_variable_mut(variable),
+    }
+}
+
+pub fn walk_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, value: &mut Value) {
+    match value {
+        Value::Array { values, base_type } => {
+            visitor.visit_concrete_type_mut(base_type);
+            for value in values {
+                visitor.visit_value_mut(value);
+            }
+        }
+        Value::Struct { fields } => {
+            for (_, value) in fields {
+                visitor.visit_value_mut(value);
+            }
+        }
+        Value::Char { .. }
+        | Value::Enum { .. }
+        | Value::Pointer { .. }
+        | Value::Real { .. }
+        | Value::SignedInteger { .. }
+        | Value::Size { .. }
+        | Value::String(_)
+        | Value::UnsignedInteger { .. } => {}
+    }
+}
+
+pub fn walk_variable_mut<V: VisitorMut + ?Sized>(visitor: &mut V, variable: &mut Variable) {
+    visitor.visit_identifier_mut(variable);
+}
+
+pub fn walk_array_subscript_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    subscript: &mut ArraySubscript,
+) {
+    visitor.visit_expression_mut(&mut subscript.array);
+    visitor.visit_expression_mut(&mut subscript.index);
+}
+
+pub fn walk_assignment_mut<V: VisitorMut + ?Sized>(visitor: &mut V, assignment: &mut Assignment) {
+    visitor.visit_expression_mut(&mut assignment.left);
+    visitor.visit_expression_mut(&mut assignment.right);
+}
+
+pub fn walk_binary_operator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    operator: &mut BinaryOperator,
+) {
+    visitor.visit_expression_mut(&mut operator.left);
+    visitor.visit_expression_mut(&mut operator.right);
+}
+
+pub fn walk_cast_mut<V: VisitorMut + ?Sized>(visitor: &mut V, cast: &mut Cast) {
+    visitor.visit_scalar_mut(&mut cast.new_ty);
+    visitor.visit_expression_mut(&mut cast.expression);
+}
+
+pub fn walk_comma_operator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    operator: &mut CommaOperator,
+) {
+    visitor.visit_expression_mut(&mut operator.left);
+    visitor.visit_expression_mut(&mut operator.right);
+}
+
+pub fn walk_compound_assignment_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    assignment: &mut CompoundAssignment,
+) {
+    visitor.visit_expression_mut(&mut assignment.left);
+    visitor.visit_expression_mut(&mut assignment.right);
+}
+
+pub fn walk_conditional_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    conditional: &mut Conditional,
+) {
+    visitor.visit_expression_mut(&mut conditional.condition);
+    visitor.visit_expression_mut(&mut conditional.then_branch);
+    visitor.visit_expression_mut(&mut conditional.else_branch);
+}
+
+pub fn walk_function_call_mut<V: VisitorMut + ?Sized>(visitor: &mut V, call: &mut FunctionCall) {
+    visitor.visit_expression_mut(&mut call.callee);
+    for argument in &mut call.arguments {
+        visitor.visit_expression_mut(argument);
+    }
+}
+
+pub fn walk_indirect_member_access_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    access: &mut IndirectMemberAccess,
+) {
+    visitor.visit_expression_mut(&mut access.left);
+    visitor.visit_identifier_mut(&mut access.member);
+}
+
+pub fn walk_initializer_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    list: &mut InitializerList,
+) {
+    match list {
+        InitializerList::Named(fields) => {
+            for (name, value) in fields {
+                visitor.visit_identifier_mut(name);
+                visitor.visit_expression_mut(value);
+            }
+        }
+        InitializerList::Ordered(values) => {
+            for value in values {
+                visitor.visit_expression_mut(value);
+            }
+        }
+    }
+}
+
+pub fn walk_member_access_mut<V: VisitorMut + ?Sized>(visitor: &mut V, access: &mut MemberAccess) {
+    visitor.visit_expression_mut(&mut access.left);
+    visitor.visit_identifier_mut(&mut access.member);
+}
+
+pub fn walk_offset_of_mut<V: VisitorMut + ?Sized>(visitor: &mut V, offset_of: &mut OffsetOf) {
+    visitor.visit_concrete_type_mut(&mut offset_of.ty);
+    visitor.visit_identifier_mut(&mut offset_of.member);
+}
+
+pub fn walk_postfix_operator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    operator: &mut PostfixOperator,
+) {
+    visitor.visit_expression_mut(&mut operator.operand);
+}
+
+pub fn walk_prefix_operator_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    operator: &mut PrefixOperator,
+) {
+    visitor.visit_expression_mut(&mut operator.operand);
+}
+
+pub fn walk_size_of_mut<V: VisitorMut + ?Sized>(visitor: &mut V, size_of: &mut SizeOf) {
+    match size_of {
+        SizeOf::Expression(expression) => visitor.visit_expression_mut(expression),
+        SizeOf::Type(ty) => visitor.visit_concrete_type_mut(ty),
+    }
+}
+
+pub fn walk_define_mut<V: VisitorMut + ?Sized>(visitor: &mut V, define: &mut Define) {
+    visitor.visit_identifier_mut(&mut define.name);
+    for parameter in define.parameters.iter_mut().flatten() {
+        visitor.visit_identifier_mut(parameter);
+    }
+}
+
+pub fn walk_do_mut<V: VisitorMut + ?Sized>(visitor: &mut V, do_statement: &mut Do) {
+    visitor.visit_statement_mut(&mut do_statement.body);
+    visitor.visit_expression_mut(&mut do_statement.condition);
+}
+
+pub fn walk_for_mut<V: VisitorMut + ?Sized>(visitor: &mut V, for_statement: &mut For) {
+    if let Some(init) = &mut for_statement.init {
+        visitor.visit_for_declaration_mut(init);
+    }
+    visitor.visit_expression_mut(&mut for_statement.condition);
+    if let Some(step) = &mut for_statement.step {
+        visitor.visit_expression_mut(step);
+    }
+    visitor.visit_statement_mut(&mut for_statement.body);
+}
+
+pub fn walk_for_declaration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut ForDeclaration,
+) {
+    match declaration {
+        ForDeclaration::Expression(expression) => visitor.visit_expression_mut(expression),
+        ForDeclaration::VariableDeclaration(declaration) => {
+            visitor.visit_variable_declaration_mut(declaration)
+        }
+        ForDeclaration::VariableDeclarationList(declaration) => {
+            visitor.visit_variable_declaration_list_mut(declaration)
+        }
+    }
+}
+
+pub fn walk_goto_mut<V: VisitorMut + ?Sized>(visitor: &mut V, goto: &mut Goto) {
+    visitor.visit_identifier_mut(&mut goto.label);
+}
+
+pub fn walk_if_mut<V: VisitorMut + ?Sized>(visitor: &mut V, if_statement: &mut If) {
+    visitor.visit_expression_mut(&mut if_statement.condition);
+    visitor.visit_statement_mut(&mut if_statement.then_statement);
+    if let Some(else_statement) = &mut if_statement.else_statement {
+        visitor.visit_statement_mut(else_statement);
+    }
+}
+
+pub fn walk_label_mut<V: VisitorMut + ?Sized>(visitor: &mut V, label: &mut Label) {
+    visitor.visit_identifier_mut(&mut label.identifier);
+    if let Some(statement) = &mut label.statement {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_return_mut<V: VisitorMut + ?Sized>(visitor: &mut V, return_statement: &mut Return) {
+    if let Some(expression) = &mut return_statement.expression {
+        visitor.visit_expression_mut(expression);
+    }
+}
+
+pub fn walk_static_assert_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    assertion: &mut StaticAssert,
+) {
+    visitor.visit_expression_mut(&mut assertion.condition);
+}
+
+pub fn walk_switch_mut<V: VisitorMut + ?Sized>(visitor: &mut V, switch: &mut Switch) {
+    visitor.visit_expression_mut(&mut switch.condition);
+    for (value, statements) in &mut switch.cases {
+        visitor.visit_expression_mut(value);
+        for statement in statements {
+            visitor.visit_statement_mut(statement);
+        }
+    }
+    for statement in switch.default.iter_mut().flatten() {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_typedef_mut<V: VisitorMut + ?Sized>(visitor: &mut V, typedef: &mut Typedef) {
+    visitor.visit_opaque_type_mut(&mut typedef.ty);
+    visitor.visit_identifier_mut(&mut typedef.alias);
+}
+
+pub fn walk_while_mut<V: VisitorMut + ?Sized>(visitor: &mut V, while_statement: &mut While) {
+    visitor.visit_expression_mut(&mut while_statement.condition);
+    visitor.visit_statement_mut(&mut while_statement.body);
+}
+
+pub fn walk_function_declaration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut FunctionDeclaration,
+) {
+    if let Some(doc) = &mut declaration.doc {
+        visitor.visit_doc_comment_mut(doc);
+    }
+    visitor.visit_identifier_mut(&mut declaration.name);
+    visitor.visit_function_type_mut(&mut declaration.ty);
+}
+
+pub fn walk_function_definition_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    definition: &mut FunctionDefinition,
+) {
+    if let Some(doc) = &mut definition.doc {
+        visitor.visit_doc_comment_mut(doc);
+    }
+    visitor.visit_identifier_mut(&mut definition.name);
+    visitor.visit_function_type_mut(&mut definition.ty);
+    visitor.visit_block_mut(&mut definition.body);
+}
+
+pub fn walk_function_parameter_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    parameter: &mut FunctionParameter,
+) {
+    visitor.visit_concrete_type_mut(&mut parameter.ty);
+    if let Some(name) = &mut parameter.name {
+        visitor.visit_identifier_mut(name);
+    }
+}
+
+pub fn walk_variable_declaration_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut VariableDeclaration,
+) {
+    if let Some(doc) = &mut declaration.doc {
+        visitor.visit_doc_comment_mut(doc);
+    }
+    if let Some(alignment) = &mut declaration.alignment {
+        visitor.visit_expression_mut(alignment);
+    }
+    visitor.visit_concrete_type_mut(&mut declaration.ty);
+    visitor.visit_identifier_mut(&mut declaration.identifier);
+    if let Some(initializer) = &mut declaration.initializer {
+        visitor.visit_expression_mut(initializer);
+    }
+}
+
+pub fn walk_variable_declaration_list_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    declaration: &mut VariableDeclarationList,
+) {
+    if let Some(doc) = &mut declaration.doc {
+        visitor.visit_doc_comment_mut(doc);
+    }
+    if let Some(alignment) = &mut declaration.alignment {
+        visitor.visit_expression_mut(alignment);
+    }
+    visitor.visit_concrete_type_mut(&mut declaration.ty);
+    for declarator in declaration.declarators.iter_mut() {
+        visitor.visit_declarator_mut(declarator);
+    }
+}
+
+pub fn walk_declarator_mut<V: VisitorMut + ?Sized>(visitor: &mut V, declarator: &mut Declarator) {
+    visitor.visit_identifier_mut(&mut declarator.identifier);
+    for size in &mut declarator.dimensions {
+        visitor.visit_array_size_mut(size);
+    }
+    if let Some(initializer) = &mut declarator.initializer {
+        visitor.visit_expression_mut(initializer);
+    }
+}
+
+pub fn walk_concrete_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, ty: &mut ConcreteType) {
+    match ty {
+        ConcreteType::Array(array) => visitor.visit_array_mut(array),
+        ConcreteType::Const(ty) => visitor.visit_concrete_type_mut(ty),
+        ConcreteType::Enum(enumeration) => visitor.visit_enum_mut(enumeration),
+        ConcreteType::Pointer(pointer) => visitor.visit_pointer_mut(pointer),
+        ConcreteType::Struct(structure) => visitor.visit_struct_mut(structure),
+        ConcreteType::Typedef(typedef) => visitor.visit_typedef_mut(typedef),
+        ConcreteType::TypedefName(name) => visitor.visit_identifier_mut(name),
+        ConcreteType::Union(union) => visitor.visit_union_mut(union),
+        ConcreteType::Bool
+        | ConcreteType::Char
+        | ConcreteType::Integer(_)
+        | ConcreteType::Real(_)
+        | ConcreteType::Size
+        | ConcreteType::StrongInt(_)
+        | ConcreteType::Void => {}
+    }
+}
+
+pub fn walk_opaque_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, ty: &mut OpaqueType) {
+    match ty {
+        OpaqueType::ConcreteType(ty) => visitor.visit_concrete_type_mut(ty),
+        OpaqueType::Function(function) => visitor.visit_function_type_mut(function),
+    }
+}
+
+pub fn walk_function_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, function: &mut Function) {
+    visitor.visit_concrete_type_mut(&mut function.return_ty);
+    for parameter in &mut function.parameters {
+        visitor.visit_function_parameter_mut(parameter);
+    }
+}
+
+pub fn walk_scalar_mut<V: VisitorMut + ?Sized>(visitor: &mut V, scalar: &mut Scalar) {
+    match scalar {
+        Scalar::Pointer(pointer) => visitor.visit_pointer_mut(pointer),
+        Scalar::Bool
+        | Scalar::Char
+        | Scalar::Integer(_)
+        | Scalar::StrongInt(_)
+        | Scalar::Real(_)
+        | Scalar::Void => {}
+    }
+}
+
+pub fn walk_pointer_mut<V: VisitorMut + ?Sized>(visitor: &mut V, pointer: &mut Pointer) {
+    visitor.visit_opaque_type_mut(&mut pointer.pointer_ty);
+}
+
+pub fn walk_array_mut<V: VisitorMut + ?Sized>(visitor: &mut V, array: &mut Array) {
+    visitor.visit_concrete_type_mut(&mut array.element_type);
+    visitor.visit_array_size_mut(&mut array.size);
+}
+
+pub fn walk_array_size_mut<V: VisitorMut + ?Sized>(visitor: &mut V, size: &mut ArraySize) {
+    match size {
+        ArraySize::Length(length) | ArraySize::Static(length) => {
+            visitor.visit_expression_mut(length)
+        }
+        ArraySize::Unspecified | ArraySize::Star => {}
+    }
+}
+
+pub fn walk_type_definition_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    definition: &mut TypeDefinition,
+) {
+    match definition {
+        TypeDefinition::Enum(enumeration) => visitor.visit_enum_mut(enumeration),
+        TypeDefinition::Struct(structure) => visitor.visit_struct_mut(structure),
+        TypeDefinition::Union(union) => visitor.visit_union_mut(union),
+    }
+}
+
+pub fn walk_enum_mut<V: VisitorMut + ?Sized>(visitor: &mut V, enumeration: &mut Enum) {
+    match enumeration {
+        Enum::Definition {
+            doc,
+            name,
+            underlying_ty,
+            values,
+            ..
+        } => {
+            if let Some(doc) = doc {
+                visitor.visit_doc_comment_mut(doc);
+            }
+            if let Some(name) = name {
+                visitor.visit_identifier_mut(name);
+            }
+            if let Some(underlying_ty) = underlying_ty {
+                visitor.visit_concrete_type_mut(underlying_ty);
+            }
+            for enumerator in values.iter_mut() {
+                visitor.visit_enumerator_mut(enumerator);
+            }
+        }
+        Enum::Tag { name } => visitor.visit_identifier_mut(name),
+    }
+}
+
+pub fn walk_enumerator_mut<V: VisitorMut + ?Sized>(visitor: &mut V, enumerator: &mut Enumerator) {
+    if let Some(doc) = &mut enumerator.doc {
+        visitor.visit_doc_comment_mut(doc);
+    }
+    visitor.visit_identifier_mut(&mut enumerator.name);
+    if let Some(value) = &mut enumerator.value {
+        visitor.visit_expression_mut(value);
+    }
+}
+
+pub fn walk_struct_mut<V: VisitorMut + ?Sized>(visitor: &mut V, structure: &mut Struct) {
+    match structure {
+        Struct::Definition {
+            doc,
+            attributes,
+            name,
+            members,
+        } => {
+            if let Some(doc) = doc {
+                visitor.visit_doc_comment_mut(doc);
+            }
+            if let Some(aligned) = &mut attributes.aligned {
+                visitor.visit_expression_mut(aligned);
+            }
+            if let Some(name) = name {
+                visitor.visit_identifier_mut(name);
+            }
+            for member in members {
+                visitor.visit_member_mut(member);
+            }
+        }
+        Struct::Tag { name } => visitor.visit_identifier_mut(name),
+    }
+}
+
+pub fn walk_union_mut<V: VisitorMut + ?Sized>(visitor: &mut V, union: &mut Union) {
+    match union {
+        Union::Definition {
+            doc,
+            attributes,
+            name,
+            members,
+        } => {
+            if let Some(doc) = doc {
+                visitor.visit_doc_comment_mut(doc);
+            }
+            if let Some(aligned) = &mut attributes.aligned {
+                visitor.visit_expression_mut(aligned);
+            }
+            if let Some(name) = name {
+                visitor.visit_identifier_mut(name);
+            }
+            for member in members.iter_mut() {
+                visitor.visit_member_mut(member);
+            }
+        }
+        Union::Tag { name } => visitor.visit_identifier_mut(name),
+    }
+}
+
+pub fn walk_member_mut<V: VisitorMut + ?Sized>(visitor: &mut V, member: &mut Member) {
+    if let Some(doc) = &mut member.doc {
+        visitor.visit_doc_comment_mut(doc);
+    }
+    if let Some(alignment) = &mut member.alignment {
+        visitor.visit_expression_mut(alignment);
+    }
+    visitor.visit_concrete_type_mut(&mut member.ty);
+    visitor.visit_identifier_mut(&mut member.name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visit::tests::{every_variant_counts, Counts, EVERY_VARIANT};
+
+    struct Rename {
+        from: Identifier,
+        to: Identifier,
+    }
+
+    impl VisitorMut for Rename {
+        fn visit_identifier_mut(&mut self, identifier: &mut Identifier) {
+            if *identifier == self.from {
+                *identifier = self.to.clone();
+            }
+        }
+    }
+
+    #[test]
+    fn rename() -> anyhow::Result<()> {
+        let mut statement = Statement::parse("{ int x = 1; x += f(x); }")?;
+
+        let mut rename = Rename {
+            from: Identifier::new("x")?,
+            to: Identifier::new("y")?,
+        };
+        rename.visit_statement_mut(&mut statement);
+        assert_eq!(statement.to_string(), "{\n  int y = 1;\n  y += f(y);\n}");
+
+        Ok(())
+    }
+
+    impl VisitorMut for Counts {
+        fn visit_expression_mut(&mut self, expression: &mut Expression) {
+            self.expression(expression);
+            walk_expression_mut(self, expression);
+        }
+
+        fn visit_statement_mut(&mut self, statement: &mut Statement) {
+            self.statement(statement);
+            walk_statement_mut(self, statement);
+        }
+
+        fn visit_identifier_mut(&mut self, identifier: &mut Identifier) {
+            self.identifiers.push(identifier.to_string());
+        }
+    }
+
+    #[test]
+    fn every_variant() -> anyhow::Result<()> {
+        let mut function = FileLevelStatement::parse(EVERY_VARIANT)?;
+
+        let mut counts = Counts::default();
+        counts.visit_file_level_statement_mut(&mut function);
+        assert_eq!(counts, every_variant_counts()?);
+
+        Ok(())
+    }
+}